@@ -0,0 +1,85 @@
+//! Compares many subscribers observing a store through `to_signal` and through `to_broadcaster`.
+//!
+//! Run with `cargo bench -p easerx --bench broadcast_subscribers`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use easerx::{State, StateStore};
+use futures_signals::signal::{Signal, SignalExt};
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+const SUBSCRIBERS: usize = 256;
+
+#[derive(Clone, Debug, PartialEq)]
+struct WidgetState {
+    tick: i64,
+    labels: Vec<String>,
+}
+
+impl State for WidgetState {}
+
+struct Harness {
+    store: StateStore<WidgetState>,
+    target: Arc<AtomicI64>,
+    seen: Arc<AtomicUsize>,
+}
+
+impl Harness {
+    fn new<F, S>(runtime: &Runtime, subscribe: F) -> Self
+    where
+        F: Fn(&StateStore<WidgetState>) -> S,
+        S: Signal<Item = i64> + Send + 'static,
+    {
+        let target = Arc::new(AtomicI64::new(-1));
+        let seen = Arc::new(AtomicUsize::new(0));
+        let store = runtime.block_on(async {
+            StateStore::new(WidgetState {
+                tick: 0,
+                labels: (0..64).map(|i| format!("label {i}")).collect(),
+            })
+        });
+        for _ in 0..SUBSCRIBERS {
+            let target = target.clone();
+            let seen = seen.clone();
+            let signal = subscribe(&store);
+            runtime.spawn(signal.for_each(move |tick| {
+                if tick == target.load(Ordering::Acquire) {
+                    seen.fetch_add(1, Ordering::AcqRel);
+                }
+                async {}
+            }));
+        }
+        Harness { store, target, seen }
+    }
+
+    async fn tick(&self) {
+        let next = self.store.get_state().tick + 1;
+        self.seen.store(0, Ordering::Release);
+        self.target.store(next, Ordering::Release);
+        self.store
+            .set_state(move |state| WidgetState { tick: next, ..state })
+            .unwrap();
+        while self.seen.load(Ordering::Acquire) < SUBSCRIBERS {
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+fn broadcast_subscribers(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("broadcast_256_subscribers");
+
+    let harness = Harness::new(&runtime, |store| store.to_signal().map(|state| state.tick));
+    group.bench_function("to_signal", |b| b.iter(|| runtime.block_on(harness.tick())));
+
+    let harness = Harness::new(&runtime, |store| {
+        store.to_broadcaster().signal_ref(|state| state.tick)
+    });
+    group.bench_function("to_broadcaster", |b| b.iter(|| runtime.block_on(harness.tick())));
+
+    group.finish();
+}
+
+criterion_group!(benches, broadcast_subscribers);
+criterion_main!(benches);
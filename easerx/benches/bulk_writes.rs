@@ -0,0 +1,57 @@
+//! Bulk `set_state` writes modelled on the `basic3_collections` example, with nothing listening and
+//! with one subscriber of each kind attached.
+//!
+//! Run with `cargo bench -p easerx --bench bulk_writes`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use easerx::{State, StateStore};
+use futures::StreamExt;
+use futures_signals::signal::SignalExt;
+use std::sync::{Arc, Mutex};
+use tokio::runtime::Runtime;
+
+const WRITES: usize = 1_000;
+
+#[derive(Clone, Debug, Default)]
+struct CollectionState {
+    arc_vec: Arc<Mutex<Vec<usize>>>,
+}
+
+impl State for CollectionState {}
+
+impl CollectionState {
+    fn arc_vec_push(self, x: usize) -> Self {
+        self.arc_vec.lock().unwrap().push(x);
+        self
+    }
+}
+
+fn push_all(runtime: &Runtime, store: &StateStore<CollectionState>) {
+    runtime.block_on(async {
+        for i in 0..WRITES {
+            store.set_state(move |state| state.arc_vec_push(i)).unwrap();
+        }
+        store.await_state().await.unwrap();
+    });
+}
+
+fn bulk_writes(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("bulk_writes");
+
+    let store = runtime.block_on(async { StateStore::new(CollectionState::default()) });
+    group.bench_function("no_subscribers", |b| b.iter(|| push_all(&runtime, &store)));
+
+    let store = runtime.block_on(async { StateStore::new(CollectionState::default()) });
+    runtime.spawn(store.to_signal().for_each(|_| async {}));
+    group.bench_function("signal_subscriber", |b| b.iter(|| push_all(&runtime, &store)));
+
+    let store = runtime.block_on(async { StateStore::new(CollectionState::default()) });
+    runtime.spawn(store.subscribe_all().for_each(|_| async {}));
+    group.bench_function("subscribe_all_subscriber", |b| b.iter(|| push_all(&runtime, &store)));
+
+    group.finish();
+}
+
+criterion_group!(benches, bulk_writes);
+criterion_main!(benches);
@@ -0,0 +1,226 @@
+//! Measures the overhead of the store against doing the same work by hand: `set_state` throughput,
+//! `execute` against a plain `spawn_blocking` writing a `Mutable`, `async_execute` with and without
+//! retain, and signal fan-out to many subscribers.
+//!
+//! Run with `cargo bench -p easerx --bench execute_overhead`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use easerx::{Async, State, StateStore};
+use futures_signals::signal::{Mutable, SignalExt};
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+const UPDATES: u64 = 1_000;
+
+#[derive(Clone, Debug, PartialEq)]
+struct SmallState {
+    count: i64,
+}
+
+impl State for SmallState {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct LargeState {
+    count: i64,
+    rows: Vec<String>,
+}
+
+impl State for LargeState {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ResultState {
+    value: Async<i64>,
+}
+
+impl State for ResultState {}
+
+fn set_state_throughput(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("set_state_throughput");
+    group.throughput(Throughput::Elements(UPDATES));
+
+    let store = runtime.block_on(async { StateStore::new(SmallState { count: 0 }) });
+    group.bench_function("small_state", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for _ in 0..UPDATES {
+                    store
+                        .set_state(|state| SmallState { count: state.count + 1 })
+                        .unwrap();
+                }
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    let store = runtime.block_on(async {
+        StateStore::new(LargeState {
+            count: 0,
+            rows: (0..1_000).map(|i| format!("row {i}")).collect(),
+        })
+    });
+    group.bench_function("large_state", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                for _ in 0..UPDATES {
+                    store
+                        .set_state(|state| LargeState {
+                            count: state.count + 1,
+                            ..state
+                        })
+                        .unwrap();
+                }
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
+fn execute_latency(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("execute_latency");
+
+    let store = runtime.block_on(async {
+        StateStore::new(ResultState {
+            value: Async::Uninitialized,
+        })
+    });
+    group.bench_function("execute", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                store
+                    .execute(|| 42_i64, |_, value| ResultState { value })
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    // The same Loading/result sequence written by hand, without the queue
+    let mutable = Mutable::new(ResultState {
+        value: Async::Uninitialized,
+    });
+    group.bench_function("spawn_blocking_mutable", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                mutable.set(ResultState {
+                    value: Async::loading(None),
+                });
+                let value = tokio::task::spawn_blocking(|| 42_i64).await.unwrap();
+                mutable.set(ResultState {
+                    value: Async::success(value),
+                });
+            })
+        })
+    });
+
+    group.finish();
+}
+
+fn async_execute_latency(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("async_execute_latency");
+
+    let store = runtime.block_on(async {
+        StateStore::new(ResultState {
+            value: Async::success(0),
+        })
+    });
+    group.bench_function("plain", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                store
+                    .async_execute(async { 42_i64 }, |_, value| ResultState { value })
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+    group.bench_function("retain", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                store
+                    .async_execute_with_retain(
+                        async { 42_i64 },
+                        |state| &state.value,
+                        |_, value| ResultState { value },
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
+struct FanOut {
+    store: StateStore<SmallState>,
+    subscribers: usize,
+    target: Arc<AtomicI64>,
+    seen: Arc<AtomicUsize>,
+}
+
+impl FanOut {
+    fn new(runtime: &Runtime, subscribers: usize) -> Self {
+        let target = Arc::new(AtomicI64::new(-1));
+        let seen = Arc::new(AtomicUsize::new(0));
+        let store = runtime.block_on(async { StateStore::new(SmallState { count: 0 }) });
+        for _ in 0..subscribers {
+            let target = target.clone();
+            let seen = seen.clone();
+            runtime.spawn(store.to_signal().for_each(move |state| {
+                if state.count == target.load(Ordering::Acquire) {
+                    seen.fetch_add(1, Ordering::AcqRel);
+                }
+                async {}
+            }));
+        }
+        FanOut {
+            store,
+            subscribers,
+            target,
+            seen,
+        }
+    }
+
+    async fn tick(&self) {
+        let next = self.store.get_state().count + 1;
+        self.seen.store(0, Ordering::Release);
+        self.target.store(next, Ordering::Release);
+        self.store.set_state(move |_| SmallState { count: next }).unwrap();
+        while self.seen.load(Ordering::Acquire) < self.subscribers {
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+fn signal_fan_out(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("signal_fan_out");
+    for subscribers in [1, 10, 100] {
+        let fan_out = FanOut::new(&runtime, subscribers);
+        group.bench_with_input(BenchmarkId::from_parameter(subscribers), &fan_out, |b, fan_out| {
+            b.iter(|| runtime.block_on(fan_out.tick()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    set_state_throughput,
+    execute_latency,
+    async_execute_latency,
+    signal_fan_out
+);
+criterion_main!(benches);
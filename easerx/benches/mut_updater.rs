@@ -0,0 +1,63 @@
+//! Compares an execute cycle on a large state with a by-value updater (`execute`) and an
+//! in-place updater (`execute_mut`).
+//!
+//! Run with `cargo bench -p easerx --bench mut_updater`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use easerx::{Async, State, StateStore};
+use tokio::runtime::Runtime;
+
+const ROWS: usize = 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq)]
+struct LargeState {
+    rows: Vec<u64>,
+    total: Async<u64>,
+}
+
+impl State for LargeState {}
+
+fn large_state() -> LargeState {
+    LargeState {
+        rows: vec![1; ROWS],
+        total: Async::Uninitialized,
+    }
+}
+
+fn mut_updater(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("execute_large_state");
+
+    let store = runtime.block_on(async { StateStore::new(large_state()) });
+    group.bench_function("by_value", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                store
+                    .execute(|| 42_u64, |state, total| LargeState { total, ..state })
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    let store = runtime.block_on(async { StateStore::new(large_state()) });
+    group.bench_function("in_place", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                store
+                    .execute_mut(|| 42_u64, |state, total| state.total = total)
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, mut_updater);
+criterion_main!(benches);
@@ -0,0 +1,81 @@
+//! Reads of the current state from many threads while the store keeps committing updates.
+//!
+//! `get_state` takes the state lock unless the `arc-swap` feature serves it from the read cache;
+//! `get_versioned_state` always takes the lock. Compare both runs:
+//!
+//! `cargo bench -p easerx --bench read_throughput` and
+//! `cargo bench -p easerx --bench read_throughput --features arc-swap`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use easerx::{State, StateStore};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use tokio::runtime::Runtime;
+
+const READERS: usize = 8;
+const READS_PER_READER: usize = 1_000;
+
+#[derive(Clone, Debug)]
+struct MetricsState {
+    tick: u64,
+    labels: Arc<Vec<String>>,
+}
+
+impl State for MetricsState {}
+
+fn read_all<F>(store: &StateStore<MetricsState>, read: F)
+where
+    F: Fn(&StateStore<MetricsState>) -> u64 + Sync,
+{
+    thread::scope(|scope| {
+        for _ in 0..READERS {
+            scope.spawn(|| {
+                for _ in 0..READS_PER_READER {
+                    criterion::black_box(read(store));
+                }
+            });
+        }
+    });
+}
+
+fn read_throughput(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let store = runtime.block_on(async {
+        StateStore::new(MetricsState {
+            tick: 0,
+            labels: Arc::new((0..64).map(|i| format!("label {i}")).collect()),
+        })
+    });
+
+    // Keeps the queue task committing, so readers contend with it for the state lock
+    let running = Arc::new(AtomicBool::new(true));
+    let writer = runtime.spawn({
+        let store = store.clone();
+        let running = running.clone();
+        async move {
+            while running.load(Ordering::Acquire) {
+                store
+                    .set_state(|state| MetricsState { tick: state.tick + 1, ..state })
+                    .unwrap();
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+
+    let mut group = c.benchmark_group("read_throughput");
+    group.bench_function("get_state", |b| {
+        b.iter(|| read_all(&store, |store| store.get_state().tick))
+    });
+    group.bench_function("get_versioned_state", |b| {
+        b.iter(|| read_all(&store, |store| store.get_versioned_state().1.tick))
+    });
+    group.finish();
+
+    running.store(false, Ordering::Release);
+    runtime.block_on(writer).unwrap();
+    criterion::black_box(store.get_state().labels.len());
+}
+
+criterion_group!(benches, read_throughput);
+criterion_main!(benches);
@@ -0,0 +1,82 @@
+//! Compares a retain-execute cycle on a large payload held as `Async<Vec<u8>>` and as `ArcAsync<Vec<u8>>`.
+//!
+//! Run with `cargo bench -p easerx --bench retain_payload`.
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use easerx::{ArcAsync, Async, State, StateStore};
+use std::sync::Arc;
+use tokio::runtime::Runtime;
+
+const PAYLOAD_SIZE: usize = 8 * 1024 * 1024;
+
+#[derive(Clone, Debug, PartialEq)]
+struct VecState {
+    blob: Async<Vec<u8>>,
+}
+
+impl State for VecState {}
+
+#[derive(Clone, Debug, PartialEq)]
+struct ArcState {
+    blob: ArcAsync<Vec<u8>>,
+}
+
+impl State for ArcState {}
+
+fn retain_payload(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("retain_execute_8mb");
+
+    let payload = vec![1u8; PAYLOAD_SIZE];
+    let store = runtime.block_on(async {
+        StateStore::new(VecState {
+            blob: Async::success(payload.clone()),
+        })
+    });
+    group.bench_function("vec", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                let payload = payload.clone();
+                store
+                    .execute_with_retain(
+                        move || payload,
+                        |state| &state.blob,
+                        |_, blob| VecState { blob },
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    let payload = Arc::new(vec![1u8; PAYLOAD_SIZE]);
+    let store = runtime.block_on(async {
+        StateStore::new(ArcState {
+            blob: Async::success(payload.clone()),
+        })
+    });
+    group.bench_function("arc", |b| {
+        b.iter(|| {
+            runtime.block_on(async {
+                let payload = payload.clone();
+                store
+                    .execute_with_retain(
+                        move || payload,
+                        |state| &state.blob,
+                        |_, blob| ArcState { blob },
+                    )
+                    .await
+                    .unwrap()
+                    .unwrap();
+                store.await_state().await.unwrap();
+            })
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, retain_payload);
+criterion_main!(benches);
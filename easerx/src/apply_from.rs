@@ -0,0 +1,199 @@
+use std::future::poll_fn;
+use std::pin::pin;
+use std::sync::Arc;
+use std::task::Poll;
+use futures_core::stream::Stream;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use tokio::task::JoinHandle;
+use crate::transition::{Origin, TransitionOrigin};
+use crate::{AsyncError, State, StateStore};
+
+/// How [`StateStore::apply_from`] handles states that arrive faster than the store applies them.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum ApplyMode {
+    /// Applies every received state, in order, each as its own commit.
+    #[default]
+    Everything,
+
+    /// Applies only the newest state available: whenever a state is received, every state that is
+    /// already waiting behind it is drained first, and only the last one is applied. The next
+    /// states are only read once that one has been committed, so a burst arriving while the store
+    /// is busy also collapses into one commit.
+    LatestOnly,
+}
+
+/// A state together with the store version it was read from or, for a remote edit, based on.
+///
+/// Send [`StateStore::snapshot`] to a peer, have it send back its edits with the version of the
+/// snapshot they started from, and apply them with [`StateStore::apply_versioned_from`], which
+/// detects edits based on an outdated state. With the `serde` feature, the version is serialized
+/// along with the state.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct StateSnapshot<S> {
+    /// The version of the store the state is based on, see [`StateStore::version`].
+    pub version: u64,
+    /// The state.
+    pub state: S,
+}
+
+impl<S: State> StateStore<S> {
+    /// Returns the current state together with its version, see
+    /// [`get_versioned_state`](Self::get_versioned_state).
+    pub fn snapshot(&self) -> StateSnapshot<S> {
+        let (version, state) = self.get_versioned_state();
+        StateSnapshot { version, state }
+    }
+
+    /// Applies the states received from `states` as wholesale replacements of the state, e.g. the
+    /// snapshots received from a remote peer.
+    ///
+    /// Each received state is queued as a reducer that replaces the current state. Conflicts with
+    /// local updates are resolved by queue order: a local reducer queued after a received state
+    /// sees that state, and a received state queued after a local reducer overwrites its result.
+    /// Use [`apply_versioned_from`](Self::apply_versioned_from) to merge instead.
+    /// See [`ApplyMode`] for how bursts are coalesced.
+    ///
+    /// The returned task ends when the stream ends or the store is closed; abort it to stop
+    /// applying earlier. States received while the store is [frozen](Self::freeze) are dropped.
+    /// Like a store clone, the task keeps the store's queue running while it is alive.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{ApplyMode, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Board {
+    ///     moves: u32,
+    /// }
+    /// impl State for Board {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(Board { moves: 0 });
+    ///     let (tx, rx) = futures::channel::mpsc::unbounded();
+    ///     for moves in 1..=3 {
+    ///         tx.unbounded_send(Board { moves })?;
+    ///     }
+    ///     drop(tx);
+    ///     store.apply_from(rx, ApplyMode::LatestOnly).await?;
+    ///     assert_eq!(store.await_state().await?.moves, 3);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn apply_from<St>(&self, states: St, mode: ApplyMode) -> JoinHandle<()>
+    where
+        St: Stream<Item = S> + Send + 'static,
+    {
+        let origin = Origin::here(TransitionOrigin::SetState);
+        self.spawn_apply(states, mode, move |store, state| {
+            store.set_state_at_version(move |_, _| Some(state), origin)
+        })
+    }
+
+    /// Like [`apply_from`](Self::apply_from), calling `on_conflict` for snapshots based on an
+    /// outdated state instead of overwriting it.
+    ///
+    /// Each snapshot is checked right before it is applied, on the store's queue:
+    ///
+    /// - a snapshot equal to the current state is skipped without a commit, so re-applying a
+    ///   snapshot is idempotent;
+    /// - a snapshot whose version is not older than the current version fast-forwards the store
+    ///   to its state;
+    /// - a snapshot whose version is older, because something was committed since the state it is
+    ///   based on, commits `on_conflict(local, remote)` instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{ApplyMode, State, StateSnapshot, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Doc {
+    ///     lines: Vec<String>,
+    /// }
+    /// impl State for Doc {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(Doc { lines: vec![] });
+    ///     let base = store.snapshot();
+    ///     store.set_state(|_| Doc { lines: vec!["local".to_string()] })?;
+    ///
+    ///     let (tx, rx) = futures::channel::mpsc::unbounded();
+    ///     tx.unbounded_send(StateSnapshot { version: base.version, state: Doc { lines: vec!["remote".to_string()] } })?;
+    ///     drop(tx);
+    ///     store
+    ///         .apply_versioned_from(rx, ApplyMode::Everything, |local: Doc, remote: Doc| Doc {
+    ///             lines: [local.lines, remote.lines].concat(),
+    ///         })
+    ///         .await?;
+    ///     assert_eq!(store.await_state().await?.lines, ["local", "remote"]);
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn apply_versioned_from<St, F>(&self, snapshots: St, mode: ApplyMode, on_conflict: F) -> JoinHandle<()>
+    where
+        S: PartialEq,
+        St: Stream<Item = StateSnapshot<S>> + Send + 'static,
+        F: Fn(S, S) -> S + Send + Sync + 'static,
+    {
+        let origin = Origin::here(TransitionOrigin::SetState);
+        let on_conflict = Arc::new(on_conflict);
+        self.spawn_apply(snapshots, mode, move |store, snapshot| {
+            let on_conflict = on_conflict.clone();
+            store.set_state_at_version(
+                move |version, local| {
+                    if local == snapshot.state {
+                        None
+                    } else if snapshot.version >= version {
+                        Some(snapshot.state)
+                    } else {
+                        Some(on_conflict(local, snapshot.state))
+                    }
+                },
+                origin,
+            )
+        })
+    }
+
+    /// Spawns the task reading `items` and queueing each one with `apply`, coalescing as `mode` says.
+    fn spawn_apply<T, St, F>(&self, items: St, mode: ApplyMode, apply: F) -> JoinHandle<()>
+    where
+        T: Send + 'static,
+        St: Stream<Item = T> + Send + 'static,
+        F: Fn(&StateStore<S>, T) -> Result<(), AsyncError> + Send + 'static,
+    {
+        let store = self.clone();
+        self.spawn(async move {
+            let mut items = pin!(items);
+            let mut ended = false;
+            while !ended {
+                let Some(mut latest) = poll_fn(|cx| items.as_mut().poll_next(cx)).await else {
+                    break;
+                };
+                if mode == ApplyMode::LatestOnly {
+                    // Drain whatever is already waiting, keeping only the newest item
+                    ended = poll_fn(|cx| loop {
+                        match items.as_mut().poll_next(cx) {
+                            Poll::Ready(Some(item)) => latest = item,
+                            Poll::Ready(None) => return Poll::Ready(true),
+                            Poll::Pending => return Poll::Ready(false),
+                        }
+                    })
+                    .await;
+                }
+                match apply(&store, latest) {
+                    Err(error) if !error.is_frozen() => break,
+                    _ => {}
+                }
+                // Let the items that arrive until this one is committed pile up
+                if mode == ApplyMode::LatestOnly && store.await_state().await.is_err() {
+                    break;
+                }
+            }
+        })
+    }
+}
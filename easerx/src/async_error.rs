@@ -1,6 +1,9 @@
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use std::cmp::PartialEq;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
 use thiserror::Error;
 
 /// Represents errors that can occur during asynchronous operations.
@@ -8,7 +11,7 @@ use thiserror::Error;
 /// This enum provides a standardized way to represent different types of errors
 /// that might occur during asynchronous operations, such as general errors,
 /// None values, cancellations, and timeouts.
-#[derive(Error, Debug, Clone, Eq, PartialEq, Hash)]
+#[derive(Error, Clone, Eq, PartialEq, Hash)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -16,8 +19,23 @@ use thiserror::Error;
 )]
 pub enum AsyncError {
     /// A general error with a message describing what went wrong.
-    #[error("{0}")]
-    Error(String),
+    ///
+    /// `context` holds structured metadata about the failure, such as a request id or the endpoint
+    /// that failed, see [`AsyncError::error_with_context`]. It is not part of the `Display` output.
+    ///
+    /// `class` tells whether retrying may help, see [`AsyncError::with_class`], and `io_kind` holds
+    /// the kind of the [`io::Error`] the error was converted from. Only `class` is serialized, and
+    /// only when set, so errors without it keep their serialized form.
+    #[error("{message}")]
+    Error {
+        message: String,
+        #[cfg_attr(feature = "serde", serde(default))]
+        context: BTreeMap<String, String>,
+        #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+        class: Option<ErrorClass>,
+        #[cfg_attr(feature = "serde", serde(skip))]
+        io_kind: Option<io::ErrorKind>,
+    },
 
     /// An operation returned None when a value was expected.
     #[error("Operation returned None!")]
@@ -27,15 +45,182 @@ pub enum AsyncError {
     #[error("Task was cancelled!")]
     Cancelled,
 
-    /// The operation timed out.
+    /// The operation timed out because the store gave up waiting, e.g. in `execute_with_timeout`.
     #[error("Deadline has elapsed!")]
     Timeout,
+
+    /// The [`Deadline`](crate::Deadline) of the operation passed; `budget` is the whole time the
+    /// deadline gave when it was created, not what was left when the execution started.
+    #[error("Deadline of {budget:?} has elapsed!")]
+    DeadlineExceeded { budget: std::time::Duration },
+
+    /// The computation itself reported a timeout, e.g. because the upstream service gave up.
+    ///
+    /// Wrap a computation's own `tokio::time::timeout` result in a [`TimeoutResult`](crate::TimeoutResult)
+    /// to produce it automatically.
+    #[error("Upstream deadline has elapsed!")]
+    UpstreamTimeout,
+
+    /// The computation panicked; `message` holds the panic message if it was a string.
+    ///
+    /// See [`PanicPolicy`](crate::PanicPolicy) to resume such panics instead.
+    #[error("Task panicked: {message}")]
+    Panic { message: String },
+
+    /// The store was frozen with [`StateStore::freeze`](crate::StateStore::freeze) and rejected the update.
+    #[error("Store is frozen!")]
+    Frozen,
+}
+
+/// A general error with only a message prints as `Error("message")`, like a tuple variant; the
+/// fields that are set are printed otherwise.
+impl fmt::Debug for AsyncError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AsyncError::Error { message, context, class: None, io_kind: None } if context.is_empty() => {
+                f.debug_tuple("Error").field(message).finish()
+            }
+            AsyncError::Error { message, context, class, io_kind } => {
+                let mut debug = f.debug_struct("Error");
+                debug.field("message", message);
+                if !context.is_empty() {
+                    debug.field("context", context);
+                }
+                if let Some(class) = class {
+                    debug.field("class", class);
+                }
+                if let Some(io_kind) = io_kind {
+                    debug.field("io_kind", io_kind);
+                }
+                debug.finish()
+            }
+            AsyncError::None => f.write_str("None"),
+            AsyncError::Cancelled => f.write_str("Cancelled"),
+            AsyncError::Timeout => f.write_str("Timeout"),
+            AsyncError::DeadlineExceeded { budget } => {
+                f.debug_struct("DeadlineExceeded").field("budget", budget).finish()
+            }
+            AsyncError::UpstreamTimeout => f.write_str("UpstreamTimeout"),
+            AsyncError::Panic { message } => f.debug_struct("Panic").field("message", message).finish(),
+            AsyncError::Frozen => f.write_str("Frozen"),
+        }
+    }
+}
+
+/// A coarse classification of a failure telling whether retrying it may help, see
+/// [`AsyncError::error_class`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub enum ErrorClass {
+    /// The failure may go away on its own, e.g. a dropped connection or a timeout.
+    Transient,
+    /// The operation fails the same way again unless something changes, e.g. a missing file or a
+    /// rejected request.
+    Permanent,
+    /// The other side asked to slow down; retrying after a delay may help.
+    RateLimited,
+}
+
+impl ErrorClass {
+    /// Returns the class of an I/O failure of `kind`, or `None` if the kind doesn't tell.
+    pub fn of_io(kind: io::ErrorKind) -> Option<ErrorClass> {
+        use io::ErrorKind::*;
+        match kind {
+            TimedOut | Interrupted | WouldBlock | ConnectionReset | ConnectionAborted | ConnectionRefused
+            | NotConnected | BrokenPipe | UnexpectedEof => Some(ErrorClass::Transient),
+            NotFound | PermissionDenied | AlreadyExists | InvalidInput | InvalidData | Unsupported => {
+                Some(ErrorClass::Permanent)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Tells which side gave up on an operation that timed out, see [`AsyncError::timeout_source`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum TimeoutSource {
+    /// The store's own timeout or a deadline elapsed ([`AsyncError::Timeout`],
+    /// [`AsyncError::DeadlineExceeded`]).
+    Store,
+    /// The computation reported a timeout ([`AsyncError::UpstreamTimeout`]).
+    Computation,
 }
 
 impl AsyncError {
     pub fn error(msg: impl Into<String>) -> Self {
-        AsyncError::Error(msg.into())
+        AsyncError::Error {
+            message: msg.into(),
+            context: BTreeMap::new(),
+            class: None,
+            io_kind: None,
+        }
+    }
+
+    /// Creates a general error of the given [`ErrorClass`], e.g. from the status of an HTTP response.
+    ///
+    /// ```rust
+    /// use easerx::{AsyncError, ErrorClass};
+    ///
+    /// fn from_status(status: u16) -> AsyncError {
+    ///     let class = match status {
+    ///         429 => ErrorClass::RateLimited,
+    ///         500.. => ErrorClass::Transient,
+    ///         _ => ErrorClass::Permanent,
+    ///     };
+    ///     AsyncError::with_class(class, format!("request failed with status {status}"))
+    /// }
+    /// assert!(from_status(503).is_retryable());
+    /// assert!(!from_status(404).is_retryable());
+    /// ```
+    pub fn with_class(class: ErrorClass, msg: impl Into<String>) -> Self {
+        AsyncError::Error {
+            message: msg.into(),
+            context: BTreeMap::new(),
+            class: Some(class),
+            io_kind: None,
+        }
     }
+
+    /// Creates a general error carrying structured metadata alongside its message.
+    ///
+    /// ```rust
+    /// use easerx::AsyncError;
+    ///
+    /// let error = AsyncError::error_with_context(
+    ///     "request failed",
+    ///     [("request_id", "42"), ("endpoint", "/users")],
+    /// );
+    /// assert_eq!(error.to_string(), "request failed");
+    /// assert_eq!(error.context_value("request_id"), Some("42"));
+    /// ```
+    pub fn error_with_context<'a>(
+        msg: impl Into<String>,
+        context: impl IntoIterator<Item = (&'a str, &'a str)>,
+    ) -> Self {
+        AsyncError::Error {
+            message: msg.into(),
+            context: context
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value.to_string()))
+                .collect(),
+            class: None,
+            io_kind: None,
+        }
+    }
+
+    /// Returns the context value stored under `key`, or `None` if there is none or this is not
+    /// a general error.
+    pub fn context_value(&self, key: &str) -> Option<&str> {
+        match self {
+            AsyncError::Error { context, .. } => context.get(key).map(String::as_str),
+            _ => None,
+        }
+    }
+
     /// Returns true if this error represents a None result.
     pub fn is_none(&self) -> bool {
         matches!(self, AsyncError::None)
@@ -51,8 +236,88 @@ impl AsyncError {
         matches!(self, AsyncError::Cancelled)
     }
 
-    /// Returns true if this error represents a timeout.
+    /// Returns true if this error represents a timeout, whichever side gave up.
     pub fn is_timeout(&self) -> bool {
-        matches!(self, AsyncError::Timeout)
+        self.timeout_source().is_some()
+    }
+
+    /// Returns true if this error represents a panicked computation.
+    pub fn is_panic(&self) -> bool {
+        matches!(self, AsyncError::Panic { .. })
+    }
+
+    /// Returns true if a frozen store rejected the update.
+    pub fn is_frozen(&self) -> bool {
+        matches!(self, AsyncError::Frozen)
+    }
+
+    /// Returns true if the store's own timeout or the deadline of the execution elapsed.
+    pub fn is_store_timeout(&self) -> bool {
+        matches!(self, AsyncError::Timeout | AsyncError::DeadlineExceeded { .. })
+    }
+
+    /// Returns true if the computation reported a timeout.
+    pub fn is_upstream_timeout(&self) -> bool {
+        matches!(self, AsyncError::UpstreamTimeout)
+    }
+
+    /// Returns true if the failure may go away on its own, so retrying the operation makes sense.
+    ///
+    /// Timeouts and general errors classified as [`ErrorClass::Transient`] or
+    /// [`ErrorClass::RateLimited`] are retryable; cancellations, panics, `None` results and other
+    /// general errors fail the same way again unless something changes.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self.error_class(), Some(ErrorClass::Transient | ErrorClass::RateLimited))
+    }
+
+    /// Returns the class of the failure: the class of a general error, set with
+    /// [`with_class`](Self::with_class) or derived from an [`io::Error`], and
+    /// [`ErrorClass::Transient`] for timeouts. Returns `None` if the failure isn't classified.
+    pub fn error_class(&self) -> Option<ErrorClass> {
+        match self {
+            AsyncError::Error { class, .. } => *class,
+            _ if self.is_timeout() => Some(ErrorClass::Transient),
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of the [`io::Error`] this error was converted from, if any.
+    pub fn io_error_kind(&self) -> Option<io::ErrorKind> {
+        match self {
+            AsyncError::Error { io_kind, .. } => *io_kind,
+            _ => None,
+        }
+    }
+
+    /// Returns which side timed out, or `None` if this error is not a timeout.
+    pub fn timeout_source(&self) -> Option<TimeoutSource> {
+        match self {
+            AsyncError::Timeout | AsyncError::DeadlineExceeded { .. } => Some(TimeoutSource::Store),
+            AsyncError::UpstreamTimeout => Some(TimeoutSource::Computation),
+            _ => None,
+        }
+    }
+}
+
+/// Converts an I/O failure into a general error keeping its message and [`io::ErrorKind`], and
+/// classified with [`ErrorClass::of_io`].
+///
+/// ```rust
+/// use std::io;
+/// use easerx::{AsyncError, ErrorClass};
+///
+/// let error = AsyncError::from(io::Error::new(io::ErrorKind::ConnectionReset, "reset by peer"));
+/// assert_eq!(error.io_error_kind(), Some(io::ErrorKind::ConnectionReset));
+/// assert_eq!(error.error_class(), Some(ErrorClass::Transient));
+/// ```
+impl From<io::Error> for AsyncError {
+    fn from(error: io::Error) -> Self {
+        let kind = error.kind();
+        AsyncError::Error {
+            message: error.to_string(),
+            context: BTreeMap::new(),
+            class: ErrorClass::of_io(kind),
+            io_kind: Some(kind),
+        }
     }
 }
@@ -0,0 +1,167 @@
+#[cfg(feature = "execute")]
+use std::cell::Cell;
+#[cfg(feature = "execute")]
+use std::fmt;
+use crate::{Async, AsyncError};
+
+/// An [`Async<T>`] for multi-phase operations that reports which stage is running and how far it got.
+///
+/// On top of the four variants of `Async<T>`, the `Stage` variant describes a running operation
+/// with the name of its current stage and the progress within that stage, so the UI can show
+/// messages like "Downloading… 40%". Use it with
+/// [`StateStore::execute_staged`](crate::StateStore::execute_staged), whose computation reports
+/// its stages through a [`StageReporter`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum AsyncStaged<T: Clone> {
+    /// The initial state before any operation has been attempted.
+    #[default]
+    Uninitialized,
+
+    /// The operation is in progress without stage information. May optionally contain the previous value.
+    Loading { value: Option<T> },
+
+    /// The operation is in progress in the stage `name`, `progress` being a fraction between `0.0` and `1.0`.
+    Stage {
+        name: &'static str,
+        progress: f32,
+        value: Option<T>,
+    },
+
+    /// The operation completed successfully with a result value.
+    Success { value: T },
+
+    /// The operation failed. Contains an error and optionally the previous value.
+    Fail { error: AsyncError, value: Option<T> },
+}
+
+impl<T: Clone> AsyncStaged<T> {
+    /// Creates a `Stage` at the start of the stage `name`, without a value.
+    pub fn stage(name: &'static str) -> Self {
+        AsyncStaged::Stage {
+            name,
+            progress: 0.0,
+            value: None,
+        }
+    }
+
+    /// Returns the name of the running stage, or `None` if no stage is running.
+    pub fn stage_name(&self) -> Option<&'static str> {
+        match self {
+            AsyncStaged::Stage { name, .. } => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Returns the progress of the running stage, or `None` if no stage is running.
+    pub fn progress(&self) -> Option<f32> {
+        match self {
+            AsyncStaged::Stage { progress, .. } => Some(*progress),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the operation is in progress, with or without stage information.
+    pub fn is_loading(&self) -> bool {
+        matches!(self, AsyncStaged::Loading { .. } | AsyncStaged::Stage { .. })
+    }
+
+    /// Returns true if the operation has completed (either successfully or with an error).
+    pub fn is_complete(&self) -> bool {
+        matches!(self, AsyncStaged::Success { .. } | AsyncStaged::Fail { .. })
+    }
+
+    /// Returns a reference to the contained value, whether it is a result or a retained value.
+    pub fn value_ref(&self) -> Option<&T> {
+        match self {
+            AsyncStaged::Uninitialized => None,
+            AsyncStaged::Loading { value }
+            | AsyncStaged::Stage { value, .. }
+            | AsyncStaged::Fail { value, .. } => value.as_ref(),
+            AsyncStaged::Success { value } => Some(value),
+        }
+    }
+
+    /// Converts into an `Async<T>`, turning a `Stage` into `Loading`.
+    pub fn into_async(self) -> Async<T> {
+        match self {
+            AsyncStaged::Uninitialized => Async::Uninitialized,
+            AsyncStaged::Loading { value } | AsyncStaged::Stage { value, .. } => Async::Loading { value },
+            AsyncStaged::Success { value } => Async::Success { value },
+            AsyncStaged::Fail { error, value } => Async::Fail { error, value },
+        }
+    }
+}
+
+impl<T: Clone> From<Async<T>> for AsyncStaged<T> {
+    fn from(value: Async<T>) -> Self {
+        match value {
+            Async::Uninitialized => AsyncStaged::Uninitialized,
+            Async::Loading { value } => AsyncStaged::Loading { value },
+            Async::Success { value } => AsyncStaged::Success { value },
+            Async::Fail { error, value } => AsyncStaged::Fail { error, value },
+        }
+    }
+}
+
+#[cfg(feature = "execute")]
+type StagePublisher = Box<dyn Fn(&'static str, f32) + Send>;
+
+#[cfg(feature = "execute")]
+/// Reports the stage and progress of a computation run by
+/// [`StateStore::execute_staged`](crate::StateStore::execute_staged).
+///
+/// Every report queues a state update with an [`AsyncStaged::Stage`], ordered before the result.
+pub struct StageReporter {
+    stages: Vec<&'static str>,
+    current: Cell<Option<&'static str>>,
+    publish: StagePublisher,
+}
+
+#[cfg(feature = "execute")]
+impl StageReporter {
+    pub(crate) fn new(stages: Vec<&'static str>, publish: StagePublisher) -> Self {
+        let current = Cell::new(stages.first().copied());
+        StageReporter {
+            stages,
+            current,
+            publish,
+        }
+    }
+
+    /// Returns the stages the computation was started with.
+    pub fn stages(&self) -> &[&'static str] {
+        &self.stages
+    }
+
+    /// Enters the stage `name` with a progress of `0.0`.
+    ///
+    /// Names that are not part of the planned stages are ignored.
+    pub fn report_stage(&self, name: &'static str) {
+        if !self.stages.contains(&name) {
+            #[cfg(feature = "tracing")]
+            tracing::debug!(stage = name, "ignored a stage that was not planned");
+            return;
+        }
+        self.current.set(Some(name));
+        (self.publish)(name, 0.0);
+    }
+
+    /// Reports the progress within the current stage, clamped to `0.0..=1.0`.
+    ///
+    /// Ignored if no stage was planned.
+    pub fn report_progress(&self, progress: f32) {
+        if let Some(name) = self.current.get() {
+            (self.publish)(name, progress.clamp(0.0, 1.0));
+        }
+    }
+}
+
+#[cfg(feature = "execute")]
+impl fmt::Debug for StageReporter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StageReporter")
+            .field("stages", &self.stages)
+            .field("current", &self.current.get())
+            .finish_non_exhaustive()
+    }
+}
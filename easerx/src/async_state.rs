@@ -1,3 +1,5 @@
+use std::fmt;
+use std::sync::Arc;
 use crate::async_error::AsyncError;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -9,7 +11,11 @@ use serde::{Deserialize, Serialize};
 /// It provides a uniform way to represent and handle asynchronous state in a reactive application.
 ///
 /// The type parameter `T` represents the successful result type of the operation.
-#[derive(Debug, Clone, Eq, PartialEq, Default, Hash)]
+///
+/// The `Debug` output shortens the value's own `Debug` representation to
+/// [`DEBUG_VALUE_LIMIT`](Self::DEBUG_VALUE_LIMIT) characters, so large payloads don't flood logs
+/// and test failure messages. Use [`fmt_full`](Self::fmt_full) for the complete output.
+#[derive(Clone, Eq, PartialEq, Default, Hash)]
 #[cfg_attr(
     feature = "serde",
     derive(Serialize, Deserialize),
@@ -30,6 +36,25 @@ pub enum Async<T: Clone> {
     Fail { error: AsyncError, value: Option<T> },
 }
 
+/// An alias of [`Async<T>`] emphasizing locally produced data.
+///
+/// The alias carries no behavior of its own; it documents that a field is computed locally
+/// (e.g. parsed from a file or derived from other state).
+pub type AsyncData<T> = Async<T>;
+
+/// An alias of [`Async<T>`] emphasizing remote (network) data.
+///
+/// With the `remote` feature enabled, `easerx::remote::AsyncRemoteExt` adds
+/// network-specific helpers such as `is_network_error`.
+pub type AsyncRemote<T> = Async<T>;
+
+/// An [`Async<T>`] holding its value behind an [`Arc`].
+///
+/// Retaining a value while loading (see `execute_with_retain`) clones it. For large payloads such as
+/// multi-megabyte buffers, prefer `ArcAsync<T>` over `Async<T>`: cloning only bumps the reference count,
+/// and the retained value is the very same allocation as the previous one.
+pub type ArcAsync<T> = Async<Arc<T>>;
+
 impl<T: Clone> Async<T> {
     /// Returns true if the operation has completed (either successfully or with an error).
     pub fn is_complete(&self) -> bool {
@@ -97,7 +122,7 @@ impl<T: Clone> Async<T> {
         }
     }
 
-    /// Returns true if the operation failed because it timed out.
+    /// Returns true if the operation failed because it timed out, whichever side gave up.
     pub fn is_fail_with_timeout(&self) -> bool {
         if let Async::Fail { error, .. } = self {
             error.is_timeout()
@@ -106,6 +131,58 @@ impl<T: Clone> Async<T> {
         }
     }
 
+    /// Returns true if the operation failed because the computation panicked.
+    pub fn is_fail_with_panic(&self) -> bool {
+        if let Async::Fail { error, .. } = self {
+            error.is_panic()
+        } else {
+            false
+        }
+    }
+
+    /// Returns true if the operation failed because the computation itself reported a timeout.
+    pub fn is_fail_with_upstream_timeout(&self) -> bool {
+        if let Async::Fail { error, .. } = self {
+            error.is_upstream_timeout()
+        } else {
+            false
+        }
+    }
+
+    /// Returns true if the operation succeeded, which settles it for good.
+    ///
+    /// Same as [`is_success`](Async::is_success), named to pair with
+    /// [`is_settled_fail`](Async::is_settled_fail).
+    pub fn is_settled_success(&self) -> bool {
+        self.is_success()
+    }
+
+    /// Returns true if the operation failed with an error that retrying won't fix,
+    /// see [`AsyncError::is_retryable`].
+    pub fn is_settled_fail(&self) -> bool {
+        matches!(self, Async::Fail { error, .. } if !error.is_retryable())
+    }
+
+    /// Returns true if the state will not change anymore without an explicit action, such as the
+    /// user pressing "retry".
+    ///
+    /// Unlike [`is_complete`](Async::is_complete), a failure with a retryable error like a timeout
+    /// is not terminal, while a cancelled operation is. `Uninitialized` and `Loading` are never terminal.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, AsyncError};
+    ///
+    /// assert!(Async::success(1).is_terminal());
+    /// assert!(Async::<i32>::fail(AsyncError::Cancelled, None).is_terminal());
+    /// assert!(!Async::<i32>::fail(AsyncError::Timeout, None).is_terminal());
+    /// assert!(!Async::<i32>::loading(None).is_terminal());
+    /// ```
+    pub fn is_terminal(&self) -> bool {
+        self.is_settled_success() || self.is_settled_fail()
+    }
+
     /// Consumes the `Async` and returns the contained value if available.
     ///
     /// This method extracts the value from any variant that might contain it:
@@ -151,6 +228,57 @@ impl<T: Clone> Async<T> {
         }
     }
 
+    /// Returns true if the operation succeeded with a value equal to `other`.
+    ///
+    /// Retained values of `Loading` and `Fail` states are not considered; use [`contains`](Self::contains) for that.
+    pub fn success_eq(&self, other: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        matches!(self, Async::Success { value } if value == other)
+    }
+
+    /// Returns true if any variant carries a value equal to `other`, including retained values.
+    pub fn contains(&self, other: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        self.value_ref() == Some(other)
+    }
+
+    /// Returns true if the operation failed with an error equal to `error`, regardless of the retained value.
+    pub fn error_eq(&self, error: &AsyncError) -> bool {
+        matches!(self, Async::Fail { error: e, .. } if e == error)
+    }
+
+    /// Compares two states like `==`, except that the retained values of `Loading` and `Fail` are ignored.
+    ///
+    /// Two `Success` states are equal if their values are, two `Fail` states if their errors are,
+    /// and any `Loading` equals any other `Loading`. This suits `stop_if` predicates that should not
+    /// depend on what was retained from a previous load.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # use easerx::Async;
+    /// let fail = Async::fail_with_message("x", Some(1));
+    /// assert!(fail.equals_ignoring_retain(&Async::fail_with_message("x", None)));
+    /// assert!(Async::loading(Some(1)).equals_ignoring_retain(&Async::loading(Some(2))));
+    /// assert!(!Async::success(1).equals_ignoring_retain(&Async::success(2)));
+    /// ```
+    pub fn equals_ignoring_retain(&self, other: &Self) -> bool
+    where
+        T: PartialEq,
+    {
+        match (self, other) {
+            (Async::Uninitialized, Async::Uninitialized) => true,
+            (Async::Loading { .. }, Async::Loading { .. }) => true,
+            (Async::Success { value }, Async::Success { value: other }) => value == other,
+            (Async::Fail { error, .. }, Async::Fail { error: other, .. }) => error == other,
+            _ => false,
+        }
+    }
+
     /// Sets or updates the retained value in `Loading` or `Fail` states.
     ///
     /// This method is useful when you want to update the retained value
@@ -201,6 +329,14 @@ impl<T: Clone> Async<T> {
         }
     }
 
+    /// Creates a new `Async` in the `Fail` state with a timeout reported by the computation itself.
+    pub fn fail_with_upstream_timeout(value: Option<T>) -> Self {
+        Async::Fail {
+            error: AsyncError::UpstreamTimeout,
+            value,
+        }
+    }
+
     /// Creates a new `Async` in the `Fail` state with a general error message.
     pub fn fail_with_message(message: impl Into<String>, value: Option<T>) -> Self {
         let error = AsyncError::error(message.into());
@@ -214,4 +350,285 @@ impl<T: Clone> Async<T> {
             value,
         }
     }
+
+    /// Converts into an [`ArcAsync<T>`], moving the contained or retained value into an `Arc`.
+    pub fn map_retained_to_arc(self) -> ArcAsync<T> {
+        match self {
+            Async::Uninitialized => Async::Uninitialized,
+            Async::Loading { value } => Async::loading(value.map(Arc::new)),
+            Async::Success { value } => Async::success(Arc::new(value)),
+            Async::Fail { error, value } => Async::fail(error, value.map(Arc::new)),
+        }
+    }
+
+    /// Transforms the contained value with a function that may fail, such as a parser.
+    ///
+    /// A `Success` whose transformation fails becomes `Fail` with [`AsyncError::Error`] carrying the
+    /// error's message, and no retained value. Retained values are transformed best-effort: a
+    /// `Loading` or `Fail` whose retained value can't be transformed keeps its variant and its
+    /// error, and only drops the retained value. `Uninitialized` stays as is and `f` isn't called.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, AsyncError};
+    ///
+    /// let port = Async::success("8080".to_string()).try_map(|text| text.parse::<u16>());
+    /// assert_eq!(port, Async::success(8080));
+    ///
+    /// let port = Async::success("http".to_string()).try_map(|text| text.parse::<u16>());
+    /// assert_eq!(port, Async::fail(AsyncError::error("invalid digit found in string"), None));
+    ///
+    /// // A retained value that can't be transformed is dropped, the variant is kept
+    /// let port = Async::loading(Some("http".to_string())).try_map(|text| text.parse::<u16>());
+    /// assert_eq!(port, Async::loading(None));
+    /// ```
+    pub fn try_map<U, E, F>(self, f: F) -> Async<U>
+    where
+        U: Clone,
+        E: ToString,
+        F: FnOnce(T) -> Result<U, E>,
+    {
+        match self {
+            Async::Uninitialized => Async::Uninitialized,
+            Async::Loading { value } => Async::Loading {
+                value: value.and_then(|value| f(value).ok()),
+            },
+            Async::Success { value } => match f(value) {
+                Ok(value) => Async::Success { value },
+                Err(error) => Async::Fail {
+                    error: AsyncError::error(error.to_string()),
+                    value: None,
+                },
+            },
+            Async::Fail { error, value } => Async::Fail {
+                error,
+                value: value.and_then(|value| f(value).ok()),
+            },
+        }
+    }
+
+    /// Combines two `Async` values into one, building the combined value with `f`.
+    ///
+    /// The phase of the result follows a fixed precedence, `Fail` > `Loading` > `Uninitialized` >
+    /// `Success`, so the result is `Success` only when both sides are:
+    ///
+    /// | `a` \ `b`      | Uninitialized | Loading | Success       | Fail     |
+    /// |----------------|---------------|---------|---------------|----------|
+    /// | Uninitialized  | Uninitialized | Loading | Uninitialized | Fail (b) |
+    /// | Loading        | Loading       | Loading | Loading       | Fail (b) |
+    /// | Success        | Uninitialized | Loading | Success       | Fail (b) |
+    /// | Fail           | Fail (a)      | Fail (a)| Fail (a)      | Fail (a) |
+    ///
+    /// When both sides fail, the error of `a` wins. A `Loading` or `Fail` result retains the value
+    /// built from both sides' values (retained or successful) if both have one, and `None` otherwise.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::Async;
+    ///
+    /// let user = Async::success("alice".to_string());
+    /// let unread = Async::loading(Some(3));
+    /// let title = Async::zip_with(&user, &unread, |user, unread| format!("{user} ({unread})"));
+    /// assert_eq!(title, Async::loading(Some("alice (3)".to_string())));
+    /// ```
+    pub fn zip_with<A, B, F>(a: &Async<A>, b: &Async<B>, f: F) -> Async<T>
+    where
+        A: Clone,
+        B: Clone,
+        F: FnOnce(&A, &B) -> T,
+    {
+        let value = match (a.value_ref(), b.value_ref()) {
+            (Some(a), Some(b)) => Some(f(a, b)),
+            _ => None,
+        };
+        match (a, b, value) {
+            (Async::Fail { error, .. }, _, value) | (_, Async::Fail { error, .. }, value) => {
+                Async::fail(error.clone(), value)
+            }
+            (Async::Loading { .. }, _, value) | (_, Async::Loading { .. }, value) => Async::loading(value),
+            (Async::Success { .. }, Async::Success { .. }, Some(value)) => Async::success(value),
+            _ => Async::Uninitialized,
+        }
+    }
+}
+
+impl<A: Clone, B: Clone> Async<(A, B)> {
+    /// Combines two `Async` values into an `Async` of both values, see [`Async::zip_with`] for the
+    /// precedence rules.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::Async;
+    ///
+    /// let user = Async::success("alice");
+    /// let settings = Async::success(true);
+    /// assert_eq!(Async::zip(&user, &settings), Async::success(("alice", true)));
+    /// assert!(Async::zip(&user, &Async::<bool>::loading(None)).is_loading());
+    /// ```
+    pub fn zip(a: &Async<A>, b: &Async<B>) -> Self {
+        Async::zip_with(a, b, |a, b| (a.clone(), b.clone()))
+    }
+}
+
+impl<A: Clone, B: Clone, C: Clone> Async<(A, B, C)> {
+    /// Combines three `Async` values into an `Async` of all values, see [`Async::zip_with`] for the
+    /// precedence rules. When several sides fail, the error of the first one wins.
+    pub fn zip3(a: &Async<A>, b: &Async<B>, c: &Async<C>) -> Self {
+        Async::zip_with(&Async::zip(a, b), c, |(a, b), c| (a.clone(), b.clone(), c.clone()))
+    }
+}
+
+impl<T> Async<Arc<T>> {
+    /// Creates a new `ArcAsync` in the `Success` state, wrapping the value in an `Arc`.
+    pub fn success_arc(value: T) -> Self {
+        Async::success(Arc::new(value))
+    }
+
+    /// Creates a new `ArcAsync` in the `Loading` state, wrapping the retained value in an `Arc`.
+    pub fn loading_arc(value: Option<T>) -> Self {
+        Async::loading(value.map(Arc::new))
+    }
+
+    /// Calls `f` with a reference to the value behind the `Arc`, if available.
+    ///
+    /// Like [`value_ref`](Self::value_ref), this includes the value retained while loading or after a
+    /// failure. It saves dereferencing the `Arc` by hand, e.g. `blob.with(Vec::len)`.
+    pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        self.value_ref().map(|value| f(value))
+    }
+}
+
+impl<T: Clone + fmt::Debug> Async<T> {
+    /// The number of characters of the value's `Debug` representation printed by `Async`'s `Debug` impl.
+    pub const DEBUG_VALUE_LIMIT: usize = 64;
+
+    /// Formats the state like its `Debug` impl, but with the complete `Debug` representation of the value.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::fmt;
+    /// use easerx::Async;
+    ///
+    /// struct Full<'a>(&'a Async<Vec<u8>>);
+    /// impl fmt::Debug for Full<'_> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         self.0.fmt_full(f)
+    ///     }
+    /// }
+    ///
+    /// let blob = Async::success(vec![0u8; 100]);
+    /// assert!(format!("{:?}", blob).contains("..."));
+    /// assert!(!format!("{:?}", Full(&blob)).contains("..."));
+    /// ```
+    pub fn fmt_full(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, |value| value)
+    }
+
+    /// Returns the `Success` value, panicking otherwise.
+    ///
+    /// The panic message describes the state: `Loading` with its retained value, or `Fail` with the
+    /// error and the retained value. Like `Option::unwrap`, this is meant for prototypes and tests
+    /// where anything but `Success` is a bug; production code should match on the state or use
+    /// [`value`](Self::value) instead.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use easerx::Async;
+    ///
+    /// assert_eq!(Async::success(7).unwrap(), 7);
+    /// ```
+    ///
+    /// ```should_panic
+    /// use easerx::Async;
+    ///
+    /// // panics with: called `Async::unwrap()` on a `Fail` value: offline (retained: Some(3))
+    /// Async::fail_with_message("offline", Some(3)).unwrap();
+    /// ```
+    #[track_caller]
+    pub fn unwrap(self) -> T {
+        match self {
+            Async::Success { value } => value,
+            other => panic!("called `Async::unwrap()` on {}", other.describe_unsuccessful()),
+        }
+    }
+
+    /// Returns the `Success` value, panicking with `msg` otherwise.
+    ///
+    /// Works like [`unwrap`](Self::unwrap), with `msg` prepended to the panic message.
+    /// The same caveat applies: keep it out of production code.
+    ///
+    /// ## Examples
+    ///
+    /// ```should_panic
+    /// use easerx::Async;
+    ///
+    /// // panics with: user should be loaded: a `Loading` value (retained: None)
+    /// Async::<String>::loading(None).expect("user should be loaded");
+    /// ```
+    #[track_caller]
+    pub fn expect(self, msg: &str) -> T {
+        match self {
+            Async::Success { value } => value,
+            other => panic!("{msg}: {}", other.describe_unsuccessful()),
+        }
+    }
+
+    fn describe_unsuccessful(&self) -> String {
+        match self {
+            Async::Uninitialized => "an `Uninitialized` value".to_string(),
+            Async::Loading { value } => format!("a `Loading` value (retained: {:?})", value.as_ref().map(Truncated)),
+            Async::Success { .. } => "a `Success` value".to_string(),
+            Async::Fail { error, value } => {
+                format!("a `Fail` value: {error} (retained: {:?})", value.as_ref().map(Truncated))
+            }
+        }
+    }
+
+    fn fmt_with<'a, V: fmt::Debug>(
+        &'a self,
+        f: &mut fmt::Formatter<'_>,
+        wrap: impl Fn(&'a T) -> V,
+    ) -> fmt::Result {
+        match self {
+            Async::Uninitialized => f.write_str("Uninitialized"),
+            Async::Loading { value } => f
+                .debug_struct("Loading")
+                .field("value", &value.as_ref().map(&wrap))
+                .finish(),
+            Async::Success { value } => f
+                .debug_struct("Success")
+                .field("value", &wrap(value))
+                .finish(),
+            Async::Fail { error, value } => f
+                .debug_struct("Fail")
+                .field("error", error)
+                .field("value", &value.as_ref().map(&wrap))
+                .finish(),
+        }
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for Async<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, Truncated)
+    }
+}
+
+/// Prints the `Debug` representation of a value, cut after [`Async::DEBUG_VALUE_LIMIT`] characters.
+struct Truncated<'a, T>(&'a T);
+
+impl<T: fmt::Debug> fmt::Debug for Truncated<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let full = format!("{:?}", self.0);
+        let limit = Async::<()>::DEBUG_VALUE_LIMIT;
+        match full.char_indices().nth(limit) {
+            Some((end, _)) => write!(f, "{}...", &full[..end]),
+            None => f.write_str(&full),
+        }
+    }
 }
@@ -0,0 +1,110 @@
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use crate::Async;
+
+/// An [`Async<T>`] that remembers when it was last accessed.
+///
+/// `AsyncTracked<T>` is the building block for least-recently-used caches of `Async<T>` values:
+/// reading the value through [`get`](Self::get) or calling [`touch`](Self::touch) refreshes the
+/// access timestamp, and [`accessed_at`](Self::accessed_at) can be used to order entries for eviction.
+///
+/// The timestamp uses interior mutability so that reads only need `&self`. It is stored in an
+/// atomic rather than a `Cell` to keep the wrapper `Sync`, which every [`State`](crate::State) requires.
+///
+/// Equality and hashing only consider the wrapped `Async<T>`; the access timestamp is metadata.
+pub struct AsyncTracked<T: Clone> {
+    value: Async<T>,
+    created_at: Instant,
+    accessed_nanos: AtomicU64,
+}
+
+impl<T: Clone> AsyncTracked<T> {
+    /// Wraps an `Async<T>`, marking it as accessed now.
+    pub fn new(value: Async<T>) -> Self {
+        AsyncTracked {
+            value,
+            created_at: Instant::now(),
+            accessed_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns a reference to the wrapped `Async<T>` and refreshes the access timestamp.
+    pub fn get(&self) -> &Async<T> {
+        self.touch();
+        &self.value
+    }
+
+    /// Returns a reference to the wrapped `Async<T>` without refreshing the access timestamp.
+    pub fn peek(&self) -> &Async<T> {
+        &self.value
+    }
+
+    /// Replaces the wrapped `Async<T>` and refreshes the access timestamp.
+    pub fn set(&mut self, value: Async<T>) {
+        self.value = value;
+        self.touch();
+    }
+
+    /// Consumes the wrapper and returns the wrapped `Async<T>`.
+    pub fn into_inner(self) -> Async<T> {
+        self.value
+    }
+
+    /// Marks the value as accessed now.
+    pub fn touch(&self) {
+        let elapsed = self.created_at.elapsed().as_nanos();
+        let elapsed = u64::try_from(elapsed).unwrap_or(u64::MAX);
+        self.accessed_nanos.fetch_max(elapsed, Ordering::Relaxed);
+    }
+
+    /// Returns the last time the value was accessed.
+    pub fn accessed_at(&self) -> Instant {
+        self.created_at + Duration::from_nanos(self.accessed_nanos.load(Ordering::Relaxed))
+    }
+}
+
+impl<T: Clone> From<Async<T>> for AsyncTracked<T> {
+    fn from(value: Async<T>) -> Self {
+        AsyncTracked::new(value)
+    }
+}
+
+impl<T: Clone> Default for AsyncTracked<T> {
+    fn default() -> Self {
+        AsyncTracked::new(Async::default())
+    }
+}
+
+impl<T: Clone> Clone for AsyncTracked<T> {
+    fn clone(&self) -> Self {
+        AsyncTracked {
+            value: self.value.clone(),
+            created_at: self.created_at,
+            accessed_nanos: AtomicU64::new(self.accessed_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl<T: Clone + PartialEq> PartialEq for AsyncTracked<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<T: Clone + Eq> Eq for AsyncTracked<T> {}
+
+impl<T: Clone + std::hash::Hash> std::hash::Hash for AsyncTracked<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for AsyncTracked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncTracked")
+            .field("value", &self.value)
+            .field("accessed_at", &self.accessed_at())
+            .finish()
+    }
+}
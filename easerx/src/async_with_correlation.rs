@@ -0,0 +1,47 @@
+use uuid::Uuid;
+use crate::Async;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An [`Async<T>`] tagged with the id of the request that triggered it.
+///
+/// Produced by [`StateStore::execute_with_correlation_id`](crate::StateStore::execute_with_correlation_id),
+/// so every state update of an execution, including `Loading`, can be traced back to its request,
+/// e.g. to attach the id to error reports or distributed traces.
+///
+/// Only available with the `uuid` feature enabled.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AsyncWithCorrelation<T: Clone> {
+    result: Async<T>,
+    correlation_id: Uuid,
+}
+
+impl<T: Clone> AsyncWithCorrelation<T> {
+    /// Tags `result` with `correlation_id`.
+    pub fn new(correlation_id: Uuid, result: Async<T>) -> Self {
+        AsyncWithCorrelation {
+            result,
+            correlation_id,
+        }
+    }
+
+    /// Returns a reference to the wrapped `Async<T>`.
+    pub fn result(&self) -> &Async<T> {
+        &self.result
+    }
+
+    /// Returns the id of the request that produced the result.
+    pub fn correlation_id(&self) -> Uuid {
+        self.correlation_id
+    }
+
+    /// Consumes the wrapper and returns the wrapped `Async<T>`.
+    pub fn into_inner(self) -> Async<T> {
+        self.result
+    }
+}
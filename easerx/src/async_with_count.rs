@@ -0,0 +1,67 @@
+use crate::Async;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An [`Async<T>`] that counts how many times it has been loaded.
+///
+/// The count increments every time the value transitions from `Loading` to a completed
+/// state (`Success` or `Fail`), so [`first_load`](Self::first_load) can be used to run
+/// one-time logic such as analytics or onboarding hints after the first fetch.
+///
+/// Use it with the `_counted` execution methods of [`StateStore`](crate::StateStore),
+/// which read the current wrapper from the state and record each transition.
+#[derive(Debug, Clone, Eq, PartialEq, Default, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(rename_all = "camelCase")
+)]
+pub struct AsyncWithCount<T: Clone> {
+    value: Async<T>,
+    load_count: u32,
+}
+
+impl<T: Clone> AsyncWithCount<T> {
+    /// Wraps an `Async<T>` with a load count of zero.
+    pub fn new(value: Async<T>) -> Self {
+        AsyncWithCount {
+            value,
+            load_count: 0,
+        }
+    }
+
+    /// Returns a reference to the wrapped `Async<T>`.
+    pub fn value(&self) -> &Async<T> {
+        &self.value
+    }
+
+    /// Consumes the wrapper and returns the wrapped `Async<T>`.
+    pub fn into_inner(self) -> Async<T> {
+        self.value
+    }
+
+    /// Returns how many loads have completed.
+    pub fn load_count(&self) -> u32 {
+        self.load_count
+    }
+
+    /// Returns true if exactly one load has completed.
+    pub fn first_load(&self) -> bool {
+        self.load_count == 1
+    }
+
+    /// Replaces the wrapped value, counting a completed load if it finishes a `Loading` state.
+    pub fn record(self, next: Async<T>) -> Self {
+        let completed = self.value.is_loading() && next.is_complete();
+        AsyncWithCount {
+            value: next,
+            load_count: self.load_count + u32::from(completed),
+        }
+    }
+}
+
+impl<T: Clone> From<Async<T>> for AsyncWithCount<T> {
+    fn from(value: Async<T>) -> Self {
+        AsyncWithCount::new(value)
+    }
+}
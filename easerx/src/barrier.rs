@@ -0,0 +1,72 @@
+use std::future::Future;
+use futures_core::future::BoxFuture;
+use crate::{ReadOnlyStore, State, StateStore, WaitError};
+
+/// A store that can be synchronized with [`barrier_all`], whatever the type of its state.
+///
+/// Implemented by [`StateStore`] and [`ReadOnlyStore`]. The trait is object safe, so stores of
+/// different state types can be listed together as `&dyn StoreBarrier`.
+pub trait StoreBarrier: Send + Sync {
+    /// Queues a barrier right away and returns a future resolving once the store has applied every
+    /// update queued before it, including a pending [`update_async`](StateStore::update_async).
+    fn barrier(&self) -> BoxFuture<'static, Result<(), WaitError>>;
+}
+
+impl<S: State> StoreBarrier for StateStore<S> {
+    fn barrier(&self) -> BoxFuture<'static, Result<(), WaitError>> {
+        Box::pin(self.queued_barrier())
+    }
+}
+
+impl<S: State> StoreBarrier for ReadOnlyStore<S> {
+    fn barrier(&self) -> BoxFuture<'static, Result<(), WaitError>> {
+        self.store.barrier()
+    }
+}
+
+/// Resolves once every store in `stores` has processed the messages queued before the call.
+///
+/// The barriers are queued when `barrier_all` is called, not when the future is first polled. Once
+/// it resolves, the state read from each store with `get_state` reflects at least every update
+/// queued before the call, which gives a frame loop reading several stores a consistent point to
+/// render from. Updates queued afterwards may already be applied too.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{barrier_all, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Input { clicks: u32 }
+/// impl State for Input {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Scene { highlighted: bool }
+/// impl State for Scene {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let input = StateStore::new(Input { clicks: 0 });
+///     let scene = StateStore::new(Scene { highlighted: false });
+///
+///     input.set_state(|input| Input { clicks: input.clicks + 1 })?;
+///     scene.set_state(|_| Scene { highlighted: true })?;
+///     barrier_all(&[&input, &scene]).await?;
+///
+///     assert_eq!(input.get_state().clicks, 1);
+///     assert!(scene.get_state().highlighted);
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// Returns [`WaitError::Closed`] if the queue of a store was stopped before it processed the barrier.
+pub fn barrier_all(stores: &[&dyn StoreBarrier]) -> impl Future<Output = Result<(), WaitError>> + Send + 'static {
+    let barriers: Vec<_> = stores.iter().map(|store| store.barrier()).collect();
+    async move {
+        for barrier in barriers {
+            barrier.await?;
+        }
+        Ok(())
+    }
+}
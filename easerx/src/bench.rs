@@ -0,0 +1,189 @@
+//! Load generators for measuring the update queue of a [`StateStore`].
+//!
+//! Only available with the `bench` feature enabled.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::task::JoinSet;
+use crate::{AsyncError, State, StateStore};
+
+type Update<S> = Arc<dyn Fn(S) -> S + Send + Sync>;
+
+/// Floods a store with `set_state` calls from concurrent tasks and measures how the queue keeps up.
+///
+/// Each of the `workers` tasks queues `ops_per_worker` updates as fast as it can. The latency of an
+/// update is the time between queueing it and its reducer running on the store's background task.
+/// By default every update returns the state unchanged; use [`update`](Self::update) to measure a
+/// realistic reducer.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::bench::StressTest;
+/// use easerx::{State, StateStore};
+///
+/// #[derive(Clone, Debug)]
+/// struct Counter {
+///     count: u64,
+/// }
+/// impl State for Counter {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Counter { count: 0 });
+///     let report = StressTest::new(store.clone(), 4, 1_000)
+///         .update(|state| Counter { count: state.count + 1 })
+///         .run()
+///         .await?;
+///     assert_eq!(store.get_state().count, 4_000);
+///     println!("{report}");
+///     Ok(())
+/// }
+/// ```
+pub struct StressTest<S: State> {
+    store: StateStore<S>,
+    workers: usize,
+    ops_per_worker: usize,
+    update: Update<S>,
+}
+
+impl<S: State> StressTest<S> {
+    /// Creates a stress test running `workers` tasks that queue `ops_per_worker` updates each.
+    pub fn new(store: StateStore<S>, workers: usize, ops_per_worker: usize) -> Self {
+        StressTest {
+            store,
+            workers,
+            ops_per_worker,
+            update: Arc::new(|state| state),
+        }
+    }
+
+    /// Sets the reducer every update applies. Defaults to returning the state unchanged.
+    pub fn update<F>(mut self, update: F) -> Self
+    where
+        F: Fn(S) -> S + Send + Sync + 'static,
+    {
+        self.update = Arc::new(update);
+        self
+    }
+
+    /// Runs the stress test and resolves once every queued update has been applied.
+    ///
+    /// Must be called from within a tokio runtime; use a multi-threaded one to put the queue
+    /// under contention.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `AsyncError` if the store stopped accepting updates during the run.
+    pub async fn run(self) -> Result<StressTestReport, AsyncError> {
+        let operations = self.workers * self.ops_per_worker;
+        let latencies = Arc::new(Mutex::new(Vec::with_capacity(operations)));
+        let depth = Arc::new(AtomicUsize::new(0));
+        let peak_depth = Arc::new(AtomicUsize::new(0));
+
+        let started = Instant::now();
+        let mut workers = JoinSet::new();
+        for _ in 0..self.workers {
+            let store = self.store.clone();
+            let update = self.update.clone();
+            let latencies = latencies.clone();
+            let depth = depth.clone();
+            let peak_depth = peak_depth.clone();
+            let ops = self.ops_per_worker;
+            workers.spawn(async move {
+                for _ in 0..ops {
+                    let queued_at = Instant::now();
+                    let queued = depth.fetch_add(1, Ordering::Relaxed) + 1;
+                    peak_depth.fetch_max(queued, Ordering::Relaxed);
+                    let update = update.clone();
+                    let latencies = latencies.clone();
+                    let depth = depth.clone();
+                    store.set_state(move |state| {
+                        depth.fetch_sub(1, Ordering::Relaxed);
+                        latencies
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(queued_at.elapsed());
+                        update(state)
+                    })?;
+                }
+                Ok::<(), AsyncError>(())
+            });
+        }
+        while let Some(joined) = workers.join_next().await {
+            joined.map_err(|e| AsyncError::error(e.to_string()))??;
+        }
+        self.store.await_state().await?;
+        let elapsed = started.elapsed();
+
+        let mut latencies = std::mem::take(&mut *latencies.lock().unwrap_or_else(|e| e.into_inner()));
+        latencies.sort_unstable();
+        Ok(StressTestReport {
+            operations,
+            elapsed,
+            p50: percentile(&latencies, 0.50),
+            p95: percentile(&latencies, 0.95),
+            p99: percentile(&latencies, 0.99),
+            peak_queue_depth: peak_depth.load(Ordering::Relaxed),
+        })
+    }
+}
+
+impl<S: State> fmt::Debug for StressTest<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StressTest")
+            .field("workers", &self.workers)
+            .field("ops_per_worker", &self.ops_per_worker)
+            .finish()
+    }
+}
+
+/// Returns the latency below which `fraction` of the sorted `latencies` fall.
+fn percentile(latencies: &[Duration], fraction: f64) -> Duration {
+    if latencies.is_empty() {
+        return Duration::ZERO;
+    }
+    let index = ((latencies.len() - 1) as f64 * fraction).round() as usize;
+    latencies[index]
+}
+
+/// The measurements of a [`StressTest`] run. `Display` prints them as a summary table.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StressTestReport {
+    /// The number of updates queued and applied.
+    pub operations: usize,
+    /// The time from starting the workers until the last update was applied.
+    pub elapsed: Duration,
+    /// The median latency between queueing an update and applying it.
+    pub p50: Duration,
+    /// The 95th percentile latency.
+    pub p95: Duration,
+    /// The 99th percentile latency.
+    pub p99: Duration,
+    /// The largest number of updates queued but not yet applied at any point of the run.
+    pub peak_queue_depth: usize,
+}
+
+impl StressTestReport {
+    /// Returns how many updates were applied per second.
+    pub fn throughput(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds == 0.0 {
+            return 0.0;
+        }
+        self.operations as f64 / seconds
+    }
+}
+
+impl fmt::Display for StressTestReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<16} {:>16}", "operations", self.operations)?;
+        writeln!(f, "{:<16} {:>16}", "elapsed", format!("{:.2?}", self.elapsed))?;
+        writeln!(f, "{:<16} {:>16}", "throughput", format!("{:.0} ops/s", self.throughput()))?;
+        writeln!(f, "{:<16} {:>16}", "latency p50", format!("{:.2?}", self.p50))?;
+        writeln!(f, "{:<16} {:>16}", "latency p95", format!("{:.2?}", self.p95))?;
+        writeln!(f, "{:<16} {:>16}", "latency p99", format!("{:.2?}", self.p99))?;
+        write!(f, "{:<16} {:>16}", "peak queue depth", self.peak_queue_depth)
+    }
+}
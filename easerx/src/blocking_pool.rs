@@ -0,0 +1,99 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::{JoinHandle, JoinSet};
+use crate::execution_span::ExecutionSpan;
+use crate::store_error::WeakStoreErrors;
+use crate::{LatencyHistogram, StoreError};
+
+/// The default time a blocking computation may wait for a thread before it is reported, see
+/// [`StateStoreBuilder::blocking_start_warning`](crate::StateStoreBuilder::blocking_start_warning).
+pub const DEFAULT_BLOCKING_START_WARNING: Duration = Duration::from_millis(100);
+
+/// Measures how the blocking computations of a store use the runtime's blocking pool.
+///
+/// Each computation captures an `Instant` when it is spawned and reads it once a thread picks it
+/// up. A long wait means the pool was saturated, which otherwise only shows as executions stuck
+/// in `Loading`.
+#[derive(Debug)]
+pub(crate) struct BlockingPressure {
+    running: AtomicUsize,
+    start_latency: LatencyHistogram,
+    warn_after: Duration,
+    errors: WeakStoreErrors,
+}
+
+impl BlockingPressure {
+    pub(crate) fn new(warn_after: Duration, errors: WeakStoreErrors) -> Self {
+        BlockingPressure {
+            running: AtomicUsize::new(0),
+            start_latency: LatencyHistogram::new(),
+            warn_after,
+            errors,
+        }
+    }
+
+    pub(crate) fn running(&self) -> usize {
+        self.running.load(Ordering::Acquire)
+    }
+
+    pub(crate) fn start_latency(&self) -> LatencyHistogram {
+        self.start_latency.clone()
+    }
+
+    /// Runs `f` in a blocking thread, inside the span of the calling task.
+    pub(crate) fn spawn<F, R>(self: &Arc<Self>, f: F) -> JoinHandle<R>
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        tokio::task::spawn_blocking(self.measured(f))
+    }
+
+    /// Like [`spawn`](Self::spawn), adding the blocking task to `tasks`.
+    pub(crate) fn spawn_on<F, R>(self: &Arc<Self>, tasks: &mut JoinSet<R>, f: F)
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        tasks.spawn_blocking(self.measured(f));
+    }
+
+    fn measured<F, R>(self: &Arc<Self>, f: F) -> impl FnOnce() -> R + Send + 'static
+    where
+        F: FnOnce() -> R + Send + 'static,
+        R: Send + 'static,
+    {
+        let pressure = self.clone();
+        let span = ExecutionSpan::current();
+        let spawned = Instant::now();
+        move || {
+            let _running = pressure.started(spawned.elapsed());
+            span.in_scope(f)
+        }
+    }
+
+    fn started(self: &Arc<Self>, waited: Duration) -> RunningGuard {
+        self.start_latency.record(waited);
+        self.running.fetch_add(1, Ordering::AcqRel);
+        if waited >= self.warn_after {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                waited = ?waited,
+                running = self.running(),
+                "blocking computation waited for a thread, the blocking pool may be saturated"
+            );
+            self.errors.publish(|| StoreError::BlockingPoolSaturated { waited });
+        }
+        RunningGuard(self.clone())
+    }
+}
+
+/// Counts a computation as running until it returns or panics.
+struct RunningGuard(Arc<BlockingPressure>);
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        self.0.running.fetch_sub(1, Ordering::AcqRel);
+    }
+}
@@ -0,0 +1,136 @@
+//! Pluggable serialization formats for persisted state.
+//!
+//! A [`Codec`] turns a serializable value into bytes and back. [`JsonCodec`] is always available
+//! with the `serde` feature; [`BincodeCodec`] and [`CborCodec`] are behind the `bincode` and `cbor`
+//! features respectively.
+//!
+//! [`encode_versioned`] and [`decode_versioned`] frame the payload with a small header carrying
+//! the codec's format tag and a schema version, so stored data can be migrated when the state
+//! shape changes.
+//!
+//! This module is only available with the `serde` feature enabled.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use crate::AsyncError;
+
+/// Magic bytes starting every versioned payload.
+const MAGIC: [u8; 2] = *b"ER";
+
+/// Length of the header written by [`encode_versioned`]: magic, format tag and schema version.
+pub const HEADER_LEN: usize = MAGIC.len() + 2;
+
+/// A serialization format for state.
+pub trait Codec: Send + Sync + 'static {
+    /// A byte identifying the format in versioned payloads.
+    fn format_tag(&self) -> u8;
+
+    /// Serializes a value into bytes.
+    fn encode<S: Serialize>(&self, value: &S) -> Result<Vec<u8>, AsyncError>;
+
+    /// Deserializes a value from bytes.
+    fn decode<S: DeserializeOwned>(&self, bytes: &[u8]) -> Result<S, AsyncError>;
+}
+
+/// A human-readable JSON codec, the default format.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonCodec;
+
+impl Codec for JsonCodec {
+    fn format_tag(&self) -> u8 {
+        b'J'
+    }
+
+    fn encode<S: Serialize>(&self, value: &S) -> Result<Vec<u8>, AsyncError> {
+        serde_json::to_vec(value).map_err(|e| AsyncError::error(e.to_string()))
+    }
+
+    fn decode<S: DeserializeOwned>(&self, bytes: &[u8]) -> Result<S, AsyncError> {
+        serde_json::from_slice(bytes).map_err(|e| AsyncError::error(e.to_string()))
+    }
+}
+
+/// A compact binary codec using `bincode`.
+///
+/// Only available with the `bincode` feature enabled.
+#[cfg(feature = "bincode")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BincodeCodec;
+
+#[cfg(feature = "bincode")]
+impl Codec for BincodeCodec {
+    fn format_tag(&self) -> u8 {
+        b'B'
+    }
+
+    fn encode<S: Serialize>(&self, value: &S) -> Result<Vec<u8>, AsyncError> {
+        bincode::serialize(value).map_err(|e| AsyncError::error(e.to_string()))
+    }
+
+    fn decode<S: DeserializeOwned>(&self, bytes: &[u8]) -> Result<S, AsyncError> {
+        bincode::deserialize(bytes).map_err(|e| AsyncError::error(e.to_string()))
+    }
+}
+
+/// A compact, self-describing binary codec using CBOR.
+///
+/// Only available with the `cbor` feature enabled.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CborCodec;
+
+#[cfg(feature = "cbor")]
+impl Codec for CborCodec {
+    fn format_tag(&self) -> u8 {
+        b'C'
+    }
+
+    fn encode<S: Serialize>(&self, value: &S) -> Result<Vec<u8>, AsyncError> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(value, &mut bytes).map_err(|e| AsyncError::error(e.to_string()))?;
+        Ok(bytes)
+    }
+
+    fn decode<S: DeserializeOwned>(&self, bytes: &[u8]) -> Result<S, AsyncError> {
+        ciborium::from_reader(bytes).map_err(|e| AsyncError::error(e.to_string()))
+    }
+}
+
+/// Serializes a value with `codec`, prefixed by a header carrying the format tag and `schema_version`.
+pub fn encode_versioned<C: Codec, S: Serialize>(
+    codec: &C,
+    schema_version: u8,
+    value: &S,
+) -> Result<Vec<u8>, AsyncError> {
+    let payload = codec.encode(value)?;
+    let mut bytes = Vec::with_capacity(HEADER_LEN + payload.len());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(codec.format_tag());
+    bytes.push(schema_version);
+    bytes.extend_from_slice(&payload);
+    Ok(bytes)
+}
+
+/// Deserializes a value written by [`encode_versioned`], returning it with its schema version.
+///
+/// Bytes that don't start with a versioned header are treated as a legacy payload written by
+/// [`Codec::encode`] directly and decoded as schema version `0`. A header written by a different
+/// codec is rejected.
+pub fn decode_versioned<C: Codec, S: DeserializeOwned>(
+    codec: &C,
+    bytes: &[u8],
+) -> Result<(u8, S), AsyncError> {
+    match bytes {
+        [m0, m1, tag, version, payload @ ..] if [*m0, *m1] == MAGIC => {
+            if *tag != codec.format_tag() {
+                return Err(AsyncError::error(format!(
+                    "Payload was encoded with format `{}`, expected `{}`",
+                    char::from(*tag),
+                    char::from(codec.format_tag())
+                )));
+            }
+            Ok((*version, codec.decode(payload)?))
+        }
+        _ => Ok((0, codec.decode(bytes)?)),
+    }
+}
@@ -0,0 +1,108 @@
+use std::future::Future;
+use std::time::Duration;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    static CURRENT_DEADLINE: Option<Deadline>;
+}
+
+/// A point in time by which a whole operation, including nested executions, must have completed.
+///
+/// Pass it to [`ExecuteOptions::deadline`](crate::ExecuteOptions::deadline), or run a future with
+/// [`with_deadline`] so that every execution started within it picks the deadline up. An execution
+/// with a deadline and no explicit [`Timeout`](crate::Timeout) times out after the time
+/// [`remaining`](Self::remaining) when it starts, or after the store's default timeout if that is
+/// shorter. Exceeding the deadline fails the execution with
+/// [`AsyncError::DeadlineExceeded`](crate::AsyncError::DeadlineExceeded), carrying the original budget.
+///
+/// Executions started while a deadline applies run their computation within the same deadline, so
+/// executions nested in an async computation share the remaining budget.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Deadline {
+    at: Instant,
+    budget: Duration,
+}
+
+impl Deadline {
+    /// Creates a deadline `budget` from now.
+    pub fn after(budget: Duration) -> Self {
+        Deadline {
+            at: Instant::now() + budget,
+            budget,
+        }
+    }
+
+    /// Creates a deadline at `at`; the budget is the time left until then.
+    pub fn at(at: Instant) -> Self {
+        Deadline {
+            at,
+            budget: at.saturating_duration_since(Instant::now()),
+        }
+    }
+
+    /// Returns the deadline of the current task, set with [`with_deadline`].
+    pub fn current() -> Option<Deadline> {
+        CURRENT_DEADLINE.try_with(|deadline| *deadline).ok().flatten()
+    }
+
+    /// Returns the instant of the deadline.
+    pub fn instant(&self) -> Instant {
+        self.at
+    }
+
+    /// Returns the whole time that was given when the deadline was created.
+    pub fn budget(&self) -> Duration {
+        self.budget
+    }
+
+    /// Returns the time left until the deadline, zero once it passed.
+    pub fn remaining(&self) -> Duration {
+        self.at.saturating_duration_since(Instant::now())
+    }
+
+    /// Returns true once the deadline passed.
+    pub fn is_elapsed(&self) -> bool {
+        self.remaining().is_zero()
+    }
+}
+
+/// Runs `future` with `deadline` as the [current deadline](Deadline::current), which executions
+/// started within it use when they have no explicit timeout.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use easerx::{with_deadline, Async, Deadline, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct TestState {
+///     data: Async<String>,
+/// }
+/// impl State for TestState {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(TestState { data: Async::Uninitialized });
+///     with_deadline(Deadline::after(Duration::from_millis(10)), async {
+///         store.async_execute(
+///             async {
+///                 tokio::time::sleep(Duration::from_secs(1)).await;
+///                 "late".to_string()
+///             },
+///             |_, data| TestState { data },
+///         )
+///         .await
+///     })
+///     .await??;
+///     assert!(store.await_state().await?.data.is_fail_with_timeout());
+///     Ok(())
+/// }
+/// ```
+pub async fn with_deadline<F: Future>(deadline: Deadline, future: F) -> F::Output {
+    CURRENT_DEADLINE.scope(Some(deadline), future).await
+}
+
+/// Runs `future` with `deadline` as the current deadline, clearing it for `None`.
+pub(crate) fn scoped<F: Future>(deadline: Option<Deadline>, future: F) -> impl Future<Output = F::Output> {
+    CURRENT_DEADLINE.scope(deadline, future)
+}
@@ -0,0 +1,75 @@
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use crate::{Async, AsyncError, ErrorClass};
+
+/// What to do with a failed execution, decided by the policy set with
+/// [`StateStore::with_error_recovery`](crate::StateStore::with_error_recovery).
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum RecoveryAction {
+    /// Runs the computation again right away, without writing the failure. Executions that run
+    /// their computation only once write the failure instead, see
+    /// [`StateStore::with_error_recovery`](crate::StateStore::with_error_recovery).
+    Retry,
+
+    /// Runs the computation again after the given delay, without writing the failure, like [`Retry`](Self::Retry).
+    RetryAfter(Duration),
+
+    /// Discards the error and restores the value retained while loading: `Success` with the
+    /// retained value, or `Uninitialized` if nothing was retained.
+    Ignore,
+
+    /// Writes the failure into the state as usual.
+    Propagate,
+}
+
+type RecoveryPolicy = Arc<dyn Fn(&AsyncError) -> RecoveryAction + Send + Sync>;
+
+/// The policy registered with [`StateStore::with_error_recovery`](crate::StateStore::with_error_recovery).
+pub(crate) struct ErrorRecovery {
+    policy: RwLock<Option<RecoveryPolicy>>,
+}
+
+impl ErrorRecovery {
+    pub(crate) fn new() -> Self {
+        ErrorRecovery {
+            policy: RwLock::new(None),
+        }
+    }
+
+    pub(crate) fn set(&self, policy: RecoveryPolicy) {
+        *self.policy.write().unwrap_or_else(|e| e.into_inner()) = Some(policy);
+    }
+
+    /// Consults the policy for a failed result. Successes, cancellations and stores without a
+    /// policy always propagate, and so do retries of [`ErrorClass::Permanent`] failures.
+    pub(crate) fn action<T: Clone>(&self, result: &Async<T>) -> RecoveryAction {
+        let Async::Fail { error, .. } = result else {
+            return RecoveryAction::Propagate;
+        };
+        if error.is_cancelled() {
+            return RecoveryAction::Propagate;
+        }
+        let policy = self.policy.read().unwrap_or_else(|e| e.into_inner()).clone();
+        match policy.map_or(RecoveryAction::Propagate, |policy| policy(error)) {
+            RecoveryAction::Retry | RecoveryAction::RetryAfter(_) if error.error_class() == Some(ErrorClass::Permanent) => {
+                RecoveryAction::Propagate
+            }
+            action => action,
+        }
+    }
+}
+
+/// The value written instead of an ignored failure.
+pub(crate) fn restored<T: Clone>(retained: Option<T>) -> Async<T> {
+    retained.map_or(Async::Uninitialized, Async::success)
+}
+
+impl fmt::Debug for ErrorRecovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let enabled = self.policy.read().map(|p| p.is_some()).unwrap_or_default();
+        f.debug_struct("ErrorRecovery")
+            .field("enabled", &enabled)
+            .finish()
+    }
+}
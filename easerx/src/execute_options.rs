@@ -0,0 +1,158 @@
+use std::time::Duration;
+use crate::{AsyncError, Deadline};
+
+/// How long [`StateStore::async_execute_cancellable_graceful`](crate::StateStore::async_execute_cancellable_graceful)
+/// waits for the cleanup of a cancelled computation before writing the cancellation anyway.
+pub const DEFAULT_CANCEL_GRACE: Duration = Duration::from_secs(5);
+
+/// How long an execution may run before it fails with [`AsyncError::Timeout`](crate::AsyncError::Timeout).
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum Timeout {
+    /// Uses the store's default, see
+    /// [`StateStoreBuilder::default_execute_timeout`](crate::StateStoreBuilder::default_execute_timeout).
+    #[default]
+    Default,
+
+    /// Fails the execution once the duration elapsed, whatever the store's default.
+    After(Duration),
+
+    /// Lets the execution run for as long as it takes, even if the store has a default.
+    None,
+}
+
+/// What the `Loading` and `Fail` states of a retaining execution keep of the previous value, see
+/// [`ExecuteOptions::retain`].
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum RetainPolicy {
+    /// Keeps the previous value while loading and after a failure.
+    #[default]
+    LoadingAndFail,
+
+    /// Keeps the previous value while loading and clears it on failure, so an error shows an
+    /// empty state.
+    LoadingOnly,
+
+    /// Clears the value while loading and brings the previous value back on failure.
+    FailOnly,
+}
+
+impl RetainPolicy {
+    /// Returns true if `Loading` keeps the previous value.
+    pub fn retains_loading(&self) -> bool {
+        matches!(self, RetainPolicy::LoadingAndFail | RetainPolicy::LoadingOnly)
+    }
+
+    /// Returns true if `Fail`, including a cancellation, keeps the previous value.
+    pub fn retains_fail(&self) -> bool {
+        matches!(self, RetainPolicy::LoadingAndFail | RetainPolicy::FailOnly)
+    }
+}
+
+/// Per-call settings of [`StateStore::execute_with_options`](crate::StateStore::execute_with_options)
+/// and [`StateStore::async_execute_with_options`](crate::StateStore::async_execute_with_options),
+/// overriding the store's defaults.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use easerx::{ExecuteOptions, Timeout};
+///
+/// let options = ExecuteOptions::new().timeout(Timeout::After(Duration::from_secs(30)));
+/// assert_eq!(options.timeout_setting(), Timeout::After(Duration::from_secs(30)));
+/// ```
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub struct ExecuteOptions {
+    timeout: Timeout,
+    deadline: Option<Deadline>,
+    retain: RetainPolicy,
+}
+
+/// The timeout an execution runs with, and the error it fails with once it elapses.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExecutionTimeout {
+    after: Duration,
+    /// The budget of the deadline the timeout comes from, if any.
+    budget: Option<Duration>,
+}
+
+impl ExecutionTimeout {
+    pub(crate) fn after(&self) -> Duration {
+        self.after
+    }
+
+    pub(crate) fn error(&self) -> AsyncError {
+        match self.budget {
+            Some(budget) => AsyncError::DeadlineExceeded { budget },
+            None => AsyncError::Timeout,
+        }
+    }
+}
+
+impl ExecuteOptions {
+    /// Creates options that keep every store default.
+    pub fn new() -> Self {
+        ExecuteOptions::default()
+    }
+
+    /// Sets the timeout of the execution. Defaults to [`Timeout::Default`].
+    pub fn timeout(mut self, timeout: Timeout) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the timeout setting of the execution.
+    pub fn timeout_setting(&self) -> Timeout {
+        self.timeout
+    }
+
+    /// Sets the deadline of the execution, replacing the one of [`with_deadline`](crate::with_deadline).
+    ///
+    /// The deadline only applies while the timeout is [`Timeout::Default`]; an explicit timeout wins.
+    pub fn deadline(mut self, deadline: Deadline) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Returns the deadline set with [`deadline`](Self::deadline).
+    pub fn deadline_setting(&self) -> Option<Deadline> {
+        self.deadline
+    }
+
+    /// Sets what a retaining execution keeps of the previous value, see
+    /// [`StateStore::execute_with_options_and_retain`](crate::StateStore::execute_with_options_and_retain).
+    /// Executions that don't retain ignore it. Defaults to [`RetainPolicy::LoadingAndFail`].
+    pub fn retain(mut self, policy: RetainPolicy) -> Self {
+        self.retain = policy;
+        self
+    }
+
+    /// Returns the retain policy of the execution.
+    pub fn retain_setting(&self) -> RetainPolicy {
+        self.retain
+    }
+
+    /// Returns the deadline of the execution: its own, or else the current one.
+    pub(crate) fn resolve_deadline(&self) -> Option<Deadline> {
+        self.deadline.or_else(Deadline::current)
+    }
+
+    /// Resolves the timeout to apply against the store's `default` and the deadline, measuring
+    /// the time left until the deadline now.
+    pub(crate) fn resolve_timeout(&self, default: Option<Duration>) -> Option<ExecutionTimeout> {
+        let plain = |after| ExecutionTimeout { after, budget: None };
+        match self.timeout {
+            Timeout::Default => match self.resolve_deadline() {
+                Some(deadline) if default.is_none_or(|default| deadline.remaining() <= default) => {
+                    Some(ExecutionTimeout {
+                        after: deadline.remaining(),
+                        budget: Some(deadline.budget()),
+                    })
+                }
+                _ => default.map(plain),
+            },
+            Timeout::After(timeout) => Some(plain(timeout)),
+            Timeout::None => None,
+        }
+    }
+}
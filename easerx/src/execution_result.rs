@@ -1,4 +1,11 @@
-use crate::Async;
+use std::any::Any;
+use std::future::Future;
+use std::io;
+use std::pin::Pin;
+use std::time::Duration;
+use tokio::time::error::Elapsed;
+use std::sync::Arc;
+use crate::{ArcAsync, Async, AsyncError};
 
 /// A trait for converting various result types into the `Async<T>` representation.
 ///
@@ -29,19 +36,34 @@ impl<T: Clone> ExecutionResult<T> for T {
 ///
 /// This implementation converts:
 /// - `Ok(value)` to `Async::Success { value }`
-/// - `Err(error)` to `Async::Fail` with the error message
+/// - `Err(error)` to `Async::Fail` with the error message. An [`AsyncError`] is kept as it is, and
+///   an [`io::Error`] is converted with its kind and [`ErrorClass`](crate::ErrorClass), see
+///   [`AsyncError::from`].
 impl<T: Clone, E> ExecutionResult<T> for Result<T, E>
 where
-    E: ToString,
+    E: ToString + 'static,
 {
     fn into_async(self) -> Async<T> {
         match self {
             Ok(value) => Async::success(value),
-            Err(error) => Async::fail_with_message(error.to_string(), None),
+            Err(error) => Async::fail(into_async_error(error), None),
         }
     }
 }
 
+/// Converts the error of a computation, keeping the structure of the error types EaseRx knows.
+fn into_async_error<E: ToString + 'static>(error: E) -> AsyncError {
+    let mut error = Some(error);
+    let slot: &mut dyn Any = &mut error;
+    if let Some(error) = slot.downcast_mut::<Option<AsyncError>>() {
+        return error.take().expect("the error is taken once");
+    }
+    if let Some(error) = slot.downcast_mut::<Option<io::Error>>() {
+        return error.take().expect("the error is taken once").into();
+    }
+    AsyncError::error(error.expect("the error is taken once").to_string())
+}
+
 /// Implementation for `Option<T>`.
 ///
 /// This implementation converts:
@@ -54,4 +76,154 @@ impl<T: Clone> ExecutionResult<T> for Option<T> {
             None => Async::fail_with_none(None),
         }
     }
-}
\ No newline at end of file
+}
+/// A trait for converting the result of a computation into an [`ArcAsync<T>`], for values that are
+/// not `Clone`.
+///
+/// This mirrors [`ExecutionResult`] without its `Clone` bound: direct values, `Result<T, E>` and
+/// `Option<T>` are converted the same way, with the value moved into an `Arc`. It is used by the
+/// `execute_arc` family of [`StateStore`](crate::StateStore), so results owning resources such as file
+/// handles or connection pools can be stored without writing the wrapping by hand.
+pub trait ArcExecutionResult<T> {
+    /// Converts the implementor into an `ArcAsync<T>` representation.
+    fn into_arc_async(self) -> ArcAsync<T>;
+}
+
+/// Implementation for direct values of type `T`.
+impl<T> ArcExecutionResult<T> for T {
+    fn into_arc_async(self) -> ArcAsync<T> {
+        Async::success(Arc::new(self))
+    }
+}
+
+/// Implementation for `Result<T, E>`, converted like [`ExecutionResult`] does.
+impl<T, E> ArcExecutionResult<T> for Result<T, E>
+where
+    E: ToString + 'static,
+{
+    fn into_arc_async(self) -> ArcAsync<T> {
+        match self {
+            Ok(value) => Async::success(Arc::new(value)),
+            Err(error) => Async::fail(into_async_error(error), None),
+        }
+    }
+}
+
+/// Implementation for `Option<T>`, converted like [`ExecutionResult`] does.
+impl<T> ArcExecutionResult<T> for Option<T> {
+    fn into_arc_async(self) -> ArcAsync<T> {
+        match self {
+            Some(value) => Async::success(Arc::new(value)),
+            None => Async::fail_with_none(None),
+        }
+    }
+}
+
+/// Carries an already wrapped result through the regular execution paths.
+#[cfg(feature = "execute")]
+pub(crate) struct ArcResult<T>(pub(crate) ArcAsync<T>);
+
+#[cfg(feature = "execute")]
+impl<T> ExecutionResult<Arc<T>> for ArcResult<T> {
+    fn into_async(self) -> ArcAsync<T> {
+        self.0
+    }
+}
+
+/// An adapter for the result of a computation's own `tokio::time::timeout`.
+///
+/// A plain `Result<R, Elapsed>` is converted like any other `Result`, turning the timeout into an
+/// error message. Wrapping it in `TimeoutResult` maps `Elapsed` to [`AsyncError::UpstreamTimeout`]
+/// instead, so the state tells a timeout reported by the computation apart from the store's own
+/// [`AsyncError::Timeout`]. On success, the inner `R` is converted as usual.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use easerx::{Async, State, StateStore, TimeoutResult};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct TestState {
+///    data: Async<String>,
+/// }
+/// impl State for TestState {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(TestState { data: Async::Uninitialized });
+/// #   #[cfg(feature = "execute")] {
+///     store.async_execute(
+///         TimeoutResult::within(Duration::from_millis(10), async {
+///             tokio::time::sleep(Duration::from_secs(1)).await;
+///             "too late".to_string()
+///         }),
+///         |_, data| TestState { data },
+///     ).await??;
+///     assert!(store.await_state().await?.data.is_fail_with_upstream_timeout());
+/// #   }
+///     Ok(())
+/// }
+/// ```
+///
+/// [`AsyncError::UpstreamTimeout`]: crate::AsyncError::UpstreamTimeout
+/// [`AsyncError::Timeout`]: crate::AsyncError::Timeout
+#[derive(Debug)]
+pub struct TimeoutResult<R>(pub Result<R, Elapsed>);
+
+impl<R> TimeoutResult<R> {
+    /// Runs `future` with `tokio::time::timeout` and wraps its result.
+    pub async fn within<F>(duration: Duration, future: F) -> Self
+    where
+        F: Future<Output = R>,
+    {
+        TimeoutResult(tokio::time::timeout(duration, future).await)
+    }
+}
+
+impl<R> From<Result<R, Elapsed>> for TimeoutResult<R> {
+    fn from(result: Result<R, Elapsed>) -> Self {
+        TimeoutResult(result)
+    }
+}
+
+impl<T: Clone, R: ExecutionResult<T>> ExecutionResult<T> for TimeoutResult<R> {
+    fn into_async(self) -> Async<T> {
+        match self.0 {
+            Ok(result) => result.into_async(),
+            Err(_) => Async::fail_with_upstream_timeout(None),
+        }
+    }
+}
+
+/// A trait for converting units of async work into a future resolving to `Async<T>`.
+///
+/// This gives generic code a single entry point for every kind of async computation EaseRx accepts:
+/// an already known `Async<T>`, or a future whose output implements [`ExecutionResult<T>`]
+/// (a direct value, a `Result<T, E>` or an `Option<T>`).
+pub trait IntoAsync<T: Clone> {
+    /// Converts the implementor into a boxed future resolving to an `Async<T>`.
+    fn into_async_future(self) -> Pin<Box<dyn Future<Output = Async<T>> + Send>>;
+}
+
+/// Implementation for an already known `Async<T>`.
+///
+/// The returned future resolves immediately to the value.
+impl<T: Clone + Send + 'static> IntoAsync<T> for Async<T> {
+    fn into_async_future(self) -> Pin<Box<dyn Future<Output = Async<T>> + Send>> {
+        Box::pin(std::future::ready(self))
+    }
+}
+
+/// Implementation for futures whose output can be converted with [`ExecutionResult`].
+///
+/// This covers `Future<Output = T>`, `Future<Output = Result<T, E>>` and `Future<Output = Option<T>>`.
+impl<T, R, F> IntoAsync<T> for F
+where
+    T: Clone + 'static,
+    R: ExecutionResult<T>,
+    F: Future<Output = R> + Send + 'static,
+{
+    fn into_async_future(self) -> Pin<Box<dyn Future<Output = Async<T>> + Send>> {
+        Box::pin(async move { self.await.into_async() })
+    }
+}
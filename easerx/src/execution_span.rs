@@ -0,0 +1,65 @@
+use std::future::Future;
+#[cfg(feature = "tracing")]
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The id of the next execution, unique within the process.
+#[cfg(feature = "tracing")]
+static NEXT_EXECUTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// The tracing context an execution runs in.
+///
+/// Spawned tasks and blocking threads don't inherit the current span, so an execution captures
+/// the span of its caller and re-enters it, together with an `execution` span carrying the
+/// execution id, wherever its work runs. Both spans are kept so the caller's span still applies
+/// when the `execution` span is filtered out. Without the `tracing` feature this is a no-op.
+#[derive(Debug, Clone)]
+pub(crate) struct ExecutionSpan {
+    #[cfg(feature = "tracing")]
+    caller: tracing::Span,
+    #[cfg(feature = "tracing")]
+    execution: tracing::Span,
+}
+
+impl ExecutionSpan {
+    /// Creates the context of a new execution started from the current span.
+    pub(crate) fn new() -> Self {
+        ExecutionSpan {
+            #[cfg(feature = "tracing")]
+            caller: tracing::Span::current(),
+            #[cfg(feature = "tracing")]
+            execution: tracing::info_span!(
+                "execution",
+                id = NEXT_EXECUTION_ID.fetch_add(1, Ordering::Relaxed)
+            ),
+        }
+    }
+
+    /// Captures the span the current task runs in, to carry it into a blocking thread.
+    pub(crate) fn current() -> Self {
+        ExecutionSpan {
+            #[cfg(feature = "tracing")]
+            caller: tracing::Span::current(),
+            #[cfg(feature = "tracing")]
+            execution: tracing::Span::none(),
+        }
+    }
+
+    /// Runs `f` inside the span.
+    pub(crate) fn in_scope<R>(&self, f: impl FnOnce() -> R) -> R {
+        #[cfg(feature = "tracing")]
+        return self.caller.in_scope(|| self.execution.in_scope(f));
+        #[cfg(not(feature = "tracing"))]
+        f()
+    }
+
+    /// Wraps `future` so every poll runs inside the span.
+    pub(crate) fn instrument<F: Future>(self, future: F) -> impl Future<Output = F::Output> {
+        #[cfg(feature = "tracing")]
+        {
+            use tracing::Instrument;
+            future.instrument(self.execution).instrument(self.caller)
+        }
+        #[cfg(not(feature = "tracing"))]
+        future
+    }
+}
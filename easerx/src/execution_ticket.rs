@@ -0,0 +1,116 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::runtime::Handle;
+use tokio::task::{JoinError, JoinHandle};
+use crate::AsyncError;
+#[cfg(feature = "debug-jobs")]
+use crate::TokenId;
+
+/// The handle of an execution started by one of the `execute` methods of [`StateStore`](crate::StateStore).
+///
+/// Awaiting the ticket works like awaiting a [`JoinHandle`]: it resolves to `Err(JoinError)` if the
+/// execution task panicked or was aborted, and otherwise to the result of the execution, which is an
+/// `AsyncError` if the state could not be updated (e.g. because the store's queue has stopped).
+///
+/// Most callers only care about the state updates and drop the ticket, so it is deliberately not
+/// `#[must_use]`. Instead, in debug builds, dropping a ticket before it resolved keeps watching the
+/// execution and logs the error (with the `tracing` feature) if it later fails, so such failures
+/// don't disappear silently. Dropping a ticket never cancels the execution.
+pub struct ExecutionTicket {
+    handle: Option<JoinHandle<Result<(), AsyncError>>>,
+    runtime: Handle,
+    #[cfg(feature = "debug-jobs")]
+    token_chain: Vec<TokenId>,
+}
+
+impl ExecutionTicket {
+    pub(crate) fn new(handle: JoinHandle<Result<(), AsyncError>>, runtime: Handle) -> Self {
+        ExecutionTicket {
+            handle: Some(handle),
+            runtime,
+            #[cfg(feature = "debug-jobs")]
+            token_chain: Vec::new(),
+        }
+    }
+
+    /// Records the ids of the tokens the execution runs with.
+    #[cfg(feature = "debug-jobs")]
+    pub(crate) fn with_token_chain(mut self, token_chain: Vec<TokenId>) -> Self {
+        self.token_chain = token_chain;
+        self
+    }
+
+    /// Returns the ids of the cancellation token the computation receives and of its ancestors,
+    /// starting with the outermost one.
+    ///
+    /// For keyed jobs this is the group token followed by the job's own token, which tells whether
+    /// cancelling a group reaches the job. Executions not started with a [`JobKey`](crate::JobKey)
+    /// return an empty chain. Only available with the `debug-jobs` feature enabled.
+    #[cfg(feature = "debug-jobs")]
+    pub fn token_chain(&self) -> &[TokenId] {
+        &self.token_chain
+    }
+
+    /// Aborts the execution task.
+    ///
+    /// The state is left as it was at the time of the abort, e.g. `Loading`.
+    /// Use the cancellable variants to end executions with a `Cancelled` state instead.
+    pub fn abort(&self) {
+        if let Some(handle) = &self.handle {
+            handle.abort();
+        }
+    }
+
+    /// Returns true if the execution task has finished.
+    pub fn is_finished(&self) -> bool {
+        self.handle.as_ref().is_none_or(JoinHandle::is_finished)
+    }
+
+    /// Converts the ticket into the underlying [`JoinHandle`], without watching the execution on drop.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the ticket has already been awaited to completion.
+    pub fn into_join_handle(mut self) -> JoinHandle<Result<(), AsyncError>> {
+        self.handle.take().expect("ExecutionTicket polled after completion")
+    }
+}
+
+impl Future for ExecutionTicket {
+    type Output = Result<Result<(), AsyncError>, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let handle = self.handle.as_mut().expect("ExecutionTicket polled after completion");
+        let result = Pin::new(handle).poll(cx);
+        if result.is_ready() {
+            self.handle = None;
+        }
+        result
+    }
+}
+
+impl Drop for ExecutionTicket {
+    fn drop(&mut self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        if let Some(handle) = self.handle.take() {
+            self.runtime.spawn(async move {
+                if let Ok(Err(_error)) = handle.await {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_error, "execution failed after its ExecutionTicket was dropped");
+                }
+            });
+        }
+    }
+}
+
+impl fmt::Debug for ExecutionTicket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutionTicket")
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
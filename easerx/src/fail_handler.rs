@@ -0,0 +1,57 @@
+use std::fmt;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, RwLock};
+use crate::AsyncError;
+
+type FailHandler = Arc<dyn Fn(&AsyncError) + Send + Sync>;
+
+/// The handlers registered with [`StateStore::on_async_fail`](crate::StateStore::on_async_fail).
+pub(crate) struct FailHandlers {
+    enabled: AtomicBool,
+    handlers: RwLock<Vec<FailHandler>>,
+}
+
+impl FailHandlers {
+    pub(crate) fn new() -> Self {
+        FailHandlers {
+            enabled: AtomicBool::new(false),
+            handlers: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn push(&self, handler: FailHandler) {
+        self.handlers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(handler);
+        self.enabled.store(true, Ordering::Release);
+    }
+
+    /// Calls every handler with `error`. A panicking handler is skipped without affecting the others.
+    pub(crate) fn notify(&self, error: &AsyncError) {
+        if !self.enabled.load(Ordering::Acquire) {
+            return;
+        }
+        let handlers = self
+            .handlers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+        for handler in handlers {
+            if catch_unwind(AssertUnwindSafe(|| handler(error))).is_err() {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(error = %error, "async fail handler panicked");
+            }
+        }
+    }
+}
+
+impl fmt::Debug for FailHandlers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.handlers.read().map(|h| h.len()).unwrap_or_default();
+        f.debug_struct("FailHandlers")
+            .field("handlers", &count)
+            .finish()
+    }
+}
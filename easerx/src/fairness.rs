@@ -0,0 +1,75 @@
+/// The default of [`FairnessPolicy::max_consecutive_actions`].
+pub const DEFAULT_MAX_CONSECUTIVE_ACTIONS: usize = 8;
+
+/// How the background task of a store interleaves its queues, see
+/// [`StateStoreBuilder::fairness`](crate::StateStoreBuilder::fairness).
+///
+/// Queued reducers always run before queued [`with_state`](crate::StateStore::with_state) actions,
+/// so a read never observes a state missing an update queued before it. An action that queues
+/// another action runs inline, though, and a chain of them keeps the background task busy: on a
+/// current-thread runtime the tasks that want to queue a reducer don't even run until it yields.
+/// The policy bounds such chains, so writes are delayed by a bounded number of reads.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{FairnessPolicy, State, StateStore};
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     counter: i32,
+/// }
+///
+/// impl State for AppState {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::builder(AppState { counter: 0 })
+///         .fairness(FairnessPolicy::new().max_consecutive_actions(2))
+///         .build();
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct FairnessPolicy {
+    max_consecutive_actions: usize,
+}
+
+impl Default for FairnessPolicy {
+    fn default() -> Self {
+        FairnessPolicy {
+            max_consecutive_actions: DEFAULT_MAX_CONSECUTIVE_ACTIONS,
+        }
+    }
+}
+
+impl FairnessPolicy {
+    /// Creates the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how many actions run back to back before the background task yields to the runtime,
+    /// letting other tasks queue their reducers, which then run before the next action.
+    ///
+    /// Values below `1` are treated as `1`.
+    /// Defaults to [`DEFAULT_MAX_CONSECUTIVE_ACTIONS`].
+    pub fn max_consecutive_actions(mut self, max: usize) -> Self {
+        self.max_consecutive_actions = max.max(1);
+        self
+    }
+
+    /// Returns the setting of [`max_consecutive_actions`](Self::max_consecutive_actions).
+    pub fn max_consecutive_actions_setting(&self) -> usize {
+        self.max_consecutive_actions
+    }
+
+    /// Counts an action run by the background task, yielding once `consecutive` actions ran back
+    /// to back. Processing a reducer resets `consecutive`.
+    pub(crate) async fn pace_action(&self, consecutive: &mut usize) {
+        *consecutive += 1;
+        if *consecutive >= self.max_consecutive_actions {
+            *consecutive = 0;
+            tokio::task::yield_now().await;
+        }
+    }
+}
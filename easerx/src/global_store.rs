@@ -0,0 +1,137 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use tokio::runtime::Handle;
+use crate::{AsyncError, State, StateStore};
+
+/// A [`StateStore`] stored in a `static`, for application-wide state.
+///
+/// A store spawns its background task on the tokio runtime it is created on, so a global store
+/// can't be built in a `static` initializer directly. `GlobalStore` defers the creation until the
+/// runtime runs:
+///
+/// - [`GlobalStore::new`] creates an empty slot that is filled with
+///   [`initialize`](Self::initialize), which must be called from within the runtime before the
+///   store is first used, typically at the start of `main`.
+/// - [`GlobalStore::lazy`] creates the store on first access, like a `std::sync::LazyLock`. The
+///   first access must then happen within the runtime.
+///
+/// Once created, `GlobalStore` dereferences to the `StateStore`, so the whole store API is
+/// available on the static. The store keeps working only as long as the runtime it was created on,
+/// which is why each `#[tokio::test]`, running on its own runtime, needs its own global store.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{GlobalStore, State};
+///
+/// #[derive(Clone, Debug, Default)]
+/// struct Counter {
+///     count: i32,
+/// }
+/// impl State for Counter {}
+///
+/// static COUNTER: GlobalStore<Counter> = GlobalStore::new();
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     COUNTER.initialize(Counter::default())?;
+///     COUNTER.set_state(|state| Counter { count: state.count + 1 })?;
+///     assert_eq!(COUNTER.await_state().await?.count, 1);
+///     Ok(())
+/// }
+/// ```
+pub struct GlobalStore<S: State> {
+    store: OnceLock<StateStore<S>>,
+    init: Option<fn() -> S>,
+}
+
+impl<S: State> GlobalStore<S> {
+    /// Creates a global store that must be initialized with [`initialize`](Self::initialize).
+    pub const fn new() -> Self {
+        GlobalStore {
+            store: OnceLock::new(),
+            init: None,
+        }
+    }
+
+    /// Creates a global store that is created from `init` on first access.
+    pub const fn lazy(init: fn() -> S) -> Self {
+        GlobalStore {
+            store: OnceLock::new(),
+            init: Some(init),
+        }
+    }
+
+    /// Creates the store with `initial_state` on the current tokio runtime.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `AsyncError` if called outside of a tokio runtime, or if the store was already
+    /// created, by an earlier call or by a first access to a lazy global store.
+    pub fn initialize(&self, initial_state: S) -> Result<&StateStore<S>, AsyncError> {
+        Self::ensure_runtime()?;
+        let mut initial_state = Some(initial_state);
+        let store = self.store.get_or_init(|| {
+            StateStore::new(initial_state.take().expect("the initializer runs at most once"))
+        });
+        match initial_state {
+            None => Ok(store),
+            Some(_) => Err(AsyncError::error("GlobalStore is already initialized")),
+        }
+    }
+
+    /// Returns the store, or `None` if it wasn't created yet. Never creates a lazy store.
+    pub fn get(&self) -> Option<&StateStore<S>> {
+        self.store.get()
+    }
+
+    /// Returns true once the store was created.
+    pub fn is_initialized(&self) -> bool {
+        self.store.get().is_some()
+    }
+
+    fn ensure_runtime() -> Result<(), AsyncError> {
+        Handle::try_current()
+            .map(|_| ())
+            .map_err(|_| AsyncError::error("GlobalStore must be initialized from within a tokio runtime"))
+    }
+}
+
+impl<S: State> Default for GlobalStore<S> {
+    fn default() -> Self {
+        GlobalStore::new()
+    }
+}
+
+impl<S: State> Deref for GlobalStore<S> {
+    type Target = StateStore<S>;
+
+    /// Returns the store, creating a lazy store on first access.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if the store wasn't initialized, or if a lazy store is first accessed outside of a
+    /// tokio runtime.
+    fn deref(&self) -> &Self::Target {
+        if let Some(store) = self.store.get() {
+            return store;
+        }
+        let Some(init) = self.init else {
+            panic!("GlobalStore used before GlobalStore::initialize was called");
+        };
+        if let Err(error) = Self::ensure_runtime() {
+            panic!("{error}");
+        }
+        self.store.get_or_init(|| StateStore::new(init()))
+    }
+}
+
+impl<S: State> fmt::Debug for GlobalStore<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GlobalStore")
+            .field("initialized", &self.is_initialized())
+            .field("lazy", &self.init.is_some())
+            .finish()
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "debug-jobs")]
+use std::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+/// A hierarchical key identifying a job started by a [`StateStore`](crate::StateStore).
+///
+/// The first segment is the group the job belongs to, e.g. the screen that started it.
+/// Cancelling the group with [`StateStore::cancel_group`](crate::StateStore::cancel_group)
+/// cancels every job whose key starts with that group.
+///
+/// ## Examples
+///
+/// ```
+/// use easerx::JobKey;
+///
+/// let key = JobKey::group("screen_x").child("load_user");
+/// assert_eq!(key.group_name(), "screen_x");
+/// assert_eq!(key.to_string(), "screen_x/load_user");
+/// ```
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct JobKey {
+    segments: Vec<String>,
+}
+
+impl JobKey {
+    /// Creates a key for the group itself.
+    pub fn group(name: impl Into<String>) -> Self {
+        JobKey {
+            segments: vec![name.into()],
+        }
+    }
+
+    /// Appends a segment below this key.
+    pub fn child(mut self, name: impl Into<String>) -> Self {
+        self.segments.push(name.into());
+        self
+    }
+
+    /// Returns the name of the group this key belongs to.
+    pub fn group_name(&self) -> &str {
+        &self.segments[0]
+    }
+
+    /// Returns all segments of the key, starting with the group name.
+    pub fn segments(&self) -> &[String] {
+        &self.segments
+    }
+}
+
+impl fmt::Display for JobKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.segments.join("/"))
+    }
+}
+
+/// Identifies a cancellation token created by the job registry of a store, see
+/// [`ExecutionTicket::token_chain`](crate::ExecutionTicket::token_chain).
+///
+/// Only available with the `debug-jobs` feature enabled.
+#[cfg(feature = "debug-jobs")]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct TokenId(u64);
+
+#[cfg(feature = "debug-jobs")]
+impl fmt::Display for TokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "token#{}", self.0)
+    }
+}
+
+/// A snapshot of a running keyed job, returned by
+/// [`StateStore::debug_jobs`](crate::StateStore::debug_jobs).
+///
+/// Only available with the `debug-jobs` feature enabled.
+#[cfg(feature = "debug-jobs")]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct JobInfo {
+    key: JobKey,
+    started_at: Instant,
+    token_chain: Vec<TokenId>,
+}
+
+#[cfg(feature = "debug-jobs")]
+impl JobInfo {
+    /// Returns the key the job was started with.
+    pub fn key(&self) -> &JobKey {
+        &self.key
+    }
+
+    /// Returns when the job was registered.
+    pub fn started_at(&self) -> Instant {
+        self.started_at
+    }
+
+    /// Returns the ids of the job's token and its ancestors, from the group token down to the
+    /// token the computation receives.
+    pub fn token_chain(&self) -> &[TokenId] {
+        &self.token_chain
+    }
+}
+
+/// A running job of a group.
+struct JobEntry {
+    key: JobKey,
+    #[cfg(feature = "debug-jobs")]
+    started_at: Instant,
+}
+
+/// The running jobs of one group, sharing a parent cancellation token.
+struct JobGroup {
+    token: CancellationToken,
+    #[cfg(feature = "debug-jobs")]
+    token_id: u64,
+    jobs: BTreeMap<u64, JobEntry>,
+}
+
+/// Tracks running keyed jobs by group.
+///
+/// Each job runs with a child token of its group's token, so cancelling the group
+/// reaches every job in it. A group is removed once it is cancelled or has no jobs left.
+/// Ids of jobs and group tokens come from the same counter, and a job's token has the job's id.
+#[derive(Default)]
+pub(crate) struct JobRegistry {
+    next_id: AtomicU64,
+    groups: Mutex<HashMap<String, JobGroup>>,
+}
+
+impl JobRegistry {
+    /// Registers a job and returns the guard removing it along with the token it must run with.
+    pub(crate) fn register(self: &Arc<Self>, key: JobKey) -> (JobGuard, CancellationToken) {
+        let group_name = key.group_name().to_string();
+        let mut groups = self.groups.lock().unwrap_or_else(|e| e.into_inner());
+        let group = groups.entry(group_name.clone()).or_insert_with(|| JobGroup {
+            token: CancellationToken::new(),
+            #[cfg(feature = "debug-jobs")]
+            token_id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            jobs: BTreeMap::new(),
+        });
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        group.jobs.insert(
+            id,
+            JobEntry {
+                key,
+                #[cfg(feature = "debug-jobs")]
+                started_at: Instant::now(),
+            },
+        );
+        let token = group.token.child_token();
+        let guard = JobGuard {
+            registry: self.clone(),
+            #[cfg(feature = "debug-jobs")]
+            token_chain: vec![TokenId(group.token_id), TokenId(id)],
+            group: group_name,
+            id,
+        };
+        (guard, token)
+    }
+
+    pub(crate) fn cancel_group(&self, group: &str) {
+        let removed = self
+            .groups
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(group);
+        if let Some(group) = removed {
+            group.token.cancel();
+        }
+    }
+
+    pub(crate) fn active_jobs(&self, group: &str) -> Vec<JobKey> {
+        self.groups
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(group)
+            .map(|group| group.jobs.values().map(|job| job.key.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Lists every running job of every group, in start order.
+    #[cfg(feature = "debug-jobs")]
+    pub(crate) fn debug_jobs(&self) -> Vec<JobInfo> {
+        let groups = self.groups.lock().unwrap_or_else(|e| e.into_inner());
+        let mut jobs: Vec<(u64, JobInfo)> = groups
+            .values()
+            .flat_map(|group| {
+                group.jobs.iter().map(|(id, job)| {
+                    let info = JobInfo {
+                        key: job.key.clone(),
+                        started_at: job.started_at,
+                        token_chain: vec![TokenId(group.token_id), TokenId(*id)],
+                    };
+                    (*id, info)
+                })
+            })
+            .collect();
+        jobs.sort_by_key(|(id, _)| *id);
+        jobs.into_iter().map(|(_, info)| info).collect()
+    }
+
+    fn remove(&self, group: &str, id: u64) {
+        let mut groups = self.groups.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(entry) = groups.get_mut(group) {
+            entry.jobs.remove(&id);
+            if entry.jobs.is_empty() {
+                groups.remove(group);
+            }
+        }
+    }
+}
+
+impl fmt::Debug for JobRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.groups.lock().map(|g| g.len()).unwrap_or_default();
+        f.debug_struct("JobRegistry")
+            .field("groups", &count)
+            .finish()
+    }
+}
+
+/// Removes a job from its group when dropped.
+pub(crate) struct JobGuard {
+    registry: Arc<JobRegistry>,
+    #[cfg(feature = "debug-jobs")]
+    token_chain: Vec<TokenId>,
+    group: String,
+    id: u64,
+}
+
+#[cfg(feature = "debug-jobs")]
+impl JobGuard {
+    /// Returns the ids of the job's token and its ancestors.
+    pub(crate) fn token_chain(&self) -> Vec<TokenId> {
+        self.token_chain.clone()
+    }
+}
+
+impl Drop for JobGuard {
+    fn drop(&mut self) {
+        self.registry.remove(&self.group, self.id);
+    }
+}
@@ -0,0 +1,132 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Bucket `i` counts latencies below `2^i` microseconds, the last one everything above.
+const BUCKETS: usize = 32;
+
+/// A histogram of the time updates spend queued, from `set_state` until their reducer ran.
+///
+/// Enable it with [`StateStoreBuilder::track_queue_latency`](crate::StateStoreBuilder::track_queue_latency)
+/// and read it with [`StateStore::queue_latency`](crate::StateStore::queue_latency). Latencies are
+/// counted in power-of-two microsecond buckets, so percentiles are upper bounds within a factor of two,
+/// capped at the largest latency recorded. Clones share the same counters.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{State, StateStore};
+///
+/// #[derive(Clone, Debug)]
+/// struct Meter {
+///     level: f32,
+/// }
+/// impl State for Meter {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::builder(Meter { level: 0.0 }).track_queue_latency().build();
+///     store.set_state(|_| Meter { level: 0.5 })?;
+///     store.await_state().await?;
+///
+///     let latency = store.queue_latency().unwrap();
+///     assert_eq!(latency.count(), 1);
+///     println!("p99 enqueue to commit: {:?}", latency.percentile(0.99));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    inner: Arc<Buckets>,
+}
+
+#[derive(Debug, Default)]
+struct Buckets {
+    counts: [AtomicU64; BUCKETS],
+    total_nanos: AtomicU64,
+    max_nanos: AtomicU64,
+}
+
+impl LatencyHistogram {
+    pub(crate) fn new() -> Self {
+        LatencyHistogram::default()
+    }
+
+    pub(crate) fn record(&self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        let bucket = ((u64::BITS - micros.leading_zeros()) as usize).min(BUCKETS - 1);
+        let nanos = u64::try_from(latency.as_nanos()).unwrap_or(u64::MAX);
+        self.inner.counts[bucket].fetch_add(1, Ordering::Relaxed);
+        self.inner.total_nanos.fetch_add(nanos, Ordering::Relaxed);
+        self.inner.max_nanos.fetch_max(nanos, Ordering::Relaxed);
+    }
+
+    /// Returns how many latencies were recorded.
+    pub fn count(&self) -> u64 {
+        self.inner.counts.iter().map(|count| count.load(Ordering::Relaxed)).sum()
+    }
+
+    /// Returns the largest latency recorded, or zero if none was.
+    pub fn max(&self) -> Duration {
+        Duration::from_nanos(self.inner.max_nanos.load(Ordering::Relaxed))
+    }
+
+    /// Returns the mean latency, or zero if none was recorded.
+    pub fn mean(&self) -> Duration {
+        match self.count() {
+            0 => Duration::ZERO,
+            count => Duration::from_nanos(self.inner.total_nanos.load(Ordering::Relaxed) / count),
+        }
+    }
+
+    /// Returns an upper bound of the latency below which `fraction` of the recorded latencies fall,
+    /// e.g. `0.99` for the 99th percentile. Returns zero if none was recorded.
+    pub fn percentile(&self, fraction: f64) -> Duration {
+        let counts = self.counts();
+        let total: u64 = counts.iter().sum();
+        if total == 0 {
+            return Duration::ZERO;
+        }
+        let rank = ((total as f64 * fraction.clamp(0.0, 1.0)).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bucket, count) in counts.iter().enumerate() {
+            seen += count;
+            if seen >= rank {
+                return bucket_bound(bucket).min(self.max());
+            }
+        }
+        self.max()
+    }
+
+    /// Returns the upper bound and count of every non-empty bucket, from the fastest to the slowest.
+    /// The slowest bucket's bound is `Duration::MAX`.
+    pub fn buckets(&self) -> Vec<(Duration, u64)> {
+        self.counts()
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .map(|(bucket, count)| (bucket_bound(bucket), count))
+            .collect()
+    }
+
+    /// Clears every recorded latency.
+    pub fn reset(&self) {
+        for count in &self.inner.counts {
+            count.store(0, Ordering::Relaxed);
+        }
+        self.inner.total_nanos.store(0, Ordering::Relaxed);
+        self.inner.max_nanos.store(0, Ordering::Relaxed);
+    }
+
+    fn counts(&self) -> Vec<u64> {
+        self.inner.counts.iter().map(|count| count.load(Ordering::Relaxed)).collect()
+    }
+}
+
+/// Returns the exclusive upper bound of `bucket`.
+fn bucket_bound(bucket: usize) -> Duration {
+    if bucket == BUCKETS - 1 {
+        Duration::MAX
+    } else {
+        Duration::from_micros(1 << bucket)
+    }
+}
@@ -50,6 +50,7 @@
 //! })?;
 //!
 //! // Execute an operation that updates state
+//! # #[cfg(feature = "execute")]
 //! store.execute(
 //!     || "example computation".to_string(),
 //!     |state, result| {
@@ -57,6 +58,7 @@
 //!     }
 //! );
 //!
+//! # #[cfg(feature = "execute")]
 //! store.to_signal()
 //!     .stop_if(|state| {state.data.is_complete()})
 //!     .for_each(|state| {
@@ -115,6 +117,27 @@
 //!   - `async_execute_cancellable`: Support for cancellation
 //!   - `async_execute_with_timeout`: Automatic timeout handling
 //!
+//! The `patterns` module composes these methods into helpers for recurring tasks: loading a value
+//! once, refreshing it when a signal changes, paginated loading and optimistic form submission.
+//!
+//! ## Feature Flags
+//!
+//! - `execute` (default): the `execute` family, cancellation, timeouts and keyed jobs. Without it,
+//!   EaseRx is a plain reactive state container: [`StateStore`] with its updates, actions and signals,
+//!   [`Async`] and the stream extensions, and it does not depend on `tokio-util`.
+//! - `tracing` (default): logs store diagnostics with `tracing`.
+//! - `serde`: persistence and codecs for serializable states; `bincode` and `cbor` add binary codecs.
+//! - `rayon`: runs computations on a rayon thread pool, implies `execute`.
+//! - `remote`: network helpers for `AsyncRemote` in the `remote` module.
+//! - `test-util`: assertion helpers, a deterministic queue harness, scripted state playback and strict lossless observation in the `testing` module.
+//! - `bench`: load generators for measuring the update queue in the `bench` module.
+//! - `debug-jobs`: introspection of running keyed jobs and their cancellation tokens, implies `execute`.
+//! - `debug-transitions`: a history of the last committed transitions and their call sites, see
+//!   `StateStore::recent_transitions`.
+//! - `uuid`: tags execution results with request ids, see `AsyncWithCorrelation`.
+//! - `arc-swap`: serves `StateStore::get_state` from a lock-free cache of the last committed state,
+//!   and adds `StateStore::get_state_arc`.
+//!
 //! ## Design Principles
 //!
 //! 1. **Simplicity**: API design is clear and easy to understand and use
@@ -124,17 +147,131 @@
 //! 5. **Performance**: Prioritizes performance in design decisions
 
 mod async_state;
+mod async_tracked;
+mod async_with_count;
+mod async_staged;
+#[cfg(feature = "uuid")]
+mod async_with_correlation;
+mod render_hint;
 mod async_error;
 mod state_store;
+mod state_store_builder;
+mod state_variant;
+mod global_store;
+mod state_event;
+mod store_error;
+mod state_stream;
+mod middleware;
+mod latency;
+mod state_size;
+mod transition;
+#[cfg(feature = "execute")]
+mod fail_handler;
+#[cfg(feature = "execute")]
+mod error_recovery;
+mod panic_policy;
 mod execution_result;
+#[cfg(feature = "execute")]
+mod execution_ticket;
+#[cfg(feature = "execute")]
+mod execution_span;
+#[cfg(feature = "execute")]
+mod blocking_pool;
+#[cfg(feature = "execute")]
+mod execute_options;
+#[cfg(feature = "execute")]
+mod deadline;
 mod stream_ext;
+mod stop_signal;
+mod query;
+#[cfg(feature = "execute")]
+mod job;
+#[cfg(feature = "execute")]
+mod parallel_batch;
+#[cfg(feature = "execute")]
+mod polling;
+mod subscription;
+mod link;
+mod read_only;
+mod apply_from;
+mod barrier;
+mod fairness;
+mod wait_error;
+mod store_map;
+mod two_phase;
+#[cfg(feature = "execute")]
+mod startup;
+mod persistence;
+#[cfg(feature = "serde")]
+mod migration;
 pub mod macros;
+#[cfg(feature = "execute")]
+pub mod patterns;
+#[cfg(feature = "remote")]
+pub mod remote;
+#[cfg(feature = "serde")]
+pub mod codec;
+#[cfg(any(test, feature = "test-util"))]
+pub mod testing;
+#[cfg(any(test, feature = "bench"))]
+pub mod bench;
 
 pub use async_state::*;
+pub use async_tracked::*;
+pub use async_with_count::*;
+pub use async_staged::*;
+#[cfg(feature = "uuid")]
+pub use async_with_correlation::*;
+pub use render_hint::*;
 pub use async_error::*;
 pub use state_store::*;
+pub use state_store_builder::*;
+pub use state_variant::*;
+pub use global_store::*;
+pub use state_event::*;
+pub use store_error::{StoreError, StoreErrorStream};
+pub use state_stream::*;
+pub use middleware::*;
+pub use latency::*;
+pub use state_size::DEFAULT_STATE_SIZE_SAMPLE_INTERVAL;
+pub use transition::TransitionOrigin;
+#[cfg(feature = "debug-transitions")]
+pub use transition::{TransitionInfo, DEFAULT_TRANSITION_HISTORY};
+#[cfg(feature = "execute")]
+pub use panic_policy::PanicPolicy;
+#[cfg(feature = "execute")]
+pub use error_recovery::RecoveryAction;
 pub use execution_result::*;
+#[cfg(feature = "execute")]
+pub use execution_ticket::*;
+#[cfg(feature = "execute")]
+pub use execute_options::*;
+#[cfg(feature = "execute")]
+pub use deadline::*;
 pub use stream_ext::*;
+pub use query::*;
+#[cfg(feature = "execute")]
+pub use job::*;
+#[cfg(feature = "execute")]
+pub use blocking_pool::DEFAULT_BLOCKING_START_WARNING;
+#[cfg(feature = "execute")]
+pub use parallel_batch::*;
+#[cfg(feature = "execute")]
+pub use polling::*;
+pub use subscription::*;
+pub use link::*;
+pub use read_only::*;
+pub use apply_from::*;
+pub use barrier::*;
+pub use fairness::*;
+pub use wait_error::WaitError;
+pub use store_map::*;
+pub use two_phase::*;
+#[cfg(feature = "execute")]
+pub use startup::*;
+pub use persistence::*;
+#[cfg(feature = "serde")]
+pub use migration::{Migration, MigrationError};
 
 /// A trait for types that can be used as state in a [`StateStore`].
 ///
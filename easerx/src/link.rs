@@ -0,0 +1,97 @@
+use futures_core::stream::Stream;
+use crate::{State, StateStore, SubscriptionGuard};
+
+/// A handle to a cross-store effect created by [`link`].
+///
+/// The link stays active for as long as the handle is alive. Dropping the handle
+/// (or calling [`unlink`](Self::unlink)) stops it; an action already running finishes.
+#[derive(Debug)]
+#[must_use = "The link is removed as soon as the handle is dropped"]
+pub struct LinkHandle {
+    guard: SubscriptionGuard,
+}
+
+impl LinkHandle {
+    /// Stops the link immediately.
+    pub fn unlink(self) {
+        drop(self);
+    }
+
+    /// Returns true if the link has stopped, e.g. because the source store was dropped.
+    pub fn is_finished(&self) -> bool {
+        self.guard.is_finished()
+    }
+}
+
+/// Runs `action` on `target` whenever `predicate` becomes true for the state of `source`.
+///
+/// The action fires on rising edges only: once when the predicate goes from false to true, and not
+/// again for further commits while it stays true. The state at link time counts as the first
+/// observed value, so a predicate that already holds fires once right away. Every committed state of
+/// `source` is observed (see [`StateStore::broadcast`]), so a quick true, false, true sequence
+/// fires twice; if the link falls behind by more than the broadcast capacity, the skipped states
+/// are not evaluated.
+///
+/// Actions are run on the link's own background task, never inline within the update of `source`
+/// that triggered them. Two links in opposite directions therefore cannot deadlock: the action only
+/// queues updates on `target`, as `set_state` and the `execute` family never wait for the queue.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{link, Async, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Auth {
+///     token: Async<String>,
+/// }
+/// impl State for Auth {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Profile {
+///     name: Option<String>,
+/// }
+/// impl State for Profile {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let auth = StateStore::new(Auth { token: Async::Uninitialized });
+///     let profile = StateStore::new(Profile { name: None });
+///     let _link = link(
+///         &auth,
+///         |auth| auth.token.is_success(),
+///         &profile,
+///         |profile, _auth| profile.set_state_forget(|_| Profile { name: Some("Ada".to_string()) }),
+///     );
+///     auth.set_state(|_| Auth { token: Async::success("secret".to_string()) })?;
+///     Ok(())
+/// }
+/// ```
+pub fn link<A, B, P, F>(source: &StateStore<A>, predicate: P, target: &StateStore<B>, action: F) -> LinkHandle
+where
+    A: State,
+    B: State,
+    P: Fn(&A) -> bool + Send + Sync + 'static,
+    F: Fn(&StateStore<B>, &A) + Send + Sync + 'static,
+{
+    // Subscribe before reading the current state, so no commit falls between the two
+    let mut states = source.broadcast();
+    let current = source.get_state();
+    let target = target.clone();
+    let handle = source.spawn(async move {
+        let mut was_true = false;
+        let mut on_state = |state: A| {
+            let is_true = predicate(&state);
+            if is_true && !was_true {
+                action(&target, &state);
+            }
+            was_true = is_true;
+        };
+        on_state(current);
+        while let Some(state) = std::future::poll_fn(|cx| std::pin::Pin::new(&mut states).poll_next(cx)).await {
+            on_state(state);
+        }
+    });
+    LinkHandle {
+        guard: SubscriptionGuard::new(handle),
+    }
+}
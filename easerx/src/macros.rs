@@ -42,4 +42,237 @@ macro_rules! combine_state_flow {
             ($($vars.clone(),)+)
         }
     };
-}
\ No newline at end of file
+}
+/// Matches an [`Async<T>`](crate::Async) with short arm patterns and returns the value of the matching arm.
+///
+/// Each arm is one of `Uninitialized`, `Loading(value)`, `Success(value)`, `Fail(error, value)` or `_`,
+/// where the bindings are ordinary patterns and `Loading`/`Fail` values are `Option<T>`.
+/// Arms accept an `if` guard like in a regular `match`, which stays exhaustive, so guarded arms
+/// need an unguarded fallback for the same variant. Arms are separated by commas, including arms
+/// whose body is a block.
+///
+/// ## Examples
+///
+/// ```
+/// use easerx::{async_match, Async};
+///
+/// let num = Async::success(7);
+/// let label = async_match!(num;
+///     Uninitialized => "idle".to_string(),
+///     Loading(_) => "loading".to_string(),
+///     Success(value) if value > 5 => format!("big {value}"),
+///     Success(value) => format!("small {value}"),
+///     Fail(error, _) => format!("error: {error}"),
+/// );
+/// assert_eq!(label, "big 7");
+/// ```
+#[macro_export]
+macro_rules! async_match {
+    ($value:expr; $($arms:tt)+) => {
+        $crate::async_match!(@arms $value; [] $($arms)+)
+    };
+
+    (@arms $value:expr; [$($out:tt)*]) => {
+        match $value {
+            $($out)*
+        }
+    };
+
+    (@arms $value:expr; [$($out:tt)*] Uninitialized $(if $guard:expr)? => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::async_match!(@arms $value;
+            [$($out)* $crate::Async::Uninitialized $(if $guard)? => $body,]
+            $($($rest)*)?
+        )
+    };
+
+    (@arms $value:expr; [$($out:tt)*] Loading($retained:pat) $(if $guard:expr)? => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::async_match!(@arms $value;
+            [$($out)* $crate::Async::Loading { value: $retained } $(if $guard)? => $body,]
+            $($($rest)*)?
+        )
+    };
+
+    (@arms $value:expr; [$($out:tt)*] Success($success:pat) $(if $guard:expr)? => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::async_match!(@arms $value;
+            [$($out)* $crate::Async::Success { value: $success } $(if $guard)? => $body,]
+            $($($rest)*)?
+        )
+    };
+
+    (@arms $value:expr; [$($out:tt)*] Fail($error:pat, $retained:pat) $(if $guard:expr)? => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::async_match!(@arms $value;
+            [$($out)* $crate::Async::Fail { error: $error, value: $retained } $(if $guard)? => $body,]
+            $($($rest)*)?
+        )
+    };
+
+    (@arms $value:expr; [$($out:tt)*] _ $(if $guard:expr)? => $body:expr $(, $($rest:tt)*)?) => {
+        $crate::async_match!(@arms $value;
+            [$($out)* _ $(if $guard)? => $body,]
+            $($($rest)*)?
+        )
+    };
+}
+
+/// Asserts the sequence of values an [`Async<T>`](crate::Async) field of a store goes through.
+///
+/// Takes a store, a getter selecting the field and the expected steps, and awaits
+/// [`testing::assert_async_flow`](crate::testing::assert_async_flow), so it must be used in an
+/// async context. The first step is the current value of the field. Each step is one of:
+///
+/// - `uninitialized`
+/// - `loading(None)` or `loading(Some(value))`
+/// - `success(value)`
+/// - `fail(message)`, `fail(message, None)` or `fail(message, Some(value))`, compared with
+///   [`Async::fail_with_message`](crate::Async::fail_with_message)
+/// - `{ expr }` for any other `Async<T>` value, e.g. `{ Async::fail_with_cancelled(None) }`
+///
+/// Values are converted with `Into`, so `success("x")` matches an `Async<String>`.
+/// The assertion fails after [`DEFAULT_FLOW_TIMEOUT`](crate::testing::DEFAULT_FLOW_TIMEOUT) unless
+/// a `timeout = duration` argument is given after the steps.
+///
+/// Only available with the `test-util` feature enabled.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{assert_async_flow, Async, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct TestState {
+///     data: Async<String>,
+/// }
+/// impl State for TestState {}
+/// // On a current-thread runtime, the execution starts only once the assertion awaits
+/// #[tokio::main(flavor = "current_thread")]
+/// async fn main() {
+///     let store = StateStore::new(TestState { data: Async::Uninitialized });
+///     store.execute(|| "x".to_string(), |state, data| TestState { data });
+///     assert_async_flow!(store, |s| &s.data, [uninitialized, loading(None), success("x")]);
+/// }
+/// ```
+#[cfg(any(test, feature = "test-util"))]
+#[macro_export]
+macro_rules! assert_async_flow {
+    (@steps [$($out:tt)*]) => {
+        vec![$($out)*]
+    };
+
+    (@steps [$($out:tt)*] uninitialized $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps [$($out)* $crate::Async::Uninitialized,] $($($rest)*)?)
+    };
+
+    (@steps [$($out:tt)*] loading(None) $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps [$($out)* $crate::Async::loading(None),] $($($rest)*)?)
+    };
+
+    (@steps [$($out:tt)*] loading(Some($value:expr)) $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps
+            [$($out)* $crate::Async::loading(Some(Into::into($value))),]
+            $($($rest)*)?
+        )
+    };
+
+    (@steps [$($out:tt)*] success($value:expr) $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps
+            [$($out)* $crate::Async::success(Into::into($value)),]
+            $($($rest)*)?
+        )
+    };
+
+    (@steps [$($out:tt)*] fail($message:expr $(, None)?) $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps
+            [$($out)* $crate::Async::fail_with_message($message, None),]
+            $($($rest)*)?
+        )
+    };
+
+    (@steps [$($out:tt)*] fail($message:expr, Some($value:expr)) $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps
+            [$($out)* $crate::Async::fail_with_message($message, Some(Into::into($value))),]
+            $($($rest)*)?
+        )
+    };
+
+    (@steps [$($out:tt)*] { $value:expr } $(, $($rest:tt)*)?) => {
+        $crate::assert_async_flow!(@steps [$($out)* $value,] $($($rest)*)?)
+    };
+
+    ($store:expr, $getter:expr, [$($steps:tt)*], timeout = $timeout:expr $(,)?) => {
+        $crate::testing::assert_async_flow(
+            &$store,
+            $getter,
+            $crate::assert_async_flow!(@steps [] $($steps)*),
+            $timeout,
+        )
+        .await
+    };
+
+    ($store:expr, $getter:expr, [$($steps:tt)*] $(,)?) => {
+        $crate::assert_async_flow!($store, $getter, [$($steps)*], timeout = $crate::testing::DEFAULT_FLOW_TIMEOUT)
+    };
+}
+
+/// Implements [`StateVariant`](crate::StateVariant) and `TryFrom` for the payloads of an enum state.
+///
+/// Takes the name of the enum, which must be in scope, and a list of its single-field tuple variants
+/// with their payload types. Every payload type must be used by one variant only; variants that are
+/// not listed, such as unit variants, can't be addressed with
+/// [`StateStore::set_state_variant`](crate::StateStore::set_state_variant).
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{state_variants, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Login { user: String }
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Dashboard { user: String, unread: u32 }
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// enum Screen {
+///     Login(Login),
+///     Loading,
+///     Dashboard(Dashboard),
+/// }
+/// impl State for Screen {}
+///
+/// state_variants!(Screen { Login(Login), Dashboard(Dashboard) });
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Screen::Login(Login { user: String::new() }));
+///     store
+///         .set_state_variant(|login: Login| Screen::Dashboard(Dashboard { user: login.user, unread: 0 }))
+///         .await?;
+///     assert!(store.set_state_variant(|login: Login| Screen::Login(login)).await.is_err());
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! state_variants {
+    ($state:ident { $($variant:ident($payload:ty)),+ $(,)? }) => {
+        $(
+            impl ::std::convert::TryFrom<$state> for $payload {
+                type Error = $state;
+
+                fn try_from(state: $state) -> ::std::result::Result<Self, $state> {
+                    match state {
+                        $state::$variant(payload) => ::std::result::Result::Ok(payload),
+                        #[allow(unreachable_patterns)]
+                        other => ::std::result::Result::Err(other),
+                    }
+                }
+            }
+
+            impl $crate::StateVariant<$state> for $payload {
+                const NAME: &'static str = ::std::stringify!($variant);
+
+                fn matches(state: &$state) -> bool {
+                    ::std::matches!(state, $state::$variant(_))
+                }
+            }
+        )+
+    };
+}
@@ -0,0 +1,159 @@
+use std::any::Any;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+
+/// A hook that observes every state update of a [`StateStore`](crate::StateStore).
+///
+/// Middlewares are invoked from the store's background task for every reducer, including the
+/// ones queued by the `execute` family. They are the extension point for logging, auditing,
+/// metrics, and other cross-cutting concerns. Keep them cheap: they run inline with state updates.
+///
+/// Both methods have empty default implementations, so a middleware only implements what it needs.
+pub trait Middleware<S>: Send + Sync + 'static {
+    /// Called right before a reducer runs.
+    ///
+    /// `reducer` is the boxed reducer itself. Reducers are closures, so it is mostly useful as a
+    /// trigger; it can be downcast only if you know the concrete boxed type.
+    fn before_set_state(&self, reducer: &dyn Any) {
+        let _ = reducer;
+    }
+
+    /// Called after a reducer produced a new state, right before the new state is committed.
+    fn after_set_state(&self, old: &S, new: &S) {
+        let _ = (old, new);
+    }
+}
+
+/// The ordered list of middlewares registered on a store.
+pub(crate) struct MiddlewareChain<S> {
+    enabled: AtomicBool,
+    middlewares: RwLock<Vec<Arc<dyn Middleware<S>>>>,
+}
+
+impl<S> MiddlewareChain<S> {
+    pub(crate) fn new() -> Self {
+        MiddlewareChain {
+            enabled: AtomicBool::new(false),
+            middlewares: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub(crate) fn push(&self, middleware: Arc<dyn Middleware<S>>) {
+        self.middlewares
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(middleware);
+        self.enabled.store(true, Ordering::Release);
+    }
+
+    /// Returns the registered middlewares, or `None` without taking the lock if there are none.
+    pub(crate) fn snapshot(&self) -> Option<Vec<Arc<dyn Middleware<S>>>> {
+        if !self.enabled.load(Ordering::Acquire) {
+            return None;
+        }
+        Some(
+            self.middlewares
+                .read()
+                .unwrap_or_else(|e| e.into_inner())
+                .clone(),
+        )
+    }
+}
+
+impl<S> fmt::Debug for MiddlewareChain<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.middlewares.read().map(|m| m.len()).unwrap_or_default();
+        f.debug_struct("MiddlewareChain")
+            .field("middlewares", &count)
+            .finish()
+    }
+}
+
+/// A middleware that logs every state transition at debug level through `tracing`.
+///
+/// Only available with the `tracing` feature (enabled by default).
+#[cfg(feature = "tracing")]
+#[derive(Debug, Clone, Default)]
+pub struct LoggingMiddleware {
+    target: Option<&'static str>,
+}
+
+#[cfg(feature = "tracing")]
+impl LoggingMiddleware {
+    /// Creates a logging middleware.
+    pub fn new() -> Self {
+        LoggingMiddleware::default()
+    }
+
+    /// Creates a logging middleware that adds the given name to every record,
+    /// which helps telling several stores apart.
+    pub fn named(name: &'static str) -> Self {
+        LoggingMiddleware { target: Some(name) }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl<S: fmt::Debug> Middleware<S> for LoggingMiddleware {
+    fn after_set_state(&self, old: &S, new: &S) {
+        let store = self.target.unwrap_or("StateStore");
+        tracing::debug!(store, ?old, ?new, "state updated");
+    }
+}
+
+/// A middleware that counts state updates.
+///
+/// Clones share the same counters, so keep a clone to read the metrics after registering it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{MetricsMiddleware, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct TestState {
+///    num: i32,
+/// }
+/// impl State for TestState {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let metrics = MetricsMiddleware::new();
+///     let store = StateStore::new(TestState{num:0}).with_middleware(metrics.clone());
+///     store.set_state(|state| TestState { num: 1 })?;
+///     store.await_state().await?;
+///     assert_eq!(metrics.committed(), 1);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MetricsMiddleware {
+    received: Arc<AtomicU64>,
+    committed: Arc<AtomicU64>,
+}
+
+impl MetricsMiddleware {
+    /// Creates a metrics middleware with all counters at zero.
+    pub fn new() -> Self {
+        MetricsMiddleware::default()
+    }
+
+    /// Returns the number of reducers that started running.
+    pub fn received(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of reducers that produced a new state.
+    pub fn committed(&self) -> u64 {
+        self.committed.load(Ordering::Relaxed)
+    }
+}
+
+impl<S> Middleware<S> for MetricsMiddleware {
+    fn before_set_state(&self, _reducer: &dyn Any) {
+        self.received.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn after_set_state(&self, _old: &S, _new: &S) {
+        self.committed.fetch_add(1, Ordering::Relaxed);
+    }
+}
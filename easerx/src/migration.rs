@@ -0,0 +1,106 @@
+use std::fmt;
+use std::sync::Arc;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+use crate::AsyncError;
+
+/// A step upgrading a persisted snapshot from one schema version to the next, see
+/// [`FilePersistence::with_migrations`](crate::FilePersistence::with_migrations).
+///
+/// Migrations work on the snapshot as a JSON value, before it is deserialized into the state, so
+/// they can rename, reshape or fill in fields the current state type no longer matches.
+///
+/// Only available with the `serde` feature enabled.
+pub trait Migration: Send + Sync + 'static {
+    /// The schema version this migration upgrades from; it produces version `from_version + 1`.
+    #[allow(clippy::wrong_self_convention)] // not a conversion, but reads like `migrate(from_version)`
+    fn from_version(&self) -> u8;
+
+    /// Upgrades a snapshot of version [`from_version`](Self::from_version).
+    fn migrate(&self, value: Value) -> Result<Value, AsyncError>;
+}
+
+/// Why a persisted snapshot could not be migrated to the current state, passed to the
+/// [`on_migration_error`](crate::FilePersistence::on_migration_error) hook.
+///
+/// New kinds of errors may be reported in the future, so matches need a wildcard arm.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum MigrationError {
+    /// The snapshot could not be decoded, or its migrated value doesn't match the state type.
+    #[error("Snapshot could not be decoded: {message}")]
+    Decode { message: String },
+
+    /// No migration upgrades from `from_version`.
+    #[error("No migration from schema version {from_version}")]
+    Missing { from_version: u8 },
+
+    /// The snapshot was written by a newer schema than the current one, e.g. by a later release.
+    #[error("Snapshot has schema version {version}, newer than the current version {current}")]
+    Newer { version: u8, current: u8 },
+
+    /// The migration from `from_version` failed.
+    #[error("Migration from schema version {from_version} failed: {error}")]
+    Failed { from_version: u8, error: AsyncError },
+}
+
+impl From<MigrationError> for AsyncError {
+    fn from(error: MigrationError) -> Self {
+        AsyncError::error(error.to_string())
+    }
+}
+
+pub(crate) type MigrationErrorHook = Arc<dyn Fn(&MigrationError) + Send + Sync>;
+
+/// The current schema version of a persisted state and the migrations leading up to it.
+#[derive(Clone)]
+pub(crate) struct Migrations {
+    current: u8,
+    steps: Arc<[Box<dyn Migration>]>,
+}
+
+impl fmt::Debug for Migrations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let from_versions: Vec<u8> = self.steps.iter().map(|step| step.from_version()).collect();
+        f.debug_struct("Migrations")
+            .field("current", &self.current)
+            .field("from_versions", &from_versions)
+            .finish()
+    }
+}
+
+impl Migrations {
+    pub(crate) fn new(current: u8, steps: Vec<Box<dyn Migration>>) -> Self {
+        Migrations {
+            current,
+            steps: steps.into(),
+        }
+    }
+
+    pub(crate) fn current(&self) -> u8 {
+        self.current
+    }
+
+    /// Upgrades `value`, a snapshot of schema `version`, step by step to the current version and
+    /// deserializes it.
+    pub(crate) fn apply<S: DeserializeOwned>(&self, version: u8, mut value: Value) -> Result<S, MigrationError> {
+        if version > self.current {
+            return Err(MigrationError::Newer {
+                version,
+                current: self.current,
+            });
+        }
+        for from_version in version..self.current {
+            let step = self
+                .steps
+                .iter()
+                .find(|step| step.from_version() == from_version)
+                .ok_or(MigrationError::Missing { from_version })?;
+            value = step
+                .migrate(value)
+                .map_err(|error| MigrationError::Failed { from_version, error })?;
+        }
+        serde_json::from_value(value).map_err(|e| MigrationError::Decode { message: e.to_string() })
+    }
+}
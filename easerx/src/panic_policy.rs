@@ -0,0 +1,92 @@
+use std::any::Any;
+#[cfg(feature = "execute")]
+use std::future::Future;
+#[cfg(feature = "execute")]
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+#[cfg(feature = "execute")]
+use std::pin::Pin;
+#[cfg(feature = "execute")]
+use std::task::{Context, Poll};
+#[cfg(feature = "execute")]
+use pin_project::pin_project;
+#[cfg(feature = "execute")]
+use tokio::task::JoinError;
+#[cfg(feature = "execute")]
+use crate::AsyncError;
+
+/// Decides what an execution does when its computation panics.
+///
+/// Set it with [`StateStoreBuilder::panic_policy`](crate::StateStoreBuilder::panic_policy).
+/// The policy applies to synchronous computations running in blocking tasks as well as to
+/// async computations, whose panics are caught while they are polled.
+#[cfg(feature = "execute")]
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum PanicPolicy {
+    /// Converts the panic into `Async::Fail` with [`AsyncError::Panic`], keeping the panic message.
+    #[default]
+    Capture,
+
+    /// Resumes the panic in the execution task, so awaiting its
+    /// [`ExecutionTicket`](crate::ExecutionTicket) returns a panicked `JoinError`.
+    /// Useful in tests that want panics to fail loudly.
+    Resume,
+}
+
+#[cfg(feature = "execute")]
+impl PanicPolicy {
+    /// Converts a caught panic payload into an error, or resumes it with [`PanicPolicy::Resume`].
+    pub(crate) fn error_from_panic(self, payload: Box<dyn Any + Send>) -> AsyncError {
+        match self {
+            PanicPolicy::Capture => AsyncError::Panic {
+                message: panic_message(payload.as_ref()),
+            },
+            PanicPolicy::Resume => resume_unwind(payload),
+        }
+    }
+
+    /// Converts the error of a blocking task that could not be joined.
+    pub(crate) fn error_from_join(self, error: JoinError) -> AsyncError {
+        match error.try_into_panic() {
+            Ok(payload) => self.error_from_panic(payload),
+            Err(error) => AsyncError::error(error.to_string()),
+        }
+    }
+}
+
+/// Extracts the message of a panic payload raised with a `&str` or a `String`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| message.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+/// A future that catches panics raised while polling the inner future.
+#[cfg(feature = "execute")]
+#[pin_project]
+pub(crate) struct CatchUnwind<F> {
+    #[pin]
+    future: F,
+}
+
+#[cfg(feature = "execute")]
+impl<F: Future> CatchUnwind<F> {
+    pub(crate) fn new(future: F) -> Self {
+        CatchUnwind { future }
+    }
+}
+
+#[cfg(feature = "execute")]
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.project().future;
+        match catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+use tokio::task::JoinHandle;
+use crate::{Async, AsyncError, ExecutionResult, ExecutionTicket, State, StateStore};
+use crate::execution_span::ExecutionSpan;
+
+type BoxedReducer<S> = Box<dyn FnOnce(S) -> S + Send>;
+
+/// Starts the execution of one computation of a batch.
+type BatchEntry<S> = Box<dyn FnOnce(&StateStore<S>) -> ExecutionTicket + Send>;
+
+/// Runs several synchronous computations with different result types in parallel.
+///
+/// Every computation gets its own state updater, which receives `Async::Loading` before the batch
+/// starts and the computation's result as soon as it completes. Once all computations have
+/// completed, the optional aggregating reducer is applied exactly once.
+///
+/// Each computation runs like [`StateStore::execute`], so the store's policies apply to every entry
+/// on its own: executions are serialized if the store serializes them, failures go through the
+/// error recovery policy and are reported to the `on_async_fail` handlers, and the default timeout
+/// bounds each computation.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{Async, ParallelBatch, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct TestState {
+///    num: Async<i32>,
+///    name: Async<String>,
+///    done: bool,
+/// }
+/// impl State for TestState {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(TestState{num: Async::default(), name: Async::default(), done: false});
+///     ParallelBatch::new(&store)
+///         .add(|| 888, |state, num| TestState { num, ..state })
+///         .add(|| Some("easerx".to_string()), |state, name| TestState { name, ..state })
+///         .aggregate(|state| TestState { done: true, ..state })
+///         .execute_all()
+///         .await??;
+///     Ok(())
+/// }
+/// ```
+#[must_use = "A batch does nothing until execute_all is called"]
+pub struct ParallelBatch<S: State> {
+    store: StateStore<S>,
+    entries: Vec<BatchEntry<S>>,
+    aggregator: Option<BoxedReducer<S>>,
+}
+
+impl<S: State> ParallelBatch<S> {
+    /// Creates an empty batch targeting the given store.
+    pub fn new(store: &StateStore<S>) -> Self {
+        ParallelBatch {
+            store: store.clone(),
+            entries: Vec::new(),
+            aggregator: None,
+        }
+    }
+
+    /// Adds a computation and the state updater that receives its result.
+    pub fn add<T, R, F, U>(mut self, computation: F, state_updater: U) -> Self
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.entries.push(Box::new(move |store| store.execute(computation, state_updater)));
+        self
+    }
+
+    /// Sets the reducer applied once every computation of the batch has completed.
+    pub fn aggregate<F>(mut self, reducer: F) -> Self
+    where
+        F: FnOnce(S) -> S + Send + 'static,
+    {
+        self.aggregator = Some(Box::new(reducer));
+        self
+    }
+
+    /// Returns the number of computations in the batch.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns true if the batch contains no computations.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Starts every computation of the batch.
+    ///
+    /// All fields are set to `Async::Loading(None)` first, then the computations run concurrently
+    /// in blocking tasks. Results are written in completion order, followed by the aggregating reducer.
+    pub fn execute_all(self) -> JoinHandle<Result<(), AsyncError>> {
+        let ParallelBatch {
+            store,
+            entries,
+            aggregator,
+        } = self;
+        let panic_policy = store.panic_policy();
+        let span = ExecutionSpan::new();
+        store.clone().spawn(span.instrument(async move {
+            let tickets: Vec<_> = entries.into_iter().map(|entry| entry(&store)).collect();
+            // The computations are all running, so awaiting the tickets in turn waits for the slowest
+            for ticket in tickets {
+                ticket.await.map_err(|e| panic_policy.error_from_join(e))??;
+            }
+
+            // Queued behind the results, which every ticket queued before resolving
+            if let Some(aggregator) = aggregator {
+                store.set_state(aggregator)?;
+            }
+            Ok(())
+        }))
+    }
+}
@@ -0,0 +1,473 @@
+//! Reusable implementations of patterns built from the primitives of this crate.
+//!
+//! - [`load_once`]: loads a value unless it is already loading or loaded.
+//! - [`refresh_on`]: reloads a value whenever a signal changes.
+//! - [`paginated_loader`]: appends pages of items to a list, one page at a time.
+//! - [`form_submit`]: applies a change optimistically and rolls it back if submitting it fails.
+//!
+//! Each of them is a thin composition of [`StateStore::set_state`], the `execute` family and
+//! signals, so they can also serve as a starting point when a variant is needed.
+
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, MutexGuard};
+use futures_signals::signal::{Signal, SignalExt};
+use crate::error_recovery::restored;
+use crate::transition::{Origin, TransitionOrigin};
+use crate::{Async, AsyncError, ExecutionResult, ExecutionTicket, IntoAsync, State, StateStore, SubscriptionGuard};
+
+/// Loads a value once: starts `computation` only if the value returned by `getter` is
+/// uninitialized or failed, see [`Async::should_load`].
+///
+/// The check runs inside the background task and marks the value as loading in the same update,
+/// so of several concurrent calls only the first one loads. Calling it again after a failure
+/// retries the load. While loading, the previous value is retained, like
+/// [`async_execute_with_retain`](StateStore::async_execute_with_retain).
+///
+/// The returned future resolves to the ticket of the load, or `None` if the value was already
+/// loading or loaded. The load starts even if the future is dropped.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::patterns::load_once;
+/// use easerx::{Async, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Profile {
+///     name: Async<String>,
+/// }
+/// impl State for Profile {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Profile { name: Async::Uninitialized });
+///     let load = || load_once(&store, |state| &state.name, async { "Ada".to_string() }, |_, name| Profile { name });
+///
+///     let first = load().await?;
+///     let second = load().await?;
+///     assert!(second.is_none());
+///     first.unwrap().await??;
+///     assert!(load().await?.is_none());
+///     assert_eq!(store.get_state().name, Async::success("Ada".to_string()));
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// Returns an `AsyncError` if the update could not be queued, e.g. because the store is frozen.
+#[track_caller]
+pub fn load_once<S, T, R, F, G, U>(
+    store: &StateStore<S>,
+    getter: G,
+    computation: F,
+    updater: U,
+) -> impl Future<Output = Result<Option<ExecutionTicket>, AsyncError>> + Send + 'static
+where
+    S: State,
+    T: Clone + Send + 'static,
+    R: ExecutionResult<T> + Send + 'static,
+    F: Future<Output = R> + Send + 'static,
+    G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+    U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+{
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    let target = store.clone();
+    let queued = store.set_state_at_version(
+        move |_, state| {
+            let current = getter.clone()(&state);
+            if !current.should_load() {
+                let _ = tx.send(None);
+                return None;
+            }
+            let loading = Async::loading(current.value_ref_clone());
+            let ticket = target.async_execute_with_retain(computation, getter, updater.clone());
+            let _ = tx.send(Some(ticket));
+            Some(updater(state, loading))
+        },
+        Origin::here(TransitionOrigin::SetState),
+    );
+    async move {
+        queued?;
+        rx.await.map_err(|e| AsyncError::error(e.to_string()))
+    }
+}
+
+/// Reloads a value whenever `trigger` changes, computing it from the latest value of the signal.
+///
+/// The first load starts right away with the current value of the signal. Loads run one at a time:
+/// changes arriving while a load is running are coalesced, and the next load uses the latest value.
+/// While loading, the previous value is retained, like
+/// [`async_execute_with_retain`](StateStore::async_execute_with_retain).
+///
+/// Reloading stops when the returned guard is dropped, or when `trigger` ends. A load that is
+/// running at that time still completes.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::patterns::refresh_on;
+/// use easerx::{Async, State, StateStore};
+/// use futures_signals::signal::Mutable;
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Search {
+///     results: Async<Vec<String>>,
+/// }
+/// impl State for Search {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Search { results: Async::Uninitialized });
+///     let query = Mutable::new("rust".to_string());
+///     let _refresh = refresh_on(
+///         &store,
+///         query.signal_cloned(),
+///         |query| async move { vec![format!("{query} book")] },
+///         |state| &state.results,
+///         |_, results| Search { results },
+///     );
+///     query.set("tokio".to_string());
+///     Ok(())
+/// }
+/// ```
+pub fn refresh_on<S, Sig, T, R, F, Fut, G, U>(
+    store: &StateStore<S>,
+    trigger: Sig,
+    mut computation: F,
+    getter: G,
+    updater: U,
+) -> SubscriptionGuard
+where
+    S: State,
+    Sig: Signal + Send + 'static,
+    T: Clone + Send + 'static,
+    R: ExecutionResult<T> + Send + 'static,
+    F: FnMut(Sig::Item) -> Fut + Send + 'static,
+    Fut: Future<Output = R> + Send + 'static,
+    G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+    U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+{
+    let target = store.clone();
+    // `for_each` waits for each load before polling the signal again, which coalesces changes
+    let handle = store.spawn(trigger.for_each(move |value| {
+        let ticket = target.async_execute_with_retain(computation(value), getter.clone(), updater.clone());
+        async move {
+            let _ = ticket.await;
+        }
+    }));
+    SubscriptionGuard::new(handle)
+}
+
+/// A page requested by a [`PaginatedLoader`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct PageRequest {
+    /// The index of the page, starting at `0`.
+    pub index: usize,
+    /// The number of items in a full page.
+    pub size: usize,
+}
+
+impl PageRequest {
+    /// Returns the index of the first item of the page.
+    pub fn offset(&self) -> usize {
+        self.index * self.size
+    }
+}
+
+type PageFetch<T> = Arc<dyn Fn(PageRequest) -> Pin<Box<dyn Future<Output = Async<Vec<T>>> + Send>> + Send + Sync>;
+type PageGetter<S, T> = Arc<dyn Fn(&S) -> &Async<Vec<T>> + Send + Sync>;
+type PageUpdater<S, T> = Arc<dyn Fn(S, Async<Vec<T>>) -> S + Send + Sync>;
+
+#[derive(Debug, Default)]
+struct PageProgress {
+    next_page: usize,
+    exhausted: bool,
+    loading: bool,
+}
+
+fn lock(progress: &Mutex<PageProgress>) -> MutexGuard<'_, PageProgress> {
+    progress.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Ends the load of a page when dropped, whether the page was fetched or the execution failed,
+/// timed out or never ran.
+struct LoadingGuard(Arc<Mutex<PageProgress>>);
+
+impl LoadingGuard {
+    /// Moves on to the next page after a page was fetched.
+    fn advance(&self, exhausted: bool) {
+        let mut progress = lock(&self.0);
+        progress.next_page += 1;
+        progress.exhausted = exhausted;
+    }
+}
+
+impl Drop for LoadingGuard {
+    fn drop(&mut self) {
+        lock(&self.0).loading = false;
+    }
+}
+
+/// Carries the already converted result of a page fetch through the execution.
+struct FetchedPage<T: Clone>(Async<Vec<T>>);
+
+impl<T: Clone> ExecutionResult<Vec<T>> for FetchedPage<T> {
+    fn into_async(self) -> Async<Vec<T>> {
+        self.0
+    }
+}
+
+/// Loads a list one page at a time, appending every page to the items already loaded.
+///
+/// Created by [`paginated_loader`]. The items live in an `Async<Vec<T>>` of the state: while a page
+/// is loading, the value is `Loading` with the items loaded so far, and a failed page leaves it
+/// `Fail` with those items, so [`load_next_page`](Self::load_next_page) retries the same page.
+/// A page shorter than the page size ends the list.
+///
+/// A failure discarded by the store's [error recovery](StateStore::with_error_recovery) keeps the
+/// items loaded so far, and the same page is loaded next time.
+///
+/// The page progress moves on once the page was fetched, so the next page can be requested as
+/// soon as the ticket of the previous one resolved, whatever its outcome. Like for any execution,
+/// the ticket resolves once the page is queued for the store, so await
+/// [`StateStore::await_state`] to read the list with it.
+///
+/// Clones share the same page progress.
+pub struct PaginatedLoader<S: State, T: Clone> {
+    store: StateStore<S>,
+    page_size: usize,
+    fetch: PageFetch<T>,
+    getter: PageGetter<S, T>,
+    updater: PageUpdater<S, T>,
+    progress: Arc<Mutex<PageProgress>>,
+}
+
+/// Creates a [`PaginatedLoader`] that fetches pages of `page_size` items with `fetch` and keeps
+/// them in the value returned by `getter`, written with `updater`.
+///
+/// A `page_size` of `0` is treated as `1`. No page is loaded until
+/// [`load_next_page`](PaginatedLoader::load_next_page) is called.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::patterns::paginated_loader;
+/// use easerx::{Async, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Feed {
+///     posts: Async<Vec<u32>>,
+/// }
+/// impl State for Feed {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Feed { posts: Async::Uninitialized });
+///     let loader = paginated_loader(
+///         &store,
+///         2,
+///         |page| async move { (page.offset() as u32..5).take(page.size).collect::<Vec<_>>() },
+///         |state| &state.posts,
+///         |_, posts| Feed { posts },
+///     );
+///     while let Some(ticket) = loader.load_next_page() {
+///         ticket.await??;
+///     }
+///     store.await_state().await?;
+///     assert!(loader.is_exhausted());
+///     assert_eq!(store.get_state().posts, Async::success(vec![0, 1, 2, 3, 4]));
+///     Ok(())
+/// }
+/// ```
+pub fn paginated_loader<S, T, F, I, G, U>(
+    store: &StateStore<S>,
+    page_size: usize,
+    fetch: F,
+    getter: G,
+    updater: U,
+) -> PaginatedLoader<S, T>
+where
+    S: State,
+    T: Clone + Send + 'static,
+    F: Fn(PageRequest) -> I + Send + Sync + 'static,
+    I: IntoAsync<Vec<T>>,
+    G: Fn(&S) -> &Async<Vec<T>> + Send + Sync + 'static,
+    U: Fn(S, Async<Vec<T>>) -> S + Send + Sync + 'static,
+{
+    PaginatedLoader {
+        store: store.clone(),
+        page_size: page_size.max(1),
+        fetch: Arc::new(move |request| fetch(request).into_async_future()),
+        getter: Arc::new(getter),
+        updater: Arc::new(updater),
+        progress: Arc::default(),
+    }
+}
+
+impl<S: State, T: Clone + Send + 'static> PaginatedLoader<S, T> {
+    /// Starts loading the next page, returning the ticket of the load.
+    ///
+    /// Returns `None` without loading anything if a page is already loading or the list is exhausted.
+    pub fn load_next_page(&self) -> Option<ExecutionTicket> {
+        let request = {
+            let mut progress = lock(&self.progress);
+            if progress.loading || progress.exhausted {
+                return None;
+            }
+            progress.loading = true;
+            PageRequest {
+                index: progress.next_page,
+                size: self.page_size,
+            }
+        };
+        let page = (self.fetch)(request);
+        let getter = self.getter.clone();
+        let updater = self.updater.clone();
+        let loading = LoadingGuard(self.progress.clone());
+        let ticket = self.store.async_execute(
+            async move {
+                let page = page.await;
+                if let Async::Success { value } = &page {
+                    loading.advance(value.len() < request.size);
+                }
+                // Dropping the guard, here or with an execution that never ran, ends the load
+                drop(loading);
+                FetchedPage(page)
+            },
+            // Appending inside the reducer keeps the pages in order with any other update of the list
+            move |state: S, page: Async<Vec<T>>| {
+                let current = getter(&state);
+                let retained = current.value_ref_clone();
+                let mut items = retained.clone().unwrap_or_default();
+                let merged = match page {
+                    Async::Loading { .. } => Async::loading(Some(items)),
+                    Async::Success { value } => {
+                        items.extend(value);
+                        Async::success(items)
+                    }
+                    Async::Fail { error, .. } => Async::fail(error, Some(items)),
+                    // A failure ignored by the store's error recovery keeps the list as it was
+                    Async::Uninitialized => restored(retained),
+                };
+                updater(state, merged)
+            },
+        );
+        Some(ticket)
+    }
+
+    /// Returns the index of the next page to load.
+    pub fn next_page(&self) -> usize {
+        lock(&self.progress).next_page
+    }
+
+    /// Returns true if a page is loading.
+    pub fn is_loading(&self) -> bool {
+        lock(&self.progress).loading
+    }
+
+    /// Returns true once a page shorter than the page size was loaded.
+    pub fn is_exhausted(&self) -> bool {
+        lock(&self.progress).exhausted
+    }
+
+    /// Returns the number of items in a full page.
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+}
+
+impl<S: State, T: Clone> Clone for PaginatedLoader<S, T> {
+    fn clone(&self) -> Self {
+        PaginatedLoader {
+            store: self.store.clone(),
+            page_size: self.page_size,
+            fetch: self.fetch.clone(),
+            getter: self.getter.clone(),
+            updater: self.updater.clone(),
+            progress: self.progress.clone(),
+        }
+    }
+}
+
+impl<S: State, T: Clone> fmt::Debug for PaginatedLoader<S, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PaginatedLoader")
+            .field("page_size", &self.page_size)
+            .field("progress", &*lock(&self.progress))
+            .finish_non_exhaustive()
+    }
+}
+
+/// Submits a change optimistically: `optimistic` is applied right away, then `submit` runs and
+/// its outcome is written with `updater`.
+///
+/// If `submit` fails, including when it is cancelled or times out, `rollback` undoes the
+/// optimistic change in the same update that writes the failure, so no state in between shows
+/// the failure with the change still applied. `rollback` receives the current state rather than
+/// a snapshot from before the change, so updates committed in the meantime are kept.
+///
+/// A failure discarded by the store's [error recovery](StateStore::with_error_recovery) is rolled
+/// back as well: `updater` then receives `Uninitialized` instead of the failure, and `rollback`
+/// runs in the same update. The error itself is only available in `updater`, when it is written.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::patterns::form_submit;
+/// use easerx::{Async, AsyncError, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Form {
+///     likes: u32,
+///     submission: Async<()>,
+/// }
+/// impl State for Form {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Form { likes: 0, submission: Async::Uninitialized });
+///     form_submit(
+///         &store,
+///         |form| Form { likes: form.likes + 1, ..form },
+///         async { Err::<(), _>(AsyncError::error("offline")) },
+///         |form, submission| Form { submission, ..form },
+///         |form| Form { likes: form.likes - 1, ..form },
+///     )?
+///     .await??;
+///     let form = store.await_state().await?;
+///     assert_eq!(form.likes, 0);
+///     assert!(form.submission.is_fail());
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// Returns an `AsyncError` if the optimistic update could not be queued, e.g. because the store
+/// is frozen. `submit` does not run in that case.
+#[track_caller]
+pub fn form_submit<S, T, R, F, O, U, B>(
+    store: &StateStore<S>,
+    optimistic: O,
+    submit: F,
+    updater: U,
+    rollback: B,
+) -> Result<ExecutionTicket, AsyncError>
+where
+    S: State,
+    T: Clone + Send + 'static,
+    R: ExecutionResult<T> + Send + 'static,
+    F: Future<Output = R> + Send + 'static,
+    O: FnOnce(S) -> S + Send + 'static,
+    U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    B: FnOnce(S) -> S + Clone + Send + 'static,
+{
+    store.set_state(optimistic)?;
+    Ok(store.async_execute(submit, move |state, submission| {
+        // `Uninitialized` is a failure ignored by the store's error recovery
+        let state = match &submission {
+            Async::Fail { .. } | Async::Uninitialized => rollback(state),
+            Async::Loading { .. } | Async::Success { .. } => state,
+        };
+        updater(state, submission)
+    }))
+}
@@ -0,0 +1,216 @@
+use std::future::Future;
+#[cfg(feature = "serde")]
+use std::fmt;
+#[cfg(feature = "serde")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "serde")]
+use std::sync::Arc;
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+#[cfg(feature = "serde")]
+use crate::codec::{decode_versioned, encode_versioned, Codec, JsonCodec};
+#[cfg(feature = "serde")]
+use crate::migration::{MigrationErrorHook, Migrations};
+#[cfg(feature = "serde")]
+use crate::{Migration, MigrationError};
+use crate::{AsyncError, State};
+
+/// A storage backend for [`StateStore::with_persistence`](crate::StateStore::with_persistence).
+///
+/// Implementations can use `async fn` for both methods; the returned futures must be `Send`
+/// because they run on a background task.
+pub trait StatePersistence<S: State>: Send + Sync + 'static {
+    /// Saves the latest committed state.
+    fn save(&self, state: &S) -> impl Future<Output = Result<(), AsyncError>> + Send;
+
+    /// Loads the persisted state, or `None` if nothing was saved yet.
+    fn load(&self) -> impl Future<Output = Result<Option<S>, AsyncError>> + Send;
+}
+
+/// A [`StatePersistence`] backend storing the state in a single file.
+///
+/// The state is encoded with a [`Codec`], JSON by default. Saving writes a temporary file next to the
+/// target and renames it over the target, so a crash mid-save never leaves a truncated file behind.
+/// A missing file loads as `None`.
+///
+/// When the shape of the state changes between releases, give the state a schema version with
+/// [`with_migrations`](Self::with_migrations) so that older files are upgraded when they load.
+///
+/// Only available with the `serde` feature enabled.
+///
+/// ## Examples
+///
+/// ```rust,no_run
+/// use easerx::{FilePersistence, State, StateStore};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// struct Settings {
+///    dark_mode: bool,
+/// }
+/// impl State for Settings {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Settings { dark_mode: false })
+///         .with_persistence(FilePersistence::new("settings.json"));
+///     store.set_state(|_| Settings { dark_mode: true })?;
+///     Ok(())
+/// }
+/// ```
+#[cfg(feature = "serde")]
+#[derive(Clone)]
+pub struct FilePersistence<C = JsonCodec> {
+    path: PathBuf,
+    codec: C,
+    migrations: Option<Migrations>,
+    on_migration_error: Option<MigrationErrorHook>,
+}
+
+#[cfg(feature = "serde")]
+impl<C: fmt::Debug> fmt::Debug for FilePersistence<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FilePersistence")
+            .field("path", &self.path)
+            .field("codec", &self.codec)
+            .field("migrations", &self.migrations)
+            .field("on_migration_error", &self.on_migration_error.is_some())
+            .finish()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl FilePersistence {
+    /// Creates a backend storing the state as JSON at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FilePersistence::with_codec(path, JsonCodec)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<C: Codec> FilePersistence<C> {
+    /// Creates a backend storing the state at `path` in the format of `codec`.
+    pub fn with_codec(path: impl Into<PathBuf>, codec: C) -> Self {
+        FilePersistence {
+            path: path.into(),
+            codec,
+            migrations: None,
+            on_migration_error: None,
+        }
+    }
+
+    /// Stores the state with the schema version `schema_version`, upgrading files of older versions
+    /// with `migrations` when they load.
+    ///
+    /// Saved files carry the schema version in a header, see
+    /// [`encode_versioned`](crate::codec::encode_versioned); files written before migrations were set
+    /// up have version `0`. A file of an older version is decoded into a JSON value, passed through
+    /// the migration of each version up to `schema_version` in turn, and only then deserialized into
+    /// the state. The codec must be self-describing for that, which rules out `BincodeCodec`.
+    ///
+    /// A file that can't be migrated fails to load with the [`MigrationError`], which is also passed
+    /// to the [`on_migration_error`](Self::on_migration_error) hook. The store then keeps its initial
+    /// state and doesn't save over the file, so the data can still be recovered.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust,no_run
+    /// use easerx::{AsyncError, FilePersistence, Migration, State, StateStore};
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_json::{json, Value};
+    ///
+    /// #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+    /// struct Settings {
+    ///    theme: String,
+    /// }
+    /// impl State for Settings {}
+    ///
+    /// /// Version 0 stored `dark_mode: bool`, version 1 stores the theme name.
+    /// struct DarkModeToTheme;
+    /// impl Migration for DarkModeToTheme {
+    ///     fn from_version(&self) -> u8 {
+    ///         0
+    ///     }
+    ///     fn migrate(&self, value: Value) -> Result<Value, AsyncError> {
+    ///         let dark = value["dark_mode"].as_bool().unwrap_or_default();
+    ///         Ok(json!({ "theme": if dark { "dark" } else { "light" } }))
+    ///     }
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let backend = FilePersistence::new("settings.json")
+    ///         .with_migrations(1, vec![Box::new(DarkModeToTheme)])
+    ///         .on_migration_error(|error| eprintln!("settings were not restored: {error}"));
+    ///     let store = StateStore::new(Settings { theme: "light".to_string() }).with_persistence(backend);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_migrations(mut self, schema_version: u8, migrations: Vec<Box<dyn Migration>>) -> Self {
+        self.migrations = Some(Migrations::new(schema_version, migrations));
+        self
+    }
+
+    /// Registers a hook called when a file fails to migrate, see
+    /// [`with_migrations`](Self::with_migrations).
+    pub fn on_migration_error<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&MigrationError) + Send + Sync + 'static,
+    {
+        self.on_migration_error = Some(Arc::new(hook));
+        self
+    }
+
+    /// Returns the path of the state file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Decodes and migrates the content of a file written with a schema version.
+    fn decode_migrated<S: DeserializeOwned>(&self, migrations: &Migrations, bytes: &[u8]) -> Result<S, AsyncError> {
+        let migrated = decode_versioned::<_, serde_json::Value>(&self.codec, bytes)
+            .map_err(|e| MigrationError::Decode { message: e.to_string() })
+            .and_then(|(version, value)| migrations.apply(version, value));
+        migrated.map_err(|error| {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(%error, path = %self.path.display(), "failed to migrate the persisted state");
+            if let Some(hook) = &self.on_migration_error {
+                hook(&error);
+            }
+            error.into()
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<S, C> StatePersistence<S> for FilePersistence<C>
+where
+    S: State + Serialize + DeserializeOwned,
+    C: Codec,
+{
+    async fn save(&self, state: &S) -> Result<(), AsyncError> {
+        let bytes = match &self.migrations {
+            Some(migrations) => encode_versioned(&self.codec, migrations.current(), state)?,
+            None => self.codec.encode(state)?,
+        };
+        let mut temporary = self.path.clone().into_os_string();
+        temporary.push(".tmp");
+        tokio::fs::write(&temporary, bytes)
+            .await
+            .map_err(|e| AsyncError::error(e.to_string()))?;
+        tokio::fs::rename(&temporary, &self.path)
+            .await
+            .map_err(|e| AsyncError::error(e.to_string()))
+    }
+
+    async fn load(&self) -> Result<Option<S>, AsyncError> {
+        match tokio::fs::read(&self.path).await {
+            Ok(bytes) => match &self.migrations {
+                Some(migrations) => self.decode_migrated(migrations, &bytes).map(Some),
+                None => self.codec.decode(&bytes).map(Some),
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(AsyncError::error(e.to_string())),
+        }
+    }
+}
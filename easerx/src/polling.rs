@@ -0,0 +1,73 @@
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::task::JoinError;
+use tokio_util::sync::CancellationToken;
+use crate::{AsyncError, ExecutionTicket};
+
+/// What [`StateStore::async_execute_until`](crate::StateStore::async_execute_until) does when an
+/// iteration fails.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, Hash)]
+pub enum PollFailure {
+    /// Stops polling, leaving the failure in the state.
+    #[default]
+    Stop,
+
+    /// Writes the failure into the state and keeps polling after the interval.
+    KeepPolling,
+}
+
+/// The handle of a polling execution started by
+/// [`StateStore::async_execute_until`](crate::StateStore::async_execute_until).
+///
+/// Awaiting the handle works like awaiting an [`ExecutionTicket`] and resolves once polling stopped.
+/// Dropping the handle does not stop polling; call [`cancel`](Self::cancel) instead.
+pub struct PollingHandle {
+    ticket: ExecutionTicket,
+    token: CancellationToken,
+}
+
+impl PollingHandle {
+    pub(crate) fn new(ticket: ExecutionTicket, token: CancellationToken) -> Self {
+        PollingHandle { ticket, token }
+    }
+
+    /// Stops polling. A computation still running is cancelled and the state is set to
+    /// `Fail` with `AsyncError::Cancelled`, retaining the last value.
+    pub fn cancel(&self) {
+        self.token.cancel();
+    }
+
+    /// Returns true if [`cancel`](Self::cancel) was called.
+    pub fn is_cancelled(&self) -> bool {
+        self.token.is_cancelled()
+    }
+
+    /// Returns true if polling has stopped.
+    pub fn is_finished(&self) -> bool {
+        self.ticket.is_finished()
+    }
+
+    /// Converts the handle into the underlying [`ExecutionTicket`].
+    pub fn into_ticket(self) -> ExecutionTicket {
+        self.ticket
+    }
+}
+
+impl Future for PollingHandle {
+    type Output = Result<Result<(), AsyncError>, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.ticket).poll(cx)
+    }
+}
+
+impl fmt::Debug for PollingHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollingHandle")
+            .field("cancelled", &self.is_cancelled())
+            .field("finished", &self.is_finished())
+            .finish()
+    }
+}
@@ -0,0 +1,72 @@
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+
+/// A read-only request against a state of type `S`.
+///
+/// A query is a small value describing what to read (it may carry parameters), paired with the
+/// type of its answer. Handlers are registered once on a [`StateStore`](crate::StateStore) with
+/// [`register_query`](crate::StateStore::register_query) and invoked with
+/// [`query`](crate::StateStore::query), which keeps view code free of state-shape knowledge.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{Query, State};
+///
+/// #[derive(Clone)]
+/// struct TodoState {
+///     todos: Vec<String>,
+/// }
+/// impl State for TodoState {}
+///
+/// struct GetTodoCount;
+/// impl Query<TodoState> for GetTodoCount {
+///     type Output = usize;
+/// }
+/// ```
+pub trait Query<S>: Send + 'static {
+    /// The answer produced by the query handler.
+    type Output: Send + 'static;
+}
+
+pub(crate) type QueryHandler<S, Q> = Arc<dyn Fn(&S, Q) -> <Q as Query<S>>::Output + Send + Sync>;
+
+/// Type-erased storage for query handlers, keyed by the query type.
+pub(crate) struct QueryRegistry {
+    handlers: RwLock<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl QueryRegistry {
+    pub(crate) fn new() -> Self {
+        QueryRegistry {
+            handlers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub(crate) fn insert<S: 'static, Q: Query<S>>(&self, handler: QueryHandler<S, Q>) {
+        self.handlers
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(TypeId::of::<Q>(), Box::new(handler));
+    }
+
+    pub(crate) fn get<S: 'static, Q: Query<S>>(&self) -> Option<QueryHandler<S, Q>> {
+        self.handlers
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(&TypeId::of::<Q>())
+            .and_then(|handler| handler.downcast_ref::<QueryHandler<S, Q>>())
+            .cloned()
+    }
+}
+
+impl fmt::Debug for QueryRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.handlers.read().map(|h| h.len()).unwrap_or_default();
+        f.debug_struct("QueryRegistry")
+            .field("handlers", &count)
+            .finish()
+    }
+}
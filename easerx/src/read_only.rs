@@ -0,0 +1,131 @@
+use futures_signals::signal::{MutableSignalCloned, Signal, SignalExt};
+use crate::{AsyncError, Query, State, StateStore, StateStream, WaitError};
+
+/// A handle to a [`StateStore`] that can only read the state, created by [`StateStore::read_only`].
+///
+/// It shares the store it was created from, so it sees every update made through the writable
+/// handle, and cloning it is as cheap as cloning the store. It exposes no way to change the state,
+/// to run executions, or to get the writable store back, so it can be handed to code that must not
+/// mutate the state, such as third-party plugins. Like a store clone, it keeps the store's queue
+/// running while it is alive.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{ReadOnlyStore, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Settings {
+///     theme: String,
+/// }
+/// impl State for Settings {}
+///
+/// fn plugin(settings: ReadOnlyStore<Settings>) -> String {
+///     settings.get_state().theme
+/// }
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Settings { theme: "dark".to_string() });
+///     assert_eq!(plugin(store.read_only()), "dark");
+///     Ok(())
+/// }
+/// ```
+///
+/// Writes don't compile:
+///
+/// ```compile_fail
+/// use easerx::{ReadOnlyStore, State};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Settings { theme: String }
+/// impl State for Settings {}
+///
+/// fn plugin(settings: ReadOnlyStore<Settings>) {
+///     settings.set_state(|_| Settings { theme: "light".to_string() });
+/// }
+/// ```
+///
+/// ```compile_fail
+/// use easerx::{Async, ReadOnlyStore, State};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Settings { theme: Async<String> }
+/// impl State for Settings {}
+///
+/// fn plugin(settings: ReadOnlyStore<Settings>) {
+///     settings.execute(|| "light".to_string(), |_, theme| Settings { theme });
+/// }
+/// ```
+///
+/// Neither does getting the writable store back:
+///
+/// ```compile_fail
+/// use easerx::{ReadOnlyStore, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Settings { theme: String }
+/// impl State for Settings {}
+///
+/// fn plugin(settings: ReadOnlyStore<Settings>) -> StateStore<Settings> {
+///     settings.into()
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct ReadOnlyStore<S: State> {
+    pub(crate) store: StateStore<S>,
+}
+
+impl<S: State> StateStore<S> {
+    /// Returns a handle to this store that can only read the state, see [`ReadOnlyStore`].
+    pub fn read_only(&self) -> ReadOnlyStore<S> {
+        ReadOnlyStore { store: self.clone() }
+    }
+}
+
+impl<S: State> ReadOnlyStore<S> {
+    /// Returns the current state, see [`StateStore::get_state`].
+    pub fn get_state(&self) -> S {
+        self.store.get_state()
+    }
+
+    /// Resolves to the state once every update queued so far has been applied, see
+    /// [`StateStore::await_state`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`WaitError::Closed`] if the store's queue stopped before it read the state.
+    pub async fn await_state(&self) -> Result<S, WaitError> {
+        self.store.await_state().await
+    }
+
+    /// Returns a signal of the state, see [`StateStore::to_signal`].
+    pub fn to_signal(&self) -> MutableSignalCloned<S> {
+        self.store.to_signal()
+    }
+
+    /// Returns a stream of the state, see [`StateStore::to_stream`].
+    pub fn to_stream(&self) -> StateStream<S> {
+        self.store.to_stream()
+    }
+
+    /// Returns a signal of a value derived from the state.
+    pub fn signal_map<U, F>(&self, f: F) -> impl Signal<Item = U> + Send + 'static
+    where
+        F: FnMut(S) -> U + Send + 'static,
+    {
+        self.store.to_signal().map(f)
+    }
+
+    /// Runs a query against the current state, see [`StateStore::query`].
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `AsyncError` if no handler is registered for `Q`, if the state channel is closed,
+    /// or if the handler panics.
+    pub async fn query<Q>(&self, query: Q) -> Result<Q::Output, AsyncError>
+    where
+        Q: Query<S>,
+    {
+        self.store.query(query).await
+    }
+}
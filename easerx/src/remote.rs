@@ -0,0 +1,59 @@
+//! Network-oriented helpers for [`AsyncRemote<T>`](crate::AsyncRemote).
+//!
+//! This module is only available with the `remote` feature enabled.
+
+use crate::{Async, AsyncError, AsyncRemote};
+
+/// Message fragments that indicate a connectivity problem rather than a domain error.
+const NETWORK_ERROR_HINTS: [&str; 7] = [
+    "network",
+    "connection",
+    "unreachable",
+    "dns",
+    "timed out",
+    "broken pipe",
+    "offline",
+];
+
+/// Extension trait adding network semantics to [`AsyncRemote<T>`](crate::AsyncRemote).
+///
+/// Since `AsyncRemote<T>` is an alias of [`Async<T>`](crate::Async), these methods are
+/// available on every `Async<T>`; importing the trait is the signal that a field holds remote data.
+pub trait AsyncRemoteExt {
+    /// Returns true if the operation failed because of a connectivity problem.
+    ///
+    /// Timeouts are always treated as network errors. General errors are classified by
+    /// looking for common connectivity phrases (e.g. "connection refused", "network unreachable")
+    /// in their message.
+    fn is_network_error(&self) -> bool;
+
+    /// Returns true if the operation is worth retrying once connectivity is restored.
+    ///
+    /// This is true for failed operations whose error is a network error. Cancelled operations
+    /// and operations that returned `None` are not retried.
+    fn should_retry_after_connectivity_restored(&self) -> bool;
+}
+
+impl<T: Clone> AsyncRemoteExt for AsyncRemote<T> {
+    fn is_network_error(&self) -> bool {
+        match self {
+            Async::Fail { error, .. } => is_network_error(error),
+            _ => false,
+        }
+    }
+
+    fn should_retry_after_connectivity_restored(&self) -> bool {
+        self.is_network_error()
+    }
+}
+
+fn is_network_error(error: &AsyncError) -> bool {
+    match error {
+        AsyncError::Timeout | AsyncError::UpstreamTimeout => true,
+        AsyncError::Error { message, .. } => {
+            let message = message.to_lowercase();
+            NETWORK_ERROR_HINTS.iter().any(|hint| message.contains(hint))
+        }
+        _ => false,
+    }
+}
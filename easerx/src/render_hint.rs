@@ -0,0 +1,142 @@
+use crate::Async;
+
+/// How a UI should render the current state of an [`Async<T>`].
+///
+/// UI bindings can map each hint to a rendering strategy once, instead of matching on
+/// `Async` variants in every view.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum RenderHint {
+    /// Render the content (a value or an error) normally.
+    Show,
+
+    /// Render nothing.
+    Hide,
+
+    /// Render the previous content dimmed while new content is loading.
+    Fade,
+
+    /// Render a placeholder skeleton while the first content is loading.
+    Skeleton,
+}
+
+impl<T: Clone> Async<T> {
+    /// Returns the default [`RenderHint`] for this state.
+    ///
+    /// - `Uninitialized` → `Hide`
+    /// - `Loading` without a retained value → `Skeleton`
+    /// - `Loading` with a retained value → `Fade`
+    /// - `Success` → `Show`
+    /// - `Fail` → `Show` (to show the error)
+    pub fn render_hint(&self) -> RenderHint {
+        RenderHints::default().hint_for(self)
+    }
+}
+
+/// A table mapping each kind of [`Async<T>`] state to a [`RenderHint`].
+///
+/// The [`Default`] table matches [`Async::render_hint`]. Override individual entries to customize
+/// the rendering of a store's fields, e.g. to keep showing retained values while reloading.
+///
+/// ## Examples
+///
+/// ```
+/// use easerx::{Async, RenderHint, RenderHints};
+///
+/// let hints = RenderHints {
+///     loading_retained: RenderHint::Show,
+///     ..RenderHints::default()
+/// };
+/// assert_eq!(hints.hint_for(&Async::loading(Some(1))), RenderHint::Show);
+/// ```
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct RenderHints {
+    /// The hint for `Uninitialized`.
+    pub uninitialized: RenderHint,
+    /// The hint for `Loading` without a retained value.
+    pub loading_empty: RenderHint,
+    /// The hint for `Loading` with a retained value.
+    pub loading_retained: RenderHint,
+    /// The hint for `Success`.
+    pub success: RenderHint,
+    /// The hint for `Fail`, regardless of the retained value.
+    pub fail: RenderHint,
+}
+
+impl RenderHints {
+    /// Returns the hint for the given state.
+    pub fn hint_for<T: Clone>(&self, value: &Async<T>) -> RenderHint {
+        match value {
+            Async::Uninitialized => self.uninitialized,
+            Async::Loading { value: None } => self.loading_empty,
+            Async::Loading { value: Some(_) } => self.loading_retained,
+            Async::Success { .. } => self.success,
+            Async::Fail { .. } => self.fail,
+        }
+    }
+}
+
+impl Default for RenderHints {
+    fn default() -> Self {
+        RenderHints {
+            uninitialized: RenderHint::Hide,
+            loading_empty: RenderHint::Skeleton,
+            loading_retained: RenderHint::Fade,
+            success: RenderHint::Show,
+            fail: RenderHint::Show,
+        }
+    }
+}
+
+/// An [`Async<T>`] carrying its own [`RenderHints`] table.
+///
+/// Use it as a state field to customize how that field is rendered while keeping the
+/// customization next to the data, so every view of the store renders it consistently.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct WithRenderHints<T: Clone> {
+    value: Async<T>,
+    hints: RenderHints,
+}
+
+impl<T: Clone> WithRenderHints<T> {
+    /// Wraps an `Async<T>` with the given hints table.
+    pub fn new(value: Async<T>, hints: RenderHints) -> Self {
+        WithRenderHints { value, hints }
+    }
+
+    /// Returns a reference to the wrapped `Async<T>`.
+    pub fn value(&self) -> &Async<T> {
+        &self.value
+    }
+
+    /// Replaces the wrapped value, keeping the hints table.
+    pub fn set(self, value: Async<T>) -> Self {
+        WithRenderHints { value, ..self }
+    }
+
+    /// Returns the hints table.
+    pub fn hints(&self) -> &RenderHints {
+        &self.hints
+    }
+
+    /// Returns the render hint of the wrapped value according to the hints table.
+    pub fn render_hint(&self) -> RenderHint {
+        self.hints.hint_for(&self.value)
+    }
+
+    /// Consumes the wrapper and returns the wrapped `Async<T>`.
+    pub fn into_inner(self) -> Async<T> {
+        self.value
+    }
+}
+
+impl<T: Clone> From<Async<T>> for WithRenderHints<T> {
+    fn from(value: Async<T>) -> Self {
+        WithRenderHints::new(value, RenderHints::default())
+    }
+}
+
+impl<T: Clone> Default for WithRenderHints<T> {
+    fn default() -> Self {
+        WithRenderHints::from(Async::default())
+    }
+}
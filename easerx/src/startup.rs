@@ -0,0 +1,316 @@
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use futures_core::future::BoxFuture;
+use thiserror::Error;
+use tokio::task::{Id, JoinSet};
+use crate::{Async, AsyncError, ExecutionResult, State, StateStore};
+
+/// The error returned by [`Startup::run`].
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum StartupError {
+    /// Two tasks were registered with the same name.
+    #[error("Startup task `{task}` is registered twice")]
+    DuplicateTask { task: &'static str },
+
+    /// A task depends on a task that was never registered.
+    #[error("Startup task `{task}` depends on unknown task `{dependency}`")]
+    UnknownDependency { task: &'static str, dependency: &'static str },
+
+    /// The dependencies form a cycle, listed so that each task runs after the next one and the
+    /// last one after the first.
+    #[error("Startup tasks form a cycle: {}", cycle_path(.tasks))]
+    Cycle { tasks: Vec<&'static str> },
+
+    /// A task failed. Every task depending on it, directly or not, was aborted without running.
+    #[error("Startup task `{task}` failed: {error}")]
+    TaskFailed {
+        task: &'static str,
+        error: AsyncError,
+        aborted: Vec<&'static str>,
+    },
+}
+
+fn cycle_path(tasks: &[&'static str]) -> String {
+    let mut path = tasks.join(" -> ");
+    if let Some(first) = tasks.first() {
+        path.push_str(" -> ");
+        path.push_str(first);
+    }
+    path
+}
+
+type TaskRun = Box<dyn FnOnce() -> BoxFuture<'static, Result<(), AsyncError>> + Send>;
+
+struct StartupTask {
+    name: &'static str,
+    after: Vec<&'static str>,
+    run: TaskRun,
+    abort: Box<dyn FnOnce(AsyncError) + Send>,
+}
+
+/// Spawns the task at `index` unless it already ran or was aborted.
+fn start(
+    runs: &mut [Option<TaskRun>],
+    indices: &mut HashMap<Id, usize>,
+    running: &mut JoinSet<Result<(), AsyncError>>,
+    index: usize,
+) {
+    if let Some(run) = runs[index].take() {
+        indices.insert(running.spawn(run()).id(), index);
+    }
+}
+
+/// Initializes several stores in dependency order, running independent initializers in parallel.
+///
+/// Each task writes the result of its async initializer into an `Async` field of its store, like
+/// [`StateStore::async_execute`]: `Loading` when it starts, then the result. A task starts as soon
+/// as every task it runs [`after`](Self::after) has committed its result, so it can read the
+/// initialized state from their stores. When a task fails, every task depending on it, directly or
+/// not, is aborted: its field is set to a failure naming the failed dependency and its initializer
+/// never runs. Independent tasks still run to completion.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{Async, Startup, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Config { url: Async<String> }
+/// impl State for Config {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Auth { token: Async<String> }
+/// impl State for Auth {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let config = StateStore::new(Config { url: Async::Uninitialized });
+///     let auth = StateStore::new(Auth { token: Async::Uninitialized });
+///
+///     let config_store = config.clone();
+///     Startup::new()
+///         .task("auth", &auth, move || async move {
+///             let url = config_store.get_state().url.value_ref_clone().unwrap_or_default();
+///             Ok::<_, String>(format!("token for {url}"))
+///         }, |_, token| Auth { token })
+///         .after("config")
+///         .task("config", &config, || async { "https://example.com".to_string() }, |_, url| Config { url })
+///         .run()
+///         .await?;
+///
+///     assert_eq!(auth.get_state().token, Async::success("token for https://example.com".to_string()));
+///     Ok(())
+/// }
+/// ```
+#[derive(Default)]
+#[must_use = "A startup does nothing until run is called"]
+pub struct Startup {
+    tasks: Vec<StartupTask>,
+}
+
+impl Startup {
+    /// Creates a startup without tasks.
+    pub fn new() -> Self {
+        Startup::default()
+    }
+
+    /// Adds a task initializing a field of `store` with the result of `initializer`.
+    ///
+    /// Dependencies may name tasks added later; they are checked by [`run`](Self::run).
+    pub fn task<S, T, R, F, Fut, U>(
+        mut self,
+        name: &'static str,
+        store: &StateStore<S>,
+        initializer: F,
+        state_updater: U,
+    ) -> Self
+    where
+        S: State,
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (run_store, abort_store) = (store.clone(), store.clone());
+        let abort_updater = state_updater.clone();
+        self.tasks.push(StartupTask {
+            name,
+            after: Vec::new(),
+            run: Box::new(move || {
+                Box::pin(async move {
+                    let loading_updater = state_updater.clone();
+                    run_store.set_state(move |state| loading_updater(state, Async::loading(None)))?;
+                    let async_result = initializer().await.into_async();
+                    let outcome = match &async_result {
+                        Async::Fail { error, .. } => {
+                            run_store.notify_async_fail(error);
+                            Err(error.clone())
+                        }
+                        _ => Ok(()),
+                    };
+                    run_store.set_state(move |state| state_updater(state, async_result))?;
+                    // Dependents start once the result is visible in the store
+                    run_store.await_state().await?;
+                    outcome
+                })
+            }),
+            abort: Box::new(move |error| {
+                abort_store.notify_async_fail(&error);
+                abort_store.set_state_forget(move |state| abort_updater(state, Async::fail(error, None)));
+            }),
+        });
+        self
+    }
+
+    /// Makes the last added task start only after the task named `dependency` succeeded.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if no task was added yet.
+    pub fn after(mut self, dependency: &'static str) -> Self {
+        let task = self.tasks.last_mut().expect("Startup::after called before adding a task");
+        task.after.push(dependency);
+        self
+    }
+
+    /// Returns the number of tasks.
+    pub fn len(&self) -> usize {
+        self.tasks.len()
+    }
+
+    /// Returns true if no task was added.
+    pub fn is_empty(&self) -> bool {
+        self.tasks.is_empty()
+    }
+
+    /// Checks the dependency graph without running anything.
+    ///
+    /// ## Errors
+    ///
+    /// Returns [`StartupError::DuplicateTask`], [`StartupError::UnknownDependency`] or
+    /// [`StartupError::Cycle`] if the tasks can't be ordered.
+    pub fn validate(&self) -> Result<(), StartupError> {
+        self.dependents().map(|_| ())
+    }
+
+    /// Runs every task, each as soon as its dependencies succeeded, and resolves once all tasks
+    /// have completed or been aborted.
+    ///
+    /// The graph is validated first, see [`validate`](Self::validate); no task runs if it is invalid.
+    ///
+    /// ## Errors
+    ///
+    /// Returns the validation error, or [`StartupError::TaskFailed`] with the first task that failed.
+    pub async fn run(self) -> Result<(), StartupError> {
+        let dependents = self.dependents()?;
+        let mut waiting: Vec<usize> = self.tasks.iter().map(|task| task.after.len()).collect();
+        let names: Vec<&'static str> = self.tasks.iter().map(|task| task.name).collect();
+        let mut runs = Vec::with_capacity(self.tasks.len());
+        let mut aborts = Vec::with_capacity(self.tasks.len());
+        for task in self.tasks {
+            runs.push(Some(task.run));
+            aborts.push(Some(task.abort));
+        }
+
+        let mut running = JoinSet::new();
+        let mut indices = HashMap::new();
+        for index in (0..waiting.len()).filter(|&index| waiting[index] == 0) {
+            start(&mut runs, &mut indices, &mut running, index);
+        }
+
+        let mut failure: Option<(&'static str, AsyncError)> = None;
+        let mut aborted = Vec::new();
+        while let Some(joined) = running.join_next_with_id().await {
+            let (index, result) = match joined {
+                Ok((id, result)) => (indices[&id], result),
+                Err(error) => {
+                    // The initializer panicked before its result was written
+                    let index = indices[&error.id()];
+                    let error = AsyncError::error(error.to_string());
+                    if let Some(abort) = aborts[index].take() {
+                        abort(error.clone());
+                    }
+                    (index, Err(error))
+                }
+            };
+            aborts[index] = None;
+            match result {
+                Ok(()) => {
+                    for &dependent in &dependents[index] {
+                        waiting[dependent] -= 1;
+                        if waiting[dependent] == 0 {
+                            start(&mut runs, &mut indices, &mut running, dependent);
+                        }
+                    }
+                }
+                Err(error) => {
+                    let failed = names[index];
+                    let mut queue: VecDeque<usize> = dependents[index].iter().copied().collect();
+                    while let Some(dependent) = queue.pop_front() {
+                        if let Some(abort) = aborts[dependent].take() {
+                            runs[dependent] = None;
+                            abort(AsyncError::error(format!("Startup dependency `{failed}` failed: {error}")));
+                            aborted.push(names[dependent]);
+                            queue.extend(dependents[dependent].iter().copied());
+                        }
+                    }
+                    failure.get_or_insert((failed, error));
+                }
+            }
+        }
+
+        match failure {
+            Some((task, error)) => Err(StartupError::TaskFailed { task, error, aborted }),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the indices of the tasks depending on each task, checking that the graph is a DAG.
+    fn dependents(&self) -> Result<Vec<Vec<usize>>, StartupError> {
+        let mut indices = HashMap::new();
+        for (index, task) in self.tasks.iter().enumerate() {
+            if indices.insert(task.name, index).is_some() {
+                return Err(StartupError::DuplicateTask { task: task.name });
+            }
+        }
+        let mut dependents = vec![Vec::new(); self.tasks.len()];
+        for (index, task) in self.tasks.iter().enumerate() {
+            for dependency in &task.after {
+                let Some(&dependency_index) = indices.get(dependency) else {
+                    return Err(StartupError::UnknownDependency { task: task.name, dependency });
+                };
+                dependents[dependency_index].push(index);
+            }
+        }
+
+        // Kahn's algorithm: whatever can't be ordered is on a cycle or depends on one
+        let mut waiting: Vec<usize> = self.tasks.iter().map(|task| task.after.len()).collect();
+        let mut ready: Vec<usize> = (0..waiting.len()).filter(|&index| waiting[index] == 0).collect();
+        while let Some(index) = ready.pop() {
+            for &dependent in &dependents[index] {
+                waiting[dependent] -= 1;
+                if waiting[dependent] == 0 {
+                    ready.push(dependent);
+                }
+            }
+        }
+        let Some(mut current) = waiting.iter().position(|&count| count > 0) else {
+            return Ok(dependents);
+        };
+        // Every unordered task waits for another unordered one, so following them ends in a cycle
+        let mut path = Vec::new();
+        while !path.contains(&current) {
+            path.push(current);
+            current = self.tasks[current]
+                .after
+                .iter()
+                .map(|dependency| indices[dependency])
+                .find(|&dependency| waiting[dependency] > 0)
+                .expect("an unordered task waits for another unordered task");
+        }
+        let start = path.iter().position(|&index| index == current).unwrap_or(0);
+        Err(StartupError::Cycle {
+            tasks: path[start..].iter().map(|&index| self.tasks[index].name).collect(),
+        })
+    }
+}
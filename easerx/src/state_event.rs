@@ -0,0 +1,127 @@
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use futures_core::stream::Stream;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::broadcast::Receiver;
+use futures_core::future::BoxFuture;
+use crate::store_error::{StoreError, WeakStoreErrors};
+
+/// An item of a lossless state subscription created by [`StateStore::subscribe_all`](crate::StateStore::subscribe_all).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum StateEvent<S> {
+    /// A committed state.
+    State(S),
+
+    /// The subscriber fell behind and the given number of states were skipped.
+    Lagged(u64),
+}
+
+impl<S> StateEvent<S> {
+    /// Returns the contained state, or `None` for a `Lagged` event.
+    pub fn state(self) -> Option<S> {
+        match self {
+            StateEvent::State(state) => Some(state),
+            StateEvent::Lagged(_) => None,
+        }
+    }
+
+    /// Returns true if this event reports skipped states.
+    pub fn is_lagged(&self) -> bool {
+        matches!(self, StateEvent::Lagged(_))
+    }
+}
+
+type RecvResult<S> = (Result<S, RecvError>, Receiver<S>);
+
+/// A stream of every committed state of a store.
+///
+/// The stream ends once the store and all of its clones have been dropped.
+#[must_use = "Streams do nothing unless polled"]
+pub struct StateEventStream<S> {
+    inner: BoxFuture<'static, RecvResult<S>>,
+    errors: Option<WeakStoreErrors>,
+}
+
+async fn recv<S: Clone>(mut rx: Receiver<S>) -> RecvResult<S> {
+    let result = rx.recv().await;
+    (result, rx)
+}
+
+impl<S: Clone + Send + 'static> StateEventStream<S> {
+    /// Creates a stream reporting its lag events to `errors`, if given.
+    pub(crate) fn new(rx: Receiver<S>, errors: Option<WeakStoreErrors>) -> Self {
+        StateEventStream {
+            inner: Box::pin(recv(rx)),
+            errors,
+        }
+    }
+}
+
+impl<S: Clone + Send + 'static> Stream for StateEventStream<S> {
+    type Item = StateEvent<S>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let (result, rx) = match self.inner.as_mut().poll(cx) {
+            Poll::Ready(ready) => ready,
+            Poll::Pending => return Poll::Pending,
+        };
+        let item = match result {
+            Ok(state) => Some(StateEvent::State(state)),
+            Err(RecvError::Lagged(skipped)) => {
+                if let Some(errors) = &self.errors {
+                    errors.publish(|| StoreError::Lagged { skipped });
+                }
+                Some(StateEvent::Lagged(skipped))
+            }
+            Err(RecvError::Closed) => None,
+        };
+        self.inner = Box::pin(recv(rx));
+        Poll::Ready(item)
+    }
+}
+
+impl<S> fmt::Debug for StateEventStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateEventStream").finish()
+    }
+}
+
+/// A stream of every committed state of a store, created by [`StateStore::broadcast`](crate::StateStore::broadcast).
+///
+/// This is [`StateEventStream`] without lag reporting: if the receiver falls more than the
+/// configured capacity behind, the skipped states are silently dropped and the stream resumes
+/// with the oldest state still buffered.
+#[must_use = "Streams do nothing unless polled"]
+pub struct StateReceiver<S> {
+    events: StateEventStream<S>,
+}
+
+impl<S: Clone + Send + 'static> StateReceiver<S> {
+    pub(crate) fn new(rx: Receiver<S>, errors: Option<WeakStoreErrors>) -> Self {
+        StateReceiver {
+            events: StateEventStream::new(rx, errors),
+        }
+    }
+}
+
+impl<S: Clone + Send + 'static> Stream for StateReceiver<S> {
+    type Item = S;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            match Pin::new(&mut self.events).poll_next(cx) {
+                Poll::Ready(Some(StateEvent::State(state))) => return Poll::Ready(Some(state)),
+                Poll::Ready(Some(StateEvent::Lagged(_))) => continue,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl<S> fmt::Debug for StateReceiver<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateReceiver").finish()
+    }
+}
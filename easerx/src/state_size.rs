@@ -0,0 +1,68 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use crate::store_error::StoreErrors;
+use crate::StoreError;
+
+/// The default number of commits between two samples of the state size, see
+/// [`StateStoreBuilder::state_size_sample_interval`](crate::StateStoreBuilder::state_size_sample_interval).
+pub const DEFAULT_STATE_SIZE_SAMPLE_INTERVAL: u64 = 100;
+
+pub(crate) type HeapSizeFn<S> = Arc<dyn Fn(&S) -> usize + Send + Sync>;
+
+/// Warns when the state grows beyond a size budget, since every reducer copies it.
+///
+/// The inline size of the state is checked once, when the store is built. With a heap size
+/// function, the heap footprint is sampled every `sample_interval` commits as well.
+pub(crate) struct StateSizeProbe<S> {
+    limit: usize,
+    heap_size: Option<HeapSizeFn<S>>,
+    sample_interval: u64,
+    /// Whether the last sample exceeded the limit, so a state that stays large is reported once.
+    exceeded: AtomicBool,
+}
+
+impl<S> std::fmt::Debug for StateSizeProbe<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StateSizeProbe")
+            .field("limit", &self.limit)
+            .field("heap_size", &self.heap_size.is_some())
+            .field("sample_interval", &self.sample_interval)
+            .finish()
+    }
+}
+
+impl<S> StateSizeProbe<S> {
+    pub(crate) fn new(limit: usize, heap_size: Option<HeapSizeFn<S>>, sample_interval: u64) -> Self {
+        StateSizeProbe {
+            limit,
+            heap_size,
+            sample_interval: sample_interval.max(1),
+            exceeded: AtomicBool::new(false),
+        }
+    }
+
+    /// Returns true if the state committed as `version` should be sampled.
+    pub(crate) fn is_due(&self, version: u64) -> bool {
+        self.heap_size.is_some() && version.is_multiple_of(self.sample_interval)
+    }
+
+    /// Measures `state` and reports it if it crossed the limit since the last sample.
+    pub(crate) fn check(&self, state: &S, errors: &StoreErrors) {
+        let bytes = size_of::<S>() + self.heap_size.as_ref().map_or(0, |heap_size| heap_size(state));
+        let exceeded = bytes > self.limit;
+        if exceeded && !self.exceeded.swap(true, Ordering::AcqRel) {
+            #[cfg(feature = "tracing")]
+            tracing::warn!(
+                bytes,
+                limit = self.limit,
+                state = std::any::type_name::<S>(),
+                "state is larger than its size budget and every reducer copies it; \
+                 move large fields behind `Arc` so clones share them"
+            );
+            let limit = self.limit;
+            errors.publish(|| StoreError::StateTooLarge { bytes, limit });
+        } else if !exceeded {
+            self.exceeded.store(false, Ordering::Release);
+        }
+    }
+}
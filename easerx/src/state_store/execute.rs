@@ -0,0 +1,3407 @@
+use std::any::TypeId;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+use tokio_util::sync::CancellationToken;
+use crate::{ArcAsync, ArcExecutionResult, Async, AsyncError, AsyncStaged, AsyncWithCount, Deadline, ExecuteOptions, ExecutionResult, ExecutionTicket, LatencyHistogram, RetainPolicy, StageReporter, State, StoreError};
+use crate::error_recovery::{restored, ErrorRecovery};
+use crate::execution_result::ArcResult;
+use crate::blocking_pool::BlockingPressure;
+use crate::deadline;
+use crate::execute_options::ExecutionTimeout;
+use crate::execution_span::ExecutionSpan;
+use crate::fail_handler::FailHandlers;
+use crate::job::{JobGuard, JobKey};
+use crate::store_error::WeakStoreErrors;
+use crate::panic_policy::CatchUnwind;
+use crate::{PanicPolicy, PollFailure, PollingHandle, RecoveryAction};
+use crate::transition::{Origin, TransitionOrigin};
+use super::execution_queue::QueueTurn;
+use super::{Reducer, ReducerSender, StateStore};
+
+/// The value a `Loading` that doesn't retain cleared, kept for the result of the execution.
+type ClearedValue<T> = Arc<Mutex<Option<T>>>;
+
+/// The computation of an execution, with the means to make it again if it can be retried.
+struct Attempts<F> {
+    first: F,
+    again: Option<Box<dyn FnMut() -> F + Send>>,
+}
+
+impl<F> Attempts<F> {
+    /// A computation that runs once, so a retry of the recovery policy cannot apply.
+    fn once(computation: F) -> Self {
+        Attempts { first: computation, again: None }
+    }
+
+    /// A computation made anew for every attempt.
+    fn repeated(mut make: impl FnMut() -> F + Send + 'static) -> Self {
+        Attempts {
+            first: make(),
+            again: Some(Box::new(make)),
+        }
+    }
+}
+
+/// The sending half used by executions to write their results.
+/// Failures go through the `with_error_recovery` policy and are reported to the `on_async_fail`
+/// handlers when their reducer runs.
+struct ExecutionSender<S> {
+    set_state_tx: ReducerSender<S>,
+    /// The call site of the execute method, for the transition log.
+    origin: Origin,
+    fail_handlers: Arc<FailHandlers>,
+    recovery: Arc<ErrorRecovery>,
+    panic_policy: PanicPolicy,
+    timeout: Option<ExecutionTimeout>,
+    retain: RetainPolicy,
+    blocking: Arc<BlockingPressure>,
+    errors: WeakStoreErrors,
+}
+
+impl<S: 'static> ExecutionSender<S> {
+    fn send(
+        &self,
+        reducer: Reducer<S>,
+        kind: TransitionOrigin,
+    ) -> Result<(), AsyncError> {
+        self.set_state_tx.send(reducer, self.origin.with_kind(kind))
+    }
+}
+
+impl<S: State> StateStore<S> {
+    /// Sets the policy deciding what happens when an execution of this store fails.
+    ///
+    /// `policy` is called with the error before a failed result is written into the state, and
+    /// replaces any policy set before. [`RecoveryAction::Propagate`] writes the failure as usual, while
+    /// [`RecoveryAction::Ignore`] discards it and restores the value retained while loading.
+    /// Cancellations are never passed to the policy.
+    ///
+    /// [`RecoveryAction::Retry`] and [`RecoveryAction::RetryAfter`] keep the state `Loading` and run
+    /// the computation again, which takes an execution that can call its computation more than once:
+    /// [`execute_recoverable`](Self::execute_recoverable),
+    /// [`async_execute_recoverable`](Self::async_execute_recoverable),
+    /// [`execute_cancellable_loop`](Self::execute_cancellable_loop) and
+    /// [`async_execute_until`](Self::async_execute_until). The other `execute*` methods take their
+    /// computation by value and run it once, so they write the failure and report a
+    /// [`StoreError::RetryUnsupported`] to the [`errors`](Self::errors) stream. Failures classified as
+    /// [`ErrorClass::Permanent`](crate::ErrorClass::Permanent) are never retried, whatever the policy
+    /// says, since they would fail the same way again.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, RecoveryAction, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    data: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { data: Async::success(1) })
+    ///         .with_error_recovery(|error| {
+    ///             if error.is_timeout() {
+    ///                 RecoveryAction::Ignore
+    ///             } else {
+    ///                 RecoveryAction::Propagate
+    ///             }
+    ///         });
+    ///     store
+    ///         .execute_with_retain(|| Err::<i32, _>("offline"), |state| &state.data, |_, data| TestState { data })
+    ///         .await??;
+    ///     assert!(store.await_state().await?.data.is_fail());
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_error_recovery<F>(self, policy: F) -> Self
+    where
+        F: Fn(&AsyncError) -> RecoveryAction + Send + Sync + 'static,
+    {
+        self.shared.recovery.set(Arc::new(policy));
+        self
+    }
+
+    /// Registers a handler that is called whenever an execution writes an `Async::Fail` into the state.
+    ///
+    /// This is a single place to turn failures into toasts or log records, whichever field failed.
+    /// Handlers cover the results written by the `execute` family, including
+    /// [`execute_batch_parallel`](Self::execute_batch_parallel): computation errors, panics, timeouts
+    /// and cancellations alike. Use [`AsyncError::is_cancelled`] and [`AsyncError::is_timeout`]
+    /// to filter out the failures that were requested or expected. A `Fail` written manually with
+    /// [`set_state`](Self::set_state) is not reported.
+    ///
+    /// Handlers are shared by all clones of the store and run in registration order on the store's
+    /// background task, right before the failure is committed, so keep them cheap. A panicking
+    /// handler is contained and does not affect the store or the other handlers.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    data: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { data: Async::Uninitialized });
+    ///     store.on_async_fail(|error| {
+    ///         if !error.is_cancelled() {
+    ///             eprintln!("request failed: {error}");
+    ///         }
+    ///     });
+    ///     store.execute(|| Err::<i32, _>("offline"), |_, data| TestState { data });
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn on_async_fail<F>(&self, handler: F)
+    where
+        F: Fn(&AsyncError) + Send + Sync + 'static,
+    {
+        self.shared.fail_handlers.push(Arc::new(handler));
+    }
+
+    /// Returns how long blocking computations of this store waited for a thread of the runtime's
+    /// blocking pool, from being spawned until they started running.
+    ///
+    /// Every blocking computation of the `execute` family is measured. Waits above a few
+    /// milliseconds mean the pool is saturated, see
+    /// [`StateStoreBuilder::blocking_start_warning`](crate::StateStoreBuilder::blocking_start_warning).
+    pub fn blocking_start_latency(&self) -> LatencyHistogram {
+        self.shared.blocking.start_latency()
+    }
+
+    /// Returns how many blocking computations of this store are running right now.
+    ///
+    /// Computations still waiting for a thread of the blocking pool are not counted.
+    pub fn blocking_computations(&self) -> usize {
+        self.shared.blocking.running()
+    }
+
+    pub(crate) fn panic_policy(&self) -> PanicPolicy {
+        self.shared.panic_policy
+    }
+
+    pub(crate) fn notify_async_fail(&self, error: &AsyncError) {
+        self.shared.fail_handlers.notify(error);
+    }
+
+    #[track_caller]
+    fn execution_sender(&self) -> ExecutionSender<S> {
+        self.execution_sender_with(ExecuteOptions::default())
+    }
+
+    /// Creates the sender of an execution, resolving `options` against the store's defaults.
+    #[track_caller]
+    fn execution_sender_with(&self, options: ExecuteOptions) -> ExecutionSender<S> {
+        ExecutionSender {
+            set_state_tx: self.set_state_tx.clone(),
+            origin: Origin::here(TransitionOrigin::ExecuteLoading),
+            fail_handlers: self.shared.fail_handlers.clone(),
+            recovery: self.shared.recovery.clone(),
+            panic_policy: self.shared.panic_policy,
+            timeout: options.resolve_timeout(self.shared.default_execute_timeout),
+            retain: options.retain_setting(),
+            blocking: self.shared.blocking.clone(),
+            errors: self.shared.errors.downgrade(),
+        }
+    }
+
+    /// Runs `computation` within the timeout of the execution, if it has one.
+    async fn within_timeout<T: Clone>(
+        timeout: Option<ExecutionTimeout>,
+        computation: impl Future<Output = Async<T>>,
+    ) -> Async<T> {
+        match timeout {
+            Some(timeout) => tokio::time::timeout(timeout.after(), computation)
+                .await
+                .unwrap_or_else(|_| Async::fail(timeout.error(), None)),
+            None => computation.await,
+        }
+    }
+
+    /// Draws the turn of an execution if the store serializes them, see
+    /// [`StateStoreBuilder::serialize_executions`](crate::StateStoreBuilder::serialize_executions).
+    fn execution_turn(&self) -> Option<QueueTurn> {
+        self.shared.execution_queue.as_ref().map(|queue| queue.enqueue())
+    }
+
+    /// Waits for the turn of a serialized execution, returning `false` if `token` was cancelled
+    /// while it waited.
+    async fn await_turn(turn: Option<&QueueTurn>, token: Option<&CancellationToken>) -> bool {
+        match turn {
+            Some(turn) => turn.wait(token).await,
+            None => true,
+        }
+    }
+
+    /// Spawns an execution task, returning the ticket that watches it.
+    /// The task runs in the span and within the deadline of the caller, see [`ExecutionSpan`].
+    fn spawn_execution<F>(&self, future: F) -> ExecutionTicket
+    where
+        F: Future<Output = Result<(), AsyncError>> + Send + 'static,
+    {
+        self.spawn_execution_within(Deadline::current(), future)
+    }
+
+    /// Like [`spawn_execution`](Self::spawn_execution), running the task within `deadline`
+    /// so that executions started by the computation inherit it.
+    fn spawn_execution_within<F>(&self, deadline: Option<Deadline>, future: F) -> ExecutionTicket
+    where
+        F: Future<Output = Result<(), AsyncError>> + Send + 'static,
+    {
+        let errors = self.shared.errors.downgrade();
+        let span = ExecutionSpan::new();
+        let handle = self.spawn(span.instrument(async move {
+            let result = deadline::scoped(deadline, future).await;
+            if let Err(error) = &result {
+                errors.publish(|| StoreError::ExecutionFailed { error: error.clone() });
+            }
+            result
+        }));
+        ExecutionTicket::new(handle, self.shared.runtime.clone())
+    }
+
+    fn update_async_state<T>(
+        set_state_tx: &ExecutionSender<S>,
+        state_updater: impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        async_state: Async<T>,
+    ) -> Result<(), AsyncError>
+    where
+        T: Send + Clone + 'static,
+    {
+        let action = set_state_tx.recovery.action(&async_state);
+        Self::write_async_state(set_state_tx, state_updater, async_state, action)
+    }
+
+    /// Writes a result whose recovery action was already decided.
+    /// Retrying is up to the caller, so only `Ignore` changes what is written.
+    fn write_async_state<T>(
+        set_state_tx: &ExecutionSender<S>,
+        state_updater: impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        async_state: Async<T>,
+        action: RecoveryAction,
+    ) -> Result<(), AsyncError>
+    where
+        T: Send + Clone + 'static,
+    {
+        let async_state = match action {
+            RecoveryAction::Ignore => restored(async_state.value_ref_clone()),
+            _ => async_state,
+        };
+        let kind = if async_state.is_loading() {
+            TransitionOrigin::ExecuteLoading
+        } else {
+            TransitionOrigin::ExecuteResult
+        };
+        let fail_handlers = set_state_tx.fail_handlers.clone();
+        set_state_tx
+            .send(Box::new(move |old_state| {
+                if let Async::Fail { error, .. } = &async_state {
+                    fail_handlers.notify(error);
+                }
+                Some(state_updater(old_state, async_state))
+            }), kind)
+    }
+
+    async fn run_computation_cancelable<T, R, F>(
+        set_state_tx: &ExecutionSender<S>,
+        computation: F,
+        token: CancellationToken,
+    ) -> Async<T>
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(Option<CancellationToken>) -> R + Send + 'static,
+    {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Async::fail_with_cancelled(None),
+            result = set_state_tx.blocking.spawn({
+                let token = token.clone();
+                move || computation(Some(token))
+            }) => match result {
+                Ok(result) => result.into_async(),
+                Err(e) => Async::fail(set_state_tx.panic_policy.error_from_join(e), None),
+            },
+        }
+    }
+
+    async fn run_computation<T, R, F>(set_state_tx: &ExecutionSender<S>, computation: F) -> Async<T>
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(Option<CancellationToken>) -> R + Send + 'static,
+    {
+        match set_state_tx.blocking.spawn(move || computation(None)).await {
+            Ok(result) => result.into_async(),
+            Err(e) => Async::fail(set_state_tx.panic_policy.error_from_join(e), None),
+        }
+    }
+
+    /// Adapts an infallible state getter to the fallible one the retain paths take.
+    fn retain_getter<T, G>(state_getter: G) -> impl FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static
+    where
+        T: Clone + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+    {
+        move |state| Some(state_getter(state))
+    }
+
+    /// Writes `Loading`, retaining the previous value if the retain policy keeps it while loading.
+    ///
+    /// Returns the slot holding the value `Loading` cleared, which a [`RetainPolicy::FailOnly`]
+    /// failure brings back.
+    fn update_async_to_loading_with_retain<T, G>(
+        set_state_tx: &ExecutionSender<S>,
+        state_updater: impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        state_getter: G,
+    ) -> Result<ClearedValue<T>, AsyncError>
+    where
+        T: Send + Clone + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+    {
+        let retains = set_state_tx.retain.retains_loading();
+        let cleared = ClearedValue::default();
+        let slot = Arc::clone(&cleared);
+        set_state_tx
+            .send(Box::new(move |old_state| {
+                let retained_value = state_getter(&old_state).and_then(Async::value_ref_clone);
+                let loading = if retains {
+                    Async::loading(retained_value)
+                } else {
+                    *slot.lock().unwrap_or_else(|e| e.into_inner()) = retained_value;
+                    Async::loading(None)
+                };
+                Some(state_updater(old_state, loading))
+            }), TransitionOrigin::ExecuteLoading)?;
+        Ok(cleared)
+    }
+
+    fn update_async_cancelable_with_retain<T, G>(
+        set_state_tx: &ExecutionSender<S>,
+        state_updater: impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        state_getter: G,
+        cleared: ClearedValue<T>,
+        async_result: Async<T>,
+        token_is_cancelled: bool,
+    ) -> Result<(), AsyncError>
+    where
+        T: Send + Clone + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+    {
+        let action = if token_is_cancelled {
+            RecoveryAction::Propagate
+        } else {
+            set_state_tx.recovery.action(&async_result)
+        };
+        Self::write_async_with_retain(set_state_tx, state_updater, state_getter, cleared, async_result, token_is_cancelled, action)
+    }
+
+    /// Writes a retaining result whose recovery action was already decided.
+    fn write_async_with_retain<T, G>(
+        set_state_tx: &ExecutionSender<S>,
+        state_updater: impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        state_getter: G,
+        cleared: ClearedValue<T>,
+        async_result: Async<T>,
+        token_is_cancelled: bool,
+        action: RecoveryAction,
+    ) -> Result<(), AsyncError>
+    where
+        T: Send + Clone + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+    {
+        let fail_handlers = set_state_tx.fail_handlers.clone();
+        let retains_fail = set_state_tx.retain.retains_fail();
+        let retains_loading = set_state_tx.retain.retains_loading();
+        set_state_tx
+            .send(Box::new(move |old_state| {
+                let retained = if retains_loading {
+                    state_getter(&old_state).and_then(Async::value_ref_clone)
+                } else {
+                    cleared.lock().unwrap_or_else(|e| e.into_inner()).take()
+                };
+                let fail_retained = retained.clone().filter(|_| retains_fail);
+                let final_result = if token_is_cancelled {
+                    Async::fail_with_cancelled(fail_retained)
+                } else if action == RecoveryAction::Ignore {
+                    restored(retained)
+                } else {
+                    async_result.set_retain_value(fail_retained)
+                };
+                if let Async::Fail { error, .. } = &final_result {
+                    fail_handlers.notify(error);
+                }
+                Some(state_updater(old_state, final_result))
+            }), TransitionOrigin::ExecuteResult)
+    }
+
+    #[track_caller]
+    fn execute_blocking_core<T, R, F, U, G>(
+        &self,
+        computation: Attempts<F>,
+        state_updater: U,
+        state_getter: Option<G>,
+        cancellation_token: Option<CancellationToken>,
+        options: ExecuteOptions,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(Option<CancellationToken>) -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender_with(options);
+        let turn = self.execution_turn();
+        self.spawn_execution_within(options.resolve_deadline(), async move {
+            match (cancellation_token, state_getter) {
+                (Some(token), Some(getter)) => {
+                    // If we have a getter and a cancellation token, we can update the state to loading with the retained value
+                    let getter_loading = getter.clone();
+                    let cleared = Self::update_async_to_loading_with_retain(&set_state_tx, state_updater.clone(), getter_loading)?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    // A serialized execution cancelled while queued ends without starting, like any cancellation
+                    if !Self::await_turn(turn.as_ref(), Some(&token)).await {
+                        return Self::update_async_cancelable_with_retain(
+                            &set_state_tx,
+                            state_updater,
+                            getter,
+                            cleared,
+                            Async::fail_with_cancelled(None),
+                            true,
+                        );
+                    }
+                    // Run the computation in a blocking context with cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, Some(&token), computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_computation_cancelable(&set_state_tx, computation, token.clone()))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    Self::write_async_with_retain(
+                        &set_state_tx,
+                        state_updater,
+                        getter,
+                        cleared,
+                        async_result,
+                        token.is_cancelled(),
+                        action,
+                    )
+                }
+                (Some(token), None) => {
+                    // If we have a cancellation token but no getter, we can update the state to loading with None
+                    Self::update_async_state(
+                        &set_state_tx,
+                        state_updater.clone(),
+                        Async::loading(None),
+                    )?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    // A serialized execution cancelled while queued ends without starting, like any cancellation
+                    if !Self::await_turn(turn.as_ref(), Some(&token)).await {
+                        return Self::update_async_state(&set_state_tx, state_updater, Async::fail_with_cancelled(None));
+                    }
+                    // Run the computation in a blocking context with cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, Some(&token), computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_computation_cancelable(&set_state_tx, computation, token.clone()))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    let final_result = if token.is_cancelled() {
+                        Async::fail_with_cancelled(None)
+                    } else {
+                        async_result
+                    };
+                    Self::write_async_state(&set_state_tx, state_updater, final_result, action)
+                }
+                (None, Some(getter)) => {
+                    // If we have a getter but no cancellation token, we can update the state to loading with the retained value
+                    let getter_loading = getter.clone();
+                    let cleared = Self::update_async_to_loading_with_retain(
+                        &set_state_tx,
+                        state_updater.clone(),
+                        getter_loading,
+                    )?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    Self::await_turn(turn.as_ref(), None).await;
+                    // Run the computation in a blocking context without cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, None, computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_computation(&set_state_tx, computation))
+                    })
+                    .await;
+                    Self::write_async_with_retain(
+                        &set_state_tx,
+                        state_updater,
+                        getter,
+                        cleared,
+                        async_result,
+                        false,
+                        action,
+                    )
+                }
+
+                (None, None) => {
+                    // If we have neither a getter nor a cancellation token, we can update the state to loading with None
+                    Self::update_async_state(
+                        &set_state_tx,
+                        state_updater.clone(),
+                        Async::loading(None),
+                    )?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    Self::await_turn(turn.as_ref(), None).await;
+                    // Run the computation in a blocking context without cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, None, computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_computation(&set_state_tx, computation))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    Self::write_async_state(&set_state_tx, state_updater, async_result, action)
+                }
+            }
+        })
+    }
+
+    /// Executes a synchronous computation and updates the state with its result.
+    ///
+    /// This method runs the computation in a blocking task to avoid blocking the async runtime,
+    /// and updates the state with the result using the provided state updater function.
+    /// The state is first set to `Async::Loading(None)` before executing the computation.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState{
+    ///     fn set_num(self, num: Async<i32>) -> Self {
+    ///       Self { num, ..self }
+    ///     }
+    /// }
+    /// fn computation() -> Option<i32> {
+    ///     Some(888)
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.execute(
+    ///         || computation(),
+    ///         |state, result| {
+    ///             state.set_num(result)
+    ///         }
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute<T, R, F, U>(
+        &self,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Send + Clone + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |_| computation()),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a synchronous computation that the recovery policy may run again, and updates the
+    /// state with its result.
+    ///
+    /// Like [`execute`](Self::execute), except that the computation is called for every attempt:
+    /// when the [`with_error_recovery`](Self::with_error_recovery) policy answers a failure with
+    /// [`RecoveryAction::Retry`] or [`RecoveryAction::RetryAfter`], the state stays `Loading` and
+    /// the computation runs again, right away or after the delay, until the policy settles on a result.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use easerx::{Async, RecoveryAction, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<usize>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { num: Async::default() })
+    ///         .with_error_recovery(|_| RecoveryAction::Retry);
+    ///     let attempts = Arc::new(AtomicUsize::new(0));
+    ///     store
+    ///         .execute_recoverable(
+    ///             move || match attempts.fetch_add(1, Ordering::SeqCst) {
+    ///                 0 => Err("flaky"),
+    ///                 attempt => Ok(attempt),
+    ///             },
+    ///             |_, num| TestState { num },
+    ///         )
+    ///         .await??;
+    ///     assert_eq!(store.await_state().await?.num, Async::success(1));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_recoverable<T, R, F, U>(
+        &self,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Send + Clone + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Fn() -> R + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::repeated(move || {
+                let computation = computation.clone();
+                move |_: Option<CancellationToken>| computation()
+            }),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a synchronous computation only if `input_key` differs from the key of the previous call.
+    ///
+    /// The store remembers the last key passed for each key type `K`. If `input_key` equals it,
+    /// nothing is executed and `None` is returned; otherwise the key is remembered and the computation
+    /// runs like [`execute`](Self::execute). This avoids re-fetching when, for example, a search
+    /// query is submitted twice. Use distinct key types to track unrelated inputs independently.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    results: Async<Vec<String>>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{results: Async::default()});
+    ///     let search = |query: String| {
+    ///         store.execute_if_changed(
+    ///             query.clone(),
+    ///             move || vec![format!("result for {query}")],
+    ///             |state, results| TestState { results, ..state },
+    ///         )
+    ///     };
+    ///     let first = search("rust".to_string());
+    ///     let second = search("rust".to_string());
+    ///     assert!(first.is_some());
+    ///     assert!(second.is_none());
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_if_changed<K, T, R, F, U>(
+        &self,
+        input_key: K,
+        computation: F,
+        state_updater: U,
+    ) -> Option<ExecutionTicket>
+    where
+        K: Eq + Send + 'static,
+        T: Send + Clone + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        {
+            let mut last_keys = self.shared.last_keys.lock().unwrap();
+            let unchanged = last_keys
+                .get(&TypeId::of::<K>())
+                .and_then(|last| last.downcast_ref::<K>())
+                .is_some_and(|last| *last == input_key);
+            if unchanged {
+                return None;
+            }
+            last_keys.insert(TypeId::of::<K>(), Box::new(input_key));
+        }
+        Some(self.execute(computation, state_updater))
+    }
+
+    /// Starts building a batch of computations with different result types that run in parallel.
+    ///
+    /// This is a shortcut for [`ParallelBatch::new`](crate::ParallelBatch::new); see its documentation for details.
+    #[track_caller]
+    pub fn execute_batch_parallel(&self) -> crate::ParallelBatch<S> {
+        crate::ParallelBatch::new(self)
+    }
+
+    /// Runs several synchronous computations concurrently and keeps the first one that succeeds.
+    ///
+    /// The state is set to `Async::Loading(None)`, then every computation runs in its own blocking task.
+    /// As soon as one of them succeeds, the others are aborted and its result is written into the state.
+    /// Failures of the other computations are ignored until all of them have failed, in which case the
+    /// last failure to complete is written. An empty list fails right away.
+    ///
+    /// Aborting cannot interrupt a blocking computation that already started; its result is discarded
+    /// when it completes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    mirror: Async<String>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{mirror: Async::default()});
+    ///     store.execute_with_abort_on_success(
+    ///         vec![
+    ///             Box::new(|| Err("mirror a is down".to_string())),
+    ///             Box::new(|| Ok("mirror b".to_string())),
+    ///         ],
+    ///         |state, mirror| TestState { mirror, ..state },
+    ///     ).await??;
+    ///     assert_eq!(store.await_state().await?.mirror, Async::success("mirror b".to_string()));
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_abort_on_success<T, R, U>(
+        &self,
+        computations: Vec<Box<dyn FnOnce() -> R + Send>>,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        self.spawn_execution(async move {
+            Self::update_async_state(&set_state_tx, state_updater.clone(), Async::loading(None))?;
+            // Yield to allow the state to be updated before running the computations
+            tokio::task::yield_now().await;
+            let panic_policy = set_state_tx.panic_policy;
+            let blocking = set_state_tx.blocking.clone();
+            let first_success = async move {
+                let mut tasks = tokio::task::JoinSet::new();
+                for computation in computations {
+                    blocking.spawn_on(&mut tasks, move || computation().into_async());
+                }
+                let mut last_failure = Async::fail_with_message("no computation to run", None);
+                while let Some(joined) = tasks.join_next().await {
+                    let async_result = match joined {
+                        Ok(async_result) => async_result,
+                        Err(e) => Async::fail(panic_policy.error_from_join(e), None),
+                    };
+                    if async_result.is_success() {
+                        tasks.abort_all();
+                        return async_result;
+                    }
+                    last_failure = async_result;
+                }
+                last_failure
+            };
+            let async_result = Self::within_timeout(set_state_tx.timeout, first_success).await;
+            Self::update_async_state(&set_state_tx, state_updater, async_result)
+        })
+    }
+
+    /// Executes a synchronous computation and updates the state with its result, retaining previous values.
+    ///
+    /// Similar to `execute`, but this method retains the previous value when transitioning to the loading state.
+    /// This is useful for UI scenarios where you want to show previous data while loading new data.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState{
+    ///     fn set_num(self, num: Async<i32>) -> Self {
+    ///       Self { num, ..self }
+    ///     }
+    /// }
+    /// fn computation() -> Option<i32> {
+    ///     Some(888)
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.execute_with_retain(
+    ///         || computation(),
+    ///         |state| &state.num,
+    ///         |state, result| {
+    ///             state.set_num(result)
+    ///         }
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_retain<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |_| computation()),
+            state_updater,
+            Some(Self::retain_getter(state_getter)),
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a synchronous computation and updates the state with its result, retaining the
+    /// previous value from a location that may not exist.
+    ///
+    /// Works like [`execute_with_retain`](Self::execute_with_retain), but `state_getter` returns an
+    /// `Option`, e.g. for entries of a map. `None` means there is nothing to retain, so the state
+    /// goes to `Loading` without a value.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    pages: HashMap<u32, Async<String>>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState {
+    ///     fn set_page(mut self, id: u32, page: Async<String>) -> Self {
+    ///         self.pages.insert(id, page);
+    ///         self
+    ///     }
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { pages: HashMap::new() });
+    ///     store.execute_with_retain_opt(
+    ///         || "page 1".to_string(),
+    ///         |state| state.pages.get(&1),
+    ///         |state, page| state.set_page(1, page),
+    ///     ).await??;
+    ///     assert!(store.await_state().await?.pages[&1].is_success());
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_retain_opt<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |_| computation()),
+            state_updater,
+            Some(state_getter),
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a cancellable synchronous computation and updates the state with its result.
+    ///
+    /// This method allows the computation to be cancelled using the provided cancellation token.
+    /// If cancelled, the state will be updated with `Async::Fail` with a cancellation error.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use tokio_util::sync::CancellationToken;
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState{
+    ///     fn set_num(self, num: Async<i32>) -> Self {
+    ///       Self { num, ..self }
+    ///     }
+    /// }
+    /// fn computation(token:CancellationToken) -> Option<i32> {
+    ///     for i in 0..1000 {
+    ///         if token.is_cancelled() {
+    ///             return None;
+    ///         }
+    ///     }
+    ///    Some(888)
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     let token = CancellationToken::new();
+    ///     let handle = store.execute_cancellable(
+    ///         token.clone(),
+    ///         |token| {
+    ///             // Check token.is_cancelled() periodically if the operation is long-running
+    ///             computation(token)
+    ///         },
+    ///         |state, result| {
+    ///             state.set_num(result)
+    ///         }
+    ///     );
+    ///
+    ///     // To cancel the operation:
+    ///     token.cancel();
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_cancellable<T, R, F, U>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |token: Option<CancellationToken>| computation(token.unwrap())),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a cancellable synchronous computation and updates the state with its result, retaining previous values.
+    ///
+    /// Combines the functionality of `execute_with_retain` and `execute_cancellable` to provide
+    /// a cancellable operation that retains previous values during loading state.
+    #[track_caller]
+    pub fn execute_cancellable_with_retain<T, R, F, U, G>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |token: Option<CancellationToken>| computation(token.unwrap())),
+            state_updater,
+            Some(Self::retain_getter(state_getter)),
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Repeats a cancellable synchronous computation on a fixed interval until the token is cancelled.
+    ///
+    /// Every iteration sets the state to `Async::Loading(None)`, runs the computation in a blocking task,
+    /// writes its `Success` or `Fail` result, then waits for `interval` before starting over.
+    /// Cancelling the token while the computation runs writes `Fail` with [`AsyncError::Cancelled`] and
+    /// ends the loop; cancelling it during the wait ends the loop and keeps the last result.
+    /// A panicking computation writes `Fail` and ends the loop as well.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, State, StateStore};
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    ticks: Async<u32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { ticks: Async::Uninitialized });
+    ///     let token = CancellationToken::new();
+    ///     let mut ticks = 0;
+    ///     let ticket = store.execute_cancellable_loop(
+    ///         token.clone(),
+    ///         Duration::from_millis(10),
+    ///         move |_token| {
+    ///             ticks += 1;
+    ///             ticks
+    ///         },
+    ///         |state, ticks| TestState { ticks, ..state },
+    ///     );
+    ///     tokio::time::sleep(Duration::from_millis(50)).await;
+    ///     token.cancel();
+    ///     ticket.await??;
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_cancellable_loop<T, R, F, U>(
+        &self,
+        cancellation_token: CancellationToken,
+        interval: std::time::Duration,
+        mut computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnMut(CancellationToken) -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        let token = cancellation_token;
+        self.spawn_execution(async move {
+            while !token.is_cancelled() {
+                Self::update_async_state(&set_state_tx, state_updater.clone(), Async::loading(None))?;
+                // Yield to allow the state to be updated before running the computation
+                tokio::task::yield_now().await;
+                let (async_result, action) = loop {
+                    // The computation is moved into the blocking task and handed back with its result
+                    let iteration = set_state_tx.blocking.spawn({
+                        let token = token.clone();
+                        move || {
+                            let result = computation(token);
+                            (computation, result)
+                        }
+                    });
+                    let async_result = tokio::select! {
+                        biased;
+                        _ = token.cancelled() => {
+                            Self::update_async_state(&set_state_tx, state_updater, Async::fail_with_cancelled(None))?;
+                            return Ok(());
+                        }
+                        joined = iteration => match joined {
+                            Ok((returned, result)) => {
+                                computation = returned;
+                                result.into_async()
+                            }
+                            Err(e) => {
+                                let failure = Async::fail(set_state_tx.panic_policy.error_from_join(e), None);
+                                return Self::update_async_state(&set_state_tx, state_updater, failure);
+                            }
+                        },
+                    };
+                    let async_result = if token.is_cancelled() {
+                        Async::fail_with_cancelled(None)
+                    } else {
+                        async_result
+                    };
+                    let action = set_state_tx.recovery.action(&async_result);
+                    match Self::wait_for_retry(action, &token).await {
+                        Some(RecoveryAction::Retry) => continue,
+                        Some(action) => break (async_result, action),
+                        None => {
+                            Self::update_async_state(&set_state_tx, state_updater, Async::fail_with_cancelled(None))?;
+                            return Ok(());
+                        }
+                    }
+                };
+                Self::write_async_state(&set_state_tx, state_updater.clone(), async_result, action)?;
+                tokio::select! {
+                    _ = token.cancelled() => {}
+                    _ = tokio::time::sleep(interval) => {}
+                }
+            }
+            Ok(())
+        })
+    }
+
+    /// Repeatedly executes an asynchronous computation until its result satisfies `done`.
+    ///
+    /// Each iteration calls `computation_factory` for a new future, sets the state to `Loading`
+    /// retaining the previous value, and writes the result into the state like
+    /// [`async_execute_with_retain`](Self::async_execute_with_retain). Polling stops once `done`
+    /// returns true for a `Success` value, or when an iteration fails and `on_failure` is
+    /// [`PollFailure::Stop`]. Otherwise the next iteration starts after `interval`.
+    ///
+    /// Call [`PollingHandle::cancel`] to stop polling early: a running computation is cancelled and
+    /// the state is set to `Fail` with `AsyncError::Cancelled`, while cancelling during the interval
+    /// leaves the last result in place.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, PollFailure, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    status: Async<String>,
+    /// }
+    /// impl State for TestState {}
+    /// async fn fetch_status() -> String {
+    ///     "done".to_string()
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{status: Async::default()});
+    ///     let handle = store.async_execute_until(
+    ///         fetch_status,
+    ///         |status| status == "done",
+    ///         Duration::from_secs(1),
+    ///         PollFailure::KeepPolling,
+    ///         |state| &state.status,
+    ///         |state, status| TestState { status, ..state },
+    ///     );
+    ///     handle.await??;
+    ///     assert_eq!(store.await_state().await?.status, Async::success("done".to_string()));
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn async_execute_until<T, R, F, Fut, D, G, U>(
+        &self,
+        mut computation_factory: F,
+        done: D,
+        interval: std::time::Duration,
+        on_failure: PollFailure,
+        state_getter: G,
+        state_updater: U,
+    ) -> PollingHandle
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnMut() -> Fut + Send + 'static,
+        D: Fn(&T) -> bool + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        let state_getter = Self::retain_getter(state_getter);
+        let token = CancellationToken::new();
+        let ticket = self.spawn_execution({
+            let token = token.clone();
+            async move {
+                loop {
+                    let cleared = Self::update_async_to_loading_with_retain(&set_state_tx, state_updater.clone(), state_getter.clone())?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    let (async_result, action) = loop {
+                        let async_result = Self::run_async_computation_cancelable(
+                            computation_factory(),
+                            token.clone(),
+                            set_state_tx.panic_policy,
+                        )
+                        .await;
+                        let action = set_state_tx.recovery.action(&async_result);
+                        match Self::wait_for_retry(action, &token).await {
+                            Some(RecoveryAction::Retry) => continue,
+                            Some(action) => break (async_result, action),
+                            None => break (async_result, RecoveryAction::Propagate),
+                        }
+                    };
+                    let finished = token.is_cancelled()
+                        || match &async_result {
+                            Async::Success { value } => done(value),
+                            Async::Fail { .. } => {
+                                action != RecoveryAction::Ignore && on_failure == PollFailure::Stop
+                            }
+                            _ => false,
+                        };
+                    Self::write_async_with_retain(
+                        &set_state_tx,
+                        state_updater.clone(),
+                        state_getter.clone(),
+                        cleared,
+                        async_result,
+                        token.is_cancelled(),
+                        action,
+                    )?;
+                    if finished {
+                        return Ok(());
+                    }
+                    tokio::select! {
+                        _ = token.cancelled() => return Ok(()),
+                        _ = tokio::time::sleep(interval) => {}
+                    }
+                }
+            }
+        });
+        PollingHandle::new(ticket, token)
+    }
+
+    /// Runs the attempts of an execution until the recovery policy settles on its result, which is
+    /// returned with the action to write it with. `Retry` and `RetryAfter` run the computation
+    /// again if it can be; otherwise the failure is propagated and reported as
+    /// [`StoreError::RetryUnsupported`]. Cancelled results are left to the caller.
+    async fn settle<T, F, Fut>(
+        set_state_tx: &ExecutionSender<S>,
+        token: Option<&CancellationToken>,
+        computation: Attempts<F>,
+        mut run: impl FnMut(F) -> Fut,
+    ) -> (Async<T>, RecoveryAction)
+    where
+        T: Clone,
+        Fut: Future<Output = Async<T>>,
+    {
+        let uncancelled = CancellationToken::new();
+        let token = token.unwrap_or(&uncancelled);
+        let Attempts { first: mut attempt, mut again } = computation;
+        loop {
+            let async_result = run(attempt).await;
+            if token.is_cancelled() {
+                return (async_result, RecoveryAction::Propagate);
+            }
+            let action = set_state_tx.recovery.action(&async_result);
+            if !matches!(action, RecoveryAction::Retry | RecoveryAction::RetryAfter(_)) {
+                return (async_result, action);
+            }
+            let Some(make) = again.as_mut() else {
+                if let Async::Fail { error, .. } = &async_result {
+                    set_state_tx.errors.publish(|| StoreError::RetryUnsupported { error: error.clone() });
+                }
+                return (async_result, RecoveryAction::Propagate);
+            };
+            if Self::wait_for_retry(action, token).await.is_none() {
+                return (Async::fail_with_cancelled(None), RecoveryAction::Propagate);
+            }
+            attempt = make();
+        }
+    }
+
+    /// Waits out the delay of a `RetryAfter` recovery action, which then resolves to `Retry`.
+    /// Returns `None` if the token was cancelled while waiting.
+    async fn wait_for_retry(action: RecoveryAction, token: &CancellationToken) -> Option<RecoveryAction> {
+        match action {
+            RecoveryAction::RetryAfter(delay) => tokio::select! {
+                _ = token.cancelled() => None,
+                _ = tokio::time::sleep(delay) => Some(RecoveryAction::Retry),
+            },
+            action => Some(action),
+        }
+    }
+
+    async fn run_async_computation_cancelable<T, R, F>(
+        computation: F,
+        token: CancellationToken,
+        panic_policy: PanicPolicy,
+    ) -> Async<T>
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+    {
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Async::fail_with_cancelled(None),
+            result = Self::run_async_computation(computation, panic_policy) => result,
+        }
+    }
+
+    async fn run_async_computation<T, R, F>(computation: F, panic_policy: PanicPolicy) -> Async<T>
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+    {
+        match CatchUnwind::new(computation).await {
+            Ok(result) => result.into_async(),
+            Err(panic) => Async::fail(panic_policy.error_from_panic(panic), None),
+        }
+    }
+
+    #[track_caller]
+    fn execute_async_core<T, R, F, U, G>(
+        &self,
+        computation: Attempts<F>,
+        state_updater: U,
+        state_getter: Option<G>,
+        cancellation_token: Option<CancellationToken>,
+        options: ExecuteOptions,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender_with(options);
+        let turn = self.execution_turn();
+        self.spawn_execution_within(options.resolve_deadline(), async move {
+            match (cancellation_token, state_getter) {
+                (Some(token), Some(getter)) => {
+                    // If we have a getter and a cancellation token, we can update the state to loading with the retained value
+                    let getter_loading = getter.clone();
+                    let cleared = Self::update_async_to_loading_with_retain(&set_state_tx, state_updater.clone(), getter_loading)?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    // A serialized execution cancelled while queued ends without starting, like any cancellation
+                    if !Self::await_turn(turn.as_ref(), Some(&token)).await {
+                        return Self::update_async_cancelable_with_retain(
+                            &set_state_tx,
+                            state_updater,
+                            getter,
+                            cleared,
+                            Async::fail_with_cancelled(None),
+                            true,
+                        );
+                    }
+                    // Run the computation in a blocking context with cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, Some(&token), computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_async_computation_cancelable(computation, token.clone(), set_state_tx.panic_policy))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    Self::write_async_with_retain(
+                        &set_state_tx,
+                        state_updater,
+                        getter,
+                        cleared,
+                        async_result,
+                        token.is_cancelled(),
+                        action,
+                    )
+                }
+                (Some(token), None) => {
+                    // If we have a cancellation token but no getter, we can update the state to loading with None
+                    Self::update_async_state(
+                        &set_state_tx,
+                        state_updater.clone(),
+                        Async::loading(None),
+                    )?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    // A serialized execution cancelled while queued ends without starting, like any cancellation
+                    if !Self::await_turn(turn.as_ref(), Some(&token)).await {
+                        return Self::update_async_state(&set_state_tx, state_updater, Async::fail_with_cancelled(None));
+                    }
+                    // Run the computation in a blocking context with cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, Some(&token), computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_async_computation_cancelable(computation, token.clone(), set_state_tx.panic_policy))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    let final_result = if token.is_cancelled() {
+                        Async::fail_with_cancelled(None)
+                    } else {
+                        async_result
+                    };
+                    Self::write_async_state(&set_state_tx, state_updater, final_result, action)
+                }
+                (None, Some(getter)) => {
+                    // If we have a getter but no cancellation token, we can update the state to loading with the retained value
+                    let getter_loading = getter.clone();
+                    let cleared = Self::update_async_to_loading_with_retain(&set_state_tx, state_updater.clone(), getter_loading)?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    Self::await_turn(turn.as_ref(), None).await;
+                    // Run the computation in a blocking context without cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, None, computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_async_computation(computation, set_state_tx.panic_policy))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    Self::write_async_with_retain(
+                        &set_state_tx,
+                        state_updater,
+                        getter,
+                        cleared,
+                        async_result,
+                        false,
+                        action,
+                    )
+                }
+                (None, None) => {
+                    // If we have neither a getter nor a cancellation token, we can update the state to loading with None
+                    Self::update_async_state(
+                        &set_state_tx,
+                        state_updater.clone(),
+                        Async::loading(None),
+                    )?;
+                    // Yield to allow the state to be updated before running the computation
+                    tokio::task::yield_now().await;
+                    Self::await_turn(turn.as_ref(), None).await;
+                    // Run the computation in a blocking context without cancellation support
+                    let (async_result, action) = Self::settle(&set_state_tx, None, computation, |computation| {
+                        Self::within_timeout(set_state_tx.timeout, Self::run_async_computation(computation, set_state_tx.panic_policy))
+                    })
+                    .await;
+                    // Send the result back to the state store
+                    Self::write_async_state(&set_state_tx, state_updater, async_result, action)
+                }
+            }
+        })
+    }
+
+    /// Executes an asynchronous computation and updates the state with its result.
+    ///
+    /// This method runs the provided future and updates the state with the result
+    /// using the provided state updater function. The state is first set to `Async::Loading(None)`
+    /// before executing the computation.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState{
+    ///     fn set_num(self, num: Async<i32>) -> Self {
+    ///       Self { num, ..self }
+    ///     }
+    /// }
+    /// async fn computation() -> Option<i32> {
+    ///     Some(888)
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.async_execute(
+    ///         async {
+    ///             // Fetch data from a database or API
+    ///             computation().await
+    ///         },
+    ///         |state, result| {
+    ///             state.set_num(result)
+    ///         }
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn async_execute<T, R, F, U>(
+        &self,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes an asynchronous computation that the recovery policy may run again, and updates the
+    /// state with its result.
+    ///
+    /// Like [`async_execute`](Self::async_execute), except that `computation_factory` is called for
+    /// a new future on every attempt, so that [`RecoveryAction::Retry`] and
+    /// [`RecoveryAction::RetryAfter`] can run it again, see
+    /// [`execute_recoverable`](Self::execute_recoverable).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, RecoveryAction, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    status: Async<String>,
+    /// }
+    /// impl State for TestState {}
+    /// async fn fetch_status() -> Result<String, std::io::Error> {
+    ///     Ok("done".to_string())
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { status: Async::default() })
+    ///         .with_error_recovery(|_| RecoveryAction::RetryAfter(Duration::from_millis(100)));
+    ///     store
+    ///         .async_execute_recoverable(fetch_status, |_, status| TestState { status })
+    ///         .await??;
+    ///     assert_eq!(store.await_state().await?.status, Async::success("done".to_string()));
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn async_execute_recoverable<T, R, F, Fut, U>(
+        &self,
+        computation_factory: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnMut() -> Fut + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::repeated(computation_factory),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes an asynchronous computation and updates the state with its result, retaining previous values.
+    ///
+    /// Similar to `async_execute`, but this method retains the previous value when transitioning
+    /// to the loading state. This is useful for UI scenarios where you want to show previous data
+    /// while loading new data.
+    #[track_caller]
+    pub fn async_execute_with_retain<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(Attempts::once(computation), state_updater, Some(Self::retain_getter(state_getter)), None, ExecuteOptions::default())
+    }
+
+    /// Executes an asynchronous computation and updates the state with its result, retaining the
+    /// previous value from a location that may not exist.
+    ///
+    /// This is the asynchronous counterpart of [`execute_with_retain_opt`](Self::execute_with_retain_opt).
+    #[track_caller]
+    pub fn async_execute_with_retain_opt<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(Attempts::once(computation), state_updater, Some(state_getter), None, ExecuteOptions::default())
+    }
+
+    /// Executes a cancellable asynchronous computation and updates the state with its result.
+    ///
+    /// This method allows the async computation to be cancelled using the provided cancellation token.
+    /// If cancelled, the state will be updated with `Async::Fail` with a cancellation error.
+    #[track_caller]
+    pub fn async_execute_cancellable<T, R, F, U, Fut>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation(cancellation_token.clone())),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a cancellable asynchronous computation and updates the state with its result, retaining previous values.
+    ///
+    /// Combines the functionality of `async_execute_with_retain` and `async_execute_cancellable` to provide
+    /// a cancellable operation that retains previous values during loading state.
+    #[track_caller]
+    pub fn async_execute_cancellable_with_retain<T, R, F, U, Fut, G>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation(cancellation_token.clone())),
+            state_updater,
+            Some(Self::retain_getter(state_getter)),
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a cancellable asynchronous computation that needs async cleanup when it is cancelled.
+    ///
+    /// Works like [`async_execute_cancellable`](Self::async_execute_cancellable), but once the token is
+    /// cancelled and the computation dropped, the future returned by `cleanup` is awaited before
+    /// `Async::Fail` with a cancellation error is written, e.g. to abort a multipart upload the
+    /// computation started. The cleanup gets at most [`DEFAULT_CANCEL_GRACE`](crate::DEFAULT_CANCEL_GRACE)
+    /// to finish; a slower or panicking cleanup is abandoned and the cancellation written anyway.
+    /// `cleanup` is not called if the computation completes without being cancelled.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, State, StateStore};
+    /// use tokio_util::sync::CancellationToken;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Upload {
+    ///    etag: Async<String>,
+    /// }
+    /// impl State for Upload {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(Upload { etag: Async::default() });
+    ///     let token = CancellationToken::new();
+    ///     let ticket = store.async_execute_cancellable_graceful(
+    ///         token.clone(),
+    ///         |_| async {
+    ///             tokio::time::sleep(Duration::from_secs(60)).await;
+    ///             "etag".to_string()
+    ///         },
+    ///         || async { /* abort the multipart upload */ },
+    ///         |_, etag| Upload { etag },
+    ///     );
+    ///     token.cancel();
+    ///     ticket.await??;
+    ///     assert!(store.await_state().await?.etag.is_fail());
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn async_execute_cancellable_graceful<T, R, F, Fut, C, CFut, U>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        cleanup: C,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        C: FnOnce() -> CFut + Send + 'static,
+        CFut: Future<Output = ()> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        let token = cancellation_token;
+        let computation = computation(token.clone());
+        self.spawn_execution(async move {
+            Self::update_async_state(&set_state_tx, state_updater.clone(), Async::loading(None))?;
+            // Yield to allow the state to be updated before running the computation
+            tokio::task::yield_now().await;
+            let async_result = Self::within_timeout(
+                set_state_tx.timeout,
+                Self::run_async_computation_cancelable(computation, token.clone(), set_state_tx.panic_policy),
+            )
+            .await;
+            if !token.is_cancelled() {
+                return Self::update_async_state(&set_state_tx, state_updater, async_result);
+            }
+            Self::run_cleanup(cleanup).await;
+            Self::update_async_state(&set_state_tx, state_updater, Async::fail_with_cancelled(None))
+        })
+    }
+
+    /// Awaits the cleanup of a cancelled computation for at most [`DEFAULT_CANCEL_GRACE`](crate::DEFAULT_CANCEL_GRACE).
+    async fn run_cleanup<C, CFut>(cleanup: C)
+    where
+        C: FnOnce() -> CFut + Send + 'static,
+        CFut: Future<Output = ()> + Send + 'static,
+    {
+        let cleanup = CatchUnwind::new(async move { cleanup().await });
+        match tokio::time::timeout(crate::DEFAULT_CANCEL_GRACE, cleanup).await {
+            Ok(Ok(())) => {}
+            Ok(Err(_payload)) => {
+                #[cfg(feature = "tracing")]
+                tracing::error!(message = %crate::panic_policy::panic_message(_payload.as_ref()), "cancellation cleanup panicked");
+            }
+            Err(_) => {
+                #[cfg(feature = "tracing")]
+                tracing::warn!(grace = ?crate::DEFAULT_CANCEL_GRACE, "cancellation cleanup did not finish within its grace period");
+            }
+        }
+    }
+
+    /// Executes a synchronous computation whose result is not `Clone`, storing it behind an `Arc`.
+    ///
+    /// Works like [`execute`](Self::execute), but the computation's value is moved into an `Arc`, so
+    /// the field is an [`ArcAsync<T>`] and `T` only needs to be `Send + Sync`. This suits results
+    /// that own resources, such as file handles or connection pools. The computation may return `T`,
+    /// `Result<T, E>` or `Option<T>`, see [`ArcExecutionResult`].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{ArcAsync, State, StateStore};
+    ///
+    /// // Owns a resource, so it cannot be cloned
+    /// #[derive(Debug)]
+    /// struct Connection {
+    ///     socket: std::net::UdpSocket,
+    /// }
+    ///
+    /// #[derive(Clone, Debug)]
+    /// struct TestState {
+    ///    connection: ArcAsync<Connection>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{connection: ArcAsync::Uninitialized});
+    ///     store.execute_arc(
+    ///         || std::net::UdpSocket::bind("127.0.0.1:0").map(|socket| Connection { socket }),
+    ///         |state, connection| TestState { connection, ..state },
+    ///     ).await??;
+    ///     let state = store.await_state().await?;
+    ///     assert!(state.connection.with(|connection| connection.socket.local_addr().is_ok()).unwrap());
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_arc<T, R, F, U>(&self, computation: F, state_updater: U) -> ExecutionTicket
+    where
+        T: Send + Sync + 'static,
+        R: ArcExecutionResult<T>,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, ArcAsync<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute(move || ArcResult(computation().into_arc_async()), state_updater)
+    }
+
+    /// Executes a synchronous computation whose result is not `Clone`, retaining the previous value
+    /// while loading.
+    ///
+    /// Combines [`execute_arc`](Self::execute_arc) and [`execute_with_retain`](Self::execute_with_retain).
+    /// Retaining only clones the `Arc`, so the retained value is the previous allocation itself.
+    #[track_caller]
+    pub fn execute_arc_with_retain<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Send + Sync + 'static,
+        R: ArcExecutionResult<T>,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> &ArcAsync<T> + Clone + Send + 'static,
+        U: FnOnce(S, ArcAsync<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_with_retain(move || ArcResult(computation().into_arc_async()), state_getter, state_updater)
+    }
+
+    /// Executes an asynchronous computation whose result is not `Clone`, storing it behind an `Arc`.
+    ///
+    /// This is the asynchronous counterpart of [`execute_arc`](Self::execute_arc).
+    #[track_caller]
+    pub fn async_execute_arc<T, R, F, U>(&self, computation: F, state_updater: U) -> ExecutionTicket
+    where
+        T: Send + Sync + 'static,
+        R: ArcExecutionResult<T>,
+        F: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, ArcAsync<T>) -> S + Clone + Send + 'static,
+    {
+        self.async_execute(async move { ArcResult(computation.await.into_arc_async()) }, state_updater)
+    }
+
+    /// Executes an asynchronous computation whose result is not `Clone`, retaining the previous value
+    /// while loading.
+    ///
+    /// This is the asynchronous counterpart of [`execute_arc_with_retain`](Self::execute_arc_with_retain).
+    #[track_caller]
+    pub fn async_execute_arc_with_retain<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Send + Sync + 'static,
+        R: ArcExecutionResult<T>,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> &ArcAsync<T> + Clone + Send + 'static,
+        U: FnOnce(S, ArcAsync<T>) -> S + Clone + Send + 'static,
+    {
+        self.async_execute_with_retain(
+            async move { ArcResult(computation.await.into_arc_async()) },
+            state_getter,
+            state_updater,
+        )
+    }
+
+    /// Wraps an updater so Loading carries `placeholder`, while failures retain the value the field
+    /// held when loading started instead of the placeholder.
+    fn placeholder_updater<T, G, U>(
+        placeholder: Option<T>,
+        state_getter: G,
+        state_updater: U,
+    ) -> impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static
+    where
+        T: Clone + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let original = Arc::new(Mutex::new(None::<T>));
+        move |state, async_value| {
+            let mut original = original.lock().unwrap_or_else(|e| e.into_inner());
+            let async_value = match async_value {
+                Async::Loading { .. } => {
+                    *original = state_getter(&state).value_ref_clone();
+                    Async::loading(placeholder)
+                }
+                Async::Fail { error, .. } => Async::fail(error, original.take()),
+                // Only an ignored failure writes Uninitialized, see `with_error_recovery`
+                Async::Uninitialized => restored(original.take()),
+                success => success,
+            };
+            drop(original);
+            state_updater(state, async_value)
+        }
+    }
+
+    /// Executes a synchronous computation, showing `placeholder` while it runs.
+    ///
+    /// Unlike [`execute_with_retain`](Self::execute_with_retain), which keeps showing the previous
+    /// value while loading, the state is first set to `Async::Loading(placeholder)`, e.g. an optimistic
+    /// value taken from user input. Retention then works as follows:
+    ///
+    /// - On success, the result replaces the placeholder.
+    /// - On failure, cancellation or timeout, `Async::Fail` retains the value the field returned by
+    ///   `state_getter` held when loading started, never the placeholder.
+    /// - A failure ignored by [`with_error_recovery`](Self::with_error_recovery) restores that value too.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    title: Async<String>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{title: Async::success("Draft".to_string())});
+    ///     store.execute_with_placeholder(
+    ///         Some("Final".to_string()),
+    ///         || Err::<String, _>("offline"),
+    ///         |state| &state.title,
+    ///         |state, title| TestState { title, ..state },
+    ///     ).await??;
+    ///     assert_eq!(
+    ///         store.await_state().await?.title,
+    ///         Async::fail_with_message("offline", Some("Draft".to_string()))
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_placeholder<T, R, F, G, U>(
+        &self,
+        placeholder: Option<T>,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |_| computation()),
+            Self::placeholder_updater(placeholder, state_getter, state_updater),
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes an asynchronous computation, showing `placeholder` while it runs.
+    ///
+    /// This is the asynchronous counterpart of [`execute_with_placeholder`](Self::execute_with_placeholder),
+    /// with the same retention rules.
+    #[track_caller]
+    pub fn async_execute_with_placeholder<T, R, F, G, U>(
+        &self,
+        placeholder: Option<T>,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation),
+            Self::placeholder_updater(placeholder, state_getter, state_updater),
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a cancellable asynchronous computation, showing `placeholder` while it runs.
+    ///
+    /// If cancelled, the state is set to `Async::Fail` with a cancellation error, retaining the value
+    /// held before loading as described in [`execute_with_placeholder`](Self::execute_with_placeholder).
+    #[track_caller]
+    pub fn async_execute_cancellable_with_placeholder<T, R, F, G, U, Fut>(
+        &self,
+        cancellation_token: CancellationToken,
+        placeholder: Option<T>,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation(cancellation_token.clone())),
+            Self::placeholder_updater(placeholder, state_getter, state_updater),
+            None::<fn(&S) -> Option<&Async<T>>>,
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Wraps a counted updater into a regular one, recording each transition on the current wrapper.
+    fn counted_updater<T, G, U>(
+        state_getter: G,
+        state_updater: U,
+    ) -> impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static
+    where
+        T: Clone + Send + 'static,
+        G: FnOnce(&S) -> &AsyncWithCount<T> + Clone + Send + 'static,
+        U: FnOnce(S, AsyncWithCount<T>) -> S + Clone + Send + 'static,
+    {
+        move |state, async_value| {
+            let counted = state_getter(&state).clone().record(async_value);
+            state_updater(state, counted)
+        }
+    }
+
+    /// Executes a synchronous computation and updates an [`AsyncWithCount<T>`] field with its result.
+    ///
+    /// Works like [`execute`](Self::execute), but the updater receives the field returned by
+    /// `state_getter` with the new value recorded, so its load count increments on every completion.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{AsyncWithCount, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: AsyncWithCount<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: AsyncWithCount::default()});
+    ///     store.execute_counted(
+    ///         || 888,
+    ///         |state| &state.num,
+    ///         |state, num| TestState { num, ..state }
+    ///     ).await??;
+    ///     assert!(store.await_state().await?.num.first_load());
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_counted<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> &AsyncWithCount<T> + Clone + Send + 'static,
+        U: FnOnce(S, AsyncWithCount<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute(computation, Self::counted_updater(state_getter, state_updater))
+    }
+
+    /// Executes a cancellable synchronous computation and updates an [`AsyncWithCount<T>`] field with its result.
+    ///
+    /// Combines `execute_cancellable` and `execute_counted`. A cancelled load also counts as completed.
+    #[track_caller]
+    pub fn execute_cancellable_counted<T, R, F, G, U>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        G: FnOnce(&S) -> &AsyncWithCount<T> + Clone + Send + 'static,
+        U: FnOnce(S, AsyncWithCount<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_cancellable(
+            cancellation_token,
+            computation,
+            Self::counted_updater(state_getter, state_updater),
+        )
+    }
+
+    /// Executes an asynchronous computation and updates an [`AsyncWithCount<T>`] field with its result.
+    ///
+    /// This is the asynchronous counterpart of [`execute_counted`](Self::execute_counted).
+    #[track_caller]
+    pub fn async_execute_counted<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> &AsyncWithCount<T> + Clone + Send + 'static,
+        U: FnOnce(S, AsyncWithCount<T>) -> S + Clone + Send + 'static,
+    {
+        self.async_execute(computation, Self::counted_updater(state_getter, state_updater))
+    }
+
+    /// Executes a cancellable asynchronous computation and updates an [`AsyncWithCount<T>`] field with its result.
+    ///
+    /// Combines `async_execute_cancellable` and `async_execute_counted`. A cancelled load also counts as completed.
+    #[track_caller]
+    pub fn async_execute_cancellable_counted<T, R, F, G, U, Fut>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        G: FnOnce(&S) -> &AsyncWithCount<T> + Clone + Send + 'static,
+        U: FnOnce(S, AsyncWithCount<T>) -> S + Clone + Send + 'static,
+    {
+        self.async_execute_cancellable(
+            cancellation_token,
+            computation,
+            Self::counted_updater(state_getter, state_updater),
+        )
+    }
+
+    /// Reads the entry of `key` from the map returned by `map_getter`.
+    fn keyed_getter<K, T, M>(key: K, map_getter: M) -> impl FnOnce(&S) -> Option<&Async<T>> + Clone + Send + 'static
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        T: Clone + 'static,
+        M: Fn(&S) -> &HashMap<K, Async<T>> + Clone + Send + 'static,
+    {
+        move |state| map_getter(state).get(&key)
+    }
+
+    /// Writes into the entry of `key` with `map_updater`.
+    fn keyed_updater<K, T, U>(key: K, map_updater: U) -> impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static
+    where
+        K: Clone + Send + 'static,
+        T: Clone,
+        U: Fn(S, K, Async<T>) -> S + Clone + Send + 'static,
+    {
+        move |state, value| map_updater(state, key, value)
+    }
+
+    /// Executes a synchronous computation and writes its result into the entry of `key` in a map of
+    /// `Async` values.
+    ///
+    /// `map_getter` returns the map, and `map_updater` writes a value into the entry of a key,
+    /// inserting it if it is missing. The entry goes to `Loading` with the value it retained, like
+    /// with [`execute_with_retain`](Self::execute_with_retain), or without one if the key is not in
+    /// the map yet. Executions for different keys run independently.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    items: HashMap<u32, Async<String>>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState {
+    ///     fn set_item(mut self, id: u32, item: Async<String>) -> Self {
+    ///         self.items.insert(id, item);
+    ///         self
+    ///     }
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { items: HashMap::new() });
+    ///     store.execute_keyed(
+    ///         7,
+    ///         || "item 7".to_string(),
+    ///         |state| &state.items,
+    ///         TestState::set_item,
+    ///     ).await??;
+    ///     assert_eq!(store.await_state().await?.items[&7], Async::success("item 7".to_string()));
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_keyed<K, T, R, F, M, U>(
+        &self,
+        key: K,
+        computation: F,
+        map_getter: M,
+        map_updater: U,
+    ) -> ExecutionTicket
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        M: Fn(&S) -> &HashMap<K, Async<T>> + Clone + Send + 'static,
+        U: Fn(S, K, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_with_retain_opt(
+            computation,
+            Self::keyed_getter(key.clone(), map_getter),
+            Self::keyed_updater(key, map_updater),
+        )
+    }
+
+    /// Executes a cancellable synchronous computation and writes its result into the entry of `key`
+    /// in a map of `Async` values.
+    ///
+    /// Works like [`execute_keyed`](Self::execute_keyed); a cancelled execution leaves the entry
+    /// failed with [`AsyncError::Cancelled`] and its retained value, like
+    /// [`execute_cancellable_with_retain`](Self::execute_cancellable_with_retain).
+    #[track_caller]
+    pub fn execute_cancellable_keyed<K, T, R, F, M, U>(
+        &self,
+        key: K,
+        cancellation_token: CancellationToken,
+        computation: F,
+        map_getter: M,
+        map_updater: U,
+    ) -> ExecutionTicket
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        M: Fn(&S) -> &HashMap<K, Async<T>> + Clone + Send + 'static,
+        U: Fn(S, K, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |token: Option<CancellationToken>| computation(token.unwrap())),
+            Self::keyed_updater(key.clone(), map_updater),
+            Some(Self::keyed_getter(key, map_getter)),
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes an asynchronous computation and writes its result into the entry of `key` in a map
+    /// of `Async` values.
+    ///
+    /// This is the asynchronous counterpart of [`execute_keyed`](Self::execute_keyed).
+    #[track_caller]
+    pub fn async_execute_keyed<K, T, R, F, M, U>(
+        &self,
+        key: K,
+        computation: F,
+        map_getter: M,
+        map_updater: U,
+    ) -> ExecutionTicket
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        M: Fn(&S) -> &HashMap<K, Async<T>> + Clone + Send + 'static,
+        U: Fn(S, K, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.async_execute_with_retain_opt(
+            computation,
+            Self::keyed_getter(key.clone(), map_getter),
+            Self::keyed_updater(key, map_updater),
+        )
+    }
+
+    /// Executes a cancellable asynchronous computation and writes its result into the entry of `key`
+    /// in a map of `Async` values.
+    ///
+    /// This is the asynchronous counterpart of [`execute_cancellable_keyed`](Self::execute_cancellable_keyed).
+    #[track_caller]
+    pub fn async_execute_cancellable_keyed<K, T, R, F, M, U, Fut>(
+        &self,
+        key: K,
+        cancellation_token: CancellationToken,
+        computation: F,
+        map_getter: M,
+        map_updater: U,
+    ) -> ExecutionTicket
+    where
+        K: Eq + Hash + Clone + Send + 'static,
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        M: Fn(&S) -> &HashMap<K, Async<T>> + Clone + Send + 'static,
+        U: Fn(S, K, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation(cancellation_token.clone())),
+            Self::keyed_updater(key.clone(), map_updater),
+            Some(Self::keyed_getter(key, map_getter)),
+            Some(cancellation_token),
+            ExecuteOptions::default(),
+        )
+    }
+
+    /// Executes a synchronous computation and tags every update of its result with `correlation_id`.
+    ///
+    /// Works like [`execute`](Self::execute), but the updater receives an [`AsyncWithCorrelation<T>`]
+    /// carrying the id passed here, for the `Loading` update as well as for the result. This ties
+    /// state updates back to the request that triggered them in traces and error reports.
+    ///
+    /// Only available with the `uuid` feature enabled.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{AsyncWithCorrelation, State, StateStore};
+    /// use uuid::Uuid;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    order: Option<AsyncWithCorrelation<u32>>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { order: None });
+    ///     let request_id = Uuid::from_u128(42);
+    ///     store.execute_with_correlation_id(
+    ///         request_id,
+    ///         || 7,
+    ///         |_, order| TestState { order: Some(order) },
+    ///     ).await??;
+    ///     let order = store.await_state().await?.order.unwrap();
+    ///     assert_eq!(order.correlation_id(), request_id);
+    ///     assert!(order.result().is_success());
+    ///   Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "uuid")]
+    #[track_caller]
+    pub fn execute_with_correlation_id<T, R, F, U>(
+        &self,
+        correlation_id: uuid::Uuid,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, crate::AsyncWithCorrelation<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute(computation, move |state, result| {
+            state_updater(state, crate::AsyncWithCorrelation::new(correlation_id, result))
+        })
+    }
+
+    /// Executes a multi-phase synchronous computation that reports its stages into an [`AsyncStaged<T>`] field.
+    ///
+    /// `stages` lists the planned stages in order. The field starts in the first stage instead of
+    /// `Loading` (or in `Loading` if `stages` is empty), then the computation moves it forward with
+    /// the [`StageReporter`] it receives, and the result is written like with [`execute`](Self::execute).
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{AsyncStaged, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    import: AsyncStaged<usize>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { import: AsyncStaged::default() });
+    ///     store.execute_staged(
+    ///         vec!["download", "parse"],
+    ///         |reporter| {
+    ///             reporter.report_progress(0.5);
+    ///             reporter.report_stage("parse");
+    ///             42
+    ///         },
+    ///         |_, import| TestState { import },
+    ///     ).await??;
+    ///     assert_eq!(store.await_state().await?.import, AsyncStaged::Success { value: 42 });
+    ///     Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_staged<T, R, F, U>(
+        &self,
+        stages: Vec<&'static str>,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(StageReporter) -> R + Send + 'static,
+        U: FnOnce(S, AsyncStaged<T>) -> S + Clone + Send + 'static,
+    {
+        let first_stage = stages.first().copied();
+        let reporter = StageReporter::new(stages, {
+            let store = self.clone();
+            let state_updater = state_updater.clone();
+            Box::new(move |name, progress| {
+                let state_updater = state_updater.clone();
+                let stage = AsyncStaged::Stage { name, progress, value: None };
+                store.set_state_forget(move |state| state_updater(state, stage));
+            })
+        });
+        self.execute(
+            move || computation(reporter),
+            move |state, async_value| match (first_stage, async_value) {
+                (Some(name), Async::Loading { .. }) => state_updater(state, AsyncStaged::stage(name)),
+                (_, async_value) => state_updater(state, async_value.into()),
+            },
+        )
+    }
+
+    /// Wraps an updater so the first non-loading value it writes is also sent to the returned receiver.
+    fn notifying_updater<T, U>(
+        state_updater: U,
+    ) -> (impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static, oneshot::Receiver<Async<T>>)
+    where
+        T: Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (tx, rx) = oneshot::channel();
+        let tx = Arc::new(Mutex::new(Some(tx)));
+        let updater = move |state, async_value: Async<T>| {
+            if !async_value.is_loading() {
+                if let Some(tx) = tx.lock().unwrap_or_else(|e| e.into_inner()).take() {
+                    let _ = tx.send(async_value.clone());
+                }
+            }
+            state_updater(state, async_value)
+        };
+        (updater, rx)
+    }
+
+    /// Executes a synchronous computation like [`execute`](Self::execute) and also returns a receiver
+    /// for its result.
+    ///
+    /// The receiver resolves with the final `Async<T>` once it is written into the state, i.e. the
+    /// value the updater receives after loading: `Success`, or `Fail` for errors, panics and
+    /// cancellations. This is the result after the [`with_error_recovery`](Self::with_error_recovery)
+    /// policy was applied. Awaiting it avoids subscribing to the state just to get one result.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { num: Async::default() });
+    ///     let (_, result) = store.execute_notifying(|| 888, |_, num| TestState { num });
+    ///     assert_eq!(result.await?, Async::success(888));
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// ## Errors
+    ///
+    /// The receiver fails with a `RecvError` if the result is never written, e.g. because the store
+    /// was closed while the computation ran.
+    #[track_caller]
+    pub fn execute_notifying<T, R, F, U>(
+        &self,
+        computation: F,
+        state_updater: U,
+    ) -> (ExecutionTicket, oneshot::Receiver<Async<T>>)
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (state_updater, result) = Self::notifying_updater(state_updater);
+        (self.execute(computation, state_updater), result)
+    }
+
+    /// Executes a cancellable synchronous computation and also returns a receiver for its result.
+    ///
+    /// Combines `execute_cancellable` and `execute_notifying`. A cancelled execution resolves the
+    /// receiver with a cancelled `Fail`.
+    #[track_caller]
+    pub fn execute_cancellable_notifying<T, R, F, U>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_updater: U,
+    ) -> (ExecutionTicket, oneshot::Receiver<Async<T>>)
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (state_updater, result) = Self::notifying_updater(state_updater);
+        (self.execute_cancellable(cancellation_token, computation, state_updater), result)
+    }
+
+    /// Executes an asynchronous computation and also returns a receiver for its result.
+    ///
+    /// This is the asynchronous counterpart of [`execute_notifying`](Self::execute_notifying).
+    #[track_caller]
+    pub fn async_execute_notifying<T, R, F, U>(
+        &self,
+        computation: F,
+        state_updater: U,
+    ) -> (ExecutionTicket, oneshot::Receiver<Async<T>>)
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (state_updater, result) = Self::notifying_updater(state_updater);
+        (self.async_execute(computation, state_updater), result)
+    }
+
+    /// Executes a cancellable asynchronous computation and also returns a receiver for its result.
+    ///
+    /// Combines `async_execute_cancellable` and `async_execute_notifying`. A cancelled execution
+    /// resolves the receiver with a cancelled `Fail`.
+    #[track_caller]
+    pub fn async_execute_cancellable_notifying<T, R, F, U, Fut>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_updater: U,
+    ) -> (ExecutionTicket, oneshot::Receiver<Async<T>>)
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (state_updater, result) = Self::notifying_updater(state_updater);
+        (self.async_execute_cancellable(cancellation_token, computation, state_updater), result)
+    }
+
+    /// Wraps an in-place updater so it can be shared by the loading and result phases of an execution.
+    fn mut_updater<T, U>(state_updater: U) -> impl FnOnce(S, Async<T>) -> S + Clone + Send + 'static
+    where
+        T: Clone + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        let state_updater = Arc::new(state_updater);
+        move |mut state, async_value| {
+            state_updater(&mut state, async_value);
+            state
+        }
+    }
+
+    /// Executes a synchronous computation and updates the state in place with its result.
+    ///
+    /// Works like [`execute`](Self::execute), but the updater mutates the state through `&mut S` instead
+    /// of taking and returning it by value. It is shared by the loading and result phases, so it doesn't
+    /// need to be `Clone` and can capture resources that aren't.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.execute_mut(|| 888, |state, num| state.num = num).await??;
+    ///     assert_eq!(store.await_state().await?.num, Async::success(888));
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_mut<T, R, F, U>(&self, computation: F, state_updater: U) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        self.execute(computation, Self::mut_updater(state_updater))
+    }
+
+    /// Executes a synchronous computation and updates the state in place with its result, retaining previous values.
+    ///
+    /// Combines `execute_with_retain` and `execute_mut`.
+    #[track_caller]
+    pub fn execute_with_retain_mut<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        self.execute_with_retain(computation, state_getter, Self::mut_updater(state_updater))
+    }
+
+    /// Executes a cancellable synchronous computation and updates the state in place with its result.
+    ///
+    /// Combines `execute_cancellable` and `execute_mut`.
+    #[track_caller]
+    pub fn execute_cancellable_mut<T, R, F, U>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        self.execute_cancellable(cancellation_token, computation, Self::mut_updater(state_updater))
+    }
+
+    /// Executes an asynchronous computation and updates the state in place with its result.
+    ///
+    /// This is the asynchronous counterpart of [`execute_mut`](Self::execute_mut).
+    #[track_caller]
+    pub fn async_execute_mut<T, R, F, U>(&self, computation: F, state_updater: U) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        self.async_execute(computation, Self::mut_updater(state_updater))
+    }
+
+    /// Executes an asynchronous computation and updates the state in place with its result, retaining previous values.
+    ///
+    /// Combines `async_execute_with_retain` and `async_execute_mut`.
+    #[track_caller]
+    pub fn async_execute_with_retain_mut<T, R, F, G, U>(
+        &self,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        self.async_execute_with_retain(computation, state_getter, Self::mut_updater(state_updater))
+    }
+
+    /// Executes a cancellable asynchronous computation and updates the state in place with its result.
+    ///
+    /// Combines `async_execute_cancellable` and `async_execute_mut`.
+    #[track_caller]
+    pub fn async_execute_cancellable_mut<T, R, F, U, Fut>(
+        &self,
+        cancellation_token: CancellationToken,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        U: Fn(&mut S, Async<T>) + Send + Sync + 'static,
+    {
+        self.async_execute_cancellable(cancellation_token, computation, Self::mut_updater(state_updater))
+    }
+
+    /// Executes a synchronous computation on a dedicated Rayon thread pool and updates the state with its result.
+    ///
+    /// `execute` runs computations on tokio's shared blocking pool. This method submits the computation
+    /// to `pool` instead and bridges the result back to the async runtime, which isolates CPU-heavy work
+    /// (FFT, image processing) from the blocking pool. A panicking computation results in `Async::Fail`.
+    ///
+    /// This method is only available with the `rayon` feature enabled.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<u64>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let pool = Arc::new(rayon::ThreadPoolBuilder::new().num_threads(2).build()?);
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.execute_with_thread_pool(
+    ///         pool,
+    ///         || (1..=20u64).product::<u64>(),
+    ///         |state, num| TestState { num, ..state }
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "rayon")]
+    #[track_caller]
+    pub fn execute_with_thread_pool<T, R, F, U>(
+        &self,
+        pool: Arc<rayon::ThreadPool>,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        self.spawn_execution(async move {
+            // Update the state to indicate loading
+            Self::update_async_state(&set_state_tx, state_updater.clone(), Async::loading(None))?;
+            // Yield to allow the state to be updated before running the computation
+            tokio::task::yield_now().await;
+            // Run the computation on the pool; a panic must not reach rayon's handler, which aborts
+            let (tx, rx) = tokio::sync::oneshot::channel();
+            let span = ExecutionSpan::current();
+            pool.spawn(move || {
+                let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| span.in_scope(computation)));
+                let _ = tx.send(result);
+            });
+            let panic_policy = set_state_tx.panic_policy;
+            let pooled = async move {
+                match rx.await {
+                    Ok(Ok(result)) => result.into_async(),
+                    Ok(Err(panic)) => Async::fail(panic_policy.error_from_panic(panic), None),
+                    Err(e) => Async::fail_with_message(e.to_string(), None),
+                }
+            };
+            let async_result = Self::within_timeout(set_state_tx.timeout, pooled).await;
+            Self::update_async_state(&set_state_tx, state_updater, async_result)
+        })
+    }
+
+    /// Executes a cancellable synchronous computation registered under a [`JobKey`].
+    ///
+    /// The computation receives a child token of the key's group, so it is cancelled by
+    /// [`cancel_group`](Self::cancel_group) as well as by any other job-specific logic.
+    /// The job is listed by [`active_jobs`](Self::active_jobs) until the returned handle completes.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, JobKey, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.execute_with_key(
+    ///         JobKey::group("screen_x").child("load_num"),
+    ///         |_token| 888,
+    ///         |state, num| TestState { num, ..state }
+    ///     );
+    ///     // Leaving the screen cancels everything it started
+    ///     store.cancel_group("screen_x");
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_key<T, R, F, U>(
+        &self,
+        key: JobKey,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce(CancellationToken) -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (guard, token) = self.shared.jobs.register(key);
+        let handle = self.execute_cancellable(token, computation, state_updater);
+        self.track_job(guard, handle)
+    }
+
+    /// Executes a cancellable asynchronous computation registered under a [`JobKey`].
+    ///
+    /// This is the asynchronous counterpart of [`execute_with_key`](Self::execute_with_key).
+    #[track_caller]
+    pub fn async_execute_with_key<T, R, F, U, Fut>(
+        &self,
+        key: JobKey,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let (guard, token) = self.shared.jobs.register(key);
+        let handle = self.async_execute_cancellable(token, computation, state_updater);
+        self.track_job(guard, handle)
+    }
+
+    /// Keeps the job registered until its execution has finished.
+    fn track_job(
+        &self,
+        guard: JobGuard,
+        handle: ExecutionTicket,
+    ) -> ExecutionTicket {
+        #[cfg(feature = "debug-jobs")]
+        let token_chain = guard.token_chain();
+        let ticket = self.spawn_execution(async move {
+            let _guard = guard;
+            handle.await.map_err(|e| AsyncError::error(e.to_string()))?
+        });
+        #[cfg(feature = "debug-jobs")]
+        let ticket = ticket.with_token_chain(token_chain);
+        ticket
+    }
+
+    /// Cancels every running job whose [`JobKey`] belongs to `group`.
+    ///
+    /// Jobs started in the same group afterwards are not affected.
+    pub fn cancel_group(&self, group: &str) {
+        self.shared.jobs.cancel_group(group);
+    }
+
+    /// Returns the keys of the jobs currently running in `group`, in start order.
+    ///
+    /// This is meant for debugging; jobs of a cancelled group are no longer listed.
+    pub fn active_jobs(&self, group: &str) -> Vec<JobKey> {
+        self.shared.jobs.active_jobs(group)
+    }
+
+    /// Lists the keyed jobs currently running in any group, in start order, with their start times
+    /// and token ids.
+    ///
+    /// Compare [`JobInfo::token_chain`](crate::JobInfo::token_chain) with
+    /// [`ExecutionTicket::token_chain`] to find out which group token a stuck job is waiting on.
+    /// Only available with the `debug-jobs` feature enabled.
+    #[cfg(feature = "debug-jobs")]
+    pub fn debug_jobs(&self) -> Vec<crate::JobInfo> {
+        self.shared.jobs.debug_jobs()
+    }
+
+    /// Executes an asynchronous computation with a timeout and updates the state with its result.
+    ///
+    /// This method runs the provided future with a timeout, and if the timeout is reached,
+    /// the state will be updated with `Async::Fail` with a timeout error.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState{
+    ///     fn set_num(self, num: Async<i32>) -> Self {
+    ///       Self { num, ..self }
+    ///     }
+    /// }
+    /// fn computation() -> Option<i32> {
+    ///     Some(888)
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.async_execute_with_timeout(
+    ///         async {
+    ///             // Some potentially slow operation
+    ///             tokio::time::sleep(Duration::from_millis(100)).await;
+    ///             computation()
+    ///         },
+    ///         Duration::from_secs(1), // 1 second timeout
+    ///         |state, result| {
+    ///             state.set_num(result)
+    ///         }
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn async_execute_with_timeout<T, R, F, U>(
+        &self,
+        computation: F,
+        timeout: std::time::Duration,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        self.spawn_execution(async move {
+            // Update the state to indicate loading
+            Self::update_async_state(&set_state_tx, state_updater.clone(), Async::loading(None))?;
+            // Yield to allow the state to be updated before running the computation
+            tokio::task::yield_now().await;
+            // Run the computation with a timeout
+            let computation = Self::run_async_computation(computation, set_state_tx.panic_policy);
+            let async_result = tokio::time::timeout(timeout, computation)
+                .await
+                .unwrap_or_else(|_| Async::fail_with_timeout(None));
+            Self::update_async_state(&set_state_tx, state_updater, async_result)
+        })
+    }
+
+    /// Executes a synchronous computation with a timeout and updates the state with its result.
+    ///
+    /// This method runs the provided computation in a blocking task with a timeout,
+    /// and if the timeout is reached, the state will be updated with `Async::Fail` with a timeout error.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    num: Async<i32>,
+    /// }
+    /// impl State for TestState {}
+    /// impl TestState{
+    ///     fn set_num(self, num: Async<i32>) -> Self {
+    ///       Self { num, ..self }
+    ///     }
+    /// }
+    /// fn computation() -> Option<i32> {
+    ///     Some(888)
+    /// }
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState{num: Async::default()});
+    ///     store.execute_with_timeout(
+    ///         || {
+    ///            // Some potentially slow operation
+    ///             std::thread::sleep(Duration::from_millis(100));
+    ///             computation()
+    ///         },
+    ///         Duration::from_secs(1), // 1 second timeout
+    ///         |state, result| {
+    ///             state.set_num(result)
+    ///         }
+    ///     );
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_timeout<T, R, F, U>(
+        &self,
+        computation: F,
+        timeout: std::time::Duration,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let set_state_tx = self.execution_sender();
+        self.spawn_execution(async move {
+            // Update the state to indicate loading
+            Self::update_async_state(&set_state_tx, state_updater.clone(), Async::loading(None))?;
+            // Yield to allow the state to be updated before running the computation
+            tokio::task::yield_now().await;
+            // Run the computation in a blocking context
+            let inner_computation = set_state_tx.blocking.spawn(computation);
+            let result = tokio::time::timeout(timeout, inner_computation).await;
+            let async_result = match result {
+                Ok(inner_result) => match inner_result {
+                    Ok(final_result) => final_result.into_async(),
+                    Err(final_error) => {
+                        Async::fail(set_state_tx.panic_policy.error_from_join(final_error), None)
+                    }
+                },
+                Err(_) => Async::fail_with_timeout(None),
+            };
+
+            Self::update_async_state(&set_state_tx, state_updater, async_result)
+        })
+    }
+
+    /// Executes a synchronous computation like [`execute`](Self::execute), with per-call `options`
+    /// overriding the store's defaults.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{Async, ExecuteOptions, State, StateStore, Timeout};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    report: Async<String>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::builder(TestState { report: Async::default() })
+    ///         .default_execute_timeout(Duration::from_secs(5))
+    ///         .build();
+    ///     // The yearly report is known to take longer than the store's default
+    ///     store.execute_with_options(
+    ///         ExecuteOptions::new().timeout(Timeout::None),
+    ///         || "report".to_string(),
+    ///         |state, report| TestState { report, ..state },
+    ///     ).await??;
+    ///     assert!(store.await_state().await?.report.is_success());
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_options<T, R, F, U>(
+        &self,
+        options: ExecuteOptions,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |_| computation()),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            options,
+        )
+    }
+
+    /// Executes an asynchronous computation like [`async_execute`](Self::async_execute), with
+    /// per-call `options` overriding the store's defaults.
+    #[track_caller]
+    pub fn async_execute_with_options<T, R, F, U>(
+        &self,
+        options: ExecuteOptions,
+        computation: F,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation),
+            state_updater,
+            None::<fn(&S) -> Option<&Async<T>>>,
+            None,
+            options,
+        )
+    }
+
+    /// Executes a synchronous computation like [`execute_with_retain`](Self::execute_with_retain),
+    /// with per-call `options` overriding the store's defaults. The options'
+    /// [`RetainPolicy`](crate::RetainPolicy) decides whether `Loading` and `Fail` keep the
+    /// previous value.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{Async, ExecuteOptions, RetainPolicy, State, StateStore};
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct TestState {
+    ///    results: Async<Vec<String>>,
+    /// }
+    /// impl State for TestState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::new(TestState { results: Async::success(vec!["old".to_string()]) });
+    ///     // A failed search should show the error, not the results of the previous query
+    ///     store.execute_with_options_and_retain(
+    ///         ExecuteOptions::new().retain(RetainPolicy::LoadingOnly),
+    ///         || Err::<Vec<String>, _>("offline"),
+    ///         |state| &state.results,
+    ///         |state, results| TestState { results, ..state },
+    ///     ).await??;
+    ///     let results = store.await_state().await?.results;
+    ///     assert!(results.is_fail());
+    ///     assert_eq!(results.value(), None);
+    ///   Ok(())
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn execute_with_options_and_retain<T, R, F, G, U>(
+        &self,
+        options: ExecuteOptions,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: FnOnce() -> R + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_blocking_core(
+            Attempts::once(move |_| computation()),
+            state_updater,
+            Some(Self::retain_getter(state_getter)),
+            None,
+            options,
+        )
+    }
+
+    /// Executes an asynchronous computation like
+    /// [`async_execute_with_retain`](Self::async_execute_with_retain), with per-call `options`
+    /// overriding the store's defaults, see
+    /// [`execute_with_options_and_retain`](Self::execute_with_options_and_retain).
+    #[track_caller]
+    pub fn async_execute_with_options_and_retain<T, R, F, G, U>(
+        &self,
+        options: ExecuteOptions,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        F: Future<Output = R> + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation),
+            state_updater,
+            Some(Self::retain_getter(state_getter)),
+            None,
+            options,
+        )
+    }
+
+    /// Executes a cancellable asynchronous computation like
+    /// [`async_execute_cancellable_with_retain`](Self::async_execute_cancellable_with_retain), with
+    /// per-call `options` overriding the store's defaults. A cancellation counts as a failure for
+    /// the options' [`RetainPolicy`](crate::RetainPolicy).
+    #[track_caller]
+    pub fn async_execute_cancellable_with_options_and_retain<T, R, F, U, Fut, G>(
+        &self,
+        cancellation_token: CancellationToken,
+        options: ExecuteOptions,
+        computation: F,
+        state_getter: G,
+        state_updater: U,
+    ) -> ExecutionTicket
+    where
+        T: Clone + Send + 'static,
+        R: ExecutionResult<T> + Send + 'static,
+        Fut: Future<Output = R> + Send + 'static,
+        F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+        G: FnOnce(&S) -> &Async<T> + Clone + Send + 'static,
+    {
+        self.execute_async_core(
+            Attempts::once(computation(cancellation_token.clone())),
+            state_updater,
+            Some(Self::retain_getter(state_getter)),
+            Some(cancellation_token),
+            options,
+        )
+    }
+}
+
+/// Runs one asynchronous computation and writes its result into two stores, e.g. a single fetch
+/// populating both a user store and a permissions store.
+///
+/// The computation resolves to a pair, directly or through a `Result` or an `Option` (see
+/// [`ExecutionResult`]); each store receives its half. Both fields go through `Loading` before the
+/// computation starts, and a failure of the computation is written into both of them and reported
+/// to the `on_async_fail` handlers of both stores. The computation runs once, on the runtime and
+/// with the timeout and panic policy of the first store.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{async_execute_into, Async, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct User { name: Async<String> }
+/// impl State for User {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Permissions { admin: Async<bool> }
+/// impl State for Permissions {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let user = StateStore::new(User { name: Async::Uninitialized });
+///     let permissions = StateStore::new(Permissions { admin: Async::Uninitialized });
+///     async_execute_into(
+///         (&user, |_, name| User { name }),
+///         (&permissions, |_, admin| Permissions { admin }),
+///         async { Ok::<_, String>(("alice".to_string(), true)) },
+///     )
+///     .await??;
+///
+///     assert_eq!(user.await_state().await?.name, Async::success("alice".to_string()));
+///     assert_eq!(permissions.await_state().await?.admin, Async::success(true));
+///     Ok(())
+/// }
+/// ```
+#[track_caller]
+pub fn async_execute_into<SA, SB, A, B, R, F, UA, UB>(
+    (store_a, state_updater_a): (&StateStore<SA>, UA),
+    (store_b, state_updater_b): (&StateStore<SB>, UB),
+    computation: F,
+) -> ExecutionTicket
+where
+    SA: State,
+    SB: State,
+    A: Clone + Send + 'static,
+    B: Clone + Send + 'static,
+    R: ExecutionResult<(A, B)> + Send + 'static,
+    F: Future<Output = R> + Send + 'static,
+    UA: FnOnce(SA, Async<A>) -> SA + Clone + Send + 'static,
+    UB: FnOnce(SB, Async<B>) -> SB + Clone + Send + 'static,
+{
+    execute_into_core((store_a, state_updater_a), (store_b, state_updater_b), computation, None)
+}
+
+/// Like [`async_execute_into`], with a computation that can be cancelled with `cancellation_token`.
+///
+/// Cancelling stops the single computation and writes `Fail` with [`AsyncError::Cancelled`] into
+/// both stores.
+#[track_caller]
+pub fn async_execute_into_cancellable<SA, SB, A, B, R, F, Fut, UA, UB>(
+    cancellation_token: CancellationToken,
+    (store_a, state_updater_a): (&StateStore<SA>, UA),
+    (store_b, state_updater_b): (&StateStore<SB>, UB),
+    computation: F,
+) -> ExecutionTicket
+where
+    SA: State,
+    SB: State,
+    A: Clone + Send + 'static,
+    B: Clone + Send + 'static,
+    R: ExecutionResult<(A, B)> + Send + 'static,
+    Fut: Future<Output = R> + Send + 'static,
+    F: FnOnce(CancellationToken) -> Fut + Send + 'static,
+    UA: FnOnce(SA, Async<A>) -> SA + Clone + Send + 'static,
+    UB: FnOnce(SB, Async<B>) -> SB + Clone + Send + 'static,
+{
+    execute_into_core(
+        (store_a, state_updater_a),
+        (store_b, state_updater_b),
+        computation(cancellation_token.clone()),
+        Some(cancellation_token),
+    )
+}
+
+#[track_caller]
+fn execute_into_core<SA, SB, A, B, R, F, UA, UB>(
+    (store_a, state_updater_a): (&StateStore<SA>, UA),
+    (store_b, state_updater_b): (&StateStore<SB>, UB),
+    computation: F,
+    cancellation_token: Option<CancellationToken>,
+) -> ExecutionTicket
+where
+    SA: State,
+    SB: State,
+    A: Clone + Send + 'static,
+    B: Clone + Send + 'static,
+    R: ExecutionResult<(A, B)> + Send + 'static,
+    F: Future<Output = R> + Send + 'static,
+    UA: FnOnce(SA, Async<A>) -> SA + Clone + Send + 'static,
+    UB: FnOnce(SB, Async<B>) -> SB + Clone + Send + 'static,
+{
+    let sender_a = store_a.execution_sender();
+    let sender_b = store_b.execution_sender();
+    store_a.spawn_execution(async move {
+        StateStore::update_async_state(&sender_a, state_updater_a.clone(), Async::loading(None))?;
+        StateStore::update_async_state(&sender_b, state_updater_b.clone(), Async::loading(None))?;
+        // Yield to allow the states to be updated before running the computation
+        tokio::task::yield_now().await;
+        let async_result = match &cancellation_token {
+            Some(token) => {
+                let computation = StateStore::<SA>::run_async_computation_cancelable(computation, token.clone(), sender_a.panic_policy);
+                let async_result = StateStore::<SA>::within_timeout(sender_a.timeout, computation).await;
+                if token.is_cancelled() {
+                    Async::fail_with_cancelled(None)
+                } else {
+                    async_result
+                }
+            }
+            None => {
+                let computation = StateStore::<SA>::run_async_computation(computation, sender_a.panic_policy);
+                StateStore::<SA>::within_timeout(sender_a.timeout, computation).await
+            }
+        };
+        let (result_a, result_b) = unzip(async_result);
+        let written_a = StateStore::update_async_state(&sender_a, state_updater_a, result_a);
+        let written_b = StateStore::update_async_state(&sender_b, state_updater_b, result_b);
+        written_a.and(written_b)
+    })
+}
+
+/// Splits the result of a computation into the results for two stores.
+fn unzip<A: Clone, B: Clone>(async_result: Async<(A, B)>) -> (Async<A>, Async<B>) {
+    match async_result {
+        Async::Uninitialized => (Async::Uninitialized, Async::Uninitialized),
+        Async::Loading { value } => {
+            let (a, b) = value.unzip();
+            (Async::loading(a), Async::loading(b))
+        }
+        Async::Success { value: (a, b) } => (Async::success(a), Async::success(b)),
+        Async::Fail { error, value } => {
+            let (a, b) = value.unzip();
+            (Async::fail(error.clone(), a), Async::fail(error, b))
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeSet;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+/// The queue running the executions of a store one at a time in submission order, see
+/// [`StateStoreBuilder::serialize_executions`](crate::StateStoreBuilder::serialize_executions).
+///
+/// Every execution draws a numbered turn when it is submitted. Turns are served in order, and a turn
+/// finishes when its execution ends or leaves the queue, so cancelled turns are skipped.
+#[derive(Debug)]
+pub(super) struct ExecutionQueue {
+    turns: Mutex<Turns>,
+    serving: watch::Sender<u64>,
+}
+
+#[derive(Debug, Default)]
+struct Turns {
+    next: u64,
+    serving: u64,
+    /// Turns that finished before every turn ahead of them did.
+    finished: BTreeSet<u64>,
+}
+
+impl ExecutionQueue {
+    pub(super) fn new() -> Arc<Self> {
+        Arc::new(ExecutionQueue {
+            turns: Mutex::default(),
+            serving: watch::Sender::new(0),
+        })
+    }
+
+    /// Draws the next turn.
+    pub(super) fn enqueue(self: &Arc<Self>) -> QueueTurn {
+        let mut turns = self.turns.lock().unwrap_or_else(|e| e.into_inner());
+        let turn = turns.next;
+        turns.next += 1;
+        QueueTurn {
+            queue: Arc::clone(self),
+            turn,
+        }
+    }
+
+    fn finish(&self, turn: u64) {
+        let mut turns = self.turns.lock().unwrap_or_else(|e| e.into_inner());
+        turns.finished.insert(turn);
+        let mut serving = turns.serving;
+        while turns.finished.remove(&serving) {
+            serving += 1;
+        }
+        turns.serving = serving;
+        self.serving.send_replace(serving);
+    }
+}
+
+/// The place of an execution in an [`ExecutionQueue`], given up when dropped.
+#[derive(Debug)]
+pub(super) struct QueueTurn {
+    queue: Arc<ExecutionQueue>,
+    turn: u64,
+}
+
+impl QueueTurn {
+    /// Waits until every execution submitted before this one has ended.
+    ///
+    /// Returns `false` if `token` was cancelled first, in which case the execution should end
+    /// without starting.
+    pub(super) async fn wait(&self, token: Option<&CancellationToken>) -> bool {
+        let mut serving = self.queue.serving.subscribe();
+        let turn = serving.wait_for(|serving| *serving >= self.turn);
+        match token {
+            Some(token) => tokio::select! {
+                biased;
+                _ = turn => true,
+                _ = token.cancelled() => false,
+            },
+            None => {
+                let _ = turn.await;
+                true
+            }
+        }
+    }
+}
+
+impl Drop for QueueTurn {
+    fn drop(&mut self) {
+        self.queue.finish(self.turn);
+    }
+}
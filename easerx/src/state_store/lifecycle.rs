@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+#[cfg(feature = "tracing")]
+use crate::panic_policy::panic_message;
+use crate::State;
+use super::store_ref::WeakStateStore;
+use super::{StateStore, StoreShared};
+
+/// The callback of [`StateStoreBuilder::on_idle`](crate::StateStoreBuilder::on_idle).
+pub(crate) type IdleHook<S> = Arc<dyn Fn(&StateStore<S>) + Send + Sync>;
+
+/// The callback of [`StateStoreBuilder::on_close`](crate::StateStoreBuilder::on_close).
+pub(crate) type CloseHook<S> = Box<dyn FnOnce(S) + Send>;
+
+/// The lifecycle hooks a store's queue task runs.
+pub(super) struct LifecycleHooks<S: State> {
+    idle: Option<(Duration, IdleHook<S>, WeakStateStore<S>)>,
+    close: Option<CloseHook<S>>,
+}
+
+impl<S: State> LifecycleHooks<S> {
+    pub(super) fn new(idle: Option<(Duration, IdleHook<S>)>, close: Option<CloseHook<S>>, store: &StateStore<S>) -> Self {
+        LifecycleHooks {
+            idle: idle.map(|(after, hook)| (after, hook, WeakStateStore::new(store))),
+            close,
+        }
+    }
+
+    /// Returns when the idle hook fires if no message arrives until then, or `None` without one.
+    pub(super) fn idle_deadline(&self) -> Option<Instant> {
+        self.idle.as_ref().map(|(after, _, _)| Instant::now() + *after)
+    }
+
+    /// Spawns the idle hook, unless every handle to the store is gone already.
+    pub(super) fn fire_idle(&self, shared: &StoreShared<S>) {
+        let Some((_, hook, weak)) = &self.idle else {
+            return;
+        };
+        if let Some(store) = weak.upgrade() {
+            let hook = Arc::clone(hook);
+            // Spawned, so a hook awaiting the store can't hold up the queue it waits on
+            shared.runtime.spawn(async move { hook(&store) });
+        }
+    }
+
+    /// Runs the close hook with the final state.
+    pub(super) fn close(self, final_state: S) {
+        let Some(hook) = self.close else {
+            return;
+        };
+        if let Err(_payload) = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| hook(final_state))) {
+            #[cfg(feature = "tracing")]
+            tracing::error!(message = %panic_message(_payload.as_ref()), "close hook panicked");
+        }
+    }
+}
@@ -0,0 +1,297 @@
+use std::cell::Cell;
+use std::fmt;
+use std::future::poll_fn;
+use std::task::Poll;
+use super::{Action, HeldUpdate, QueueReceivers, Reducer};
+use crate::{State, StateStore, StateStoreBuilder};
+
+thread_local! {
+    /// The tag of the message being processed, set by [`tagged`] closures when they run.
+    static CURRENT_TAG: Cell<Option<&'static str>> = const { Cell::new(None) };
+}
+
+/// Wraps a reducer or an action so the [`Step`] processing it reports `tag`.
+///
+/// Messages queued without a tag, including the ones queued by executions, report no tag.
+///
+/// ```rust
+/// use easerx::testing::{tagged, DeterministicStore};
+/// use easerx::State;
+///
+/// #[derive(Clone, Debug, Default)]
+/// struct Counter {
+///     count: u64,
+/// }
+/// impl State for Counter {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut harness = DeterministicStore::new(Counter::default());
+///     harness.store().set_state(tagged("increment", |state: Counter| Counter { count: state.count + 1 }))?;
+///     let step = harness.step().await.unwrap();
+///     assert_eq!(step.tag(), Some("increment"));
+///     Ok(())
+/// }
+/// ```
+pub fn tagged<A, R, F>(tag: &'static str, f: F) -> impl FnOnce(A) -> R
+where
+    F: FnOnce(A) -> R,
+{
+    move |arg| {
+        CURRENT_TAG.with(|current| current.set(Some(tag)));
+        f(arg)
+    }
+}
+
+/// The kind of message a [`Step`] processed.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum StepKind {
+    /// A reducer queued by `set_state` or an execution.
+    SetState,
+    /// A reducer queued by `priority_set_state`.
+    PrioritySetState,
+    /// An action queued by `with_state`.
+    WithState,
+    /// The completion of an async reducer queued by `update_async`.
+    AsyncUpdate,
+}
+
+/// The description of one message processed by [`DeterministicStore::step`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub struct Step {
+    kind: StepKind,
+    tag: Option<&'static str>,
+    version: u64,
+}
+
+impl Step {
+    /// Returns the kind of the processed message.
+    pub fn kind(&self) -> StepKind {
+        self.kind
+    }
+
+    /// Returns the tag the message was queued with, see [`tagged`].
+    pub fn tag(&self) -> Option<&'static str> {
+        self.tag
+    }
+
+    /// Returns the version of the state after the message was processed.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+/// Formats as `SetState(tag)`, or just `SetState` for an untagged message.
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.tag {
+            Some(tag) => write!(f, "{:?}({tag})", self.kind),
+            None => write!(f, "{:?}", self.kind),
+        }
+    }
+}
+
+/// A store whose queues are processed one message at a time by the test, instead of by a
+/// background task.
+///
+/// Each [`step`](Self::step) processes exactly one message with the same priorities as a regular
+/// store: queued reducers go before queued actions, priority reducers overtake the others every
+/// other turn, and while an `update_async` future is pending
+/// only actions are serviced until it completes. This makes the ordering of nested `set_state` and
+/// `with_state` calls observable and assertable.
+///
+/// Operations waiting on the queue, such as `await_state`, only resolve once the harness processed
+/// their message, so don't await them before stepping.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::testing::{tagged, DeterministicStore, StepKind};
+/// use easerx::State;
+///
+/// #[derive(Clone, Debug, Default)]
+/// struct Counter {
+///     count: u64,
+/// }
+/// impl State for Counter {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let mut harness = DeterministicStore::new(Counter::default());
+///     let store = harness.store().clone();
+///     store.with_state(tagged("read", |_: Counter| {}))?;
+///     store.set_state(tagged("write", |state: Counter| Counter { count: state.count + 1 }))?;
+///
+///     let steps = harness.run_until_idle().await;
+///     let tags: Vec<_> = steps.iter().map(|step| step.tag().unwrap()).collect();
+///     // Reducers take priority over actions queued before them
+///     assert_eq!(tags, ["write", "read"]);
+///     assert_eq!(steps[0].kind(), StepKind::SetState);
+///     Ok(())
+/// }
+/// ```
+pub struct DeterministicStore<S: State> {
+    store: StateStore<S>,
+    queues: QueueReceivers<S>,
+    held: Option<(HeldUpdate<S>, Option<&'static str>)>,
+    after_priority: bool,
+}
+
+impl<S: State> DeterministicStore<S> {
+    /// Creates a harness around a store with the given initial state.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn new(initial_state: S) -> Self {
+        DeterministicStore::from_builder(StateStore::builder(initial_state))
+    }
+
+    /// Creates a harness around the store configured by `builder`.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn from_builder(builder: StateStoreBuilder<S>) -> Self {
+        let (store, queues) = StateStore::unstarted(builder);
+        DeterministicStore {
+            store,
+            queues,
+            held: None,
+            after_priority: false,
+        }
+    }
+
+    /// Returns the store whose queues this harness processes. Clone it to queue messages from
+    /// within reducers and actions.
+    pub fn store(&self) -> &StateStore<S> {
+        &self.store
+    }
+
+    /// Processes the next queued message and describes it, or returns `None` if nothing is queued.
+    ///
+    /// While an `update_async` future is pending, this returns its completion if it is ready, or
+    /// else the next queued action, or else waits for whichever of the two comes first.
+    pub async fn step(&mut self) -> Option<Step> {
+        self.close_if_requested();
+        if self.held.is_some() {
+            return self.step_held().await;
+        }
+        if self.priority_turn() {
+            if let Ok(reducer) = self.queues.priority.try_recv() {
+                return Some(self.apply(reducer, StepKind::PrioritySetState));
+            }
+        }
+        if let Ok(reducer) = self.queues.set_state.try_recv() {
+            return Some(self.apply(reducer, StepKind::SetState));
+        }
+        let action = self.queues.with_state.try_recv().ok()?;
+        Some(self.run(action))
+    }
+
+    /// Waits for the next message, processes it and describes it.
+    ///
+    /// Useful when messages are queued by other tasks, such as executions. Returns `None` once the
+    /// store was closed and both queues are drained.
+    pub async fn next_step(&mut self) -> Option<Step> {
+        let closed = self.store.shared.closed.clone();
+        loop {
+            if let Some(step) = self.step().await {
+                return Some(step);
+            }
+            if closed.is_raised() {
+                return None;
+            }
+            tokio::select! {
+                biased;
+                Some(reducer) = self.queues.priority.recv() => {
+                    return Some(self.apply(reducer, StepKind::PrioritySetState));
+                }
+                Some(reducer) = self.queues.set_state.recv() => {
+                    return Some(self.apply(reducer, StepKind::SetState));
+                }
+                Some(action) = self.queues.with_state.recv() => return Some(self.run(action)),
+                _ = closed.raised() => {}
+            }
+        }
+    }
+
+    /// Processes messages until nothing is queued and returns their descriptions in order.
+    pub async fn run_until_idle(&mut self) -> Vec<Step> {
+        let mut steps = Vec::new();
+        while let Some(step) = self.step().await {
+            steps.push(step);
+        }
+        steps
+    }
+
+    fn close_if_requested(&mut self) {
+        if self.store.shared.closed.is_raised() {
+            self.queues.set_state.close();
+            self.queues.priority.close();
+            self.queues.with_state.close();
+        }
+    }
+
+    /// Mirrors the queue's fairness: after a priority reducer, queued regular reducers go first.
+    fn priority_turn(&self) -> bool {
+        !self.after_priority || self.queues.set_state.is_empty()
+    }
+
+    fn apply(&mut self, reducer: Reducer<S>, kind: StepKind) -> Step {
+        self.after_priority = kind == StepKind::PrioritySetState;
+        let tag = Self::with_tag(|| {
+            StateStore::apply_reducer(&self.store.state, &self.store.shared, reducer)
+        });
+        let held = self.store.shared.held.lock().unwrap().take();
+        self.held = held.map(|held| (held, tag));
+        self.describe(kind, tag)
+    }
+
+    fn run(&self, action: Action<S>) -> Step {
+        let tag = Self::with_tag(|| action(self.store.state.get_cloned()));
+        self.describe(StepKind::WithState, tag)
+    }
+
+    async fn step_held(&mut self) -> Option<Step> {
+        let (HeldUpdate { future, .. }, _) = self.held.as_mut()?;
+        let ready = poll_fn(|cx| Poll::Ready(future.as_mut().poll(cx))).await;
+        let result = match ready {
+            Poll::Ready(result) => result,
+            Poll::Pending => {
+                if let Ok(action) = self.queues.with_state.try_recv() {
+                    return Some(self.run(action));
+                }
+                tokio::select! {
+                    biased;
+                    result = future => result,
+                    Some(action) = self.queues.with_state.recv() => return Some(self.run(action)),
+                }
+            }
+        };
+        let (HeldUpdate { done, .. }, tag) = self.held.take()?;
+        let result = result.map(|new_state| {
+            let reducer: Reducer<S> = Box::new(move |_| Some(new_state));
+            StateStore::apply_reducer(&self.store.state, &self.store.shared, reducer);
+        });
+        let _ = done.send(result);
+        Some(self.describe(StepKind::AsyncUpdate, tag))
+    }
+
+    /// Runs `f` and returns the tag a [`tagged`] closure set while it ran.
+    fn with_tag(f: impl FnOnce()) -> Option<&'static str> {
+        CURRENT_TAG.with(|current| current.set(None));
+        f();
+        CURRENT_TAG.with(|current| current.take())
+    }
+
+    fn describe(&self, kind: StepKind, tag: Option<&'static str>) -> Step {
+        Step {
+            kind,
+            tag,
+            version: self.store.version(),
+        }
+    }
+}
+
+impl<S: State> fmt::Debug for DeterministicStore<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeterministicStore")
+            .field("holding_async_update", &self.held.is_some())
+            .finish_non_exhaustive()
+    }
+}
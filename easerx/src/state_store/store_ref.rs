@@ -0,0 +1,226 @@
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Weak};
+use tokio::sync::mpsc::WeakUnboundedSender;
+use crate::latency::LatencyHistogram;
+#[cfg(feature = "debug-transitions")]
+use crate::transition::TransitionLog;
+use crate::State;
+use super::{Action, Reducer, ReducerSender, StateStore, StoreShared};
+
+/// Identifies a store for as long as the process runs, see [`StateStore::id`].
+///
+/// Every store gets a new id when it is built; clones of a store share it.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash, Ord, PartialOrd)]
+pub struct StoreId(u64);
+
+impl StoreId {
+    pub(super) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(1);
+        StoreId(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for StoreId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "store#{}", self.0)
+    }
+}
+
+/// A weak reference to a [`StateStore`] that can be kept in the state of another store, created
+/// by [`StateStore::store_ref`].
+///
+/// Keeping a `StateStore` in a state makes every clone of the state keep the other store running,
+/// and two stores referring to each other, or a [`StoreMap`](crate::StoreMap) child referring to
+/// its parent, are never freed. A `StoreRef` holds neither the target's queue nor its state: it
+/// [`resolve`](Self::resolve)s to the store while some handle to the store is alive, and to `None`
+/// once the store was dropped. Resolve it where the store is used, e.g. in an event handler, rather
+/// than keeping the resolved store around.
+///
+/// References compare and hash by the [`StoreId`] of their target, so a state holding one can
+/// still derive `PartialEq`, and two references are equal exactly when they point to the same store.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{State, StateStore, StoreRef};
+///
+/// #[derive(Clone, Debug, PartialEq, Default)]
+/// struct Conversation {
+///     unread: u32,
+/// }
+/// impl State for Conversation {}
+///
+/// #[derive(Clone, Debug, PartialEq, Default)]
+/// struct Inbox {
+///     selected: Option<StoreRef<Conversation>>,
+/// }
+/// impl State for Inbox {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let inbox = StateStore::new(Inbox::default());
+///     let conversation = StateStore::new(Conversation { unread: 3 });
+///     let selected = conversation.store_ref();
+///     inbox.set_state(move |_| Inbox { selected: Some(selected) })?;
+///
+///     let selected = inbox.await_state().await?.selected.and_then(|selected| selected.resolve());
+///     assert_eq!(selected.map(|store| store.get_state().unread), Some(3));
+///
+///     drop(conversation);
+///     let selected = inbox.get_state().selected.and_then(|selected| selected.resolve());
+///     assert!(selected.is_none());
+///     Ok(())
+/// }
+/// ```
+pub struct StoreRef<S: State> {
+    id: StoreId,
+    store: WeakStateStore<S>,
+}
+
+impl<S: State> StateStore<S> {
+    /// Returns the id of this store, shared by its clones.
+    pub fn id(&self) -> StoreId {
+        self.shared.id
+    }
+
+    /// Returns a weak reference to this store, see [`StoreRef`].
+    pub fn store_ref(&self) -> StoreRef<S> {
+        StoreRef {
+            id: self.id(),
+            store: WeakStateStore::new(self),
+        }
+    }
+}
+
+impl<S: State> StoreRef<S> {
+    /// Returns the id of the referenced store.
+    pub fn id(&self) -> StoreId {
+        self.id
+    }
+
+    /// Returns the referenced store, or `None` once every handle to it was dropped.
+    ///
+    /// A store that was [closed](StateStore::close) but is still referenced elsewhere resolves;
+    /// check [`StateStore::is_closed`] if that matters.
+    pub fn resolve(&self) -> Option<StateStore<S>> {
+        self.store.upgrade()
+    }
+}
+
+impl<S: State> Clone for StoreRef<S> {
+    fn clone(&self) -> Self {
+        StoreRef {
+            id: self.id,
+            store: self.store.clone(),
+        }
+    }
+}
+
+impl<S: State> PartialEq for StoreRef<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<S: State> Eq for StoreRef<S> {}
+
+impl<S: State> Hash for StoreRef<S> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl<S: State> fmt::Debug for StoreRef<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("StoreRef").field(&self.id).finish()
+    }
+}
+
+/// A handle to a store that keeps neither its queue running nor its state alive.
+pub(super) struct WeakStateStore<S: State> {
+    shared: Weak<StoreShared<S>>,
+    set_state_tx: WeakReducerSender<S>,
+    priority_tx: WeakReducerSender<S>,
+    with_state_tx: WeakUnboundedSender<Action<S>>,
+}
+
+impl<S: State> WeakStateStore<S> {
+    pub(super) fn new(store: &StateStore<S>) -> Self {
+        WeakStateStore {
+            shared: Arc::downgrade(&store.shared),
+            set_state_tx: WeakReducerSender::new(&store.set_state_tx),
+            priority_tx: WeakReducerSender::new(&store.priority_tx),
+            with_state_tx: store.with_state_tx.downgrade(),
+        }
+    }
+
+    /// Returns the store, or `None` once every handle to it was dropped.
+    pub(super) fn upgrade(&self) -> Option<StateStore<S>> {
+        // The senders first: the queue task keeps the shared data alive a little longer
+        let set_state_tx = self.set_state_tx.upgrade()?;
+        let priority_tx = self.priority_tx.upgrade()?;
+        let with_state_tx = self.with_state_tx.upgrade()?;
+        let shared = self.shared.upgrade()?;
+        Some(StateStore {
+            state: shared.state.clone(),
+            shared,
+            set_state_tx,
+            priority_tx,
+            with_state_tx,
+        })
+    }
+}
+
+impl<S: State> Clone for WeakStateStore<S> {
+    fn clone(&self) -> Self {
+        WeakStateStore {
+            shared: self.shared.clone(),
+            set_state_tx: self.set_state_tx.clone(),
+            priority_tx: self.priority_tx.clone(),
+            with_state_tx: self.with_state_tx.clone(),
+        }
+    }
+}
+
+struct WeakReducerSender<S> {
+    tx: WeakUnboundedSender<Reducer<S>>,
+    frozen: Arc<AtomicBool>,
+    latency: Option<LatencyHistogram>,
+    #[cfg(feature = "debug-transitions")]
+    transitions: TransitionLog,
+}
+
+impl<S> WeakReducerSender<S> {
+    fn new(sender: &ReducerSender<S>) -> Self {
+        WeakReducerSender {
+            tx: sender.tx.downgrade(),
+            frozen: sender.frozen.clone(),
+            latency: sender.latency.clone(),
+            #[cfg(feature = "debug-transitions")]
+            transitions: sender.transitions.clone(),
+        }
+    }
+
+    fn upgrade(&self) -> Option<ReducerSender<S>> {
+        Some(ReducerSender {
+            tx: self.tx.upgrade()?,
+            frozen: self.frozen.clone(),
+            latency: self.latency.clone(),
+            #[cfg(feature = "debug-transitions")]
+            transitions: self.transitions.clone(),
+        })
+    }
+}
+
+impl<S> Clone for WeakReducerSender<S> {
+    fn clone(&self) -> Self {
+        WeakReducerSender {
+            tx: self.tx.clone(),
+            frozen: self.frozen.clone(),
+            latency: self.latency.clone(),
+            #[cfg(feature = "debug-transitions")]
+            transitions: self.transitions.clone(),
+        }
+    }
+}
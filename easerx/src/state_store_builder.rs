@@ -0,0 +1,358 @@
+use std::sync::Arc;
+use std::time::Duration;
+use crate::state_size::HeapSizeFn;
+use crate::state_store::{CloseHook, IdleHook};
+use crate::{FairnessPolicy, Middleware, State, StateStore};
+#[cfg(feature = "execute")]
+use crate::PanicPolicy;
+
+/// The default number of states buffered per lossless subscriber.
+pub const DEFAULT_BROADCAST_CAPACITY: usize = 64;
+
+/// The default number of messages the store's background task processes before yielding.
+pub const DEFAULT_YIELD_BATCH_SIZE: usize = 64;
+
+/// A builder for configuring a [`StateStore`] before it starts processing updates.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{StateStore, State};
+///
+/// #[derive(Clone)]
+/// struct AppState {
+///     counter: i32,
+/// }
+///
+/// impl State for AppState {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::builder(AppState { counter: 0 })
+///         .broadcast_capacity(16)
+///         .build();
+///     Ok(())
+/// }
+/// ```
+#[must_use = "A builder does nothing until build is called"]
+pub struct StateStoreBuilder<S: State> {
+    pub(crate) initial_state: S,
+    pub(crate) broadcast_capacity: usize,
+    pub(crate) middlewares: Vec<Arc<dyn Middleware<S>>>,
+    #[cfg(feature = "execute")]
+    pub(crate) panic_policy: PanicPolicy,
+    #[cfg(feature = "execute")]
+    pub(crate) default_execute_timeout: Option<Duration>,
+    #[cfg(feature = "execute")]
+    pub(crate) blocking_start_warning: Duration,
+    #[cfg(feature = "execute")]
+    pub(crate) serialize_executions: bool,
+    pub(crate) yield_batch_size: usize,
+    pub(crate) fairness: FairnessPolicy,
+    pub(crate) track_queue_latency: bool,
+    pub(crate) state_size_limit: Option<usize>,
+    pub(crate) approx_heap_size: Option<HeapSizeFn<S>>,
+    pub(crate) state_size_sample_interval: u64,
+    #[cfg(feature = "debug-transitions")]
+    pub(crate) transition_history: usize,
+    pub(crate) idle_hook: Option<(Duration, IdleHook<S>)>,
+    pub(crate) close_hook: Option<CloseHook<S>>,
+}
+
+impl<S: State> StateStoreBuilder<S> {
+    /// Creates a builder with the provided initial state and default settings.
+    pub fn new(initial_state: S) -> Self {
+        StateStoreBuilder {
+            initial_state,
+            broadcast_capacity: DEFAULT_BROADCAST_CAPACITY,
+            middlewares: Vec::new(),
+            #[cfg(feature = "execute")]
+            panic_policy: PanicPolicy::default(),
+            #[cfg(feature = "execute")]
+            default_execute_timeout: None,
+            #[cfg(feature = "execute")]
+            blocking_start_warning: crate::DEFAULT_BLOCKING_START_WARNING,
+            #[cfg(feature = "execute")]
+            serialize_executions: false,
+            yield_batch_size: DEFAULT_YIELD_BATCH_SIZE,
+            fairness: FairnessPolicy::default(),
+            track_queue_latency: false,
+            state_size_limit: None,
+            approx_heap_size: None,
+            state_size_sample_interval: crate::DEFAULT_STATE_SIZE_SAMPLE_INTERVAL,
+            #[cfg(feature = "debug-transitions")]
+            transition_history: crate::DEFAULT_TRANSITION_HISTORY,
+            idle_hook: None,
+            close_hook: None,
+        }
+    }
+
+    /// Sets how many states each [`subscribe_all`](StateStore::subscribe_all) subscriber may fall behind
+    /// before it starts skipping states.
+    ///
+    /// Every buffered entry is a clone of the state, so the memory held by the channel grows with
+    /// `capacity × size of the state`. Values below `1` are treated as `1`.
+    /// Defaults to [`DEFAULT_BROADCAST_CAPACITY`].
+    pub fn broadcast_capacity(mut self, capacity: usize) -> Self {
+        self.broadcast_capacity = capacity;
+        self
+    }
+
+    /// Adds a middleware that observes every state update, see [`StateStore::with_middleware`].
+    pub fn middleware<M: Middleware<S>>(mut self, middleware: M) -> Self {
+        self.middlewares.push(Arc::new(middleware));
+        self
+    }
+
+    /// Sets what executions do when their computation panics.
+    /// Defaults to [`PanicPolicy::Capture`].
+    #[cfg(feature = "execute")]
+    pub fn panic_policy(mut self, policy: PanicPolicy) -> Self {
+        self.panic_policy = policy;
+        self
+    }
+
+    /// Sets a timeout applied to every one-shot execution of the store, so no computation is awaited
+    /// forever.
+    ///
+    /// Async computations and the awaited portion of blocking ones are wrapped in the timeout; an
+    /// execution that exceeds it fails with [`AsyncError::Timeout`](crate::AsyncError::Timeout).
+    /// Blocking computations keep running on their thread, only their result is discarded.
+    /// Override it per call with [`ExecuteOptions`](crate::ExecuteOptions), including opting out
+    /// with [`Timeout::None`](crate::Timeout::None). Executions that run their computation
+    /// repeatedly, such as [`execute_cancellable_loop`](StateStore::execute_cancellable_loop) and
+    /// polling, and the explicit `*_with_timeout` methods are not affected.
+    /// Defaults to no timeout.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{State, StateStore};
+    ///
+    /// #[derive(Clone)]
+    /// struct AppState {
+    ///     counter: i32,
+    /// }
+    ///
+    /// impl State for AppState {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::builder(AppState { counter: 0 })
+    ///         .default_execute_timeout(Duration::from_secs(10))
+    ///         .build();
+    ///     Ok(())
+    /// }
+    /// ```
+    #[cfg(feature = "execute")]
+    pub fn default_execute_timeout(mut self, timeout: Duration) -> Self {
+        self.default_execute_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long the computation of an execution may wait for a thread of the runtime's blocking
+    /// pool before the wait is reported.
+    ///
+    /// A computation that waits longer logs a warning and reports
+    /// [`StoreError::BlockingPoolSaturated`](crate::StoreError::BlockingPoolSaturated) through
+    /// [`StateStore::errors`]. Long waits mean every blocking thread is busy, e.g. with more long
+    /// computations than tokio's `max_blocking_threads`, and show as executions stuck in `Loading`.
+    /// The waits of all computations are recorded in [`StateStore::blocking_start_latency`].
+    /// Defaults to [`DEFAULT_BLOCKING_START_WARNING`](crate::DEFAULT_BLOCKING_START_WARNING).
+    #[cfg(feature = "execute")]
+    pub fn blocking_start_warning(mut self, threshold: Duration) -> Self {
+        self.blocking_start_warning = threshold;
+        self
+    }
+
+    /// Runs the executions of the store one at a time, in the order they were submitted, e.g. for
+    /// a store guarding a single hardware resource.
+    ///
+    /// Each execution still writes its `Loading` state right away, so the pending work shows, but
+    /// its computation only starts once the execution submitted before it has written its final
+    /// state. An execution whose token is cancelled while it waits leaves the queue without
+    /// starting its computation, and writes `Fail` with [`AsyncError::Cancelled`](crate::AsyncError::Cancelled)
+    /// like any other cancelled execution.
+    ///
+    /// Applies to [`execute`](StateStore::execute), [`async_execute`](StateStore::async_execute) and
+    /// their retaining, cancellable and [`ExecuteOptions`](crate::ExecuteOptions) variants.
+    /// Defaults to running executions concurrently.
+    #[cfg(feature = "execute")]
+    pub fn serialize_executions(mut self) -> Self {
+        self.serialize_executions = true;
+        self
+    }
+
+    /// Sets how many queued updates and actions the background task processes before yielding to
+    /// the runtime.
+    ///
+    /// Without yielding, a burst of thousands of reducers keeps the task busy until the queue
+    /// drains, which starves every other task on a current-thread runtime. Smaller batches keep the
+    /// runtime responsive at the cost of more context switches during bursts; `0` disables the
+    /// explicit yield and leaves scheduling to tokio's own cooperative budget.
+    /// Defaults to [`DEFAULT_YIELD_BATCH_SIZE`].
+    pub fn yield_batch_size(mut self, batch_size: usize) -> Self {
+        self.yield_batch_size = batch_size;
+        self
+    }
+
+    /// Sets how the background task interleaves reducers with chains of
+    /// [`with_state`](StateStore::with_state) actions, see [`FairnessPolicy`].
+    /// Defaults to [`FairnessPolicy::default`].
+    pub fn fairness(mut self, policy: FairnessPolicy) -> Self {
+        self.fairness = policy;
+        self
+    }
+
+    /// Records how long every update waits in the queue, from being queued until its reducer ran.
+    ///
+    /// Read the histogram with [`StateStore::queue_latency`]. Tracking costs a timestamp and a few
+    /// atomic increments per update, so it is off by default.
+    pub fn track_queue_latency(mut self) -> Self {
+        self.track_queue_latency = true;
+        self
+    }
+
+    /// Warns when the state takes more than `bytes`, since every reducer copies the whole state.
+    ///
+    /// The inline size of the state, `std::mem::size_of::<S>()`, is checked when the store is built.
+    /// Add [`approx_heap_size`](Self::approx_heap_size) to also count what the state owns on the
+    /// heap, sampled every [`state_size_sample_interval`](Self::state_size_sample_interval) commits.
+    /// A state over the budget logs a warning (with the `tracing` feature) and reports
+    /// [`StoreError::StateTooLarge`](crate::StoreError::StateTooLarge) through [`StateStore::errors`].
+    /// Move large fields behind `Arc`, e.g. with [`ArcAsync`](crate::ArcAsync), so clones share them.
+    /// Off by default.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use easerx::{State, StateStore};
+    ///
+    /// #[derive(Clone)]
+    /// struct Catalog {
+    ///     items: Vec<String>,
+    /// }
+    ///
+    /// impl State for Catalog {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::builder(Catalog { items: Vec::new() })
+    ///         .warn_if_state_larger_than(1 << 20)
+    ///         .approx_heap_size(|catalog| catalog.items.iter().map(String::capacity).sum())
+    ///         .build();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn warn_if_state_larger_than(mut self, bytes: usize) -> Self {
+        self.state_size_limit = Some(bytes);
+        self
+    }
+
+    /// Sets the function estimating how many bytes a state owns on the heap, for
+    /// [`warn_if_state_larger_than`](Self::warn_if_state_larger_than).
+    ///
+    /// It runs on the store's queue while reads of the state wait, so keep it cheap.
+    pub fn approx_heap_size<F>(mut self, heap_size: F) -> Self
+    where
+        F: Fn(&S) -> usize + Send + Sync + 'static,
+    {
+        self.approx_heap_size = Some(Arc::new(heap_size));
+        self
+    }
+
+    /// Sets how many commits pass between two samples of the heap size, see
+    /// [`approx_heap_size`](Self::approx_heap_size). Values below `1` are treated as `1`.
+    /// Defaults to [`DEFAULT_STATE_SIZE_SAMPLE_INTERVAL`](crate::DEFAULT_STATE_SIZE_SAMPLE_INTERVAL).
+    pub fn state_size_sample_interval(mut self, commits: u64) -> Self {
+        self.state_size_sample_interval = commits;
+        self
+    }
+
+    /// Sets how many transitions [`StateStore::recent_transitions`] keeps; `0` disables the history.
+    /// Defaults to [`DEFAULT_TRANSITION_HISTORY`](crate::DEFAULT_TRANSITION_HISTORY).
+    ///
+    /// Only available with the `debug-transitions` feature enabled.
+    #[cfg(feature = "debug-transitions")]
+    pub fn transition_history(mut self, capacity: usize) -> Self {
+        self.transition_history = capacity;
+        self
+    }
+
+    /// Calls `hook` with the store whenever its queue has processed no message for `after`, e.g. to
+    /// release a cache nobody used for a while.
+    ///
+    /// The timer runs in the store's queue task and restarts with every reducer and action the
+    /// queue processes; after firing, the hook fires again only once the store was used and then
+    /// went quiet for `after` again. The hook is spawned on the store's runtime, so it may update
+    /// or await the store. It doesn't fire once every handle to the store is dropped or the store is
+    /// closing.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use easerx::{State, StateStore};
+    ///
+    /// #[derive(Clone)]
+    /// struct Gallery {
+    ///     thumbnails: Vec<Vec<u8>>,
+    /// }
+    ///
+    /// impl State for Gallery {}
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    ///     let store = StateStore::builder(Gallery { thumbnails: Vec::new() })
+    ///         .on_idle(Duration::from_secs(60), |store| {
+    ///             let _ = store.set_state(|_| Gallery { thumbnails: Vec::new() });
+    ///         })
+    ///         .build();
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn on_idle<F>(mut self, after: Duration, hook: F) -> Self
+    where
+        F: Fn(&StateStore<S>) + Send + Sync + 'static,
+    {
+        self.idle_hook = Some((after, Arc::new(hook)));
+        self
+    }
+
+    /// Calls `hook` with the final state once the store's queue stops: after
+    /// [`StateStore::close`] drained the messages already queued, or once every handle to the store
+    /// is dropped.
+    ///
+    /// The hook runs on the queue task right before [`StateStore::closed`] resolves, so keep it
+    /// short; spawn longer teardown work. A panic in the hook is caught and logged.
+    pub fn on_close<F>(mut self, hook: F) -> Self
+    where
+        F: FnOnce(S) + Send + 'static,
+    {
+        self.close_hook = Some(Box::new(hook));
+        self
+    }
+
+    /// Builds the store and spawns its background task.
+    ///
+    /// Must be called from within a tokio runtime.
+    pub fn build(self) -> StateStore<S> {
+        StateStore::from_builder(self)
+    }
+}
+
+impl<S: State + std::fmt::Debug> std::fmt::Debug for StateStoreBuilder<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut builder = f.debug_struct("StateStoreBuilder");
+        builder
+            .field("initial_state", &self.initial_state)
+            .field("broadcast_capacity", &self.broadcast_capacity)
+            .field("middlewares", &self.middlewares.len());
+        #[cfg(feature = "execute")]
+        builder
+            .field("panic_policy", &self.panic_policy)
+            .field("default_execute_timeout", &self.default_execute_timeout)
+            .field("serialize_executions", &self.serialize_executions);
+        builder
+            .field("yield_batch_size", &self.yield_batch_size)
+            .field("fairness", &self.fairness)
+            .finish()
+    }
+}
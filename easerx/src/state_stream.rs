@@ -0,0 +1,78 @@
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use futures_core::stream::Stream;
+use futures_signals::signal::{MutableSignalCloned, SignalStream};
+use pin_project::pin_project;
+use crate::{EaseRxStreamExt, Last, Materialize, StopAfter, StopIf, StopIfWithGrace};
+
+/// A stream of the states of a [`StateStore`](crate::StateStore), created by
+/// [`StateStore::to_stream`](crate::StateStore::to_stream).
+///
+/// The stream emits the current state first and then the latest state whenever it changes;
+/// rapid updates may be conflated. It works with any `Stream` adaptor, and mirrors the
+/// [`EaseRxStreamExt`] methods as inherent methods so they don't need the trait import.
+#[pin_project]
+#[must_use = "Streams do nothing unless polled"]
+pub struct StateStream<S> {
+    #[pin]
+    inner: SignalStream<MutableSignalCloned<S>>,
+}
+
+impl<S: Clone> StateStream<S> {
+    pub(crate) fn new(inner: SignalStream<MutableSignalCloned<S>>) -> Self {
+        StateStream { inner }
+    }
+
+    /// Returns the underlying signal stream.
+    pub fn into_inner(self) -> SignalStream<MutableSignalCloned<S>> {
+        self.inner
+    }
+
+    /// Stops the stream once `test` returns true, see [`EaseRxStreamExt::stop_if`].
+    pub fn stop_if<F>(self, test: F) -> StopIf<Self, F>
+    where
+        F: FnMut(&S) -> bool,
+    {
+        EaseRxStreamExt::stop_if(self, test)
+    }
+
+    /// Stops the stream a grace period after `test` returns true,
+    /// see [`EaseRxStreamExt::stop_if_with_grace`].
+    pub fn stop_if_with_grace<F>(self, test: F, grace: Duration) -> StopIfWithGrace<Self, F>
+    where
+        F: FnMut(&S) -> bool,
+    {
+        EaseRxStreamExt::stop_if_with_grace(self, test, grace)
+    }
+
+    /// Stops the stream after `n` states, see [`EaseRxStreamExt::stop_after`].
+    pub fn stop_after(self, n: usize) -> StopAfter<Self> {
+        EaseRxStreamExt::stop_after(self, n)
+    }
+
+    /// Resolves to the last state of the stream, see [`EaseRxStreamExt::last`].
+    pub fn last(self) -> Last<Self> {
+        EaseRxStreamExt::last(self)
+    }
+
+    /// Wraps every state into a [`StreamEvent`](crate::StreamEvent), see [`EaseRxStreamExt::materialize`].
+    pub fn materialize(self) -> Materialize<Self> {
+        EaseRxStreamExt::materialize(self)
+    }
+}
+
+impl<S: Clone> Stream for StateStream<S> {
+    type Item = S;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.project().inner.poll_next(cx)
+    }
+}
+
+impl<S> fmt::Debug for StateStream<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StateStream").finish()
+    }
+}
@@ -0,0 +1,29 @@
+use thiserror::Error;
+use crate::AsyncError;
+
+/// The payload of one variant of an enum state, used by [`StateStore::set_state_variant`](crate::StateStore::set_state_variant)
+/// and [`StateStore::is_variant`](crate::StateStore::is_variant).
+///
+/// The conversion from the state hands the state back unchanged when it holds another variant,
+/// so a reducer never has to re-match the variant or silently skip its work.
+/// Implement it with the [`state_variants!`](crate::state_variants) macro, which also generates
+/// the matching `TryFrom<S>` implementations.
+pub trait StateVariant<S>: TryFrom<S, Error = S> {
+    /// The name of the variant, for error messages.
+    const NAME: &'static str;
+
+    /// Returns true if `state` holds this variant.
+    fn matches(state: &S) -> bool;
+}
+
+/// The error returned by [`StateStore::set_state_variant`](crate::StateStore::set_state_variant).
+#[derive(Error, Debug, Clone, Eq, PartialEq)]
+pub enum VariantMismatch {
+    /// The state held another variant when the reducer was applied, so the state was left unchanged.
+    #[error("Expected state variant {expected}")]
+    Wrong { expected: &'static str },
+
+    /// The update could not be delivered to the state store.
+    #[error(transparent)]
+    Store(#[from] AsyncError),
+}
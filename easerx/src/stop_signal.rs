@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// A signal that is raised once and can be awaited by any number of tasks.
+///
+/// This covers what the store needs from a `CancellationToken` for closing and stopping its queue,
+/// without depending on `tokio-util`. Clones share the same signal.
+#[derive(Debug, Clone)]
+pub(crate) struct StopSignal {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl StopSignal {
+    pub(crate) fn new() -> Self {
+        StopSignal {
+            tx: Arc::new(watch::Sender::new(false)),
+        }
+    }
+
+    /// Raises the signal, waking every task waiting in [`raised`](Self::raised).
+    pub(crate) fn raise(&self) {
+        self.tx.send_replace(true);
+    }
+
+    pub(crate) fn is_raised(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Resolves once the signal is raised, immediately if it already was.
+    pub(crate) async fn raised(&self) {
+        let mut rx = self.tx.subscribe();
+        // The sender lives as long as `self`, so waiting cannot fail
+        let _ = rx.wait_for(|raised| *raised).await;
+    }
+}
@@ -0,0 +1,116 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use futures_core::stream::Stream;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use crate::{AsyncError, StateReceiver};
+
+/// The number of errors buffered for each [`StateStore::errors`](crate::StateStore::errors) subscriber.
+const ERRORS_CAPACITY: usize = 64;
+
+/// An internal error of a [`StateStore`](crate::StateStore), reported by
+/// [`StateStore::errors`](crate::StateStore::errors).
+///
+/// New kinds of errors may be reported in the future, so matches need a wildcard arm.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum StoreError {
+    /// A reducer passed to `set_state` panicked. The update was skipped and the state left unchanged.
+    #[error("Reducer panicked: {message}")]
+    ReducerPanic { message: String },
+
+    /// An execution could not complete, e.g. because the store was closed before it wrote its result.
+    #[error("Execution failed: {error}")]
+    ExecutionFailed { error: AsyncError },
+
+    /// A state subscriber fell behind and skipped the given number of states.
+    #[error("Subscriber lagged behind by {skipped} states")]
+    Lagged { skipped: u64 },
+
+    /// A blocking computation of an execution waited longer than the configured threshold for a
+    /// thread of the runtime's blocking pool, see
+    /// [`StateStoreBuilder::blocking_start_warning`](crate::StateStoreBuilder::blocking_start_warning).
+    #[cfg(feature = "execute")]
+    #[error("Blocking computation waited {waited:?} for a thread")]
+    BlockingPoolSaturated { waited: std::time::Duration },
+
+    /// The [`with_error_recovery`](crate::StateStore::with_error_recovery) policy asked to retry an
+    /// execution whose computation can only run once, so the failure was written instead.
+    /// Use [`execute_recoverable`](crate::StateStore::execute_recoverable) or
+    /// [`async_execute_recoverable`](crate::StateStore::async_execute_recoverable) for executions to retry.
+    #[cfg(feature = "execute")]
+    #[error("Execution cannot be retried: {error}")]
+    RetryUnsupported { error: AsyncError },
+
+    /// The state grew beyond the size budget set with
+    /// [`StateStoreBuilder::warn_if_state_larger_than`](crate::StateStoreBuilder::warn_if_state_larger_than).
+    /// Reported again only after the state went back under the budget in between.
+    #[error("State takes about {bytes} bytes, more than the budget of {limit} bytes")]
+    StateTooLarge { bytes: usize, limit: usize },
+}
+
+/// The sending half of the error channel of a store.
+#[derive(Debug, Clone)]
+pub(crate) struct StoreErrors {
+    tx: broadcast::Sender<StoreError>,
+}
+
+impl StoreErrors {
+    pub(crate) fn new() -> Self {
+        StoreErrors {
+            tx: broadcast::channel(ERRORS_CAPACITY).0,
+        }
+    }
+
+    /// Reports an error. `error` is only built while someone is subscribed.
+    pub(crate) fn publish(&self, error: impl FnOnce() -> StoreError) {
+        if self.tx.receiver_count() > 0 {
+            let _ = self.tx.send(error());
+        }
+    }
+
+    pub(crate) fn subscribe(&self) -> StoreErrorStream {
+        StoreErrorStream {
+            receiver: StateReceiver::new(self.tx.subscribe(), None),
+        }
+    }
+
+    /// Returns a handle that reports errors without keeping the error streams open.
+    pub(crate) fn downgrade(&self) -> WeakStoreErrors {
+        WeakStoreErrors {
+            tx: self.tx.downgrade(),
+        }
+    }
+}
+
+/// A handle to the error channel of a store that does not keep it open.
+#[derive(Debug, Clone)]
+pub(crate) struct WeakStoreErrors {
+    tx: broadcast::WeakSender<StoreError>,
+}
+
+impl WeakStoreErrors {
+    pub(crate) fn publish(&self, error: impl FnOnce() -> StoreError) {
+        if let Some(tx) = self.tx.upgrade() {
+            StoreErrors { tx }.publish(error);
+        }
+    }
+}
+
+/// The stream returned by [`StateStore::errors`](crate::StateStore::errors).
+///
+/// If the subscriber falls behind, the oldest errors are dropped. The stream ends once the store
+/// and all of its clones have been dropped.
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct StoreErrorStream {
+    receiver: StateReceiver<StoreError>,
+}
+
+impl Stream for StoreErrorStream {
+    type Item = StoreError;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.receiver).poll_next(cx)
+    }
+}
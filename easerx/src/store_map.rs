@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::Hash;
+use std::sync::{Arc, Mutex};
+use futures_signals::signal::{Mutable, MutableSignalCloned, Signal, SignalExt};
+use crate::{State, StateStore, SubscriptionGuard};
+
+struct Child<S: State> {
+    store: StateStore<S>,
+    // Bumps the map revision whenever the child commits a new state
+    _watch: SubscriptionGuard,
+}
+
+type Children<K, S> = Arc<Mutex<HashMap<K, Child<S>>>>;
+
+/// A dynamic collection of child [`StateStore`]s, one per key.
+///
+/// `StoreMap` suits lists whose items each need their own store, e.g. one store per open
+/// conversation. Children are created on demand with [`get_or_create`](Self::get_or_create) and
+/// closed (see [`StateStore::close`]) when removed or when the map is dropped, so their background
+/// tasks don't leak and late execution results can't update them anymore.
+///
+/// ## Examples
+///
+/// ```rust
+/// use easerx::{State, StoreMap};
+///
+/// #[derive(Clone, Debug, PartialEq, Default)]
+/// struct Conversation {
+///     unread: u32,
+/// }
+/// impl State for Conversation {}
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let conversations = StoreMap::new();
+///     let alice = conversations.get_or_create("alice", Conversation::default);
+///     alice.set_state(|state| Conversation { unread: state.unread + 1 })?;
+///     conversations.remove(&"alice");
+///     Ok(())
+/// }
+/// ```
+pub struct StoreMap<K, S: State> {
+    children: Children<K, S>,
+    keys: Mutable<Vec<K>>,
+    revision: Mutable<u64>,
+}
+
+impl<K, S> StoreMap<K, S>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    S: State,
+{
+    /// Creates an empty `StoreMap`.
+    pub fn new() -> Self {
+        StoreMap {
+            children: Arc::new(Mutex::new(HashMap::new())),
+            keys: Mutable::new(Vec::new()),
+            revision: Mutable::new(0),
+        }
+    }
+
+    /// Returns the child store for `key`, creating it with the state returned by `init` if missing.
+    ///
+    /// Creating a child must happen within a tokio runtime, like [`StateStore::new`].
+    pub fn get_or_create(&self, key: K, init: impl FnOnce() -> S) -> StateStore<S> {
+        let mut children = self.children.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(child) = children.get(&key) {
+            return child.store.clone();
+        }
+        let store = StateStore::new(init());
+        let revision = self.revision.clone();
+        let watch = SubscriptionGuard::new(store.spawn(store.to_signal().for_each(move |_| {
+            revision.replace_with(|revision| revision.wrapping_add(1));
+            async {}
+        })));
+        children.insert(key.clone(), Child {
+            store: store.clone(),
+            _watch: watch,
+        });
+        self.keys.lock_mut().push(key);
+        store
+    }
+
+    /// Returns the child store for `key`, if present.
+    pub fn get(&self, key: &K) -> Option<StateStore<S>> {
+        self.children
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(key)
+            .map(|child| child.store.clone())
+    }
+
+    /// Removes and closes the child store for `key`, returning true if it was present.
+    ///
+    /// Clones of the removed store handed out earlier keep their last state but reject updates.
+    pub fn remove(&self, key: &K) -> bool {
+        let removed = self
+            .children
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(key);
+        let Some(child) = removed else {
+            return false;
+        };
+        child.store.close();
+        self.keys.lock_mut().retain(|k| k != key);
+        self.revision.replace_with(|revision| revision.wrapping_add(1));
+        true
+    }
+
+    /// Returns the keys of the children, in creation order.
+    pub fn keys(&self) -> Vec<K> {
+        self.keys.get_cloned()
+    }
+
+    /// Returns the number of children.
+    pub fn len(&self) -> usize {
+        self.keys.lock_ref().len()
+    }
+
+    /// Returns true if there are no children.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a signal of the keys of the children, emitting whenever a child is created or removed.
+    pub fn keys_signal(&self) -> MutableSignalCloned<Vec<K>> {
+        self.keys.signal_cloned()
+    }
+
+    /// Returns a signal of snapshots of every child's state, keyed by child.
+    ///
+    /// A new snapshot is produced when membership changes or any child commits a state.
+    /// Like other signals, rapid changes are conflated into a single snapshot.
+    pub fn combined_signal(&self) -> impl Signal<Item = HashMap<K, S>> + Send + 'static {
+        let children = self.children.clone();
+        self.revision.signal().map(move |_| {
+            children
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .iter()
+                .map(|(key, child)| (key.clone(), child.store.get_state()))
+                .collect()
+        })
+    }
+}
+
+impl<K, S> Default for StoreMap<K, S>
+where
+    K: Eq + Hash + Clone + Send + Sync + 'static,
+    S: State,
+{
+    fn default() -> Self {
+        StoreMap::new()
+    }
+}
+
+impl<K, S: State> Drop for StoreMap<K, S> {
+    fn drop(&mut self) {
+        let children = std::mem::take(&mut *self.children.lock().unwrap_or_else(|e| e.into_inner()));
+        for child in children.into_values() {
+            child.store.close();
+        }
+    }
+}
+
+impl<K: fmt::Debug, S: State> fmt::Debug for StoreMap<K, S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StoreMap")
+            .field("keys", &*self.keys.lock_ref())
+            .finish()
+    }
+}
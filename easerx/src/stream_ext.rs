@@ -1,7 +1,12 @@
+use std::future::Future;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+use std::time::Duration;
 use futures_core::stream::Stream;
 use pin_project::pin_project;
+use tokio::task::JoinHandle;
+use tokio::time::{Instant, Sleep};
+use crate::AsyncError;
 
 /// Extension trait that provides additional utility methods for Stream types.
 ///
@@ -40,9 +45,247 @@ pub trait EaseRxStreamExt: Stream {
             test,
         }
     }
+
+    /// Creates a stream that keeps producing items for a grace period after the predicate returns true.
+    ///
+    /// This works like [`stop_if`](EaseRxStreamExt::stop_if), but instead of terminating right after
+    /// the matching item, the stream keeps yielding items until `grace` has elapsed (or the inner
+    /// stream ends). This is useful in tests to assert that no unexpected trailing emission follows
+    /// the final state. The grace timer uses `tokio::time`, so it also works under paused time.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use futures_signals::signal::SignalExt;
+    /// use easerx::EaseRxStreamExt;
+    ///
+    /// async fn example() {
+    ///     let stream = futures_signals::signal::always(0)
+    ///         .to_stream()
+    ///         .stop_if_with_grace(|&value| value > 5, Duration::from_millis(50));
+    ///
+    ///     // Items arriving within 50ms after a value greater than 5 are still produced
+    /// }
+    /// ```
+    fn stop_if_with_grace<F>(self, test: F, grace: Duration) -> StopIfWithGrace<Self, F>
+    where
+        F: FnMut(&Self::Item) -> bool,
+        Self: Sized,
+    {
+        StopIfWithGrace {
+            stream: self,
+            stopped: false,
+            test,
+            grace,
+            deadline: None,
+        }
+    }
+
+    /// Creates a stream that stops once it has produced `n` items, the `n`-th included.
+    ///
+    /// This is the count-based counterpart of [`stop_if`](EaseRxStreamExt::stop_if): the item that
+    /// reaches the limit is always emitted, and the stream ends right after it without polling the
+    /// inner stream again. That matters for a [`StateStream`](crate::StateStream), which never ends on
+    /// its own: asking for the next item after the `n`-th would wait for another state change.
+    ///
+    /// It yields the same items as `futures::StreamExt::take`, and also ends without producing
+    /// anything when `n` is `0`; it is provided so state streams can be limited without importing
+    /// `futures`, and to read alongside `stop_if` in chains.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// use easerx::EaseRxStreamExt;
+    ///
+    /// async fn example() {
+    ///     let items = futures::stream::iter(1..).stop_after(3).collect::<Vec<_>>().await;
+    ///     assert_eq!(items, vec![1, 2, 3]);
+    /// }
+    /// ```
+    fn stop_after(self, n: usize) -> StopAfter<Self>
+    where
+        Self: Sized,
+    {
+        StopAfter {
+            stream: self,
+            remaining: n,
+        }
+    }
+
+    /// Drives the stream to completion and returns the last item it produced.
+    ///
+    /// Returns `None` if the stream ended without producing any item. Streams created from a
+    /// [`StateStore`](crate::StateStore) always emit the current state first, so combined with
+    /// [`stop_if`](EaseRxStreamExt::stop_if) this resolves to the state that triggered the stop condition.
+    ///
+    /// If `futures::StreamExt` is also in scope, call this as `EaseRxStreamExt::last(stream)`
+    /// to disambiguate.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use futures_signals::signal::SignalExt;
+    /// use easerx::EaseRxStreamExt;
+    ///
+    /// async fn example() {
+    ///     let last = futures_signals::signal::always(0)
+    ///         .to_stream()
+    ///         .stop_if(|&value| value == 0)
+    ///         .last()
+    ///         .await;
+    ///
+    ///     assert_eq!(last, Some(0));
+    /// }
+    /// ```
+    fn last(self) -> Last<Self>
+    where
+        Self: Sized,
+    {
+        Last {
+            stream: self,
+            last: None,
+        }
+    }
+
+    /// Wraps every item into a [`StreamEvent::Item`] and emits a final [`StreamEvent::End`] once the stream ends.
+    ///
+    /// This is the Rx `Materialize` operator: it makes the end of the stream an observable value,
+    /// e.g. to assert in a test that a stream ends right after `stop_if` fired, or to log the
+    /// lifecycle of a stream without changing its item type. Use [`dematerialize`](EaseRxStreamExt::dematerialize)
+    /// to convert the events back.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// use easerx::{EaseRxStreamExt, StreamEvent};
+    ///
+    /// async fn example() {
+    ///     let events = futures::stream::iter([1, 2]).materialize().collect::<Vec<_>>().await;
+    ///     assert_eq!(events, vec![StreamEvent::Item(1), StreamEvent::Item(2), StreamEvent::End]);
+    /// }
+    /// ```
+    fn materialize(self) -> Materialize<Self>
+    where
+        Self: Sized,
+    {
+        Materialize {
+            stream: self,
+            ended: false,
+        }
+    }
+
+    /// Converts a stream of [`StreamEvent`]s back into its items.
+    ///
+    /// Items are yielded as `Ok`, and a [`StreamEvent::Error`] as `Err`. The stream ends after an
+    /// `Error` or an `End` event, or when the inner stream ends.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use futures::StreamExt;
+    /// use easerx::{AsyncError, EaseRxStreamExt, StreamEvent};
+    ///
+    /// async fn example() {
+    ///     let events = [StreamEvent::Item(1), StreamEvent::Error(AsyncError::error("broken")), StreamEvent::Item(2)];
+    ///     let items = futures::stream::iter(events).dematerialize().collect::<Vec<_>>().await;
+    ///     assert_eq!(items, vec![Ok(1), Err(AsyncError::error("broken"))]);
+    /// }
+    /// ```
+    fn dematerialize<T>(self) -> Dematerialize<Self>
+    where
+        Self: Stream<Item = StreamEvent<T>> + Sized,
+    {
+        Dematerialize {
+            stream: self,
+            ended: false,
+        }
+    }
 }
 impl<T: ?Sized> EaseRxStreamExt for T where T: Stream {}
 
+/// Forwards the items of `stream` to `sink`, at most once per `period`, on a spawned task.
+///
+/// This is meant for handing states over to a UI thread, e.g. through a cursive `cb_sink` or a
+/// ratatui redraw channel, without flooding it during bursts. The first item is forwarded right
+/// away and opens a window of `period`; items arriving within the window are conflated, and the
+/// latest one is forwarded when the window closes, opening the next one.
+///
+/// The task ends once the stream ends, after forwarding the item still held back. Dropping the
+/// returned handle leaves the task running; call [`JoinHandle::abort`] to stop forwarding early.
+///
+/// ## Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use easerx::forward_throttled;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let forwarding = forward_throttled(futures::stream::iter(0..100), Duration::from_millis(16), |n| {
+///         println!("render {n}");
+///     });
+///     // Renders 0, then 99 once the stream ended
+///     forwarding.await.unwrap();
+/// }
+/// ```
+///
+/// ## Panics
+///
+/// Panics if called outside of a tokio runtime.
+pub fn forward_throttled<St, F>(stream: St, period: Duration, mut sink: F) -> JoinHandle<()>
+where
+    St: Stream + Send + 'static,
+    St::Item: Send,
+    F: FnMut(St::Item) + Send + 'static,
+{
+    tokio::spawn(async move {
+        let mut stream = std::pin::pin!(stream);
+        let window = tokio::time::sleep(period);
+        tokio::pin!(window);
+        let (mut throttling, mut held) = (false, None);
+        loop {
+            tokio::select! {
+                biased;
+                item = std::future::poll_fn(|cx| stream.as_mut().poll_next(cx)) => match item {
+                    Some(item) if throttling => held = Some(item),
+                    Some(item) => {
+                        sink(item);
+                        window.as_mut().reset(Instant::now() + period);
+                        throttling = true;
+                    }
+                    None => break,
+                },
+                _ = &mut window, if throttling => match held.take() {
+                    Some(item) => {
+                        sink(item);
+                        window.as_mut().reset(Instant::now() + period);
+                    }
+                    None => throttling = false,
+                },
+            }
+        }
+        if let Some(item) = held {
+            sink(item);
+        }
+    })
+}
+
+/// A lifecycle event of a stream, produced by [`EaseRxStreamExt::materialize`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent<T> {
+    /// An item of the stream.
+    Item(T),
+
+    /// The stream failed. `materialize` never produces it, but streams built by hand can.
+    Error(AsyncError),
+
+    /// The stream ended.
+    End,
+}
+
 /// A stream that stops producing items once a predicate returns true.
 ///
 /// This stream is created by the `stop_if` method on `EaseRxStreamExt`.
@@ -87,3 +330,201 @@ where A: Stream,
         }
     }
 }
+
+
+/// A stream that stops producing items after a given number of them.
+///
+/// This stream is created by the `stop_after` method on `EaseRxStreamExt`.
+#[pin_project(project = StopAfterProj)]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct StopAfter<A> {
+    #[pin]
+    stream: A,
+    remaining: usize,
+}
+
+impl<A> Stream for StopAfter<A>
+where A: Stream {
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let StopAfterProj { stream, remaining } = self.project();
+
+        if *remaining == 0 {
+            return Poll::Ready(None);
+        }
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(value)) => {
+                *remaining -= 1;
+                Poll::Ready(Some(value))
+            },
+            Poll::Ready(None) => {
+                *remaining = 0;
+                Poll::Ready(None)
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.remaining == 0 {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.stream.size_hint();
+        let upper = upper.map_or(self.remaining, |upper| upper.min(self.remaining));
+        (lower.min(self.remaining), Some(upper))
+    }
+}
+
+/// A stream that stops producing items a grace period after a predicate returns true.
+///
+/// This stream is created by the `stop_if_with_grace` method on `EaseRxStreamExt`.
+#[pin_project(project = StopIfWithGraceProj)]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct StopIfWithGrace<A, B> {
+    #[pin]
+    stream: A,
+    stopped: bool,
+    test: B,
+    grace: Duration,
+    deadline: Option<Pin<Box<Sleep>>>,
+}
+
+impl<A, B> Stream for StopIfWithGrace<A, B>
+where A: Stream,
+      B: FnMut(&A::Item) -> bool {
+    type Item = A::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let StopIfWithGraceProj { stream, stopped, test, grace, deadline } = self.project();
+
+        if *stopped {
+            return Poll::Ready(None);
+        }
+
+        if let Some(sleep) = deadline.as_mut() {
+            if sleep.as_mut().poll(cx).is_ready() {
+                *stopped = true;
+                return Poll::Ready(None);
+            }
+        }
+
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(value)) => {
+                if deadline.is_none() && test(&value) {
+                    *deadline = Some(Box::pin(tokio::time::sleep(*grace)));
+                }
+
+                Poll::Ready(Some(value))
+            },
+            Poll::Ready(None) => {
+                *stopped = true;
+                Poll::Ready(None)
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A future that resolves to the last item of a stream.
+///
+/// This future is created by the `last` method on `EaseRxStreamExt`.
+#[pin_project(project = LastProj)]
+#[derive(Debug)]
+#[must_use = "Futures do nothing unless awaited"]
+pub struct Last<A: Stream> {
+    #[pin]
+    stream: A,
+    last: Option<A::Item>,
+}
+
+impl<A> Future for Last<A>
+where A: Stream {
+    type Output = Option<A::Item>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let LastProj { mut stream, last } = self.project();
+
+        loop {
+            match stream.as_mut().poll_next(cx) {
+                Poll::Ready(Some(value)) => *last = Some(value),
+                Poll::Ready(None) => return Poll::Ready(last.take()),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A stream that wraps the items of another stream into [`StreamEvent`]s.
+///
+/// This stream is created by the `materialize` method on `EaseRxStreamExt`.
+#[pin_project(project = MaterializeProj)]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct Materialize<A> {
+    #[pin]
+    stream: A,
+    ended: bool,
+}
+
+impl<A> Stream for Materialize<A>
+where A: Stream {
+    type Item = StreamEvent<A::Item>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let MaterializeProj { stream, ended } = self.project();
+
+        if *ended {
+            return Poll::Ready(None);
+        }
+
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(value)) => Poll::Ready(Some(StreamEvent::Item(value))),
+            Poll::Ready(None) => {
+                *ended = true;
+                Poll::Ready(Some(StreamEvent::End))
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// A stream that converts [`StreamEvent`]s back into items.
+///
+/// This stream is created by the `dematerialize` method on `EaseRxStreamExt`.
+#[pin_project(project = DematerializeProj)]
+#[derive(Debug)]
+#[must_use = "Streams do nothing unless polled"]
+pub struct Dematerialize<A> {
+    #[pin]
+    stream: A,
+    ended: bool,
+}
+
+impl<A, T> Stream for Dematerialize<A>
+where A: Stream<Item = StreamEvent<T>> {
+    type Item = Result<T, AsyncError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let DematerializeProj { stream, ended } = self.project();
+
+        if *ended {
+            return Poll::Ready(None);
+        }
+
+        match stream.poll_next(cx) {
+            Poll::Ready(Some(StreamEvent::Item(value))) => Poll::Ready(Some(Ok(value))),
+            Poll::Ready(Some(StreamEvent::Error(error))) => {
+                *ended = true;
+                Poll::Ready(Some(Err(error)))
+            },
+            Poll::Ready(Some(StreamEvent::End)) | Poll::Ready(None) => {
+                *ended = true;
+                Poll::Ready(None)
+            },
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
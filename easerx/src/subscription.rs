@@ -0,0 +1,33 @@
+use tokio::task::JoinHandle;
+
+/// A handle to a background subscription on a [`StateStore`](crate::StateStore).
+///
+/// The subscription stays active for as long as the guard is alive. Dropping the guard
+/// (or calling [`unsubscribe`](Self::unsubscribe)) stops the subscription.
+#[derive(Debug)]
+#[must_use = "The subscription is cancelled as soon as the guard is dropped"]
+pub struct SubscriptionGuard {
+    handle: JoinHandle<()>,
+}
+
+impl SubscriptionGuard {
+    pub(crate) fn new(handle: JoinHandle<()>) -> Self {
+        SubscriptionGuard { handle }
+    }
+
+    /// Stops the subscription immediately.
+    pub fn unsubscribe(self) {
+        drop(self);
+    }
+
+    /// Returns true if the subscription has stopped, e.g. because the store was dropped.
+    pub fn is_finished(&self) -> bool {
+        self.handle.is_finished()
+    }
+}
+
+impl Drop for SubscriptionGuard {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
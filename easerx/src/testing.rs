@@ -0,0 +1,347 @@
+//! Helpers for testing code built on EaseRx, including [`DeterministicStore`] for stepping
+//! through the queue of a store one message at a time.
+//!
+//! Only available with the `test-util` feature enabled.
+
+use std::fmt::Debug;
+use std::future::poll_fn;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use futures_core::Stream;
+use crate::{Async, AsyncError, State, StateEvent, StateEventStream, StateStore, StateStoreBuilder};
+
+pub use crate::state_store::stepped::{tagged, DeterministicStore, Step, StepKind};
+
+/// The default timeout of [`assert_async_flow!`](crate::assert_async_flow).
+pub const DEFAULT_FLOW_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Asserts that the field selected by `getter` goes through exactly the `expected` values.
+///
+/// The first observed value is the current value of the field; the following ones are read from
+/// [`StateStore::subscribe_all`], so no intermediate state is conflated away. Consecutive equal values
+/// are observed once, so commits that leave the field unchanged don't count as steps.
+/// Observation stops as soon as the flow diverges, once `expected.len()` values were observed,
+/// or when `timeout` elapses.
+///
+/// Prefer the [`assert_async_flow!`](crate::assert_async_flow) macro, which builds the expected
+/// values from a short step syntax.
+///
+/// ## Panics
+///
+/// Panics with the expected and observed flows if a value differs from the expected one, or if
+/// fewer values than expected were observed within `timeout`.
+pub async fn assert_async_flow<S, T, G>(
+    store: &StateStore<S>,
+    getter: G,
+    expected: Vec<Async<T>>,
+    timeout: Duration,
+) where
+    S: State,
+    T: Clone + Debug + PartialEq,
+    G: Fn(&S) -> &Async<T>,
+{
+    let mut events = store.subscribe_all();
+    let mut observed = vec![getter(&store.get_state()).clone()];
+
+    let collect = async {
+        while observed.len() < expected.len() && observed == expected[..observed.len()] {
+            let event = poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await;
+            let Some(event) = event else {
+                break;
+            };
+            if let Some(state) = event.state() {
+                let value = getter(&state);
+                if observed.last() != Some(value) {
+                    observed.push(value.clone());
+                }
+            }
+        }
+    };
+    let timed_out = tokio::time::timeout(timeout, collect).await.is_err();
+
+    if let Some(step) = expected.iter().zip(&observed).position(|(e, o)| e != o) {
+        panic!(
+            "async flow diverged at step {step}:\n  expected: {:?}\n  observed: {:?}\nexpected flow: {expected:?}\nobserved flow: {observed:?}",
+            expected[step], observed[step]
+        );
+    }
+    if observed.len() < expected.len() {
+        let reason = if timed_out {
+            format!("timed out after {timeout:?}")
+        } else {
+            "the store was dropped".to_string()
+        };
+        panic!(
+            "async flow incomplete, {reason}: observed {} of {} steps, waiting for {:?}\nexpected flow: {expected:?}\nobserved flow: {observed:?}",
+            observed.len(),
+            expected.len(),
+            expected[observed.len()]
+        );
+    }
+}
+
+/// Equality within a tolerance, for comparing the results of numeric computations.
+pub trait ApproxEq {
+    /// Returns `true` if `self` and `other` are equal within `tolerance`.
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool;
+}
+
+/// `Success` values are equal if they differ by at most `tolerance`; the other variants,
+/// including the values retained by `Loading` and `Fail`, are compared exactly.
+impl ApproxEq for Async<f64> {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        match (self, other) {
+            (Async::Success { value: a }, Async::Success { value: b }) => (a - b).abs() <= tolerance,
+            _ => self == other,
+        }
+    }
+}
+
+/// `Success` values are equal if they differ by at most `tolerance`; the other variants,
+/// including the values retained by `Loading` and `Fail`, are compared exactly.
+impl ApproxEq for Async<f32> {
+    fn approx_eq(&self, other: &Self, tolerance: f64) -> bool {
+        match (self, other) {
+            (Async::Success { value: a }, Async::Success { value: b }) => {
+                (f64::from(*a) - f64::from(*b)).abs() <= tolerance
+            }
+            _ => self == other,
+        }
+    }
+}
+
+/// Asserts that `a` and `b` are equal within `tolerance`, see [`ApproxEq`].
+///
+/// ## Panics
+///
+/// Panics with both values if they are not approximately equal.
+#[track_caller]
+pub fn assert_async_approx_eq(a: &Async<f64>, b: &Async<f64>, tolerance: f64) {
+    assert!(
+        a.approx_eq(b, tolerance),
+        "assertion `a ≈ b` failed (tolerance: {tolerance})\n  a: {a:?}\n  b: {b:?}"
+    );
+}
+
+/// One step of a [`Script`].
+pub enum ScriptStep<S> {
+    /// Replaces the whole state.
+    Emit(S),
+    /// Waits before playing the next step. Follows tokio's clock, so it completes instantly
+    /// with paused time.
+    Wait(Duration),
+    /// Derives the next state from the current one, see [`ScriptStep::emit_async`].
+    EmitAsync(Box<dyn FnOnce(S) -> S + Send>),
+}
+
+impl<S: State> ScriptStep<S> {
+    /// Writes `value` into the state with `updater`, like an execution writing its `Async` field.
+    pub fn emit_async<T, U>(updater: U, value: Async<T>) -> Self
+    where
+        T: Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Send + 'static,
+    {
+        ScriptStep::EmitAsync(Box::new(move |state| updater(state, value)))
+    }
+
+    /// Returns the steps [`execute`](StateStore::execute) commits for a computation
+    /// producing `result`: `Loading` without a value, then `result`.
+    pub fn execute<T, U>(updater: U, result: Async<T>) -> Vec<Self>
+    where
+        T: Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        vec![
+            ScriptStep::emit_async(updater.clone(), Async::loading(None)),
+            ScriptStep::emit_async(updater, result),
+        ]
+    }
+
+    /// Returns the steps [`execute_with_retain`](StateStore::execute_with_retain) commits for a
+    /// computation producing `result`: `Loading` retaining the value selected by `getter`, then
+    /// `result`, which retains that value too if it is a `Fail`.
+    pub fn execute_with_retain<T, G, U>(getter: G, updater: U, result: Async<T>) -> Vec<Self>
+    where
+        T: Clone + Send + 'static,
+        G: Fn(&S) -> &Async<T> + Clone + Send + 'static,
+        U: FnOnce(S, Async<T>) -> S + Clone + Send + 'static,
+    {
+        let loading_getter = getter.clone();
+        let loading_updater = updater.clone();
+        vec![
+            ScriptStep::EmitAsync(Box::new(move |state| {
+                let retained = loading_getter(&state).value_ref_clone();
+                loading_updater(state, Async::loading(retained))
+            })),
+            ScriptStep::EmitAsync(Box::new(move |state| {
+                let retained = getter(&state).value_ref_clone();
+                updater(state, result.set_retain_value(retained))
+            })),
+        ]
+    }
+}
+
+impl<S> Debug for ScriptStep<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScriptStep::Emit(_) => f.write_str("Emit(..)"),
+            ScriptStep::Wait(duration) => f.debug_tuple("Wait").field(duration).finish(),
+            ScriptStep::EmitAsync(_) => f.write_str("EmitAsync(..)"),
+        }
+    }
+}
+
+/// A scripted sequence of states played into a store, so view tests can replay exactly what a
+/// real execution emits at controlled points in time.
+///
+/// Each emitted state is committed before the next step is played, so
+/// [`subscribe_all`](StateStore::subscribe_all) observes every one of them.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::time::Duration;
+/// use easerx::testing::ScriptStep;
+/// use easerx::{Async, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Profile {
+///     name: Async<String>,
+/// }
+/// impl State for Profile {}
+/// #[tokio::main(flavor = "current_thread", start_paused = true)]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let store = StateStore::new(Profile { name: Async::success("Ada".to_string()) });
+///     let mut steps = vec![ScriptStep::Wait(Duration::from_millis(100))];
+///     steps.extend(ScriptStep::execute_with_retain(
+///         |state: &Profile| &state.name,
+///         |_, name| Profile { name },
+///         Async::success("Grace".to_string()),
+///     ));
+///     store.script(steps).play().await?;
+///     assert_eq!(store.get_state().name, Async::success("Grace".to_string()));
+///     Ok(())
+/// }
+/// ```
+#[must_use = "A script does nothing until it is played"]
+pub struct Script<S: State> {
+    store: StateStore<S>,
+    steps: Vec<ScriptStep<S>>,
+}
+
+impl<S: State> Script<S> {
+    /// Creates a script playing `steps` into `store`.
+    pub fn new(store: &StateStore<S>, steps: Vec<ScriptStep<S>>) -> Self {
+        Script {
+            store: store.clone(),
+            steps,
+        }
+    }
+
+    /// Appends more steps to the script.
+    pub fn then(mut self, steps: impl IntoIterator<Item = ScriptStep<S>>) -> Self {
+        self.steps.extend(steps);
+        self
+    }
+
+    /// Plays every step in order and resolves once the last one was committed.
+    ///
+    /// ## Errors
+    ///
+    /// Returns an `AsyncError` if the store was closed during playback.
+    pub async fn play(self) -> Result<(), AsyncError> {
+        for step in self.steps {
+            match step {
+                ScriptStep::Emit(state) => self.store.set_state(move |_| state)?,
+                ScriptStep::Wait(duration) => {
+                    tokio::time::sleep(duration).await;
+                    continue;
+                }
+                ScriptStep::EmitAsync(update) => self.store.set_state(update)?,
+            }
+            self.store.await_state().await?;
+        }
+        Ok(())
+    }
+}
+
+impl<S: State> Debug for Script<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Script").field("steps", &self.steps).finish_non_exhaustive()
+    }
+}
+
+impl<S: State> StateStore<S> {
+    /// Creates a [`Script`] playing `steps` into this store.
+    ///
+    /// Only available with the `test-util` feature enabled.
+    pub fn script(&self, steps: Vec<ScriptStep<S>>) -> Script<S> {
+        Script::new(self, steps)
+    }
+}
+
+/// The broadcast capacity set by [`StateStoreBuilder::strict_observation`], large enough that a
+/// test only overflows it when its observer stalls.
+pub const STRICT_BROADCAST_CAPACITY: usize = 4096;
+
+/// A stream of every committed state that panics instead of skipping states, created by
+/// [`StateStore::strict_stream`].
+#[must_use = "Streams do nothing unless polled"]
+pub struct StrictStateStream<S> {
+    events: StateEventStream<S>,
+}
+
+impl<S: Clone + Send + 'static> Stream for StrictStateStream<S> {
+    type Item = S;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.events).poll_next(cx) {
+            Poll::Ready(Some(StateEvent::State(state))) => Poll::Ready(Some(state)),
+            Poll::Ready(Some(StateEvent::Lagged(skipped))) => panic!(
+                "strict stream skipped {skipped} state(s): the observer fell more than the broadcast \
+                 capacity behind; raise it with `StateStoreBuilder::strict_observation` or \
+                 `broadcast_capacity`, or poll the stream while the store commits"
+            ),
+            Poll::Ready(None) => Poll::Ready(None),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+impl<S> Debug for StrictStateStream<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StrictStateStream").finish()
+    }
+}
+
+impl<S: State> StateStore<S> {
+    /// Subscribes to every committed state like [`subscribe_all`](Self::subscribe_all), but panics
+    /// if states were skipped, so a test observing too slowly fails with a clear message instead of
+    /// asserting on a hole in the sequence.
+    ///
+    /// Build the store with [`StateStoreBuilder::strict_observation`] for a capacity that only
+    /// overflows when the observer stalls.
+    ///
+    /// Only available with the `test-util` feature enabled.
+    ///
+    /// ## Panics
+    ///
+    /// The stream panics when polled after the subscriber fell more than the broadcast capacity behind.
+    pub fn strict_stream(&self) -> StrictStateStream<S> {
+        StrictStateStream {
+            events: self.subscribe_all(),
+        }
+    }
+}
+
+impl<S: State> StateStoreBuilder<S> {
+    /// Raises the broadcast capacity to at least [`STRICT_BROADCAST_CAPACITY`], for tests observing
+    /// the store with [`strict_stream`](StateStore::strict_stream).
+    ///
+    /// Only available with the `test-util` feature enabled.
+    pub fn strict_observation(mut self) -> Self {
+        self.broadcast_capacity = self.broadcast_capacity.max(STRICT_BROADCAST_CAPACITY);
+        self
+    }
+}
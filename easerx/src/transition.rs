@@ -0,0 +1,157 @@
+use std::panic::Location;
+#[cfg(feature = "debug-transitions")]
+use std::collections::VecDeque;
+#[cfg(feature = "debug-transitions")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "debug-transitions")]
+use std::time::SystemTime;
+
+/// The default number of transitions kept by [`StateStore::recent_transitions`](crate::StateStore::recent_transitions).
+#[cfg(feature = "debug-transitions")]
+pub const DEFAULT_TRANSITION_HISTORY: usize = 32;
+
+/// What queued the reducer behind a state transition, see [`TransitionInfo`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum TransitionOrigin {
+    /// A reducer queued with `set_state` or one of its variants, such as `compute`.
+    SetState,
+
+    /// A reducer queued with `priority_set_state`.
+    PrioritySetState,
+
+    /// The `Loading` transition written when an execution starts.
+    ExecuteLoading,
+
+    /// The result written when an execution completes, fails or is cancelled.
+    ExecuteResult,
+
+    /// The result of `update_async`, including the state restored by `with_persistence`.
+    UpdateAsync,
+}
+
+/// The origin of a queued reducer and the call site that queued it.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(not(feature = "debug-transitions"), allow(dead_code))]
+pub(crate) struct Origin {
+    kind: TransitionOrigin,
+    location: Option<&'static Location<'static>>,
+}
+
+impl Origin {
+    /// Captures the caller of the enclosing `#[track_caller]` method.
+    #[track_caller]
+    pub(crate) fn here(kind: TransitionOrigin) -> Self {
+        Origin {
+            kind,
+            location: Some(Location::caller()),
+        }
+    }
+
+    /// An origin without a call site, for methods that can't capture one, like `async fn`s.
+    pub(crate) fn unlocated(kind: TransitionOrigin) -> Self {
+        Origin { kind, location: None }
+    }
+
+    /// The same call site with another origin.
+    #[cfg(feature = "execute")]
+    pub(crate) fn with_kind(self, kind: TransitionOrigin) -> Self {
+        Origin { kind, ..self }
+    }
+}
+
+/// A committed state transition, as reported by
+/// [`StateStore::recent_transitions`](crate::StateStore::recent_transitions).
+///
+/// Only available with the `debug-transitions` feature enabled.
+#[cfg(feature = "debug-transitions")]
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TransitionInfo {
+    origin: TransitionOrigin,
+    location: Option<&'static Location<'static>>,
+    at: SystemTime,
+    version: u64,
+}
+
+#[cfg(feature = "debug-transitions")]
+impl TransitionInfo {
+    /// Returns what queued the reducer.
+    pub fn origin(&self) -> TransitionOrigin {
+        self.origin
+    }
+
+    /// Returns the call site of the public method that queued the reducer, e.g. the `set_state` or
+    /// `execute` call. `None` for `update_async`, whose call site can't be captured.
+    pub fn location(&self) -> Option<&'static Location<'static>> {
+        self.location
+    }
+
+    /// Returns when the transition was committed.
+    pub fn at(&self) -> SystemTime {
+        self.at
+    }
+
+    /// Returns the version the transition committed, see [`StateStore::version`](crate::StateStore::version).
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+}
+
+/// A ring buffer of the last committed transitions of a store.
+///
+/// A reducer stages its origin when it produces a new state, and the commit that follows records it
+/// with the new version. Both run on the store's queue, one right after the other.
+#[cfg(feature = "debug-transitions")]
+#[derive(Debug, Clone)]
+pub(crate) struct TransitionLog {
+    inner: Arc<Mutex<LogEntries>>,
+}
+
+#[cfg(feature = "debug-transitions")]
+#[derive(Debug)]
+struct LogEntries {
+    capacity: usize,
+    staged: Option<Origin>,
+    entries: VecDeque<TransitionInfo>,
+}
+
+#[cfg(feature = "debug-transitions")]
+impl TransitionLog {
+    pub(crate) fn new(capacity: usize) -> Self {
+        TransitionLog {
+            inner: Arc::new(Mutex::new(LogEntries {
+                capacity,
+                staged: None,
+                entries: VecDeque::with_capacity(capacity),
+            })),
+        }
+    }
+
+    pub(crate) fn stage(&self, origin: Origin) {
+        self.inner.lock().unwrap_or_else(|e| e.into_inner()).staged = Some(origin);
+    }
+
+    pub(crate) fn commit(&self, version: u64) {
+        let mut log = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(origin) = log.staged.take() else {
+            return;
+        };
+        if log.capacity == 0 {
+            return;
+        }
+        if log.entries.len() == log.capacity {
+            log.entries.pop_front();
+        }
+        log.entries.push_back(TransitionInfo {
+            origin: origin.kind,
+            location: origin.location,
+            at: SystemTime::now(),
+            version,
+        });
+    }
+
+    pub(crate) fn recent(&self) -> Vec<TransitionInfo> {
+        let log = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        log.entries.iter().cloned().collect()
+    }
+}
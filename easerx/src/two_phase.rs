@@ -0,0 +1,157 @@
+use std::any::Any;
+use std::future::Future;
+use std::sync::Arc;
+use futures_core::future::BoxFuture;
+use crate::{AsyncError, State, StateStore, WaitError};
+
+/// A type-erased view of a [`StateStore`], so stores of different state types can be handled together.
+///
+/// Every `StateStore<S>` implements this trait. Use `downcast_ref` on a `dyn AnyStateStore`
+/// to get the typed store back, e.g. inside the callbacks of [`two_phase_commit`].
+pub trait AnyStateStore: Send + Sync {
+    /// Returns the type name of the state held by the store, for diagnostics.
+    fn state_type_name(&self) -> &'static str;
+
+    /// Returns the current state version, see [`StateStore::version`].
+    fn version(&self) -> u64;
+
+    /// Returns `true` once the store was closed, see [`StateStore::close`].
+    fn is_closed(&self) -> bool;
+
+    /// Resolves once every update queued so far has been applied, see [`StateStore::await_state`].
+    fn flush(&self) -> BoxFuture<'_, Result<(), WaitError>>;
+
+    /// Returns the store as [`Any`] for downcasting.
+    fn as_any(&self) -> &dyn Any;
+}
+
+impl<S: State> AnyStateStore for StateStore<S> {
+    fn state_type_name(&self) -> &'static str {
+        std::any::type_name::<S>()
+    }
+
+    fn version(&self) -> u64 {
+        StateStore::version(self)
+    }
+
+    fn is_closed(&self) -> bool {
+        StateStore::is_closed(self)
+    }
+
+    fn flush(&self) -> BoxFuture<'_, Result<(), WaitError>> {
+        Box::pin(async move { self.await_state().await.map(|_| ()) })
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+impl dyn AnyStateStore + '_ {
+    /// Returns the typed store if it holds a state of type `S`.
+    pub fn downcast_ref<S: State>(&self) -> Option<&StateStore<S>> {
+        self.as_any().downcast_ref::<StateStore<S>>()
+    }
+}
+
+/// Coordinates an update across several stores: either every store commits, or none does.
+///
+/// `prepare` is called on each store in order and should validate and stage the update without
+/// changing the state, e.g. by checking the current state of the store. If it fails for one store,
+/// `rollback` is called on the stores already prepared, in reverse order, and the error is returned;
+/// `commit` is never called. Once every store is prepared, `commit` is called on each store in order
+/// and the returned future resolves when all of them have applied their queued updates.
+///
+/// This is an in-process pattern, not a distributed transaction: the stores keep processing other
+/// updates while the transaction runs, and an error returned by `commit` is reported without
+/// undoing the stores committed before it.
+///
+/// ## Examples
+///
+/// ```rust
+/// use std::sync::Arc;
+/// use easerx::{two_phase_commit, AnyStateStore, AsyncError, State, StateStore};
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct User { name: String }
+/// impl State for User {}
+///
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Auth { token: Option<String> }
+/// impl State for Auth {}
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let user = StateStore::new(User { name: "guest".to_string() });
+///     let auth = StateStore::new(Auth { token: None });
+///     let stores: Vec<Arc<dyn AnyStateStore>> = vec![Arc::new(user.clone()), Arc::new(auth.clone())];
+///
+///     two_phase_commit(
+///         stores,
+///         |store| match store.downcast_ref::<Auth>() {
+///             Some(auth) if auth.get_state().token.is_some() => Err(AsyncError::error("already signed in")),
+///             _ => Ok(()),
+///         },
+///         |store| {
+///             if let Some(user) = store.downcast_ref::<User>() {
+///                 user.set_state(|_| User { name: "alice".to_string() })?;
+///             } else if let Some(auth) = store.downcast_ref::<Auth>() {
+///                 auth.set_state(|_| Auth { token: Some("secret".to_string()) })?;
+///             }
+///             Ok(())
+///         },
+///         |_| {},
+///     )
+///     .await?;
+///
+///     assert_eq!(user.get_state().name, "alice");
+///     assert!(auth.get_state().token.is_some());
+///     Ok(())
+/// }
+/// ```
+///
+/// ## Errors
+///
+/// Returns the first error returned by `prepare` or `commit`, or an `AsyncError` if a store
+/// could not apply its queued updates.
+pub fn two_phase_commit<F, G, H>(
+    stores: Vec<Arc<dyn AnyStateStore>>,
+    mut prepare: F,
+    mut commit: G,
+    mut rollback: H,
+) -> impl Future<Output = Result<(), AsyncError>>
+where
+    F: FnMut(&dyn AnyStateStore) -> Result<(), AsyncError>,
+    G: FnMut(&dyn AnyStateStore) -> Result<(), AsyncError>,
+    H: FnMut(&dyn AnyStateStore),
+{
+    let outcome = prepare_all(&stores, &mut prepare, &mut rollback)
+        .and_then(|()| stores.iter().try_for_each(|store| commit(store.as_ref())));
+    async move {
+        outcome?;
+        for store in &stores {
+            store.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+fn prepare_all<F, H>(
+    stores: &[Arc<dyn AnyStateStore>],
+    prepare: &mut F,
+    rollback: &mut H,
+) -> Result<(), AsyncError>
+where
+    F: FnMut(&dyn AnyStateStore) -> Result<(), AsyncError>,
+    H: FnMut(&dyn AnyStateStore),
+{
+    for (prepared, store) in stores.iter().enumerate() {
+        if let Err(error) = prepare(store.as_ref()) {
+            for store in stores[..prepared].iter().rev() {
+                rollback(store.as_ref());
+            }
+            return Err(error);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,158 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use futures::channel::mpsc;
+use crate::unit_tests::TestState;
+use crate::{ApplyMode, AsyncError, StateSnapshot, StateStore};
+
+fn counted(count: i32) -> TestState {
+    TestState::default().set_count(count)
+}
+
+/// Waits until the store has committed the state with `count`.
+async fn applied(store: &StateStore<TestState>, count: i32) -> Result<(), AsyncError> {
+    while store.await_state().await?.count != count {
+        tokio::task::yield_now().await;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_everything_commits_each_state() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let (tx, rx) = mpsc::unbounded();
+    let handle = store.apply_from(rx, ApplyMode::Everything);
+    for count in 1..=5 {
+        tx.unbounded_send(counted(count)).unwrap();
+    }
+    applied(&store, 5).await?;
+    for count in 6..=10 {
+        tx.unbounded_send(counted(count)).unwrap();
+    }
+    drop(tx);
+    handle.await.unwrap();
+
+    assert_eq!(store.await_state().await?.count, 10);
+    assert_eq!(store.version(), 10);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_latest_only_coalesces_bursts() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let (tx, rx) = mpsc::unbounded();
+    // The first burst is waiting before the task reads anything
+    for count in 1..=5 {
+        tx.unbounded_send(counted(count)).unwrap();
+    }
+    let handle = store.apply_from(rx, ApplyMode::LatestOnly);
+    applied(&store, 5).await?;
+    assert_eq!(store.version(), 1);
+
+    for count in 6..=10 {
+        tx.unbounded_send(counted(count)).unwrap();
+    }
+    drop(tx);
+    handle.await.unwrap();
+
+    assert_eq!(store.await_state().await?.count, 10);
+    assert_eq!(store.version(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_applied_states_and_local_updates_follow_queue_order() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let (tx, rx) = mpsc::unbounded();
+    tx.unbounded_send(counted(10)).unwrap();
+    let handle = store.apply_from(rx, ApplyMode::Everything);
+    applied(&store, 10).await?;
+
+    // A local reducer queued after a received state builds on it...
+    store.set_state(|state| state.add_count(1))?;
+    assert_eq!(store.await_state().await?.count, 11);
+    // ...and a received state queued after a local reducer replaces its result
+    tx.unbounded_send(counted(20)).unwrap();
+    drop(tx);
+    handle.await.unwrap();
+    assert_eq!(store.await_state().await?.count, 20);
+    Ok(())
+}
+
+fn merge_counts(local: TestState, remote: TestState) -> TestState {
+    let count = local.count + remote.count;
+    local.set_count(count)
+}
+
+#[tokio::test]
+async fn test_apply_versioned_fast_forwards_current_snapshot() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.set_count(1))?;
+    store.await_state().await?;
+    let base = store.snapshot();
+    assert_eq!(base.version, 1);
+
+    let (tx, rx) = mpsc::unbounded();
+    tx.unbounded_send(StateSnapshot { version: base.version, state: counted(5) }).unwrap();
+    drop(tx);
+    store
+        .apply_versioned_from(rx, ApplyMode::Everything, |_, _| panic!("no conflict expected"))
+        .await
+        .unwrap();
+
+    assert_eq!(store.snapshot(), StateSnapshot { version: 2, state: counted(5) });
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_versioned_merges_outdated_snapshot() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let base = store.snapshot();
+    // A local edit lands after the remote read the base state
+    store.set_state(|state| state.set_count(1))?;
+
+    let (tx, rx) = mpsc::unbounded();
+    tx.unbounded_send(StateSnapshot { version: base.version, state: counted(10) }).unwrap();
+    drop(tx);
+    let conflicts = Arc::new(AtomicUsize::new(0));
+    let counter = conflicts.clone();
+    store
+        .apply_versioned_from(rx, ApplyMode::Everything, move |local, remote| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            merge_counts(local, remote)
+        })
+        .await
+        .unwrap();
+
+    assert_eq!(conflicts.load(Ordering::SeqCst), 1);
+    assert_eq!(store.snapshot(), StateSnapshot { version: 2, state: counted(11) });
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_apply_versioned_reapply_is_idempotent() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let snapshot = StateSnapshot { version: store.version(), state: counted(3) };
+
+    let (tx, rx) = mpsc::unbounded();
+    for _ in 0..3 {
+        tx.unbounded_send(snapshot.clone()).unwrap();
+    }
+    drop(tx);
+    store
+        .apply_versioned_from(rx, ApplyMode::Everything, merge_counts)
+        .await
+        .unwrap();
+
+    // The first delivery fast-forwards, the others find the state already applied
+    assert_eq!(store.snapshot(), StateSnapshot { version: 1, state: counted(3) });
+    Ok(())
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_snapshot_serializes_version() {
+    let snapshot = StateSnapshot { version: 7, state: "draft".to_string() };
+    let json = serde_json::to_string(&snapshot).unwrap();
+    assert_eq!(json, r#"{"version":7,"state":"draft"}"#);
+    assert_eq!(serde_json::from_str::<StateSnapshot<String>>(&json).unwrap(), snapshot);
+}
@@ -0,0 +1,46 @@
+use crate::testing::{assert_async_approx_eq, ApproxEq};
+use crate::{Async, AsyncError};
+
+#[test]
+fn test_approx_eq_uninitialized() {
+    assert!(Async::<f64>::Uninitialized.approx_eq(&Async::Uninitialized, 0.1));
+    assert!(!Async::<f64>::Uninitialized.approx_eq(&Async::success(0.0), 0.1));
+}
+
+#[test]
+fn test_approx_eq_loading_compares_exactly() {
+    assert!(Async::<f64>::loading(None).approx_eq(&Async::loading(None), 0.1));
+    assert!(Async::loading(Some(1.0)).approx_eq(&Async::loading(Some(1.0)), 0.1));
+    assert!(!Async::loading(Some(1.0)).approx_eq(&Async::loading(Some(1.05)), 0.1));
+}
+
+#[test]
+fn test_approx_eq_success_within_tolerance() {
+    assert!(Async::success(1.0).approx_eq(&Async::success(1.0 + 1e-9), 1e-6));
+    assert!(Async::success(1.0).approx_eq(&Async::success(0.95), 0.1));
+    assert!(!Async::success(1.0).approx_eq(&Async::success(1.2), 0.1));
+    assert!(!Async::success(f64::NAN).approx_eq(&Async::success(f64::NAN), 0.1));
+
+    assert!(Async::success(0.1_f32 + 0.2).approx_eq(&Async::success(0.3_f32), 1e-6));
+    assert!(!Async::success(1.0_f32).approx_eq(&Async::success(1.5_f32), 0.1));
+}
+
+#[test]
+fn test_approx_eq_fail_compares_exactly() {
+    let error = AsyncError::error("diverged");
+    assert!(Async::<f64>::fail(error.clone(), Some(2.0)).approx_eq(&Async::fail(error.clone(), Some(2.0)), 0.1));
+    assert!(!Async::<f64>::fail(error.clone(), Some(2.0)).approx_eq(&Async::fail(error.clone(), Some(2.01)), 0.1));
+    assert!(!Async::<f64>::fail(error, None).approx_eq(&Async::fail_with_timeout(None), 0.1));
+    assert!(!Async::<f32>::fail_with_cancelled(None).approx_eq(&Async::success(0.0), 0.1));
+}
+
+#[test]
+fn test_assert_async_approx_eq() {
+    assert_async_approx_eq(&Async::success(1.0 / 3.0), &Async::success(0.33333), 1e-5);
+}
+
+#[test]
+#[should_panic(expected = "assertion `a ≈ b` failed")]
+fn test_assert_async_approx_eq_panics() {
+    assert_async_approx_eq(&Async::success(1.0), &Async::loading(Some(1.0)), 0.1);
+}
@@ -0,0 +1,197 @@
+use crate::{ArcAsync, Async};
+#[cfg(feature = "execute")]
+use crate::{AsyncError, State, StateStore};
+#[cfg(feature = "execute")]
+use futures::StreamExt;
+use std::sync::Arc;
+#[cfg(feature = "execute")]
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "execute")]
+#[derive(Clone, Debug, PartialEq, Default)]
+struct BlobState {
+    blob: ArcAsync<Vec<u8>>,
+}
+
+#[cfg(feature = "execute")]
+impl State for BlobState {}
+
+#[cfg(feature = "execute")]
+impl BlobState {
+    fn set_blob(self, blob: ArcAsync<Vec<u8>>) -> Self {
+        Self { blob }
+    }
+}
+
+#[test]
+fn test_map_retained_to_arc() {
+    assert_eq!(Async::<i32>::Uninitialized.map_retained_to_arc(), Async::Uninitialized);
+    assert_eq!(Async::success(1).map_retained_to_arc(), ArcAsync::success_arc(1));
+    assert_eq!(Async::loading(Some(2)).map_retained_to_arc(), ArcAsync::loading_arc(Some(2)));
+    assert_eq!(
+        Async::fail_with_cancelled(Some(3)).map_retained_to_arc(),
+        Async::fail_with_cancelled(Some(Arc::new(3)))
+    );
+}
+
+#[cfg(feature = "execute")]
+// Collects every committed blob state until the execution completes
+async fn blob_states(store: &StateStore<BlobState>) -> Vec<ArcAsync<Vec<u8>>> {
+    store
+        .subscribe_all()
+        .filter_map(|event| async move { event.state().map(|state| state.blob.clone()) })
+        .take_while(|blob| {
+            let loading = blob.is_loading();
+            async move { loading }
+        })
+        .collect()
+        .await
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_with_retain_preserves_arc_identity() -> Result<(), AsyncError> {
+    let original = Arc::new(vec![7u8; 1024]);
+    let store = StateStore::new(BlobState {
+        blob: Async::success(original.clone()),
+    });
+
+    let states = tokio::spawn({
+        let store = store.clone();
+        async move { blob_states(&store).await }
+    });
+    tokio::task::yield_now().await;
+
+    store
+        .execute_with_retain(
+            || Arc::new(vec![8u8; 1024]),
+            |state| &state.blob,
+            BlobState::set_blob,
+        )
+        .await
+        .unwrap()?;
+
+    let states = states.await.unwrap();
+    let retained = states[0].value_ref().expect("loading retains the previous value");
+    assert!(Arc::ptr_eq(retained, &original));
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_cancelled_retain_preserves_arc_identity() -> Result<(), AsyncError> {
+    let original = Arc::new(vec![7u8; 1024]);
+    let store = StateStore::new(BlobState {
+        blob: Async::success(original.clone()),
+    });
+
+    let token = CancellationToken::new();
+    token.cancel();
+    store
+        .async_execute_cancellable_with_retain(
+            token,
+            |_| async { Arc::new(vec![8u8; 1024]) },
+            |state| &state.blob,
+            BlobState::set_blob,
+        )
+        .await
+        .unwrap()?;
+
+    let state = store.await_state().await?;
+    assert!(state.blob.is_fail_with_canceled());
+    assert!(Arc::ptr_eq(state.blob.value_ref().unwrap(), &original));
+    Ok(())
+}
+
+/// Owns its id like a handle would, and deliberately isn't `Clone`
+#[derive(Debug, PartialEq)]
+struct Resource {
+    id: u32,
+}
+
+#[cfg(feature = "execute")]
+#[derive(Clone, Debug, Default)]
+struct ResourceState {
+    resource: ArcAsync<Resource>,
+}
+
+#[cfg(feature = "execute")]
+impl State for ResourceState {}
+
+#[cfg(feature = "execute")]
+impl ResourceState {
+    fn set_resource(self, resource: ArcAsync<Resource>) -> Self {
+        Self { resource }
+    }
+}
+
+#[test]
+fn test_with_reads_through_arc() {
+    assert_eq!(ArcAsync::success_arc(Resource { id: 1 }).with(|resource| resource.id), Some(1));
+    assert_eq!(ArcAsync::loading_arc(Some(Resource { id: 2 })).with(|resource| resource.id), Some(2));
+    assert_eq!(ArcAsync::<Resource>::loading_arc(None).with(|resource| resource.id), None);
+    assert_eq!(ArcAsync::<Resource>::Uninitialized.with(|resource| resource.id), None);
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_arc_stores_non_clone_result() -> Result<(), AsyncError> {
+    let store = StateStore::new(ResourceState::default());
+    store
+        .execute_arc(|| Resource { id: 7 }, ResourceState::set_resource)
+        .await
+        .unwrap()?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.resource.with(|resource| resource.id), Some(7));
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_arc_converts_errors() -> Result<(), AsyncError> {
+    let store = StateStore::new(ResourceState::default());
+    store
+        .execute_arc(|| Err::<Resource, _>("no such device"), ResourceState::set_resource)
+        .await
+        .unwrap()?;
+    assert_eq!(
+        store.await_state().await?.resource,
+        Async::fail_with_message("no such device", None)
+    );
+
+    store
+        .async_execute_arc(async { None::<Resource> }, ResourceState::set_resource)
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.resource.is_fail_with_none());
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_arc_with_retain_keeps_previous_allocation() -> Result<(), AsyncError> {
+    let original = Arc::new(Resource { id: 1 });
+    let store = StateStore::new(ResourceState {
+        resource: Async::success(original.clone()),
+    });
+
+    store
+        .async_execute_arc_with_retain(
+            async { Err::<Resource, _>("busy") },
+            |state| &state.resource,
+            ResourceState::set_resource,
+        )
+        .await
+        .unwrap()?;
+    let state = store.await_state().await?;
+    assert!(state.resource.is_fail());
+    assert!(Arc::ptr_eq(state.resource.value_ref().unwrap(), &original));
+
+    store
+        .execute_arc_with_retain(|| Resource { id: 2 }, |state| &state.resource, ResourceState::set_resource)
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.resource.with(|resource| resource.id), Some(2));
+    Ok(())
+}
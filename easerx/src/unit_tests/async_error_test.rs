@@ -1,4 +1,4 @@
-use crate::AsyncError;
+use crate::{AsyncError, ErrorClass, TimeoutSource};
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
@@ -37,7 +37,7 @@ fn test_async_error_serde() {
 
     let error = AsyncError::error("message");
     let serialized = serde_json::to_string(&error).unwrap();
-    assert_eq!(serialized, r#"{"error":"message"}"#);
+    assert_eq!(serialized, r#"{"error":{"message":"message","context":{}}}"#);
 
     let deserialized: AsyncError = serde_json::from_str(&serialized).unwrap();
     assert_eq!(deserialized, error);
@@ -144,3 +144,140 @@ fn test_async_error_hash() {
     assert_ne!(err1_hash, cancelled1_hash);
     assert_ne!(err1_hash, timeout1_hash);
 }
+
+#[test]
+fn test_async_error_timeout_source() {
+    assert!(AsyncError::Timeout.is_timeout());
+    assert!(AsyncError::Timeout.is_store_timeout());
+    assert!(!AsyncError::Timeout.is_upstream_timeout());
+    assert_eq!(AsyncError::Timeout.timeout_source(), Some(TimeoutSource::Store));
+
+    assert!(AsyncError::UpstreamTimeout.is_timeout());
+    assert!(AsyncError::UpstreamTimeout.is_upstream_timeout());
+    assert!(!AsyncError::UpstreamTimeout.is_store_timeout());
+    assert_eq!(
+        AsyncError::UpstreamTimeout.timeout_source(),
+        Some(TimeoutSource::Computation)
+    );
+
+    assert_eq!(AsyncError::Cancelled.timeout_source(), None);
+    assert!(!AsyncError::error("timeout").is_timeout());
+}
+
+#[test]
+fn test_async_error_is_retryable() {
+    assert!(AsyncError::Timeout.is_retryable());
+    assert!(AsyncError::UpstreamTimeout.is_retryable());
+
+    assert!(!AsyncError::Cancelled.is_retryable());
+    assert!(!AsyncError::None.is_retryable());
+    assert!(!AsyncError::error("failed").is_retryable());
+    assert!(!AsyncError::Panic { message: "boom".to_string() }.is_retryable());
+}
+
+#[test]
+fn test_async_error_with_context() {
+    let error = AsyncError::error_with_context(
+        "request failed",
+        [("request_id", "42"), ("endpoint", "/users")],
+    );
+    assert!(error.is_error());
+    assert_eq!(error.to_string(), "request failed");
+    assert_eq!(error.context_value("request_id"), Some("42"));
+    assert_eq!(error.context_value("endpoint"), Some("/users"));
+    assert_eq!(error.context_value("user_id"), None);
+
+    assert_eq!(
+        format!("{error:?}"),
+        r#"Error { message: "request failed", context: {"endpoint": "/users", "request_id": "42"} }"#
+    );
+    assert_eq!(AsyncError::error("request failed").context_value("request_id"), None);
+    assert_eq!(AsyncError::Timeout.context_value("request_id"), None);
+    // The context is part of the error's identity
+    assert_ne!(error, AsyncError::error("request failed"));
+    assert_eq!(
+        error,
+        AsyncError::error_with_context("request failed", [("endpoint", "/users"), ("request_id", "42")])
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_async_error_with_context_serde() {
+    let error = AsyncError::error_with_context("request failed", [("request_id", "42"), ("endpoint", "/users")]);
+    let serialized = serde_json::to_string(&error).unwrap();
+    assert_eq!(
+        serialized,
+        r#"{"error":{"message":"request failed","context":{"endpoint":"/users","request_id":"42"}}}"#
+    );
+    let deserialized: AsyncError = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, error);
+    assert_eq!(deserialized.context_value("endpoint"), Some("/users"));
+
+    // A missing context deserializes as an empty one
+    let deserialized: AsyncError = serde_json::from_str(r#"{"error":{"message":"request failed"}}"#).unwrap();
+    assert_eq!(deserialized, AsyncError::error("request failed"));
+}
+
+#[test]
+fn test_async_error_from_io_error() {
+    let error = AsyncError::from(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset by peer"));
+    assert!(error.is_error());
+    assert_eq!(error.to_string(), "reset by peer");
+    assert_eq!(error.io_error_kind(), Some(std::io::ErrorKind::ConnectionReset));
+    assert_eq!(error.error_class(), Some(ErrorClass::Transient));
+    assert!(error.is_retryable());
+
+    let error = AsyncError::from(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+    assert_eq!(error.error_class(), Some(ErrorClass::Permanent));
+    assert!(!error.is_retryable());
+
+    // Kinds that don't tell are left unclassified
+    let error = AsyncError::from(std::io::Error::other("disk on fire"));
+    assert_eq!(error.io_error_kind(), Some(std::io::ErrorKind::Other));
+    assert_eq!(error.error_class(), None);
+    assert_eq!(AsyncError::error("failed").io_error_kind(), None);
+}
+
+#[test]
+fn test_async_error_with_class() {
+    let cases = [
+        (ErrorClass::Transient, true),
+        (ErrorClass::RateLimited, true),
+        (ErrorClass::Permanent, false),
+    ];
+    for (class, retryable) in cases {
+        let error = AsyncError::with_class(class, "request failed");
+        assert_eq!(error.error_class(), Some(class));
+        assert_eq!(error.is_retryable(), retryable, "{class:?}");
+        assert_eq!(error.to_string(), "request failed");
+        assert_ne!(error, AsyncError::error("request failed"));
+    }
+    assert_eq!(
+        format!("{:?}", AsyncError::with_class(ErrorClass::RateLimited, "slow down")),
+        r#"Error { message: "slow down", class: RateLimited }"#
+    );
+    assert_eq!(AsyncError::Timeout.error_class(), Some(ErrorClass::Transient));
+    assert_eq!(AsyncError::Cancelled.error_class(), None);
+    assert_eq!(AsyncError::error("failed").error_class(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_async_error_with_class_serde() {
+    let error = AsyncError::with_class(ErrorClass::RateLimited, "slow down");
+    let serialized = serde_json::to_string(&error).unwrap();
+    assert_eq!(serialized, r#"{"error":{"message":"slow down","context":{},"class":"rateLimited"}}"#);
+    assert_eq!(serde_json::from_str::<AsyncError>(&serialized).unwrap(), error);
+
+    // Unclassified errors keep their format
+    assert_eq!(
+        serde_json::to_string(&AsyncError::error("failed")).unwrap(),
+        r#"{"error":{"message":"failed","context":{}}}"#
+    );
+    // The I/O error kind is not serialized, the class derived from it is
+    let error = AsyncError::from(std::io::Error::from(std::io::ErrorKind::TimedOut));
+    let deserialized: AsyncError = serde_json::from_str(&serde_json::to_string(&error).unwrap()).unwrap();
+    assert_eq!(deserialized.io_error_kind(), None);
+    assert_eq!(deserialized.error_class(), Some(ErrorClass::Transient));
+}
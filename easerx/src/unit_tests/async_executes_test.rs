@@ -561,3 +561,45 @@ async fn test_async_execute_with_timeout() {
     assert_eq!(state_vec[1], Async::loading(None));
     assert_eq!(state_vec[2], Async::fail_with_timeout(None));
 }
+
+#[tokio::test]
+async fn test_async_execute_with_timeout_store_timeout() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .async_execute_with_timeout(
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                "late".to_string()
+            },
+            Duration::from_millis(10),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.is_fail_with_timeout());
+    assert!(!data.is_fail_with_upstream_timeout());
+    assert!(data.error_eq(&AsyncError::Timeout));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_execute_with_timeout_upstream_timeout() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .async_execute_with_timeout(
+            crate::TimeoutResult::within(Duration::from_millis(10), async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                Ok::<_, String>("late".to_string())
+            }),
+            Duration::from_secs(5),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.is_fail_with_timeout());
+    assert!(data.is_fail_with_upstream_timeout());
+    assert!(data.error_eq(&AsyncError::UpstreamTimeout));
+    Ok(())
+}
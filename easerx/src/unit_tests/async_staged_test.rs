@@ -0,0 +1,124 @@
+use crate::{Async, AsyncError, AsyncStaged};
+#[cfg(feature = "execute")]
+use crate::{State, StateStore};
+#[cfg(feature = "execute")]
+use futures::StreamExt;
+
+#[test]
+fn test_stage_accessors() {
+    let stage = AsyncStaged::<i32>::Stage {
+        name: "download",
+        progress: 0.4,
+        value: Some(1),
+    };
+    assert_eq!(stage.stage_name(), Some("download"));
+    assert_eq!(stage.progress(), Some(0.4));
+    assert!(stage.is_loading());
+    assert!(!stage.is_complete());
+    assert_eq!(stage.value_ref(), Some(&1));
+
+    let success = AsyncStaged::Success { value: 2 };
+    assert_eq!(success.stage_name(), None);
+    assert_eq!(success.progress(), None);
+    assert!(success.is_complete());
+}
+
+#[test]
+fn test_conversion_from_and_into_async() {
+    assert_eq!(AsyncStaged::from(Async::success(1)), AsyncStaged::Success { value: 1 });
+    assert_eq!(
+        AsyncStaged::from(Async::<i32>::fail_with_message("boom", None)),
+        AsyncStaged::Fail {
+            error: AsyncError::error("boom"),
+            value: None
+        }
+    );
+    assert_eq!(AsyncStaged::<i32>::stage("parse").into_async(), Async::loading(None));
+}
+
+#[cfg(feature = "execute")]
+#[derive(Clone, Debug, PartialEq, Default)]
+struct ImportState {
+    import: AsyncStaged<usize>,
+}
+
+#[cfg(feature = "execute")]
+impl State for ImportState {}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_staged_reports_full_sequence() -> Result<(), AsyncError> {
+    let store = StateStore::new(ImportState::default());
+    let observed = tokio::spawn(
+        store
+            .subscribe_all()
+            .filter_map(|event| async move { event.state().map(|state| state.import) })
+            .take_while(|import| {
+                let running = !import.is_complete();
+                async move { running }
+            })
+            .collect::<Vec<_>>(),
+    );
+    tokio::task::yield_now().await;
+
+    store
+        .execute_staged(
+            vec!["download", "parse"],
+            |reporter| {
+                reporter.report_progress(0.5);
+                reporter.report_progress(1.5);
+                reporter.report_stage("unplanned");
+                reporter.report_stage("parse");
+                reporter.report_progress(0.25);
+                42
+            },
+            |_, import| ImportState { import },
+        )
+        .await
+        .unwrap()?;
+
+    let stage = |name, progress| AsyncStaged::Stage {
+        name,
+        progress,
+        value: None,
+    };
+    assert_eq!(
+        observed.await.unwrap(),
+        vec![
+            stage("download", 0.0),
+            stage("download", 0.5),
+            stage("download", 1.0),
+            stage("parse", 0.0),
+            stage("parse", 0.25),
+        ]
+    );
+    assert_eq!(store.await_state().await?.import, AsyncStaged::Success { value: 42 });
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_staged_without_stages_loads() -> Result<(), AsyncError> {
+    let store = StateStore::new(ImportState::default());
+
+    store
+        .execute_staged(
+            Vec::new(),
+            |reporter| {
+                reporter.report_progress(0.5);
+                Err::<usize, _>("offline")
+            },
+            |_, import| ImportState { import },
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(
+        store.await_state().await?.import,
+        AsyncStaged::Fail {
+            error: AsyncError::error("offline"),
+            value: None
+        }
+    );
+    Ok(())
+}
@@ -105,7 +105,7 @@ fn test_async_state_serde() {
     let serialized_fail_err = serde_json::to_string(&fail_err).unwrap();
     assert_eq!(
         serialized_fail_err,
-        r#"{"fail":{"error":{"error":"test"},"value":42}}"#
+        r#"{"fail":{"error":{"error":{"message":"test","context":{}}},"value":42}}"#
     );
 
     let deserialized_fail: Async<i32> = serde_json::from_str(&serialized_fail_err).unwrap();
@@ -412,3 +412,248 @@ fn test_complex_state_transitions() {
     assert!(state.is_loading());
     assert!(state.value_ref().is_none());
 }
+
+#[test]
+fn test_value_equality_helpers() {
+    let uninitialized: Async<i32> = Async::Uninitialized;
+    assert!(!uninitialized.success_eq(&1));
+    assert!(!uninitialized.contains(&1));
+    assert!(!uninitialized.error_eq(&AsyncError::None));
+
+    let loading = Async::loading(Some(1));
+    assert!(!loading.success_eq(&1));
+    assert!(loading.contains(&1));
+    assert!(!loading.contains(&2));
+    assert!(!Async::<i32>::loading(None).contains(&1));
+
+    let success = Async::success(1);
+    assert!(success.success_eq(&1));
+    assert!(!success.success_eq(&2));
+    assert!(success.contains(&1));
+    assert!(!success.error_eq(&AsyncError::None));
+
+    let fail = Async::fail_with_cancelled(Some(1));
+    assert!(!fail.success_eq(&1));
+    assert!(fail.contains(&1));
+    assert!(fail.error_eq(&AsyncError::Cancelled));
+    assert!(!fail.error_eq(&AsyncError::Timeout));
+    assert!(Async::<i32>::fail_with_message("boom", None).error_eq(&AsyncError::error("boom")));
+    assert!(!Async::<i32>::fail_with_message("boom", None).error_eq(&AsyncError::error("bang")));
+}
+
+#[test]
+fn test_async_debug_truncates_long_values() {
+    // The Debug representation of a string adds two quotes
+    let at_limit = "a".repeat(62);
+    let debug_str = format!("{:?}", Async::success(at_limit.clone()));
+    assert_eq!(debug_str, format!("Success {{ value: \"{}\" }}", at_limit));
+
+    let over_limit = "a".repeat(63);
+    let debug_str = format!("{:?}", Async::success(over_limit));
+    assert_eq!(debug_str, format!("Success {{ value: \"{}... }}", "a".repeat(63)));
+
+    let retained = Async::fail(AsyncError::Timeout, Some(vec![0u8; 1024 * 1024]));
+    let debug_str = format!("{:?}", retained);
+    assert!(debug_str.starts_with("Fail { error: Timeout, value: Some([0, 0,"));
+    assert!(debug_str.ends_with("...) }"));
+    assert!(debug_str.len() < 128);
+}
+
+#[test]
+fn test_async_fmt_full() {
+    struct Full<'a>(&'a Async<String>);
+    impl std::fmt::Debug for Full<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt_full(f)
+        }
+    }
+
+    let value = "b".repeat(200);
+    let loading = Async::loading(Some(value.clone()));
+    assert_eq!(
+        format!("{:?}", Full(&loading)),
+        format!("Loading {{ value: Some(\"{}\") }}", value)
+    );
+    assert_eq!(format!("{:?}", Full(&Async::Uninitialized)), "Uninitialized");
+}
+
+#[test]
+fn test_async_equals_ignoring_retain() {
+    assert!(Async::fail_with_message("x", Some(1))
+        .equals_ignoring_retain(&Async::fail_with_message("x", None)));
+    assert!(!Async::fail_with_message("x", Some(1))
+        .equals_ignoring_retain(&Async::fail_with_message("y", Some(1))));
+
+    assert!(Async::loading(Some(1)).equals_ignoring_retain(&Async::loading(None)));
+    assert!(Async::success(1).equals_ignoring_retain(&Async::success(1)));
+    assert!(!Async::success(1).equals_ignoring_retain(&Async::success(2)));
+    assert!(Async::<i32>::Uninitialized.equals_ignoring_retain(&Async::Uninitialized));
+
+    assert!(!Async::success(1).equals_ignoring_retain(&Async::loading(Some(1))));
+    assert!(!Async::<i32>::loading(None).equals_ignoring_retain(&Async::fail_with_timeout(None)));
+    assert!(!Async::<i32>::Uninitialized.equals_ignoring_retain(&Async::loading(None)));
+}
+
+#[test]
+fn test_unwrap_and_expect_success() {
+    assert_eq!(Async::success(42).unwrap(), 42);
+    assert_eq!(Async::success("done".to_string()).expect("should succeed"), "done");
+}
+
+#[test]
+#[should_panic(expected = "called `Async::unwrap()` on an `Uninitialized` value")]
+fn test_unwrap_uninitialized_panics() {
+    Async::<i32>::Uninitialized.unwrap();
+}
+
+#[test]
+#[should_panic(expected = "called `Async::unwrap()` on a `Loading` value (retained: Some(1))")]
+fn test_unwrap_loading_panics() {
+    Async::loading(Some(1)).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "called `Async::unwrap()` on a `Fail` value: offline (retained: Some(3))")]
+fn test_unwrap_fail_panics_with_error_and_retained_value() {
+    Async::fail_with_message("offline", Some(3)).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "user should be loaded: a `Fail` value: Task was cancelled! (retained: None)")]
+fn test_expect_fail_panics_with_message() {
+    Async::<String>::fail_with_cancelled(None).expect("user should be loaded");
+}
+
+fn zip_phases() -> Vec<(&'static str, Async<i32>)> {
+    vec![
+        ("uninitialized", Async::Uninitialized),
+        ("loading", Async::loading(Some(1))),
+        ("success", Async::success(2)),
+        ("fail", Async::fail_with_message("boom", Some(3))),
+    ]
+}
+
+#[test]
+fn test_zip_phase_matrix() {
+    let expected = [
+        ["uninitialized", "loading", "uninitialized", "fail"],
+        ["loading", "loading", "loading", "fail"],
+        ["uninitialized", "loading", "success", "fail"],
+        ["fail", "fail", "fail", "fail"],
+    ];
+    let phase = |value: &Async<(i32, i32)>| match value {
+        Async::Uninitialized => "uninitialized",
+        Async::Loading { .. } => "loading",
+        Async::Success { .. } => "success",
+        Async::Fail { .. } => "fail",
+    };
+    for (row, (a_name, a)) in zip_phases().iter().enumerate() {
+        for (column, (b_name, b)) in zip_phases().iter().enumerate() {
+            let zipped = Async::zip(a, b);
+            assert_eq!(phase(&zipped), expected[row][column], "zip({a_name}, {b_name})");
+            let value = match (a.value_ref(), b.value_ref()) {
+                (Some(a), Some(b)) => Some((*a, *b)),
+                _ => None,
+            };
+            assert_eq!(zipped.value_ref().copied(), value, "zip({a_name}, {b_name})");
+        }
+    }
+}
+
+#[test]
+fn test_zip_first_error_wins() {
+    let a = Async::<i32>::fail_with_message("first", None);
+    let b = Async::<i32>::fail_with_message("second", Some(2));
+    assert_eq!(Async::zip(&a, &b), Async::fail_with_message("first", None));
+    assert_eq!(Async::zip(&b, &a), Async::fail_with_message("second", None));
+
+    let success = Async::success(1);
+    assert_eq!(Async::zip(&success, &b), Async::fail_with_message("second", Some((1, 2))));
+}
+
+#[test]
+fn test_zip_with_builds_value() {
+    let sum = Async::zip_with(&Async::success(1), &Async::success(2), |a, b| a + b);
+    assert_eq!(sum, Async::success(3));
+
+    let sum = Async::zip_with(&Async::loading(Some(1)), &Async::success(2), |a, b| a + b);
+    assert_eq!(sum, Async::loading(Some(3)));
+
+    let mut called = false;
+    let sum = Async::zip_with(&Async::<i32>::loading(None), &Async::success(2), |a, b| {
+        called = true;
+        a + b
+    });
+    assert_eq!(sum, Async::loading(None));
+    assert!(!called);
+}
+
+#[test]
+fn test_zip3() {
+    let a = Async::success(1);
+    let b = Async::success("two");
+    let c = Async::success(3.0);
+    assert_eq!(Async::zip3(&a, &b, &c), Async::success((1, "two", 3.0)));
+
+    let loading = Async::loading(Some("two"));
+    assert_eq!(Async::zip3(&a, &loading, &c), Async::loading(Some((1, "two", 3.0))));
+
+    let first = Async::<i32>::fail_with_message("first", None);
+    let third = Async::<f64>::fail_with_message("third", None);
+    assert_eq!(Async::zip3(&first, &b, &third), Async::fail_with_message("first", None));
+    assert_eq!(Async::zip3(&a, &Async::<&str>::Uninitialized, &third), Async::fail_with_message("third", None));
+    assert_eq!(Async::zip3(&a, &Async::<&str>::Uninitialized, &c), Async::Uninitialized);
+}
+
+#[test]
+fn test_is_terminal() {
+    assert!(!Async::<i32>::Uninitialized.is_terminal());
+    assert!(!Async::loading(Some(1)).is_terminal());
+
+    assert!(Async::success(1).is_terminal());
+    assert!(Async::success(1).is_settled_success());
+    assert!(!Async::success(1).is_settled_fail());
+
+    let cancelled = Async::<i32>::fail(AsyncError::Cancelled, Some(1));
+    assert!(cancelled.is_terminal());
+    assert!(cancelled.is_settled_fail());
+    assert!(!cancelled.is_settled_success());
+
+    let failed = Async::<i32>::fail_with_message("failed", None);
+    assert!(failed.is_terminal());
+
+    let timed_out = Async::<i32>::fail(AsyncError::Timeout, Some(1));
+    assert!(timed_out.is_complete());
+    assert!(!timed_out.is_terminal());
+    assert!(!timed_out.is_settled_fail());
+    assert!(!Async::<i32>::fail(AsyncError::UpstreamTimeout, None).is_terminal());
+}
+
+fn parse(text: String) -> Result<u16, std::num::ParseIntError> {
+    text.parse()
+}
+
+#[test]
+fn test_try_map_success() {
+    assert_eq!(Async::success("42".to_string()).try_map(parse), Async::success(42));
+    let failed = Async::success("forty-two".to_string()).try_map(parse);
+    assert_eq!(failed, Async::fail(AsyncError::error("invalid digit found in string"), None));
+    assert_eq!(Async::<String>::Uninitialized.try_map(parse), Async::Uninitialized);
+}
+
+#[test]
+fn test_try_map_loading_with_retained() {
+    assert_eq!(Async::loading(Some("7".to_string())).try_map(parse), Async::loading(Some(7)));
+    // The retained value is dropped, the load is still in progress
+    assert_eq!(Async::loading(Some("seven".to_string())).try_map(parse), Async::loading(None));
+    assert_eq!(Async::<String>::loading(None).try_map(parse), Async::loading(None));
+}
+
+#[test]
+fn test_try_map_fail_with_retained() {
+    let retained = Async::fail(AsyncError::Timeout, Some("7".to_string())).try_map(parse);
+    assert_eq!(retained, Async::fail(AsyncError::Timeout, Some(7)));
+    // The original error is kept rather than replaced by the transformation's
+    let dropped = Async::fail(AsyncError::Timeout, Some("seven".to_string())).try_map(parse);
+    assert_eq!(dropped, Async::fail(AsyncError::Timeout, None));
+}
@@ -0,0 +1,84 @@
+use crate::{Async, AsyncTracked, State};
+use std::time::Duration;
+
+fn pause() {
+    std::thread::sleep(Duration::from_millis(2));
+}
+
+#[test]
+fn test_touch_updates_timestamp() {
+    let tracked = AsyncTracked::new(Async::success(1));
+    let created = tracked.accessed_at();
+
+    pause();
+    tracked.touch();
+
+    assert!(tracked.accessed_at() > created);
+}
+
+#[test]
+fn test_get_touches_but_peek_does_not() {
+    let tracked = AsyncTracked::new(Async::success(1));
+    let created = tracked.accessed_at();
+
+    pause();
+    assert_eq!(tracked.peek(), &Async::success(1));
+    assert_eq!(tracked.accessed_at(), created);
+
+    assert_eq!(tracked.get(), &Async::success(1));
+    assert!(tracked.accessed_at() > created);
+}
+
+#[test]
+fn test_sort_by_accessed_at_for_eviction() {
+    let entries: Vec<(&str, AsyncTracked<i32>)> = vec![
+        ("a", Async::success(1).into()),
+        ("b", Async::loading(Some(2)).into()),
+        ("c", Async::fail_with_none(None).into()),
+    ];
+
+    // Access order: c, a, b
+    pause();
+    entries[2].1.touch();
+    pause();
+    let _ = entries[0].1.get();
+    pause();
+    entries[1].1.touch();
+
+    let mut eviction_order: Vec<_> = entries.iter().collect();
+    eviction_order.sort_by_key(|(_, tracked)| tracked.accessed_at());
+    let keys: Vec<_> = eviction_order.iter().map(|(key, _)| *key).collect();
+
+    assert_eq!(keys, vec!["c", "a", "b"]);
+}
+
+#[test]
+fn test_equality_ignores_timestamp() {
+    let first = AsyncTracked::new(Async::success(1));
+    pause();
+    let second = AsyncTracked::new(Async::success(1));
+    second.touch();
+
+    assert_eq!(first, second);
+    assert_ne!(first, AsyncTracked::new(Async::success(2)));
+
+    let cloned = second.clone();
+    assert_eq!(cloned.accessed_at(), second.accessed_at());
+    assert_eq!(cloned.into_inner(), Async::success(1));
+}
+
+#[test]
+fn test_usable_in_state() {
+    #[derive(Clone, Debug, PartialEq)]
+    struct CacheState {
+        entry: AsyncTracked<String>,
+    }
+    impl State for CacheState {}
+
+    let mut state = CacheState {
+        entry: AsyncTracked::default(),
+    };
+    assert!(state.entry.peek().is_uninitialized());
+    state.entry.set(Async::success("cached".to_string()));
+    assert_eq!(state.entry.get().value_ref(), Some(&"cached".to_string()));
+}
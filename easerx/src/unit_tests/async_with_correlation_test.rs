@@ -0,0 +1,72 @@
+use uuid::Uuid;
+use crate::{Async, AsyncWithCorrelation};
+#[cfg(feature = "execute")]
+use std::sync::{Arc, Mutex};
+#[cfg(feature = "execute")]
+use crate::{AsyncError, State, StateStore};
+
+#[test]
+fn test_async_with_correlation_accessors() {
+    let id = Uuid::from_u128(7);
+    let tagged = AsyncWithCorrelation::new(id, Async::success(1));
+    assert_eq!(tagged.correlation_id(), id);
+    assert_eq!(tagged.result(), &Async::success(1));
+    assert_eq!(tagged.into_inner(), Async::success(1));
+}
+
+#[cfg(feature = "execute")]
+#[derive(Clone, Debug, PartialEq, Default)]
+struct OrderState {
+    order: Option<AsyncWithCorrelation<String>>,
+}
+
+#[cfg(feature = "execute")]
+impl State for OrderState {}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_with_correlation_id_threads_id_to_updater() -> Result<(), AsyncError> {
+    let store = StateStore::new(OrderState::default());
+    let request_id = Uuid::from_u128(0x1234_5678);
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_in_updater = seen.clone();
+    store
+        .execute_with_correlation_id(
+            request_id,
+            || "order placed".to_string(),
+            move |_, order| {
+                seen_in_updater
+                    .lock()
+                    .unwrap()
+                    .push((order.correlation_id(), order.result().is_loading()));
+                OrderState { order: Some(order) }
+            },
+        )
+        .await
+        .unwrap()?;
+
+    let order = store.await_state().await?.order.unwrap();
+    assert_eq!(order.correlation_id(), request_id);
+    assert_eq!(order.result(), &Async::success("order placed".to_string()));
+    assert_eq!(*seen.lock().unwrap(), vec![(request_id, true), (request_id, false)]);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_failed_execution_keeps_its_id() -> Result<(), AsyncError> {
+    let store = StateStore::new(OrderState::default());
+    let failing = Uuid::from_u128(1);
+    store
+        .execute_with_correlation_id(
+            failing,
+            || Err::<String, _>(AsyncError::error("payment declined")),
+            |_, order| OrderState { order: Some(order) },
+        )
+        .await
+        .unwrap()?;
+    let order = store.await_state().await?.order.unwrap();
+    assert_eq!(order.correlation_id(), failing);
+    assert!(order.result().is_fail_with_error());
+    Ok(())
+}
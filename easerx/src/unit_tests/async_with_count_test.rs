@@ -0,0 +1,105 @@
+use crate::{Async, AsyncWithCount};
+#[cfg(feature = "execute")]
+use crate::{AsyncError, State, StateStore};
+#[cfg(feature = "execute")]
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "execute")]
+#[derive(Clone, Debug, PartialEq, Default)]
+struct CountedState {
+    data: AsyncWithCount<i32>,
+}
+
+#[cfg(feature = "execute")]
+impl State for CountedState {}
+
+#[cfg(feature = "execute")]
+impl CountedState {
+    fn set_data(self, data: AsyncWithCount<i32>) -> Self {
+        Self { data }
+    }
+}
+
+#[test]
+fn test_record_counts_completed_loads() {
+    let counted = AsyncWithCount::new(Async::Uninitialized);
+    assert_eq!(counted.load_count(), 0);
+    assert!(!counted.first_load());
+
+    let counted = counted.record(Async::loading(None));
+    assert_eq!(counted.load_count(), 0);
+
+    let counted = counted.record(Async::success(1));
+    assert_eq!(counted.load_count(), 1);
+    assert!(counted.first_load());
+
+    // A completion that does not follow a loading state is not a load
+    let counted = counted.record(Async::success(2));
+    assert_eq!(counted.load_count(), 1);
+
+    let counted = counted
+        .record(Async::loading(Some(2)))
+        .record(Async::fail_with_message("boom", Some(2)));
+    assert_eq!(counted.load_count(), 2);
+    assert!(!counted.first_load());
+    assert!(counted.value().is_fail());
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_counted_sequential_loads() -> Result<(), AsyncError> {
+    let store = StateStore::new(CountedState::default());
+
+    let mut first_loads = Vec::new();
+    for i in 1..=3 {
+        store
+            .execute_counted(move || i, |state| &state.data, CountedState::set_data)
+            .await.unwrap()?;
+        let state = store.await_state().await?;
+        assert_eq!(state.data.value(), &Async::success(i));
+        first_loads.push(state.data.first_load());
+    }
+
+    assert_eq!(store.get_state().data.load_count(), 3);
+    assert_eq!(first_loads, vec![true, false, false]);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_async_execute_counted_sequential_loads() -> Result<(), AsyncError> {
+    let store = StateStore::new(CountedState::default());
+
+    for i in 1..=3 {
+        store
+            .async_execute_counted(async move { i }, |state| &state.data, CountedState::set_data)
+            .await.unwrap()?;
+    }
+
+    let state = store.await_state().await?;
+    assert_eq!(state.data.load_count(), 3);
+    assert!(!state.data.first_load());
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_cancelled_load_is_counted() -> Result<(), AsyncError> {
+    let store = StateStore::new(CountedState::default());
+    let token = CancellationToken::new();
+    token.cancel();
+
+    store
+        .async_execute_cancellable_counted(
+            token,
+            |_| async { 1 },
+            |state| &state.data,
+            CountedState::set_data,
+        )
+        .await.unwrap()?;
+
+    let state = store.await_state().await?;
+    assert!(state.data.value().is_fail_with_canceled());
+    assert!(state.data.first_load());
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use crate::unit_tests::TestState;
+use crate::{barrier_all, AsyncError, StateStore, StoreBarrier};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_barrier_all_makes_interleaved_updates_visible() -> Result<(), AsyncError> {
+    let a = StateStore::new(TestState::default());
+    let b = StateStore::new(TestState::default());
+    let b_view = b.read_only();
+    for frame in 1..=100 {
+        a.set_state(move |state| state.set_count(frame))?;
+        b.set_state(|state| state.add_count(1))?;
+        a.set_state(|state| state.add_count(1000))?;
+        b.set_state(|state| state.add_count(1000))?;
+        barrier_all(&[&a, &b_view]).await?;
+        // Both stores reflect the same frame
+        assert_eq!(a.get_state().count, frame + 1000);
+        assert_eq!(b.get_state().count, frame * 1001);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_barrier_all_is_queued_on_call() -> Result<(), AsyncError> {
+    let a = StateStore::new(TestState::default());
+    let b = StateStore::new(TestState::default());
+    a.set_state(|state| state.set_count(1))?;
+    let barrier = barrier_all(&[&a, &b]);
+
+    // Closing drains the queue, which still processes the barrier queued before
+    a.close();
+    a.closed().await;
+    barrier.await?;
+    assert_eq!(a.get_state().count, 1);
+    assert!(a.barrier().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_barrier_all_without_stores() -> Result<(), AsyncError> {
+    Ok(barrier_all(&[]).await?)
+}
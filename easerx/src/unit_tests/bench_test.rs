@@ -0,0 +1,62 @@
+use std::time::Duration;
+use crate::bench::{StressTest, StressTestReport};
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_stress_test_applies_every_update() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let report = StressTest::new(store.clone(), 4, 250)
+        .update(|state| state.add_count(1))
+        .run()
+        .await?;
+
+    assert_eq!(store.get_state().count, 1_000);
+    assert_eq!(report.operations, 1_000);
+    assert!(report.p50 <= report.p95);
+    assert!(report.p95 <= report.p99);
+    assert!(report.peak_queue_depth >= 1);
+    assert!(report.peak_queue_depth <= 1_000);
+    assert!(report.throughput() > 0.0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stress_test_without_operations() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let report = StressTest::new(store.clone(), 0, 100).run().await?;
+    assert_eq!(report.operations, 0);
+    assert_eq!(report.p99, Duration::ZERO);
+    assert_eq!(report.peak_queue_depth, 0);
+    assert_eq!(store.version(), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stress_test_fails_on_closed_store() {
+    let store = StateStore::new(TestState::default());
+    store.close();
+    store.closed().await;
+    let result = StressTest::new(store, 2, 10).run().await;
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_stress_test_report_display() {
+    let report = StressTestReport {
+        operations: 2_000,
+        elapsed: Duration::from_secs(2),
+        p50: Duration::from_micros(5),
+        p95: Duration::from_micros(40),
+        p99: Duration::from_micros(90),
+        peak_queue_depth: 17,
+    };
+    assert_eq!(report.throughput(), 1_000.0);
+    let table = report.to_string();
+    let lines: Vec<_> = table.lines().collect();
+    assert_eq!(lines.len(), 7);
+    assert!(lines[0].starts_with("operations") && lines[0].ends_with("2000"));
+    assert!(lines[2].ends_with("1000 ops/s"));
+    assert!(lines[3].ends_with("5.00µs"));
+    assert!(lines[6].starts_with("peak queue depth") && lines[6].ends_with("17"));
+}
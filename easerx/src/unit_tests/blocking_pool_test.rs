@@ -0,0 +1,85 @@
+use std::time::Duration;
+use futures::StreamExt;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore, StoreError};
+
+/// A runtime whose blocking pool has a single thread, so a second computation has to wait.
+fn tiny_blocking_pool() -> tokio::runtime::Runtime {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(1)
+        .max_blocking_threads(1)
+        .enable_all()
+        .build()
+        .unwrap()
+}
+
+fn sleepy(millis: u64) -> impl FnOnce() -> String + Send + 'static {
+    move || {
+        std::thread::sleep(Duration::from_millis(millis));
+        "done".to_string()
+    }
+}
+
+#[test]
+fn test_saturated_blocking_pool_is_reported() -> Result<(), AsyncError> {
+    tiny_blocking_pool().block_on(async {
+        let store = StateStore::builder(TestState::default())
+            .blocking_start_warning(Duration::from_millis(50))
+            .build();
+        let mut errors = store.errors();
+
+        let first = store.execute(sleepy(200), |state, data| state.set_async_data(data));
+        let second = store.execute(sleepy(0), |state, data| state.set_async_data(data));
+        first.await.unwrap()?;
+        second.await.unwrap()?;
+
+        let latency = store.blocking_start_latency();
+        assert_eq!(latency.count(), 2);
+        assert!(latency.max() >= Duration::from_millis(100), "max: {:?}", latency.max());
+        match errors.next().await {
+            Some(StoreError::BlockingPoolSaturated { waited }) => {
+                assert!(waited >= Duration::from_millis(100), "waited: {waited:?}")
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        Ok(())
+    })
+}
+
+#[tokio::test]
+async fn test_idle_blocking_pool_is_not_reported() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let mut errors = store.errors();
+    store
+        .execute(sleepy(0), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    store.set_state(|_| panic!("marker"))?;
+
+    // The panic is the first error, so the computation didn't report a saturated pool
+    assert!(matches!(errors.next().await, Some(StoreError::ReducerPanic { .. })));
+    assert_eq!(store.blocking_start_latency().count(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_computations_gauge() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+    let ticket = store.execute(
+        move || {
+            started_tx.send(()).unwrap();
+            release_rx.recv().unwrap();
+            "done".to_string()
+        },
+        |state, data| state.set_async_data(data),
+    );
+    tokio::task::spawn_blocking(move || started_rx.recv().unwrap()).await.unwrap();
+    assert_eq!(store.blocking_computations(), 1);
+
+    release_tx.send(()).unwrap();
+    ticket.await.unwrap()?;
+    assert_eq!(store.blocking_computations(), 0);
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+#[cfg(feature = "execute")]
+use crate::Async;
+#[cfg(feature = "execute")]
+use futures_signals::signal::SignalExt;
+
+#[tokio::test]
+async fn test_blocking_set_state_from_thread() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let store_clone = store.clone();
+    let thread = std::thread::spawn(move || {
+        store_clone.blocking_set_state(|state| state.add_count(1))?;
+        store_clone.blocking_set_state(|state| state.add_count(2))?;
+        // The updates are committed once blocking_set_state returns
+        Ok::<_, AsyncError>(store_clone.get_state().count)
+    });
+    let count = tokio::task::spawn_blocking(move || thread.join().unwrap())
+        .await
+        .unwrap()?;
+
+    assert_eq!(count, 3);
+    assert_eq!(store.await_state().await?.count, 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_set_state_waits_behind_update_async() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+    let held = tokio::spawn({
+        let store = store.clone();
+        async move {
+            store
+                .update_async(move |state| {
+                    Box::pin(async move {
+                        let _ = started_tx.send(());
+                        tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+                        state
+                    })
+                })
+                .await
+        }
+    });
+    started_rx.await.unwrap();
+
+    let store_clone = store.clone();
+    let thread = std::thread::spawn(move || {
+        // Queued behind the held update, which keeps answering reads meanwhile
+        store_clone.blocking_set_state(|state| state.add_count(1))?;
+        Ok::<_, AsyncError>(store_clone.get_state().count)
+    });
+    let count = tokio::task::spawn_blocking(move || thread.join().unwrap())
+        .await
+        .unwrap()?;
+
+    assert_eq!(count, 1);
+    held.await.unwrap()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_await_state_from_thread() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.set_count(7))?;
+
+    let store_clone = store.clone();
+    let thread = std::thread::spawn(move || store_clone.blocking_await_state());
+    let state = tokio::task::spawn_blocking(move || thread.join().unwrap())
+        .await
+        .unwrap()?;
+
+    assert_eq!(state.count, 7);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_from_thread() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let store_clone = store.clone();
+    std::thread::spawn(move || {
+        store_clone.execute(
+            || "from thread".to_string(),
+            |state, data| state.set_async_data(data),
+        );
+    })
+    .join()
+    .unwrap();
+
+    store
+        .to_signal()
+        .stop_if(|state| state.data.is_complete())
+        .for_each(|_| async {})
+        .await;
+    assert_eq!(
+        store.get_state().data,
+        Async::success("from thread".to_string())
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_methods_reject_runtime_context() {
+    let store = StateStore::new(TestState::default());
+
+    let result = store.blocking_set_state(|state| state.add_count(1));
+    assert!(matches!(result, Err(AsyncError::Error { message, .. }) if message.contains("blocking_set_state")));
+
+    let result = store.blocking_await_state();
+    assert!(matches!(result, Err(AsyncError::Error { message, .. }) if message.contains("blocking_await_state")));
+}
+
+/// Writes a final update when dropped, like a session guard whose owner cannot `.await`.
+struct CountOnDrop {
+    store: StateStore<TestState>,
+}
+
+impl Drop for CountOnDrop {
+    fn drop(&mut self) {
+        self.store
+            .blocking_set_state(|state| state.add_count(10))
+            .expect("dropped outside the runtime");
+    }
+}
+
+#[tokio::test]
+async fn test_blocking_set_state_from_drop_on_thread() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let guard = CountOnDrop { store: store.clone() };
+    let thread = std::thread::spawn(move || drop(guard));
+    tokio::task::spawn_blocking(move || thread.join().unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(store.get_state().count, 10);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_set_state_inside_runtime_errors_instead_of_deadlocking() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    assert!(store.blocking_set_state(|state| state.add_count(1)).is_err());
+
+    let store_clone = store.clone();
+    let from_blocking_pool = tokio::task::spawn_blocking(move || {
+        store_clone.blocking_set_state(|state| state.add_count(1))
+    })
+    .await
+    .unwrap();
+    assert!(from_blocking_pool.is_err());
+
+    let store_clone = store.clone();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    store.with_state(move |_| {
+        let _ = tx.send(store_clone.blocking_set_state(|state| state.add_count(1)));
+    })?;
+    assert!(rx.await.unwrap().is_err());
+
+    assert_eq!(store.await_state().await?.count, 0);
+    Ok(())
+}
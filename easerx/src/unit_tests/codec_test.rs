@@ -0,0 +1,66 @@
+use crate::codec::{decode_versioned, encode_versioned, Codec, JsonCodec, HEADER_LEN};
+use crate::Async;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Snapshot {
+    count: i32,
+    data: Async<String>,
+}
+
+fn snapshot() -> Snapshot {
+    Snapshot {
+        count: 7,
+        data: Async::success("persisted".to_string()),
+    }
+}
+
+fn assert_round_trip<C: Codec>(codec: C) {
+    let bytes = codec.encode(&snapshot()).unwrap();
+    assert_eq!(codec.decode::<Snapshot>(&bytes).unwrap(), snapshot());
+
+    let bytes = encode_versioned(&codec, 3, &snapshot()).unwrap();
+    assert_eq!(bytes[2], codec.format_tag());
+    assert_eq!(bytes[3], 3);
+    assert_eq!(decode_versioned::<_, Snapshot>(&codec, &bytes).unwrap(), (3, snapshot()));
+}
+
+#[test]
+fn test_json_codec_round_trip() {
+    assert_round_trip(JsonCodec);
+}
+
+#[cfg(feature = "bincode")]
+#[test]
+fn test_bincode_codec_round_trip() {
+    assert_round_trip(crate::codec::BincodeCodec);
+}
+
+#[cfg(feature = "cbor")]
+#[test]
+fn test_cbor_codec_round_trip() {
+    assert_round_trip(crate::codec::CborCodec);
+}
+
+#[test]
+fn test_unversioned_payload_falls_back_to_version_zero() {
+    let bytes = JsonCodec.encode(&snapshot()).unwrap();
+    assert_eq!(decode_versioned::<_, Snapshot>(&JsonCodec, &bytes).unwrap(), (0, snapshot()));
+}
+
+#[test]
+fn test_corrupted_header_is_rejected() {
+    let mut bytes = encode_versioned(&JsonCodec, 1, &snapshot()).unwrap();
+
+    // A header from another codec
+    bytes[2] = b'X';
+    let error = decode_versioned::<_, Snapshot>(&JsonCodec, &bytes).unwrap_err();
+    assert!(error.to_string().contains("format `X`"));
+
+    // A damaged magic makes the whole buffer a (broken) legacy payload
+    bytes[0] = 0;
+    assert!(decode_versioned::<_, Snapshot>(&JsonCodec, &bytes).is_err());
+
+    // A truncated buffer
+    assert!(decode_versioned::<_, Snapshot>(&JsonCodec, &bytes[..HEADER_LEN - 1]).is_err());
+}
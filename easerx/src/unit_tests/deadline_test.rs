@@ -0,0 +1,82 @@
+use std::time::Duration;
+use tokio::time::Instant;
+use crate::unit_tests::TestState;
+use crate::{with_deadline, AsyncError, Deadline, ExecuteOptions, StateStore, Timeout};
+
+async fn slow_data(delay: Duration) -> String {
+    tokio::time::sleep(delay).await;
+    "slow".to_string()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_nested_execute_gets_remaining_budget() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let started = Instant::now();
+    let inner_store = store.clone();
+    with_deadline(Deadline::after(Duration::from_secs(10)), async {
+        // The explicit timeout keeps the outer execution alive; its computation still runs within the deadline
+        let options = ExecuteOptions::new().timeout(Timeout::After(Duration::from_secs(60)));
+        store.async_execute_with_options(
+            options,
+            async move {
+                tokio::time::sleep(Duration::from_secs(6)).await;
+                let remaining = Deadline::current().map(|deadline| deadline.remaining());
+                assert_eq!(remaining, Some(Duration::from_secs(4)));
+                inner_store
+                    .async_execute(slow_data(Duration::from_secs(60)), |state, data| state.set_async_data(data))
+                    .await
+                    .unwrap()
+                    .unwrap();
+                started.elapsed().as_secs() as i32
+            },
+            |state, elapsed| state.set_count(elapsed.value().unwrap_or(-1)),
+        )
+        .await
+    })
+    .await
+    .unwrap()?;
+
+    let state = store.await_state().await?;
+    // The inner execution started with 4 of the 10 seconds left
+    assert_eq!(state.count, 10);
+    assert!(state.data.error_eq(&AsyncError::DeadlineExceeded { budget: Duration::from_secs(10) }));
+    assert!(state.data.is_fail_with_timeout());
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_shorter_default_timeout_wins_over_deadline() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .default_execute_timeout(Duration::from_secs(1))
+        .build();
+    let options = ExecuteOptions::new().deadline(Deadline::after(Duration::from_secs(10)));
+    store
+        .async_execute_with_options(options, slow_data(Duration::from_secs(5)), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.data.error_eq(&AsyncError::Timeout));
+
+    let options = ExecuteOptions::new().deadline(Deadline::after(Duration::from_millis(500)));
+    store
+        .async_execute_with_options(options, slow_data(Duration::from_secs(5)), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.error_eq(&AsyncError::DeadlineExceeded { budget: Duration::from_millis(500) }));
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_explicit_timeout_ignores_deadline() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let options = ExecuteOptions::new()
+        .deadline(Deadline::after(Duration::from_secs(1)))
+        .timeout(Timeout::None);
+    store
+        .async_execute_with_options(options, slow_data(Duration::from_secs(5)), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data.value(), Some("slow".to_string()));
+    assert!(Deadline::current().is_none());
+    Ok(())
+}
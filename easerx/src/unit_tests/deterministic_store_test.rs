@@ -0,0 +1,173 @@
+use std::sync::{Arc, Mutex};
+use futures_core::future::BoxFuture;
+use crate::testing::{tagged, DeterministicStore, Step, StepKind};
+use crate::unit_tests::TestState;
+use crate::AsyncError;
+
+fn tags(steps: &[Step]) -> Vec<&'static str> {
+    steps.iter().map(|step| step.tag().unwrap_or("-")).collect()
+}
+
+// The orderings below are the ones documented by the extended1_order_of_nested example.
+
+#[tokio::test]
+async fn test_nested_with_state_runs_before_chained_set_state() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    harness.store().with_state(tagged("W1", move |_: TestState| {
+        let inner = store.clone();
+        store.with_state_forget(tagged("W2", move |_: TestState| {
+            let s1 = inner.clone();
+            inner.set_state_forget(tagged("S1", move |state: TestState| {
+                let s2 = s1.clone();
+                s1.set_state_forget(tagged("S2", move |state: TestState| {
+                    s2.set_state_forget(tagged("S3", |state: TestState| state));
+                    state
+                }));
+                state
+            }));
+        }));
+    }))?;
+
+    let steps = harness.run_until_idle().await;
+    assert_eq!(tags(&steps), ["W1", "W2", "S1", "S2", "S3"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_queued_by_action_overtakes_earlier_with_state() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    harness.store().with_state(tagged("W", move |_: TestState| {
+        store.with_state_forget(tagged("W1", |_: TestState| {}));
+        store.set_state_forget(tagged("S1", |state: TestState| state));
+    }))?;
+
+    let steps = harness.run_until_idle().await;
+    assert_eq!(tags(&steps), ["W", "S1", "W1"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_chain_drains_before_nested_with_state() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    harness.store().with_state(tagged("W", move |_: TestState| {
+        let inner = store.clone();
+        store.with_state_forget(tagged("W1", move |_: TestState| {
+            inner.with_state_forget(tagged("W2", |_: TestState| {}));
+        }));
+        let s1 = store.clone();
+        store.set_state_forget(tagged("S1", move |state: TestState| {
+            s1.set_state_forget(tagged("S2", |state: TestState| state));
+            state
+        }));
+    }))?;
+
+    let steps = harness.run_until_idle().await;
+    assert_eq!(tags(&steps), ["W", "S1", "S2", "W1", "W2"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_queued_set_state_goes_before_queued_with_state() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    store.with_state(tagged("W", |_: TestState| {}))?;
+    store.set_state(tagged("S", |state: TestState| state.add_count(1)))?;
+    store.with_state(tagged("W'", |_: TestState| {}))?;
+
+    let steps = harness.run_until_idle().await;
+    assert_eq!(tags(&steps), ["S", "W", "W'"]);
+    assert_eq!(steps[0].kind(), StepKind::SetState);
+    assert_eq!(steps[0].version(), 1);
+    assert_eq!(steps[1].kind(), StepKind::WithState);
+    assert_eq!(steps[1].to_string(), "WithState(W)");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_actions_observe_state_of_earlier_reducers() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let seen_clone = seen.clone();
+    store.with_state(tagged("W", move |state: TestState| seen_clone.lock().unwrap().push(state.count)))?;
+    store.set_state(tagged("S1", |state: TestState| state.add_count(1)))?;
+
+    assert_eq!(harness.step().await.map(|step| step.tag()), Some(Some("S1")));
+    assert!(seen.lock().unwrap().is_empty());
+    assert_eq!(harness.step().await.map(|step| step.tag()), Some(Some("W")));
+    assert_eq!(*seen.lock().unwrap(), [1]);
+    assert_eq!(harness.step().await, None);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_update_holds_reducers_but_services_actions() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+    let update = tokio::spawn({
+        let store = store.clone();
+        async move {
+            store
+                .update_async(tagged("U", move |state: TestState| -> BoxFuture<'static, TestState> {
+                    Box::pin(async move {
+                        let _ = release_rx.await;
+                        state.set_count(10)
+                    })
+                }))
+                .await
+        }
+    });
+
+    let started = harness.next_step().await.unwrap();
+    assert_eq!((started.kind(), started.tag()), (StepKind::SetState, Some("U")));
+    assert_eq!(started.version(), 0);
+
+    store.set_state(tagged("S", |state: TestState| state.add_count(1)))?;
+    store.with_state(tagged("W", |_: TestState| {}))?;
+    // The pending update keeps `S` queued, while `W` reads the state before the update
+    assert_eq!(harness.step().await.map(|step| step.tag()), Some(Some("W")));
+
+    release_tx.send(()).unwrap();
+    let completed = harness.step().await.unwrap();
+    assert_eq!((completed.kind(), completed.tag()), (StepKind::AsyncUpdate, Some("U")));
+    assert_eq!(completed.version(), 1);
+    update.await.unwrap()?;
+
+    let steps = harness.run_until_idle().await;
+    assert_eq!(tags(&steps), ["S"]);
+    assert_eq!(store.get_state().count, 11);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_next_step_waits_for_execution_reducers() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    let _ticket = store.execute(|| "done".to_string(), |state, data| state.set_async_data(data));
+
+    let loading = harness.next_step().await.unwrap();
+    assert_eq!((loading.kind(), loading.tag()), (StepKind::SetState, None));
+    assert!(store.get_state().data.is_loading());
+    let success = harness.next_step().await.unwrap();
+    assert_eq!(success.kind(), StepKind::SetState);
+    assert_eq!(store.get_state().data.value_ref().map(String::as_str), Some("done"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_next_step_returns_none_once_closed_and_drained() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(TestState::default());
+    let store = harness.store().clone();
+    store.set_state(tagged("S", |state: TestState| state.add_count(1)))?;
+    store.close();
+
+    assert_eq!(harness.next_step().await.and_then(|step| step.tag()), Some("S"));
+    assert_eq!(harness.next_step().await, None);
+    assert!(store.set_state(|state| state).is_err());
+    Ok(())
+}
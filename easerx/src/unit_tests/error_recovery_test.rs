@@ -0,0 +1,342 @@
+use crate::unit_tests::TestState;
+use crate::{assert_async_flow, Async, AsyncError, ErrorClass, PollFailure, RecoveryAction, StateStore, StoreError};
+use futures::StreamExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test]
+async fn test_error_recovery_propagate() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default())
+        .with_error_recovery(|_| RecoveryAction::Propagate);
+
+    store
+        .execute(|| Err::<String, _>("offline"), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::fail_with_message("offline", None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_recovery_ignore_restores_retained_value() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default().set_async_data(Async::success("cached".to_string())))
+        .with_error_recovery(|_| RecoveryAction::Ignore);
+    let failures = Arc::new(AtomicUsize::new(0));
+    let counter = failures.clone();
+    store.on_async_fail(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+
+    store
+        .execute_with_retain(
+            || Err::<String, _>("offline"),
+            |state| &state.data,
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::success("cached".to_string()));
+
+    store
+        .execute(|| Err::<String, _>("offline"), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::Uninitialized);
+    assert_eq!(failures.load(Ordering::SeqCst), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_recovery_retry_rejected_by_execute() -> Result<(), AsyncError> {
+    for action in [RecoveryAction::Retry, RecoveryAction::RetryAfter(Duration::from_secs(5))] {
+        let store = StateStore::new(TestState::default()).with_error_recovery(move |_| action);
+        let mut errors = store.errors();
+
+        store
+            .execute(|| Err::<String, _>("offline"), |state, data| state.set_async_data(data))
+            .await
+            .unwrap()?;
+        assert_eq!(store.await_state().await?.data, Async::fail_with_message("offline", None));
+        assert!(matches!(errors.next().await, Some(StoreError::RetryUnsupported { .. })));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_recovery_retry_rejected_by_async_execute() -> Result<(), AsyncError> {
+    for action in [RecoveryAction::Retry, RecoveryAction::RetryAfter(Duration::from_secs(5))] {
+        let store = StateStore::new(TestState::default()).with_error_recovery(move |_| action);
+        let mut errors = store.errors();
+
+        store
+            .async_execute(
+                async { Err::<String, _>("offline") },
+                |state, data| state.set_async_data(data),
+            )
+            .await
+            .unwrap()?;
+        assert_eq!(store.await_state().await?.data, Async::fail_with_message("offline", None));
+        assert!(matches!(errors.next().await, Some(StoreError::RetryUnsupported { .. })));
+    }
+    Ok(())
+}
+
+/// Fails the first `failures` calls, then succeeds with the number of the attempt.
+fn flaky(failures: usize) -> impl Fn() -> Result<String, String> + Clone + Send + 'static {
+    let attempts = Arc::new(AtomicUsize::new(0));
+    move || {
+        let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+        if attempt < failures {
+            Err("flaky".to_string())
+        } else {
+            Ok(format!("attempt {attempt}"))
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_execute_recoverable_retry() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Retry);
+
+    store.execute_recoverable(flaky(2), |state, data| state.set_async_data(data));
+
+    // The failed attempts are not written
+    assert_async_flow!(store, |state| &state.data, [uninitialized, loading(None), success("attempt 2")]);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_execute_recoverable_retry_after() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default())
+        .with_error_recovery(|_| RecoveryAction::RetryAfter(Duration::from_secs(5)));
+    let started = tokio::time::Instant::now();
+
+    store
+        .execute_recoverable(flaky(2), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::success("attempt 2".to_string()));
+    assert!(started.elapsed() >= Duration::from_secs(10));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execute_recoverable_ignore() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Ignore);
+    let computation = flaky(1);
+    let attempts = computation.clone();
+
+    store
+        .execute_recoverable(computation, |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::Uninitialized);
+    // The ignored failure was not retried
+    assert_eq!(attempts(), Ok("attempt 1".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execute_recoverable_propagate() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Propagate);
+
+    store
+        .execute_recoverable(flaky(1), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::fail_with_message("flaky", None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_execute_recoverable_retry() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Retry);
+    let computation = flaky(2);
+
+    store.async_execute_recoverable(
+        move || {
+            let result = computation();
+            async move { result }
+        },
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(store, |state| &state.data, [uninitialized, loading(None), success("attempt 2")]);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_async_execute_recoverable_retry_after() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default())
+        .with_error_recovery(|_| RecoveryAction::RetryAfter(Duration::from_secs(5)));
+    let computation = flaky(2);
+    let started = tokio::time::Instant::now();
+
+    store
+        .async_execute_recoverable(
+            move || {
+                let result = computation();
+                async move { result }
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::success("attempt 2".to_string()));
+    assert_eq!(started.elapsed(), Duration::from_secs(10));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_execute_recoverable_ignore() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Ignore);
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+
+    store
+        .async_execute_recoverable(
+            move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+                async { Err::<String, _>("offline") }
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::Uninitialized);
+    assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_execute_recoverable_propagate() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Propagate);
+
+    store
+        .async_execute_recoverable(
+            || async { Err::<String, _>("offline") },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::fail_with_message("offline", None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_recovery_skips_cancellation() -> Result<(), AsyncError> {
+    let consulted = Arc::new(AtomicUsize::new(0));
+    let counter = consulted.clone();
+    let store = StateStore::new(TestState::default()).with_error_recovery(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        RecoveryAction::Ignore
+    });
+    let token = CancellationToken::new();
+    token.cancel();
+
+    store
+        .execute_cancellable(token, |_| "never".to_string(), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::fail_with_cancelled(None));
+    assert_eq!(consulted.load(Ordering::SeqCst), 0);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_error_recovery_retry_after_in_polling() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default())
+        .with_error_recovery(|_| RecoveryAction::RetryAfter(Duration::from_secs(5)));
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+
+    let handle = store.async_execute_until(
+        move || {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    Err("flaky".to_string())
+                } else {
+                    Ok("done".to_string())
+                }
+            }
+        },
+        |status| status == "done",
+        Duration::from_secs(1),
+        PollFailure::Stop,
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [uninitialized, loading(None), success("done")],
+        timeout = Duration::from_secs(30),
+    );
+    handle.await.unwrap()?;
+    assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_error_recovery_retry_in_cancellable_loop() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default()).with_error_recovery(|_| RecoveryAction::Retry);
+    let token = CancellationToken::new();
+    let mut attempts = 0;
+
+    store.execute_cancellable_loop(
+        token.clone(),
+        Duration::from_secs(60),
+        move |_| {
+            attempts += 1;
+            if attempts < 3 {
+                Err("flaky".to_string())
+            } else {
+                Ok(format!("attempt {attempts}"))
+            }
+        },
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(store, |state| &state.data, [uninitialized, loading(None), success("attempt 3")]);
+    token.cancel();
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_error_recovery_never_retries_permanent_failures() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default())
+        .with_error_recovery(|_| RecoveryAction::RetryAfter(Duration::from_secs(5)));
+    let attempts = Arc::new(AtomicUsize::new(0));
+    let counter = attempts.clone();
+
+    let handle = store.async_execute_until(
+        move || {
+            let attempt = counter.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt == 0 {
+                    Err(AsyncError::with_class(ErrorClass::Transient, "unavailable"))
+                } else {
+                    Err(AsyncError::from(std::io::Error::from(std::io::ErrorKind::NotFound)))
+                }
+            }
+        },
+        |_: &String| true,
+        Duration::from_secs(1),
+        PollFailure::Stop,
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    handle.await.unwrap()?;
+    // The transient failure was retried, the permanent one was written
+    assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    let Async::Fail { error, .. } = store.await_state().await?.data else {
+        panic!("the permanent failure should be written");
+    };
+    assert_eq!(error.error_class(), Some(ErrorClass::Permanent));
+    Ok(())
+}
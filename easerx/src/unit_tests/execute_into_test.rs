@@ -0,0 +1,95 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use crate::unit_tests::TestState;
+use crate::{async_execute_into, async_execute_into_cancellable, Async, AsyncError, State, StateStore};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Permissions {
+    admin: Async<bool>,
+}
+
+impl State for Permissions {}
+
+fn set_admin(_: Permissions, admin: Async<bool>) -> Permissions {
+    Permissions { admin }
+}
+
+fn stores() -> (StateStore<TestState>, StateStore<Permissions>) {
+    (StateStore::new(TestState::default()), StateStore::new(Permissions::default()))
+}
+
+#[tokio::test]
+async fn test_execute_into_writes_both_stores() -> Result<(), AsyncError> {
+    let (user, permissions) = stores();
+    let runs = Arc::new(AtomicUsize::new(0));
+    let counter = runs.clone();
+    async_execute_into(
+        (&user, |state: TestState, data| state.set_async_data(data)),
+        (&permissions, set_admin),
+        async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            ("alice".to_string(), true)
+        },
+    )
+    .await
+    .unwrap()?;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    assert_eq!(user.await_state().await?.data, Async::success("alice".to_string()));
+    assert_eq!(permissions.await_state().await?.admin, Async::success(true));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execute_into_propagates_failure_to_both_stores() -> Result<(), AsyncError> {
+    let (user, permissions) = stores();
+    let failures = Arc::new(AtomicUsize::new(0));
+    let counter = failures.clone();
+    user.on_async_fail(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    let counter = failures.clone();
+    permissions.on_async_fail(move |_| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    });
+    async_execute_into(
+        (&user, |state: TestState, data| state.set_async_data(data)),
+        (&permissions, set_admin),
+        async { Err::<(String, bool), _>("forbidden") },
+    )
+    .await
+    .unwrap()?;
+
+    let error = AsyncError::error("forbidden");
+    assert!(user.await_state().await?.data.error_eq(&error));
+    assert!(permissions.await_state().await?.admin.error_eq(&error));
+    assert_eq!(failures.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execute_into_cancellation_cancels_both_stores() -> Result<(), AsyncError> {
+    let (user, permissions) = stores();
+    let token = CancellationToken::new();
+    let ticket = async_execute_into_cancellable(
+        token.clone(),
+        (&user, |state: TestState, data| state.set_async_data(data)),
+        (&permissions, set_admin),
+        |token| async move {
+            token.cancelled().await;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            ("alice".to_string(), true)
+        },
+    );
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(user.await_state().await?.data.is_loading());
+    assert!(permissions.await_state().await?.admin.is_loading());
+
+    token.cancel();
+    ticket.await.unwrap()?;
+    assert!(user.await_state().await?.data.is_fail_with_canceled());
+    assert!(permissions.await_state().await?.admin.is_fail_with_canceled());
+    Ok(())
+}
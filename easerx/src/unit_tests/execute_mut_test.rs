@@ -0,0 +1,107 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, ExecutionTicket, StateStore};
+use futures::StreamExt;
+use std::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+
+/// Runs an execution on a fresh store and returns the sequence of `data` values it committed.
+async fn observe<F>(start: F) -> Vec<Async<String>>
+where
+    F: FnOnce(&StateStore<TestState>) -> ExecutionTicket,
+{
+    let store = StateStore::new(TestState::default().set_async_data(Async::success("initial".to_string())));
+    let events = store.subscribe_all();
+    start(&store).await.unwrap().unwrap();
+    store.await_state().await.unwrap();
+    drop(store);
+    events
+        .filter_map(|event| futures::future::ready(event.state()))
+        .map(|state| state.data)
+        .collect()
+        .await
+}
+
+#[tokio::test]
+async fn test_execute_mut_matches_execute() {
+    let by_value = observe(|store| {
+        store.execute(|| "done".to_string(), |state, data| state.set_async_data(data))
+    })
+    .await;
+    let in_place = observe(|store| store.execute_mut(|| "done".to_string(), |state, data| state.data = data)).await;
+
+    assert_eq!(by_value, vec![Async::loading(None), Async::success("done".to_string())]);
+    assert_eq!(in_place, by_value);
+}
+
+#[tokio::test]
+async fn test_execute_with_retain_mut_matches_execute_with_retain() {
+    let by_value = observe(|store| {
+        store.execute_with_retain(
+            || Err::<String, _>("failed"),
+            |state| &state.data,
+            |state, data| state.set_async_data(data),
+        )
+    })
+    .await;
+    let in_place = observe(|store| {
+        store.execute_with_retain_mut(
+            || Err::<String, _>("failed"),
+            |state| &state.data,
+            |state, data| state.data = data,
+        )
+    })
+    .await;
+
+    assert_eq!(
+        by_value,
+        vec![
+            Async::loading(Some("initial".to_string())),
+            Async::fail_with_message("failed", Some("initial".to_string())),
+        ]
+    );
+    assert_eq!(in_place, by_value);
+}
+
+#[tokio::test]
+async fn test_async_execute_cancellable_mut_matches_async_execute_cancellable() {
+    let by_value = observe(|store| {
+        let token = CancellationToken::new();
+        token.cancel();
+        store.async_execute_cancellable(
+            token,
+            |_| async { "never".to_string() },
+            |state, data| state.set_async_data(data),
+        )
+    })
+    .await;
+    let in_place = observe(|store| {
+        let token = CancellationToken::new();
+        token.cancel();
+        store.async_execute_cancellable_mut(token, |_| async { "never".to_string() }, |state, data| state.data = data)
+    })
+    .await;
+
+    assert_eq!(by_value, vec![Async::loading(None), Async::fail_with_cancelled(None)]);
+    assert_eq!(in_place, by_value);
+}
+
+#[tokio::test]
+async fn test_execute_mut_with_non_clone_updater() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    // A Mutex is not Clone, so this updater could not be used with `async_execute`
+    let seen = Mutex::new(Vec::new());
+
+    store
+        .async_execute_mut(async { "done".to_string() }, move |state, data| {
+            seen.lock().unwrap().push(data.is_loading());
+            state.count = seen.lock().unwrap().len() as i32;
+            state.data = data;
+        })
+        .await
+        .unwrap()?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.data, Async::success("done".to_string()));
+    assert_eq!(state.count, 2);
+    Ok(())
+}
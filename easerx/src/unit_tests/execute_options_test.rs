@@ -0,0 +1,128 @@
+use std::time::Duration;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, ExecuteOptions, StateStore, Timeout};
+
+fn store_with_default_timeout(timeout: Duration) -> StateStore<TestState> {
+    StateStore::builder(TestState::default())
+        .default_execute_timeout(timeout)
+        .build()
+}
+
+async fn slow_data(delay: Duration) -> String {
+    tokio::time::sleep(delay).await;
+    "slow".to_string()
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_default_timeout_applies_to_async_execute() -> Result<(), AsyncError> {
+    let store = store_with_default_timeout(Duration::from_secs(1));
+    store
+        .async_execute(slow_data(Duration::from_secs(5)), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.is_fail_with_timeout());
+    assert!(data.error_eq(&AsyncError::Timeout));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_default_timeout_applies_to_blocking_execute() -> Result<(), AsyncError> {
+    let store = store_with_default_timeout(Duration::from_millis(20));
+    store
+        .execute(
+            || {
+                std::thread::sleep(Duration::from_millis(300));
+                "slow".to_string()
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.data.is_fail_with_timeout());
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_fast_computation_within_default_timeout() -> Result<(), AsyncError> {
+    let store = store_with_default_timeout(Duration::from_secs(10));
+    store
+        .async_execute(slow_data(Duration::from_secs(1)), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data.value(), Some("slow".to_string()));
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_per_call_timeout_overrides_default() -> Result<(), AsyncError> {
+    let store = store_with_default_timeout(Duration::from_secs(1));
+    store
+        .async_execute_with_options(
+            ExecuteOptions::new().timeout(Timeout::After(Duration::from_secs(10))),
+            slow_data(Duration::from_secs(5)),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.data.is_success());
+
+    store
+        .async_execute_with_options(
+            ExecuteOptions::new().timeout(Timeout::After(Duration::from_secs(1))),
+            slow_data(Duration::from_secs(5)),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.data.is_fail_with_timeout());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_per_call_timeout_without_store_default() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .execute_with_options(
+            ExecuteOptions::new().timeout(Timeout::After(Duration::from_millis(20))),
+            || {
+                std::thread::sleep(Duration::from_millis(300));
+                "slow".to_string()
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.data.is_fail_with_timeout());
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_opt_out_of_default_timeout() -> Result<(), AsyncError> {
+    let store = store_with_default_timeout(Duration::from_secs(1));
+    store
+        .async_execute_with_options(
+            ExecuteOptions::new().timeout(Timeout::None),
+            slow_data(Duration::from_secs(60)),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.data.value(), Some("slow".to_string()));
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_default_options_use_store_default() -> Result<(), AsyncError> {
+    let store = store_with_default_timeout(Duration::from_secs(1));
+    store
+        .async_execute_with_options(
+            ExecuteOptions::default(),
+            slow_data(Duration::from_secs(5)),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    assert!(store.await_state().await?.data.is_fail_with_timeout());
+    Ok(())
+}
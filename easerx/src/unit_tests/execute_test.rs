@@ -1,7 +1,8 @@
 use crate::async_error::AsyncError;
 use crate::unit_tests::TestState;
-use crate::{Async, StateStore};
+use crate::{assert_async_flow, Async, StateStore};
 use futures_signals::signal::SignalExt;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
@@ -171,29 +172,11 @@ async fn test_execute_with_retain_success() {
         |state, async_data| state.set_async_data(async_data),
     );
 
-    let state_vec = Arc::new(RwLock::new(Vec::new()));
-
-    store
-        .to_signal()
-        .stop_if(|_| {
-            let len = state_vec.read().unwrap().len();
-            len >= 2
-        })
-        .for_each(|state| {
-            state_vec.write().unwrap().push(state.data);
-            async {}
-        })
-        .await;
-
-    let state_vec = state_vec
-        .read()
-        .unwrap()
-        .iter().cloned()
-        .collect::<Vec<_>>();
-
-    assert_eq!(state_vec[0], Async::success("initial".to_string()));
-    assert_eq!(state_vec[1], Async::loading(Some("initial".to_string())));
-    assert_eq!(state_vec[2], Async::success("success".to_string()));
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [success("initial"), loading(Some("initial")), success("success")]
+    );
 }
 
 // Test execute with retain value fail
@@ -210,31 +193,10 @@ async fn test_execute_with_retain_fail() {
         |state, async_data| state.set_async_data(async_data),
     );
 
-    let state_vec = Arc::new(RwLock::new(Vec::new()));
-
-    store
-        .to_signal()
-        .stop_if(|_| {
-            let len = state_vec.read().unwrap().len();
-            len >= 2
-        })
-        .for_each(|state| {
-            state_vec.write().unwrap().push(state.data);
-            async {}
-        })
-        .await;
-
-    let state_vec = state_vec
-        .read()
-        .unwrap()
-        .iter().cloned()
-        .collect::<Vec<_>>();
-
-    assert_eq!(state_vec[0], Async::success("initial".to_string()));
-    assert_eq!(state_vec[1], Async::loading(Some("initial".to_string())));
-    assert_eq!(
-        state_vec[2],
-        Async::fail_with_message("Operation failed", Some("initial".to_string()))
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [success("initial"), loading(Some("initial")), fail("Operation failed", Some("initial"))]
     );
 }
 
@@ -395,31 +357,10 @@ async fn test_execute_cancellable_with_retain_fail() {
         |state, async_data| state.set_async_data(async_data),
     );
 
-    let state_vec = Arc::new(RwLock::new(Vec::new()));
-
-    store
-        .to_signal()
-        .stop_if(|_| {
-            let len = state_vec.read().unwrap().len();
-            len >= 2
-        })
-        .for_each(|state| {
-            state_vec.write().unwrap().push(state.data);
-            async {}
-        })
-        .await;
-
-    let state_vec = state_vec
-        .read()
-        .unwrap()
-        .iter().cloned()
-        .collect::<Vec<_>>();
-
-    assert_eq!(state_vec[0], Async::success("initial".to_string()));
-    assert_eq!(state_vec[1], Async::loading(Some("initial".to_string())));
-    assert_eq!(
-        state_vec[2],
-        Async::fail_with_message("Result".to_string(), Some("initial".to_string()))
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [success("initial"), loading(Some("initial")), fail("Result", Some("initial"))]
     );
 }
 
@@ -595,3 +536,191 @@ async fn test_execute_with_timeout_computation_join_error() {
         })
         .await;
 }*/
+
+#[tokio::test]
+async fn test_execute_cancellable_loop_sequence() -> Result<(), AsyncError> {
+    use futures::{FutureExt, StreamExt};
+
+    let store = StateStore::new(TestState::default());
+    let mut events = store.subscribe_all();
+    let token = CancellationToken::new();
+    let (started_tx, started_rx) = std::sync::mpsc::channel();
+    let mut iteration = 0;
+
+    let ticket = store.execute_cancellable_loop(
+        token.clone(),
+        Duration::from_millis(5),
+        move |token| {
+            iteration += 1;
+            if iteration == 4 {
+                started_tx.send(()).unwrap();
+                while !token.is_cancelled() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+            }
+            format!("run {iteration}")
+        },
+        |state, data| state.set_async_data(data),
+    );
+
+    tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+        .await
+        .unwrap();
+    token.cancel();
+    ticket.await.unwrap()?;
+    store.await_state().await?;
+
+    let mut sequence = Vec::new();
+    while let Some(Some(event)) = events.next().now_or_never() {
+        sequence.push(event.state().unwrap().data);
+    }
+    assert_eq!(
+        sequence,
+        vec![
+            Async::loading(None),
+            Async::success("run 1".to_string()),
+            Async::loading(None),
+            Async::success("run 2".to_string()),
+            Async::loading(None),
+            Async::success("run 3".to_string()),
+            Async::loading(None),
+            Async::fail_with_cancelled(None),
+        ]
+    );
+    Ok(())
+}
+
+// Test execute_if_changed skips unchanged keys
+#[tokio::test]
+async fn test_execute_if_changed() {
+    let store = StateStore::new(TestState::default());
+    let runs = Arc::new(AtomicUsize::new(0));
+    let run = |query: &'static str| {
+        let runs = runs.clone();
+        move || {
+            runs.fetch_add(1, Ordering::SeqCst);
+            format!("result for {query}")
+        }
+    };
+
+    let first = store.execute_if_changed("rust", run("rust"), |state, data| state.set_async_data(data));
+    first.unwrap().await.unwrap().unwrap();
+    let second = store.execute_if_changed("rust", run("rust"), |state, data| state.set_async_data(data));
+    assert!(second.is_none());
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+
+    let third = store.execute_if_changed("tokio", run("tokio"), |state, data| state.set_async_data(data));
+    third.unwrap().await.unwrap().unwrap();
+    assert_eq!(runs.load(Ordering::SeqCst), 2);
+    assert_eq!(
+        store.await_state().await.unwrap().data,
+        Async::success("result for tokio".to_string())
+    );
+
+    // Keys of another type are tracked independently
+    let other = store.execute_if_changed(1_u32, run("other"), |state, data| state.set_async_data(data));
+    assert!(other.is_some());
+}
+
+// Test execute_with_abort_on_success keeps the fastest success
+#[tokio::test]
+async fn test_execute_with_abort_on_success() {
+    let store = StateStore::new(TestState::default());
+    let finished = Arc::new(AtomicUsize::new(0));
+    let racer = |millis: u64, result: Result<&'static str, &'static str>| {
+        let finished = finished.clone();
+        Box::new(move || {
+            std::thread::sleep(Duration::from_millis(millis));
+            finished.fetch_add(1, Ordering::SeqCst);
+            result.map(str::to_string)
+        }) as Box<dyn FnOnce() -> Result<String, &'static str> + Send>
+    };
+
+    store
+        .execute_with_abort_on_success(
+            vec![racer(300, Ok("slow")), racer(20, Err("fastest fails")), racer(100, Ok("fast"))],
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        store.await_state().await.unwrap().data,
+        Async::success("fast".to_string())
+    );
+    assert_eq!(finished.load(Ordering::SeqCst), 2);
+}
+
+// Test execute_with_abort_on_success writes the last failure when all fail
+#[tokio::test]
+async fn test_execute_with_abort_on_success_all_fail() {
+    let store = StateStore::new(TestState::default());
+    let racer = |millis: u64, error: &'static str| {
+        Box::new(move || {
+            std::thread::sleep(Duration::from_millis(millis));
+            Err::<String, _>(error)
+        }) as Box<dyn FnOnce() -> Result<String, &'static str> + Send>
+    };
+
+    store
+        .execute_with_abort_on_success(
+            vec![racer(80, "last"), racer(10, "first"), racer(40, "second")],
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+    assert_eq!(
+        store.await_state().await.unwrap().data,
+        Async::fail_with_message("last", None)
+    );
+}
+
+// Test execute_notifying resolves with the final result
+#[tokio::test]
+async fn test_execute_notifying() {
+    let store = StateStore::new(TestState::default());
+
+    let (_, result) = store.execute_notifying(
+        || "Hello".to_string(),
+        |state, data| state.set_async_data(data),
+    );
+    assert_eq!(result.await.unwrap(), Async::success("Hello".to_string()));
+
+    let (_, result) = store.async_execute_notifying(
+        async { Err::<String, _>("offline") },
+        |state, data| state.set_async_data(data),
+    );
+    assert_eq!(result.await.unwrap(), Async::fail_with_message("offline", None));
+}
+
+// Test the notifying receivers of cancelled executions
+#[tokio::test]
+async fn test_execute_cancellable_notifying() {
+    let store = StateStore::new(TestState::default());
+
+    let token = CancellationToken::new();
+    let (_, result) = store.execute_cancellable_notifying(
+        token.clone(),
+        |token| {
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            "late".to_string()
+        },
+        |state, data| state.set_async_data(data),
+    );
+    token.cancel();
+    assert!(result.await.unwrap().is_fail_with_canceled());
+
+    let token = CancellationToken::new();
+    token.cancel();
+    let (_, result) = store.async_execute_cancellable_notifying(
+        token,
+        |_| async { "never".to_string() },
+        |state, data| state.set_async_data(data),
+    );
+    assert!(result.await.unwrap().is_fail_with_canceled());
+}
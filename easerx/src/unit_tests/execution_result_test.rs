@@ -1,4 +1,4 @@
-use crate::{Async, ExecutionResult};
+use crate::{Async, AsyncError, ErrorClass, ExecutionResult, IntoAsync};
 
 #[test]
 fn test_value_to_async() {
@@ -65,9 +65,9 @@ fn test_result_with_custom_error() {
     #[derive(Debug)]
     struct CustomError(String);
 
-    impl ToString for CustomError {
-        fn to_string(&self) -> String {
-            self.0.clone()
+    impl std::fmt::Display for CustomError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{}", self.0)
         }
     }
 
@@ -79,3 +79,57 @@ fn test_result_with_custom_error() {
         Async::fail_with_message("custom error".to_string(), None)
     );
 }
+
+#[test]
+fn test_result_with_async_error_keeps_it() {
+    let error = AsyncError::with_class(ErrorClass::RateLimited, "slow down");
+    let result: Result<i32, AsyncError> = Err(error.clone());
+    assert_eq!(result.into_async(), Async::<i32>::fail(error, None));
+
+    let result: Result<i32, AsyncError> = Err(AsyncError::Timeout);
+    assert_eq!(result.into_async(), Async::<i32>::fail(AsyncError::Timeout, None));
+}
+
+#[test]
+fn test_result_with_io_error() {
+    let result: std::io::Result<i32> = Err(std::io::Error::new(std::io::ErrorKind::NotFound, "no such file"));
+    let Async::<i32>::Fail { error, .. } = result.into_async() else {
+        panic!("an error should fail");
+    };
+    assert_eq!(error.to_string(), "no such file");
+    assert_eq!(error.io_error_kind(), Some(std::io::ErrorKind::NotFound));
+    assert_eq!(error.error_class(), Some(ErrorClass::Permanent));
+}
+
+async fn resolve<T: Clone>(work: impl IntoAsync<T>) -> Async<T> {
+    work.into_async_future().await
+}
+
+#[tokio::test]
+async fn test_into_async_from_async() {
+    assert_eq!(resolve(Async::success(42)).await, Async::success(42));
+    assert_eq!(
+        resolve(Async::<i32>::loading(Some(1))).await,
+        Async::loading(Some(1))
+    );
+}
+
+#[tokio::test]
+async fn test_into_async_from_value_future() {
+    assert_eq!(resolve(async { 42 }).await, Async::success(42));
+}
+
+#[tokio::test]
+async fn test_into_async_from_result_future() {
+    let ok = resolve(async { Ok::<i32, String>(42) }).await;
+    assert_eq!(ok, Async::success(42));
+
+    let err = resolve::<i32>(async { Err::<i32, String>("boom".to_string()) }).await;
+    assert_eq!(err, Async::fail(AsyncError::error("boom"), None));
+}
+
+#[tokio::test]
+async fn test_into_async_from_option_future() {
+    assert_eq!(resolve(async { Some(42) }).await, Async::success(42));
+    assert!(resolve::<i32>(async { None::<i32> }).await.is_fail_with_none());
+}
@@ -0,0 +1,120 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+use std::sync::{Mutex, OnceLock};
+use tokio_util::sync::CancellationToken;
+use tracing::span::Id;
+use tracing::Subscriber;
+use tracing_subscriber::layer::{Context, SubscriberExt};
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+const TARGET: &str = "execution_span_test";
+
+/// The names and ids of the spans in scope of one captured event, innermost first.
+type Scope = Vec<(&'static str, Id)>;
+
+/// Records the span scope of every event logged with [`TARGET`].
+struct ScopeRecorder;
+
+impl<S> Layer<S> for ScopeRecorder
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_event(&self, event: &tracing::Event<'_>, ctx: Context<'_, S>) {
+        if event.metadata().target() != TARGET {
+            return;
+        }
+        let scope = ctx
+            .event_scope(event)
+            .map(|scope| scope.map(|span| (span.name(), span.id())).collect())
+            .unwrap_or_default();
+        captured().lock().unwrap().push(scope);
+    }
+}
+
+fn captured() -> &'static Mutex<Vec<Scope>> {
+    static CAPTURED: OnceLock<Mutex<Vec<Scope>>> = OnceLock::new();
+    CAPTURED.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+// Blocking threads don't see a thread-local default subscriber, so install a global one
+fn install_recorder() {
+    static INSTALLED: OnceLock<()> = OnceLock::new();
+    INSTALLED.get_or_init(|| {
+        let subscriber = tracing_subscriber::registry().with(ScopeRecorder);
+        tracing::subscriber::set_global_default(subscriber).expect("no global subscriber yet");
+    });
+}
+
+/// Returns the scope of the captured event logged inside `caller`.
+fn scope_of(caller: &tracing::Span) -> Scope {
+    let caller = caller.id().expect("the caller span is enabled");
+    captured()
+        .lock()
+        .unwrap()
+        .iter()
+        .find(|scope| scope.iter().any(|(_, id)| *id == caller))
+        .cloned()
+        .expect("an event was logged inside the caller's span")
+}
+
+fn assert_runs_in_caller_span(caller: &tracing::Span) {
+    let scope = scope_of(caller);
+    let names: Vec<_> = scope.iter().map(|(name, _)| *name).collect();
+    assert_eq!(names, vec!["execution", "user_action"], "scope: {scope:?}");
+}
+
+#[tokio::test]
+async fn test_blocking_computation_runs_in_caller_span() -> Result<(), AsyncError> {
+    install_recorder();
+    let store = StateStore::new(TestState::default());
+
+    let caller = tracing::info_span!("user_action", action = "blocking");
+    let ticket = caller.in_scope(|| {
+        store.execute(
+            || tracing::info!(target: TARGET, "computing"),
+            |state, _| state,
+        )
+    });
+    ticket.await.unwrap()?;
+
+    assert_runs_in_caller_span(&caller);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_computation_runs_in_caller_span() -> Result<(), AsyncError> {
+    install_recorder();
+    let store = StateStore::new(TestState::default());
+
+    let caller = tracing::info_span!("user_action", action = "async");
+    let ticket = caller.in_scope(|| {
+        store.async_execute(
+            async { tracing::info!(target: TARGET, "computing") },
+            |state, _| state,
+        )
+    });
+    ticket.await.unwrap()?;
+
+    assert_runs_in_caller_span(&caller);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancellable_computation_runs_in_caller_span() -> Result<(), AsyncError> {
+    install_recorder();
+    let store = StateStore::new(TestState::default());
+
+    let caller = tracing::info_span!("user_action", action = "cancellable");
+    let ticket = caller.in_scope(|| {
+        store.execute_cancellable(
+            CancellationToken::new(),
+            |_| tracing::info!(target: TARGET, "computing"),
+            |state, _| state,
+        )
+    });
+    ticket.await.unwrap()?;
+
+    assert_runs_in_caller_span(&caller);
+    Ok(())
+}
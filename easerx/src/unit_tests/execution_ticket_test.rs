@@ -0,0 +1,73 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore};
+
+#[tokio::test]
+async fn test_ticket_resolves_like_join_handle() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let ticket = store.execute(|| "done".to_string(), |state, data| state.set_async_data(data));
+    ticket.await.unwrap()?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.data, Async::success("done".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ticket_abort() {
+    let store = StateStore::new(TestState::default());
+
+    let ticket = store.async_execute(
+        async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            "done".to_string()
+        },
+        |state, data| state.set_async_data(data),
+    );
+    ticket.abort();
+
+    assert!(ticket.await.unwrap_err().is_cancelled());
+}
+
+#[cfg(all(debug_assertions, feature = "tracing"))]
+#[tokio::test]
+async fn test_dropped_ticket_logs_execution_error() {
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let captured = Captured::default();
+    let writer = captured.clone();
+    let subscriber = tracing_subscriber::fmt()
+        .with_writer(move || writer.clone())
+        .with_ansi(false)
+        .finish();
+    let _guard = tracing::subscriber::set_default(subscriber);
+
+    let store = StateStore::new(TestState::default());
+    // Closing stops the store's queue, so executions can no longer write their results
+    store.close();
+    store.closed().await;
+    assert!(store.await_state().await.is_err());
+
+    drop(store.execute(|| "lost".to_string(), |state, data| state.set_async_data(data)));
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+
+    let logs = String::from_utf8(captured.0.lock().unwrap().clone()).unwrap();
+    assert!(logs.contains("execution failed after its ExecutionTicket was dropped"), "{logs}");
+}
@@ -0,0 +1,114 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+fn recording_store() -> (StateStore<TestState>, Arc<Mutex<Vec<AsyncError>>>) {
+    let store = StateStore::new(TestState::default());
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let recorded = errors.clone();
+    store.on_async_fail(move |error| recorded.lock().unwrap().push(error.clone()));
+    (store, errors)
+}
+
+#[tokio::test]
+async fn test_on_async_fail_reports_errors() -> Result<(), AsyncError> {
+    let (store, errors) = recording_store();
+    store
+        .execute(|| Err::<String, _>("offline"), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    store.await_state().await?;
+    assert_eq!(*errors.lock().unwrap(), vec![AsyncError::error("offline")]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_async_fail_reports_timeout() -> Result<(), AsyncError> {
+    let (store, errors) = recording_store();
+    store
+        .async_execute_with_timeout(
+            async {
+                tokio::time::sleep(Duration::from_secs(10)).await;
+                "late".to_string()
+            },
+            Duration::from_millis(10),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    store.await_state().await?;
+    let errors = errors.lock().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].is_timeout());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_async_fail_reports_cancelled() -> Result<(), AsyncError> {
+    let (store, errors) = recording_store();
+    let token = CancellationToken::new();
+    let ticket = store.execute_cancellable(
+        token.clone(),
+        |token| {
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(1));
+            }
+            "cancelled".to_string()
+        },
+        |state, data| state.set_async_data(data),
+    );
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    token.cancel();
+    ticket.await.unwrap()?;
+    store.await_state().await?;
+    let errors = errors.lock().unwrap();
+    assert_eq!(errors.len(), 1);
+    assert!(errors[0].is_cancelled());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_async_fail_ignores_success_and_manual_fail() -> Result<(), AsyncError> {
+    let (store, errors) = recording_store();
+    store
+        .execute(|| "ok".to_string(), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    store.set_state(|state| state.set_async_data(Async::fail_with_timeout(None)))?;
+    store.await_state().await?;
+    assert!(errors.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_async_fail_contains_panicking_handler() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.on_async_fail(|_| panic!("handler failure"));
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let recorded = errors.clone();
+    store.on_async_fail(move |error| recorded.lock().unwrap().push(error.clone()));
+    store
+        .execute(|| Err::<String, _>("offline"), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    let state = store.await_state().await?;
+    assert!(state.data.error_eq(&AsyncError::error("offline")));
+    assert_eq!(errors.lock().unwrap().len(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_async_fail_reports_parallel_batch() -> Result<(), AsyncError> {
+    let (store, errors) = recording_store();
+    store
+        .execute_batch_parallel()
+        .add(|| Err::<String, _>("batch"), |state, data| state.set_async_data(data))
+        .execute_all()
+        .await
+        .unwrap()?;
+    store.await_state().await?;
+    assert_eq!(*errors.lock().unwrap(), vec![AsyncError::error("batch")]);
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use std::sync::{Arc, Mutex};
+use crate::unit_tests::TestState;
+use crate::{AsyncError, FairnessPolicy, StateStore, DEFAULT_MAX_CONSECUTIVE_ACTIONS};
+
+type Log = Arc<Mutex<Vec<&'static str>>>;
+
+/// Queues a chain of `remaining` reads, each queuing the next from within the previous one.
+fn read_chain(store: StateStore<TestState>, log: Log, remaining: usize) {
+    let next = store.clone();
+    store
+        .with_state(move |_| {
+            log.lock().unwrap().push("read");
+            if remaining > 1 {
+                read_chain(next, log, remaining - 1);
+            }
+        })
+        .unwrap();
+}
+
+/// Returns the longest run of reads between two writes, ignoring the reads after the last write.
+fn longest_read_run(log: &[&str]) -> usize {
+    let last_write = log.iter().rposition(|entry| *entry == "write").expect("a write was logged");
+    log[..last_write]
+        .split(|entry| *entry == "write")
+        .map(<[_]>::len)
+        .max()
+        .unwrap_or(0)
+}
+
+async fn interleave(store: StateStore<TestState>, reads: usize, writes: usize) -> Result<Vec<&'static str>, AsyncError> {
+    let log = Log::default();
+    read_chain(store.clone(), Arc::clone(&log), reads);
+    let writer = tokio::spawn({
+        let store = store.clone();
+        let log = Arc::clone(&log);
+        async move {
+            for _ in 0..writes {
+                let log = Arc::clone(&log);
+                store.set_state(move |state| {
+                    log.lock().unwrap().push("write");
+                    state.add_count(1)
+                })?;
+                // Woken as soon as the write is applied, so the writer is ready at the next yield
+                store.await_state().await?;
+            }
+            Ok::<_, AsyncError>(())
+        }
+    });
+    writer.await.unwrap()?;
+    assert_eq!(store.await_state().await?.count, writes as i32);
+    let log = log.lock().unwrap().clone();
+    Ok(log)
+}
+
+// `tokio::test` runs on a current-thread runtime, where the writer only runs when the queue yields
+#[tokio::test]
+async fn test_read_chain_lets_writes_through_on_current_thread() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let log = interleave(store, 10_000, 50).await?;
+
+    let first_write = log.iter().position(|entry| *entry == "write").unwrap();
+    assert!(first_write <= DEFAULT_MAX_CONSECUTIVE_ACTIONS, "first write after {first_write} reads");
+    assert!(longest_read_run(&log) <= DEFAULT_MAX_CONSECUTIVE_ACTIONS);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fairness_policy_bounds_read_runs() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .fairness(FairnessPolicy::new().max_consecutive_actions(2))
+        .build();
+    let log = interleave(store, 1_000, 100).await?;
+    assert!(longest_read_run(&log) <= 2, "{log:?}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_fairness_policy_settings() {
+    assert_eq!(FairnessPolicy::default().max_consecutive_actions_setting(), DEFAULT_MAX_CONSECUTIVE_ACTIONS);
+    assert_eq!(FairnessPolicy::new().max_consecutive_actions(0).max_consecutive_actions_setting(), 1);
+}
@@ -0,0 +1,77 @@
+use futures_signals::signal::SignalExt;
+use futures::StreamExt;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, Query, StateStore};
+
+struct GetCount;
+
+impl Query<TestState> for GetCount {
+    type Output = i32;
+}
+
+#[tokio::test]
+async fn test_frozen_store_rejects_writes() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.set_count(1))?;
+    store.freeze();
+    assert!(store.is_frozen());
+
+    // Clones share the frozen flag
+    let clone = store.clone();
+    assert_eq!(clone.set_state(|state| state.set_count(2)), Err(AsyncError::Frozen));
+    assert_eq!(store.priority_set_state(|state| state.set_count(3)), Err(AsyncError::Frozen));
+    let updated = store.update_async(|state| Box::pin(async move { state.set_count(4) })).await;
+    assert!(updated.unwrap_err().is_frozen());
+
+    // The update queued before the freeze is still applied
+    assert_eq!(store.await_state().await?.count, 1);
+    assert_eq!(store.version(), 1);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_frozen_store_rejects_execution_results() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.freeze();
+    let result = store
+        .execute(|| "done".to_string(), |state, data| state.set_async_data(data))
+        .await
+        .unwrap();
+    assert_eq!(result, Err(AsyncError::Frozen));
+    assert!(store.await_state().await?.data.is_uninitialized());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_frozen_store_keeps_serving_reads() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.register_query::<GetCount, _>(|state, _| state.count);
+    let mut signal = store.to_signal().map(|state| state.count).to_stream();
+    store.set_state(|state| state.set_count(5))?;
+    store.freeze();
+
+    assert_eq!(store.await_state().await?.count, 5);
+    assert_eq!(store.get_state().count, 5);
+    assert_eq!(store.query(GetCount).await?, 5);
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    store.with_state(move |state| {
+        let _ = tx.send(state.count);
+    })?;
+    assert_eq!(rx.await.unwrap(), 5);
+    assert_eq!(signal.next().await, Some(5));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_unfreeze_restores_writes() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.freeze();
+    assert!(store.set_state(|state| state.set_count(1)).is_err());
+    store.unfreeze();
+    assert!(!store.is_frozen());
+
+    store.set_state(|state| state.set_count(2))?;
+    assert_eq!(store.await_state().await?.count, 2);
+    Ok(())
+}
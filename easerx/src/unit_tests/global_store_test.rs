@@ -0,0 +1,46 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, GlobalStore};
+
+static INITIALIZED: GlobalStore<TestState> = GlobalStore::new();
+static LAZY: GlobalStore<TestState> = GlobalStore::lazy(TestState::default);
+static UNINITIALIZED: GlobalStore<TestState> = GlobalStore::new();
+
+#[tokio::test]
+async fn test_initialize_global_store() -> Result<(), AsyncError> {
+    assert!(INITIALIZED.get().is_none());
+
+    INITIALIZED.initialize(TestState::default().set_count(1))?;
+    INITIALIZED.set_state(|state| state.add_count(1))?;
+
+    assert!(INITIALIZED.is_initialized());
+    assert_eq!(INITIALIZED.await_state().await?.count, 2);
+    assert!(INITIALIZED.initialize(TestState::default()).is_err());
+    assert_eq!(INITIALIZED.get_state().count, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_lazy_global_store_is_created_on_first_access() -> Result<(), AsyncError> {
+    assert!(!LAZY.is_initialized());
+
+    LAZY.set_state(|state| state.add_count(3))?;
+
+    assert!(LAZY.is_initialized());
+    assert_eq!(LAZY.await_state().await?.count, 3);
+    assert!(LAZY.initialize(TestState::default()).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_initialize_outside_runtime_fails() {
+    let result = UNINITIALIZED.initialize(TestState::default());
+    assert!(result.is_err());
+    assert!(!UNINITIALIZED.is_initialized());
+}
+
+#[test]
+#[should_panic(expected = "GlobalStore used before GlobalStore::initialize was called")]
+fn test_access_before_initialize_panics() {
+    static NEVER_INITIALIZED: GlobalStore<TestState> = GlobalStore::new();
+    let _ = NEVER_INITIALIZED.get_state();
+}
@@ -0,0 +1,81 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore, DEFAULT_CANCEL_GRACE};
+
+#[tokio::test]
+async fn test_graceful_cancel_runs_cleanup_before_writing_cancelled() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let token = CancellationToken::new();
+    let cleaned = Arc::new(AtomicBool::new(false));
+    let ticket = store.async_execute_cancellable_graceful(
+        token.clone(),
+        |_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            "uploaded".to_string()
+        },
+        {
+            let cleaned = Arc::clone(&cleaned);
+            let store = store.clone();
+            move || async move {
+                // The cancellation is not written until the cleanup finished
+                assert!(store.get_state().data.is_loading());
+                cleaned.store(true, Ordering::SeqCst);
+            }
+        },
+        |state, data| state.set_async_data(data),
+    );
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    token.cancel();
+    ticket.await.unwrap()?;
+
+    assert!(cleaned.load(Ordering::SeqCst));
+    assert_eq!(store.await_state().await?.data, Async::fail_with_cancelled(None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_graceful_cancel_skips_cleanup_on_success() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let cleaned = Arc::new(AtomicBool::new(false));
+    store
+        .async_execute_cancellable_graceful(
+            CancellationToken::new(),
+            |_| async { "uploaded".to_string() },
+            {
+                let cleaned = Arc::clone(&cleaned);
+                move || async move { cleaned.store(true, Ordering::SeqCst) }
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+
+    assert!(!cleaned.load(Ordering::SeqCst));
+    assert_eq!(store.await_state().await?.data, Async::success("uploaded".to_string()));
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_graceful_cancel_caps_slow_cleanup() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let token = CancellationToken::new();
+    token.cancel();
+    let started = Instant::now();
+    store
+        .async_execute_cancellable_graceful(
+            token,
+            |_| async { "uploaded".to_string() },
+            || tokio::time::sleep(Duration::from_secs(3600)),
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+
+    assert!(started.elapsed() >= DEFAULT_CANCEL_GRACE && started.elapsed() < Duration::from_secs(3600));
+    assert_eq!(store.await_state().await?.data, Async::fail_with_cancelled(None));
+    Ok(())
+}
@@ -0,0 +1,164 @@
+use crate::{Async, AsyncError, JobKey, State, StateStore};
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct ScreensState {
+    user: Async<String>,
+    feed: Async<String>,
+    settings: Async<String>,
+}
+
+impl State for ScreensState {}
+
+impl ScreensState {
+    fn set_user(self, user: Async<String>) -> Self {
+        Self { user, ..self }
+    }
+
+    fn set_feed(self, feed: Async<String>) -> Self {
+        Self { feed, ..self }
+    }
+
+    fn set_settings(self, settings: Async<String>) -> Self {
+        Self { settings, ..self }
+    }
+}
+
+#[test]
+fn test_job_key_hierarchy() {
+    let key = JobKey::group("screen_x").child("load_user").child("avatar");
+    assert_eq!(key.group_name(), "screen_x");
+    assert_eq!(key.segments(), ["screen_x", "load_user", "avatar"]);
+    assert_eq!(key.to_string(), "screen_x/load_user/avatar");
+}
+
+#[tokio::test]
+async fn test_cancel_group_leaves_other_groups_running() -> Result<(), AsyncError> {
+    let store = StateStore::new(ScreensState::default());
+
+    let user = store.async_execute_with_key(
+        JobKey::group("screen_a").child("load_user"),
+        |token| async move {
+            token.cancelled().await;
+            "user".to_string()
+        },
+        ScreensState::set_user,
+    );
+    let feed = store.execute_with_key(
+        JobKey::group("screen_a").child("load_feed"),
+        |token| {
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            "feed".to_string()
+        },
+        ScreensState::set_feed,
+    );
+    let settings = store.async_execute_with_key(
+        JobKey::group("screen_b").child("load_settings"),
+        |_token| async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            "settings".to_string()
+        },
+        ScreensState::set_settings,
+    );
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(
+        store.active_jobs("screen_a"),
+        vec![
+            JobKey::group("screen_a").child("load_user"),
+            JobKey::group("screen_a").child("load_feed"),
+        ]
+    );
+    assert_eq!(
+        store.active_jobs("screen_b"),
+        vec![JobKey::group("screen_b").child("load_settings")]
+    );
+
+    store.cancel_group("screen_a");
+    assert!(store.active_jobs("screen_a").is_empty());
+
+    user.await.unwrap()?;
+    feed.await.unwrap()?;
+    settings.await.unwrap()?;
+
+    let state = store.await_state().await?;
+    assert!(state.user.is_fail_with_canceled());
+    assert!(state.feed.is_fail_with_canceled());
+    assert_eq!(state.settings, Async::success("settings".to_string()));
+    assert!(store.active_jobs("screen_b").is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_group_is_reusable_after_cancel() -> Result<(), AsyncError> {
+    let store = StateStore::new(ScreensState::default());
+    store.cancel_group("screen_a");
+
+    store
+        .async_execute_with_key(
+            JobKey::group("screen_a").child("load_user"),
+            |_token| async { "user".to_string() },
+            ScreensState::set_user,
+        )
+        .await
+        .unwrap()?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.user, Async::success("user".to_string()));
+    assert!(store.active_jobs("screen_a").is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "debug-jobs")]
+#[tokio::test]
+async fn test_token_chain_links_job_to_group_token() -> Result<(), AsyncError> {
+    let store = StateStore::new(ScreensState::default());
+    let pending = |token: tokio_util::sync::CancellationToken| async move {
+        token.cancelled().await;
+        "cancelled".to_string()
+    };
+
+    let user = store.async_execute_with_key(JobKey::group("screen_a").child("load_user"), pending, ScreensState::set_user);
+    let feed = store.async_execute_with_key(JobKey::group("screen_a").child("load_feed"), pending, ScreensState::set_feed);
+    let settings = store.async_execute_with_key(
+        JobKey::group("screen_b").child("load_settings"),
+        pending,
+        ScreensState::set_settings,
+    );
+
+    let user_chain = user.token_chain().to_vec();
+    let feed_chain = feed.token_chain().to_vec();
+    let settings_chain = settings.token_chain().to_vec();
+    assert_eq!(user_chain.len(), 2);
+    assert_eq!(user_chain[0], feed_chain[0], "jobs of a group share the parent token");
+    assert_ne!(user_chain[1], feed_chain[1]);
+    assert_ne!(user_chain[0], settings_chain[0]);
+
+    let jobs = store.debug_jobs();
+    let keys: Vec<_> = jobs.iter().map(|job| job.key().to_string()).collect();
+    assert_eq!(keys, ["screen_a/load_user", "screen_a/load_feed", "screen_b/load_settings"]);
+    assert_eq!(jobs[0].token_chain(), user_chain);
+    assert_eq!(jobs[1].token_chain(), feed_chain);
+    assert_eq!(jobs[2].token_chain(), settings_chain);
+    assert!(jobs[0].started_at() <= jobs[1].started_at());
+
+    store.cancel_group("screen_a");
+    store.cancel_group("screen_b");
+    user.await.unwrap()?;
+    feed.await.unwrap()?;
+    settings.await.unwrap()?;
+    assert!(store.debug_jobs().is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "debug-jobs")]
+#[tokio::test]
+async fn test_token_chain_is_empty_without_key() -> Result<(), AsyncError> {
+    let store = StateStore::new(ScreensState::default());
+    let ticket = store.execute(|| "user".to_string(), ScreensState::set_user);
+    assert!(ticket.token_chain().is_empty());
+    ticket.await.unwrap()?;
+    Ok(())
+}
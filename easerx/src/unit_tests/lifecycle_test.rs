@@ -0,0 +1,109 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+
+fn idle_counter() -> (Arc<AtomicUsize>, impl Fn(&StateStore<TestState>) + Send + Sync + 'static) {
+    let fired = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&fired);
+    (fired, move |_: &StateStore<TestState>| {
+        counter.fetch_add(1, Ordering::SeqCst);
+    })
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_on_idle_fires_after_quiet_period() -> Result<(), AsyncError> {
+    let (fired, hook) = idle_counter();
+    let store = StateStore::builder(TestState::default())
+        .on_idle(Duration::from_secs(10), hook)
+        .build();
+
+    tokio::time::sleep(Duration::from_secs(9)).await;
+    assert_eq!(fired.load(Ordering::SeqCst), 0);
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+    // Fires once per quiet period
+    tokio::time::sleep(Duration::from_secs(60)).await;
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+
+    // Activity rearms it
+    store.set_state(|state| state.add_count(1))?;
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    assert_eq!(fired.load(Ordering::SeqCst), 2);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_on_idle_resets_on_activity() -> Result<(), AsyncError> {
+    let (fired, hook) = idle_counter();
+    let store = StateStore::builder(TestState::default())
+        .on_idle(Duration::from_secs(10), hook)
+        .build();
+
+    for _ in 0..6 {
+        tokio::time::sleep(Duration::from_secs(5)).await;
+        store.with_state(|_| {})?;
+    }
+    assert_eq!(fired.load(Ordering::SeqCst), 0);
+
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    assert_eq!(fired.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_on_idle_hook_can_update_the_store() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .on_idle(Duration::from_secs(10), |store| {
+            let _ = store.set_state(|state| state.set_count(-1));
+        })
+        .build();
+    store.set_state(|state| state.set_count(5))?;
+
+    tokio::time::sleep(Duration::from_secs(11)).await;
+    assert_eq!(store.await_state().await?.count, -1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_close_receives_final_state() -> Result<(), AsyncError> {
+    let final_state = Arc::new(Mutex::new(None));
+    let slot = Arc::clone(&final_state);
+    let store = StateStore::builder(TestState::default())
+        .on_close(move |state| *slot.lock().unwrap() = Some(state))
+        .build();
+
+    store.set_state(|state| state.set_count(3))?;
+    store.set_state(|state| state.add_count(1))?;
+    store.close();
+    assert_eq!(*final_state.lock().unwrap(), None);
+    store.closed().await;
+
+    // The updates queued before the close were drained first
+    assert_eq!(final_state.lock().unwrap().as_ref().map(|state| state.count), Some(4));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_on_close_runs_when_store_is_dropped() {
+    let closed = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&closed);
+    let store = StateStore::builder(TestState::default())
+        .on_close(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .build();
+    let other = store.clone();
+    drop(store);
+    drop(other);
+
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while closed.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .expect("the close hook should run");
+}
@@ -0,0 +1,138 @@
+use crate::unit_tests::TestState;
+use crate::{link, AsyncError, StateStore};
+use std::time::Duration;
+use tokio::time::sleep;
+
+async fn settle() {
+    sleep(Duration::from_millis(10)).await;
+}
+
+#[tokio::test]
+async fn test_link_fires_on_rising_edges_only() -> Result<(), AsyncError> {
+    let source = StateStore::new(TestState::default());
+    let target = StateStore::new(TestState::default());
+    let _link = link(
+        &source,
+        |state| state.count > 0,
+        &target,
+        |target, _| target.set_state_forget(|state| state.add_count(1)),
+    );
+
+    // Every commit is observed, so the drop to zero between 2 and 3 is not conflated away
+    for count in [1, 2, 5, 0, 3, 4] {
+        source.set_state(move |state| state.set_count(count))?;
+    }
+    source.await_state().await?;
+    settle().await;
+
+    assert_eq!(target.await_state().await?.count, 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_passes_the_triggering_state() -> Result<(), AsyncError> {
+    let source = StateStore::new(TestState::default());
+    let target = StateStore::new(TestState::default());
+    let _link = link(
+        &source,
+        |state| state.count >= 10,
+        &target,
+        |target, source| {
+            let count = source.count;
+            target.set_state_forget(move |state| state.set_count(count))
+        },
+    );
+
+    for count in [5, 12, 15] {
+        source.set_state(move |state| state.set_count(count))?;
+    }
+    settle().await;
+
+    assert_eq!(target.await_state().await?.count, 12);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_fires_when_predicate_already_holds() -> Result<(), AsyncError> {
+    let source = StateStore::new(TestState::default().set_count(1));
+    let target = StateStore::new(TestState::default());
+    let _link = link(
+        &source,
+        |state| state.count > 0,
+        &target,
+        |target, _| target.set_state_forget(|state| state.add_count(1)),
+    );
+    settle().await;
+    source.set_state(|state| state.set_count(2))?;
+    settle().await;
+
+    assert_eq!(target.await_state().await?.count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dropping_link_handle_stops_the_link() -> Result<(), AsyncError> {
+    let source = StateStore::new(TestState::default());
+    let target = StateStore::new(TestState::default());
+    let handle = link(
+        &source,
+        |state| state.count > 0,
+        &target,
+        |target, _| target.set_state_forget(|state| state.add_count(1)),
+    );
+
+    source.set_state(|state| state.set_count(1))?;
+    settle().await;
+    assert!(!handle.is_finished());
+    drop(handle);
+
+    source.set_state(|state| state.set_count(0))?;
+    source.set_state(|state| state.set_count(1))?;
+    settle().await;
+
+    assert_eq!(target.await_state().await?.count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_link_finishes_when_source_is_dropped() -> Result<(), AsyncError> {
+    let source = StateStore::new(TestState::default());
+    let target = StateStore::new(TestState::default());
+    let handle = link(&source, |state| state.count > 0, &target, |_, _| {});
+    drop(source);
+    settle().await;
+
+    assert!(handle.is_finished());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_links_in_both_directions_do_not_deadlock() -> Result<(), AsyncError> {
+    let ping = StateStore::new(TestState::default());
+    let pong = StateStore::new(TestState::default());
+    // Each store hands the next odd number to the other, dropping it to zero first for a new rising edge
+    let hand_over = |target: &StateStore<TestState>, source: &TestState| {
+        let next = source.count + 2;
+        target.set_state_forget(|state| state.set_count(0));
+        target.set_state_forget(move |state| state.set_count(next));
+    };
+    let _forth = link(&ping, |state| state.count % 2 == 1 && state.count < 10, &pong, hand_over);
+    let _back = link(&pong, |state| state.count % 2 == 1 && state.count < 10, &ping, hand_over);
+
+    ping.set_state(|state| state.set_count(1))?;
+    let pong_state = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let state = pong.await_state().await?;
+            if state.count == 11 {
+                return Ok::<_, AsyncError>(state);
+            }
+            settle().await;
+        }
+    })
+    .await
+    .expect("links deadlocked")?;
+
+    assert_eq!(pong_state.count, 11);
+    assert_eq!(ping.await_state().await?.count, 9);
+    Ok(())
+}
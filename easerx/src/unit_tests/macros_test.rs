@@ -0,0 +1,56 @@
+use crate::{async_match, Async, AsyncError};
+
+fn describe(value: &Async<i32>) -> String {
+    async_match!(value;
+        Uninitialized => "uninitialized".to_string(),
+        Loading(Some(previous)) => format!("reloading {previous}"),
+        Loading(None) => "loading".to_string(),
+        Success(value) => format!("success {value}"),
+        Fail(error, retained) => format!("fail {error} {retained:?}"),
+    )
+}
+
+#[test]
+fn test_async_match_all_arms() {
+    assert_eq!(describe(&Async::Uninitialized), "uninitialized");
+    assert_eq!(describe(&Async::loading(None)), "loading");
+    assert_eq!(describe(&Async::loading(Some(1))), "reloading 1");
+    assert_eq!(describe(&Async::success(2)), "success 2");
+    assert_eq!(
+        describe(&Async::fail(AsyncError::Timeout, Some(3))),
+        format!("fail {} Some(3)", AsyncError::Timeout)
+    );
+}
+
+#[test]
+fn test_async_match_guards() {
+    let classify = |value: Async<i32>| {
+        async_match!(value;
+            Success(value) if value > 10 => "big",
+            Success(_) => "small",
+            Fail(AsyncError::Cancelled, _) => "cancelled",
+            Fail(_, retained) if retained.is_some() => "failed with data",
+            _ => "other"
+        )
+    };
+
+    assert_eq!(classify(Async::success(11)), "big");
+    assert_eq!(classify(Async::success(1)), "small");
+    assert_eq!(classify(Async::fail_with_cancelled(Some(1))), "cancelled");
+    assert_eq!(classify(Async::fail_with_timeout(Some(1))), "failed with data");
+    assert_eq!(classify(Async::fail_with_timeout(None)), "other");
+    assert_eq!(classify(Async::loading(None)), "other");
+}
+
+#[test]
+fn test_async_match_block_bodies() {
+    let mut loads = 0;
+    let value = async_match!(Async::success(5);
+        Success(value) => {
+            loads += 1;
+            value * 2
+        },
+        _ => 0,
+    );
+    assert_eq!((value, loads), (10, 1));
+}
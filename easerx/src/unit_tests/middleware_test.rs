@@ -0,0 +1,103 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, MetricsMiddleware, Middleware, StateStore};
+use std::any::Any;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Default)]
+struct RecordingMiddleware {
+    name: &'static str,
+    log: Arc<Mutex<Vec<String>>>,
+}
+
+impl Middleware<TestState> for RecordingMiddleware {
+    fn before_set_state(&self, _reducer: &dyn Any) {
+        self.log.lock().unwrap().push(format!("{}:before", self.name));
+    }
+
+    fn after_set_state(&self, old: &TestState, new: &TestState) {
+        self.log
+            .lock()
+            .unwrap()
+            .push(format!("{}:{}->{}", self.name, old.count, new.count));
+    }
+}
+
+#[tokio::test]
+async fn test_middlewares_chained_in_order() -> Result<(), AsyncError> {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let first = RecordingMiddleware {
+        name: "first",
+        log: log.clone(),
+    };
+    let second = RecordingMiddleware {
+        name: "second",
+        log: log.clone(),
+    };
+    let store = StateStore::new(TestState::default())
+        .with_middleware(first)
+        .with_middleware(second);
+
+    store.set_state(|state| state.add_count(1))?;
+    store.set_state(|state| state.add_count(2))?;
+    store.await_state().await?;
+
+    assert_eq!(
+        *log.lock().unwrap(),
+        vec![
+            "first:before",
+            "second:before",
+            "first:0->1",
+            "second:0->1",
+            "first:before",
+            "second:before",
+            "first:1->3",
+            "second:1->3",
+        ]
+    );
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_metrics_middleware_counts_execute_updates() -> Result<(), AsyncError> {
+    let metrics = MetricsMiddleware::new();
+    let store = StateStore::builder(TestState::default())
+        .middleware(metrics.clone())
+        .build();
+
+    store
+        .execute(|| "done".to_string(), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    store.await_state().await?;
+
+    // Loading and Success
+    assert_eq!(metrics.received(), 2);
+    assert_eq!(metrics.committed(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rejected_compare_and_set_is_not_committed() -> Result<(), AsyncError> {
+    let metrics = MetricsMiddleware::new();
+    let store = StateStore::new(TestState::default()).with_middleware(metrics.clone());
+
+    store.set_state(|state| state.add_count(1))?;
+    let _ = store.set_state_if_version(0, |state| state.add_count(1)).await;
+
+    assert_eq!(metrics.received(), 2);
+    assert_eq!(metrics.committed(), 1);
+    Ok(())
+}
+
+#[cfg(feature = "tracing")]
+#[tokio::test]
+async fn test_logging_middleware() -> Result<(), AsyncError> {
+    let store =
+        StateStore::new(TestState::default()).with_middleware(crate::LoggingMiddleware::named("test"));
+
+    store.set_state(|state| state.add_count(1))?;
+
+    assert_eq!(store.await_state().await?.count, 1);
+    Ok(())
+}
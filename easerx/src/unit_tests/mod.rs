@@ -2,12 +2,103 @@ use crate::{Async, State};
 
 // Import test modules
 mod async_state_test;
+mod async_tracked_test;
+mod async_with_count_test;
+mod async_staged_test;
+#[cfg(feature = "uuid")]
+mod async_with_correlation_test;
+mod arc_async_test;
+mod render_hint_test;
 mod async_error_test;
 mod execution_result_test;
+#[cfg(feature = "execute")]
 mod async_executes_test;
+#[cfg(feature = "execute")]
 mod execute_test;
+#[cfg(feature = "execute")]
+mod execute_mut_test;
+#[cfg(feature = "execute")]
+mod retain_opt_test;
+#[cfg(feature = "execute")]
+mod placeholder_test;
+#[cfg(feature = "execute")]
+mod execution_ticket_test;
+#[cfg(feature = "execute")]
+mod execute_options_test;
+#[cfg(feature = "execute")]
+mod retain_policy_test;
+#[cfg(feature = "execute")]
+mod graceful_cancel_test;
+#[cfg(feature = "execute")]
+mod serialize_executions_test;
+#[cfg(feature = "execute")]
+mod execute_into_test;
+#[cfg(feature = "execute")]
+mod patterns_test;
+#[cfg(feature = "execute")]
+mod deadline_test;
+#[cfg(all(feature = "execute", feature = "tracing"))]
+mod execution_span_test;
 mod state_store_test;
+mod state_variant_test;
+mod freeze_test;
+mod read_only_test;
+mod apply_from_test;
+mod barrier_test;
+mod wait_error_test;
+mod fairness_test;
+#[cfg(feature = "arc-swap")]
+mod read_cache_test;
+mod state_size_test;
+mod global_store_test;
+mod state_event_test;
+mod store_error_test;
+mod state_stream_test;
+mod blocking_test;
+#[cfg(feature = "execute")]
+mod blocking_pool_test;
+mod middleware_test;
+#[cfg(feature = "execute")]
+mod fail_handler_test;
+#[cfg(feature = "execute")]
+mod error_recovery_test;
+#[cfg(feature = "execute")]
+mod panic_policy_test;
 mod stream_ext_test;
+mod macros_test;
+mod two_phase_test;
+#[cfg(feature = "execute")]
+mod startup_test;
+mod testing_test;
+mod strict_stream_test;
+mod script_test;
+mod bench_test;
+mod deterministic_store_test;
+mod priority_lane_test;
+#[cfg(feature = "debug-transitions")]
+mod transition_test;
+mod approx_eq_test;
+mod query_test;
+#[cfg(feature = "execute")]
+mod job_test;
+#[cfg(feature = "rayon")]
+mod thread_pool_test;
+#[cfg(feature = "execute")]
+mod parallel_batch_test;
+#[cfg(feature = "execute")]
+mod polling_test;
+mod subscription_test;
+mod link_test;
+mod lifecycle_test;
+mod store_ref_test;
+mod store_map_test;
+mod version_test;
+#[cfg(feature = "serde")]
+mod codec_test;
+#[cfg(feature = "serde")]
+mod persistence_test;
+#[cfg(feature = "remote")]
+mod remote_test;
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct TestState {
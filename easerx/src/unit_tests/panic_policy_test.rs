@@ -0,0 +1,118 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, PanicPolicy, StateStore};
+use tokio_util::sync::CancellationToken;
+
+fn panic_error(message: &str) -> AsyncError {
+    AsyncError::Panic {
+        message: message.to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_blocking_panic_is_captured() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .execute(
+            || -> String { panic!("blocking panic") },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.is_fail_with_panic());
+    assert!(data.error_eq(&panic_error("blocking panic")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_panic_with_formatted_message() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let code = 42;
+    store
+        .execute_cancellable(
+            CancellationToken::new(),
+            move |_| -> String { panic!("failed with code {code}") },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.error_eq(&panic_error("failed with code 42")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_panic_is_captured() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .async_execute(
+            async {
+                tokio::task::yield_now().await;
+                if true {
+                    panic!("async panic");
+                }
+                "unreachable".to_string()
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert_eq!(data, Async::fail(panic_error("async panic"), None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parallel_batch_panic_is_captured() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .execute_batch_parallel()
+        .add(
+            || -> String { panic!("batch panic") },
+            |state, data| state.set_async_data(data),
+        )
+        .execute_all()
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.error_eq(&panic_error("batch panic")));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_policy_propagates_blocking_panic() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .panic_policy(PanicPolicy::Resume)
+        .build();
+    let joined = store
+        .execute(
+            || -> String { panic!("loud panic") },
+            |state, data| state.set_async_data(data),
+        )
+        .await;
+    let error = joined.expect_err("the execution task should panic");
+    assert!(error.is_panic());
+
+    let data = store.await_state().await?.data;
+    assert!(data.is_loading());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_resume_policy_propagates_async_panic() {
+    let store = StateStore::builder(TestState::default())
+        .panic_policy(PanicPolicy::Resume)
+        .build();
+    let joined = store
+        .async_execute(
+            async {
+                if true {
+                    panic!("loud async panic");
+                }
+                "unreachable".to_string()
+            },
+            |state, data| state.set_async_data(data),
+        )
+        .await;
+    assert!(joined.expect_err("the execution task should panic").is_panic());
+}
@@ -0,0 +1,150 @@
+use crate::{Async, AsyncError, ParallelBatch, RecoveryAction, State, StateStore};
+use futures_signals::signal::SignalExt;
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct BatchState {
+    num: Async<i32>,
+    name: Async<String>,
+    flags: Async<Vec<bool>>,
+    completed: bool,
+}
+
+impl State for BatchState {}
+
+#[tokio::test]
+async fn test_parallel_batch_heterogeneous_types() -> Result<(), AsyncError> {
+    let store = StateStore::new(BatchState::default());
+
+    let batch = ParallelBatch::new(&store)
+        .add(
+            || {
+                std::thread::sleep(Duration::from_millis(30));
+                888
+            },
+            |state, num| BatchState { num, ..state },
+        )
+        .add(
+            || Some("easerx".to_string()),
+            |state, name| BatchState { name, ..state },
+        )
+        .add(
+            || {
+                std::thread::sleep(Duration::from_millis(10));
+                Ok::<_, String>(vec![true, false])
+            },
+            |state, flags| BatchState { flags, ..state },
+        )
+        .aggregate(|state| BatchState {
+            completed: true,
+            ..state
+        });
+    assert_eq!(batch.len(), 3);
+
+    batch.execute_all().await.unwrap()?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.num, Async::success(888));
+    assert_eq!(state.name, Async::success("easerx".to_string()));
+    assert_eq!(state.flags, Async::success(vec![true, false]));
+    assert!(state.completed);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parallel_batch_aggregates_after_all_results() -> Result<(), AsyncError> {
+    let store = StateStore::new(BatchState::default());
+
+    store
+        .execute_batch_parallel()
+        .add(|| 1, |state, num| BatchState { num, ..state })
+        .add(
+            || Err::<String, _>("failed"),
+            |state, name| BatchState { name, ..state },
+        )
+        .aggregate(|state| {
+            let completed = state.num.is_complete() && state.name.is_complete();
+            BatchState { completed, ..state }
+        })
+        .execute_all();
+
+    let mut states = Vec::new();
+    store
+        .to_signal()
+        .stop_if(|state| state.completed)
+        .for_each(|state| {
+            states.push(state);
+            async {}
+        })
+        .await;
+
+    let last = states.last().unwrap();
+    assert_eq!(last.num, Async::success(1));
+    assert_eq!(last.name, Async::fail_with_message("failed", None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parallel_batch_sets_loading_first() -> Result<(), AsyncError> {
+    let store = StateStore::new(BatchState::default());
+
+    ParallelBatch::new(&store)
+        .add(
+            || {
+                std::thread::sleep(Duration::from_millis(100));
+                1
+            },
+            |state, num| BatchState { num, ..state },
+        )
+        .add(
+            || {
+                std::thread::sleep(Duration::from_millis(100));
+                "name".to_string()
+            },
+            |state, name| BatchState { name, ..state },
+        )
+        .execute_all();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+
+    let state = store.get_state();
+    assert_eq!(state.num, Async::loading(None));
+    assert_eq!(state.name, Async::loading(None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_parallel_batch_applies_store_policies() -> Result<(), AsyncError> {
+    let store = StateStore::builder(BatchState {
+        name: Async::success("cached".to_string()),
+        ..BatchState::default()
+    })
+    .default_execute_timeout(Duration::from_millis(50))
+    .build()
+    .with_error_recovery(|error| {
+        if error.is_timeout() {
+            RecoveryAction::Propagate
+        } else {
+            RecoveryAction::Ignore
+        }
+    });
+
+    store
+        .execute_batch_parallel()
+        .add(
+            || {
+                std::thread::sleep(Duration::from_millis(300));
+                1
+            },
+            |state, num| BatchState { num, ..state },
+        )
+        .add(|| Err::<String, _>("offline"), |state, name| BatchState { name, ..state })
+        .execute_all()
+        .await
+        .unwrap()?;
+
+    let state = store.await_state().await?;
+    assert!(state.num.is_fail_with_timeout());
+    // The ignored failure restores nothing, since a plain execution retains no value
+    assert_eq!(state.name, Async::Uninitialized);
+    Ok(())
+}
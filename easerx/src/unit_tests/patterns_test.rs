@@ -0,0 +1,311 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use futures::{FutureExt, StreamExt};
+use futures_signals::signal::Mutable;
+use crate::patterns::{form_submit, load_once, paginated_loader, refresh_on, PageRequest};
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, RecoveryAction, State, StateStore};
+
+#[tokio::test]
+async fn test_load_once_loads_a_single_time() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let runs = Arc::new(AtomicUsize::new(0));
+    let load = || {
+        let runs = runs.clone();
+        load_once(
+            &store,
+            |state| &state.data,
+            async move {
+                runs.fetch_add(1, Ordering::SeqCst);
+                "loaded".to_string()
+            },
+            |state, data| state.set_async_data(data),
+        )
+    };
+
+    let first = load();
+    let second = load();
+    let ticket = first.await?.expect("the first call loads");
+    assert!(second.await?.is_none());
+    ticket.await.unwrap()?;
+    assert!(load().await?.is_none());
+    assert_eq!(store.await_state().await?.data, Async::success("loaded".to_string()));
+    assert_eq!(runs.load(Ordering::SeqCst), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_load_once_retries_after_failure() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let ticket = load_once(
+        &store,
+        |state| &state.data,
+        async { Err::<String, _>("offline") },
+        |state, data| state.set_async_data(data),
+    )
+    .await?;
+    ticket.unwrap().await.unwrap()?;
+    assert!(store.await_state().await?.data.is_fail());
+
+    let ticket = load_once(
+        &store,
+        |state| &state.data,
+        async { "online".to_string() },
+        |state, data| state.set_async_data(data),
+    )
+    .await?;
+    assert!(ticket.is_some());
+    ticket.unwrap().await.unwrap()?;
+    assert_eq!(store.await_state().await?.data, Async::success("online".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_refresh_on_reloads_with_latest_trigger() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let query = Mutable::new(1);
+    let _refresh = refresh_on(
+        &store,
+        query.signal(),
+        |n: i32| async move { format!("result {n}") },
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+    let first = store
+        .await_state_matching(|state| state.data.is_success(), Duration::from_secs(1))
+        .await?;
+    assert_eq!(first.data, Async::success("result 1".to_string()));
+
+    query.set(2);
+    query.set(3);
+    let latest = store
+        .await_state_matching(|state| state.data.success_eq(&"result 3".to_string()), Duration::from_secs(1))
+        .await?;
+    assert_eq!(latest.data, Async::success("result 3".to_string()));
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_refresh_on_stops_when_guard_dropped() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let query = Mutable::new(1);
+    let refresh = refresh_on(
+        &store,
+        query.signal(),
+        |n: i32| async move { format!("result {n}") },
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+    store
+        .await_state_matching(|state| state.data.is_success(), Duration::from_secs(1))
+        .await?;
+    refresh.unsubscribe();
+    query.set(2);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(store.await_state().await?.data, Async::success("result 1".to_string()));
+    Ok(())
+}
+
+/// The todos added one by one by the `demo_simple_todo` example.
+const DEMO_TODOS: [&str; 6] = [
+    "Build a Todo App",
+    "Contribute to Open Source",
+    "Read Rust Book",
+    "Learn Async Rust",
+    "Play a game",
+    "Have breakfast",
+];
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct TodoState {
+    todos: Async<Vec<String>>,
+}
+
+impl State for TodoState {}
+
+async fn fetch_todos(page: PageRequest) -> Result<Vec<String>, AsyncError> {
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    Ok(DEMO_TODOS.iter().skip(page.offset()).take(page.size).map(|todo| todo.to_string()).collect())
+}
+
+#[tokio::test]
+async fn test_paginated_loader_loads_todos_progressively() -> Result<(), AsyncError> {
+    let store = StateStore::new(TodoState::default());
+    let loader = paginated_loader(&store, 4, fetch_todos, |state| &state.todos, |_, todos| TodoState { todos });
+
+    let ticket = loader.load_next_page().unwrap();
+    assert!(loader.is_loading());
+    assert!(loader.load_next_page().is_none());
+    ticket.await.unwrap()?;
+    let first_page = store.await_state().await?.todos;
+    assert_eq!(first_page.value_ref().map(Vec::len), Some(4));
+    assert!(!loader.is_exhausted());
+
+    let ticket = loader.load_next_page().unwrap();
+    let loading = store
+        .await_state_matching(|state| state.todos.is_loading(), Duration::from_secs(1))
+        .await?;
+    // The loaded todos stay visible while the next page loads
+    assert_eq!(loading.todos.value_ref().map(Vec::len), Some(4));
+    ticket.await.unwrap()?;
+
+    assert!(loader.is_exhausted());
+    assert_eq!(loader.next_page(), 2);
+    assert!(loader.load_next_page().is_none());
+    let expected: Vec<String> = DEMO_TODOS.iter().map(|todo| todo.to_string()).collect();
+    assert_eq!(store.await_state().await?.todos, Async::success(expected));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_paginated_loader_retries_failed_page() -> Result<(), AsyncError> {
+    let store = StateStore::new(TodoState::default());
+    let failures = Arc::new(AtomicUsize::new(1));
+    let loader = paginated_loader(
+        &store,
+        2,
+        move |page: PageRequest| {
+            let failures = failures.clone();
+            async move {
+                if page.index == 1 && failures.fetch_sub(1, Ordering::SeqCst) > 0 {
+                    return Err(AsyncError::error("offline"));
+                }
+                fetch_todos(page).await
+            }
+        },
+        |state| &state.todos,
+        |_, todos| TodoState { todos },
+    );
+    loader.load_next_page().unwrap().await.unwrap()?;
+    loader.load_next_page().unwrap().await.unwrap()?;
+    let failed = store.await_state().await?.todos;
+    assert!(failed.is_fail());
+    assert_eq!(failed.value_ref().map(Vec::len), Some(2));
+    assert_eq!(loader.next_page(), 1);
+
+    loader.load_next_page().unwrap().await.unwrap()?;
+    let todos = store.await_state().await?.todos;
+    assert_eq!(todos.value_ref().map(Vec::len), Some(4));
+    assert_eq!(loader.next_page(), 2);
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct FormState {
+    likes: u32,
+    submission: Async<u32>,
+}
+
+impl State for FormState {}
+
+#[tokio::test]
+async fn test_form_submit_keeps_optimistic_change_on_success() -> Result<(), AsyncError> {
+    let store = StateStore::new(FormState::default());
+    let ticket = form_submit(
+        &store,
+        |form| FormState { likes: form.likes + 1, ..form },
+        async { 1 },
+        |form, submission| FormState { submission, ..form },
+        |form| FormState { likes: form.likes - 1, ..form },
+    )?;
+    assert_eq!(store.await_state().await?.likes, 1);
+    ticket.await.unwrap()?;
+    assert_eq!(
+        store.await_state().await?,
+        FormState { likes: 1, submission: Async::success(1) }
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_form_submit_rolls_back_on_failure() -> Result<(), AsyncError> {
+    let store = StateStore::new(FormState::default());
+    let mut observed = store.broadcast();
+    let ticket = form_submit(
+        &store,
+        |form| FormState { likes: form.likes + 1, ..form },
+        async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Err::<u32, _>(AsyncError::error("offline"))
+        },
+        |form, submission| FormState { submission, ..form },
+        |form| FormState { likes: form.likes - 1, ..form },
+    )?;
+    // Committed while the submission runs, and kept by the rollback
+    store.set_state(|form| FormState { likes: form.likes + 10, ..form })?;
+    ticket.await.unwrap()?;
+
+    let form = store.await_state().await?;
+    assert_eq!(form.likes, 10);
+    assert!(form.submission.is_fail());
+    while let Some(Some(state)) = observed.next().now_or_never() {
+        assert!(!(state.submission.is_fail() && state.likes == 11), "failure shown before rollback");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_paginated_loader_recovers_from_frozen_store() -> Result<(), AsyncError> {
+    let store = StateStore::new(TodoState::default());
+    let loader = paginated_loader(&store, 4, fetch_todos, |state| &state.todos, |_, todos| TodoState { todos });
+
+    store.freeze();
+    let rejected = loader.load_next_page().unwrap().await.unwrap();
+    assert_eq!(rejected, Err(AsyncError::Frozen));
+    assert!(!loader.is_loading());
+    assert_eq!(loader.next_page(), 0);
+
+    store.unfreeze();
+    loader.load_next_page().unwrap().await.unwrap()?;
+    assert_eq!(store.await_state().await?.todos.value_ref().map(Vec::len), Some(4));
+    assert_eq!(loader.next_page(), 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_paginated_loader_ignored_failure_keeps_items() -> Result<(), AsyncError> {
+    let store = StateStore::new(TodoState::default()).with_error_recovery(|_| RecoveryAction::Ignore);
+    let failures = Arc::new(AtomicUsize::new(1));
+    let loader = paginated_loader(
+        &store,
+        2,
+        move |page: PageRequest| {
+            let failures = failures.clone();
+            async move {
+                if page.index == 1 && failures.fetch_sub(1, Ordering::SeqCst) > 0 {
+                    return Err(AsyncError::error("offline"));
+                }
+                fetch_todos(page).await
+            }
+        },
+        |state| &state.todos,
+        |_, todos| TodoState { todos },
+    );
+    loader.load_next_page().unwrap().await.unwrap()?;
+    loader.load_next_page().unwrap().await.unwrap()?;
+    let first_page: Vec<String> = DEMO_TODOS[..2].iter().map(|todo| todo.to_string()).collect();
+    assert_eq!(store.await_state().await?.todos, Async::success(first_page));
+    assert!(!loader.is_loading());
+    assert_eq!(loader.next_page(), 1);
+
+    loader.load_next_page().unwrap().await.unwrap()?;
+    assert_eq!(store.await_state().await?.todos.value_ref().map(Vec::len), Some(4));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_form_submit_rolls_back_ignored_failure() -> Result<(), AsyncError> {
+    let store = StateStore::new(FormState::default()).with_error_recovery(|_| RecoveryAction::Ignore);
+    form_submit(
+        &store,
+        |form| FormState { likes: form.likes + 1, ..form },
+        async { Err::<u32, _>(AsyncError::error("offline")) },
+        |form, submission| FormState { submission, ..form },
+        |form| FormState { likes: form.likes - 1, ..form },
+    )?
+    .await
+    .unwrap()?;
+    assert_eq!(store.await_state().await?, FormState::default());
+    Ok(())
+}
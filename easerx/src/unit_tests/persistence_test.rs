@@ -0,0 +1,239 @@
+use crate::codec::{decode_versioned, encode_versioned, JsonCodec};
+use crate::{AsyncError, FilePersistence, Migration, MigrationError, State, StatePersistence, StateStore};
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+struct Settings {
+    volume: u8,
+    theme: String,
+}
+
+impl State for Settings {}
+
+fn temp_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("easerx-{}-{name}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+async fn wait_for_saved(backend: &FilePersistence, expected: &Settings) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            let saved: Option<Settings> = backend.load().await.unwrap();
+            if saved.as_ref() == Some(expected) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("the state should be saved");
+}
+
+async fn wait_for_state(store: &StateStore<Settings>, expected: &Settings) {
+    let reached = tokio::time::timeout(
+        Duration::from_secs(5),
+        store
+            .to_stream()
+            .filter(|state| futures::future::ready(state == expected))
+            .next(),
+    )
+    .await
+    .expect("the state should be restored");
+    assert_eq!(reached.as_ref(), Some(expected));
+}
+
+#[tokio::test]
+async fn test_file_persistence_restores_state() -> Result<(), AsyncError> {
+    let path = temp_file("restore");
+    let expected = Settings {
+        volume: 7,
+        theme: "dark".to_string(),
+    };
+
+    let store = StateStore::new(Settings::default())
+        .with_persistence(FilePersistence::new(&path));
+    let next = expected.clone();
+    store.set_state(move |_| next)?;
+    wait_for_saved(&FilePersistence::new(&path), &expected).await;
+    store.close();
+    store.closed().await;
+    drop(store);
+
+    let restored = StateStore::new(Settings::default())
+        .with_persistence(FilePersistence::new(&path));
+    wait_for_state(&restored, &expected).await;
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_updates_apply_on_top_of_restored_state() -> Result<(), AsyncError> {
+    let path = temp_file("on-top");
+    let backend = FilePersistence::new(&path);
+    backend
+        .save(&Settings {
+            volume: 3,
+            theme: "light".to_string(),
+        })
+        .await?;
+
+    let store = StateStore::new(Settings::default()).with_persistence(backend.clone());
+    store.set_state(|state| Settings {
+        volume: state.volume + 1,
+        ..state
+    })?;
+
+    let expected = Settings {
+        volume: 4,
+        theme: "light".to_string(),
+    };
+    wait_for_state(&store, &expected).await;
+    wait_for_saved(&backend, &expected).await;
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_persistence_missing_file_keeps_initial_state() -> Result<(), AsyncError> {
+    let path = temp_file("missing");
+    let backend = FilePersistence::new(&path);
+    let loaded: Option<Settings> = backend.load().await?;
+    assert_eq!(loaded, None);
+
+    let initial = Settings {
+        volume: 1,
+        theme: "system".to_string(),
+    };
+    let store = StateStore::new(initial.clone()).with_persistence(backend.clone());
+    store.set_state(|state| state)?;
+    assert_eq!(store.await_state().await?, initial);
+    wait_for_saved(&backend, &initial).await;
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+/// Version 1 stored the volume as `level`, version 2 renamed it.
+struct RenameLevel;
+
+impl Migration for RenameLevel {
+    fn from_version(&self) -> u8 {
+        1
+    }
+
+    fn migrate(&self, mut value: Value) -> Result<Value, AsyncError> {
+        let level = value
+            .as_object_mut()
+            .and_then(|fields| fields.remove("level"))
+            .ok_or_else(|| AsyncError::error("missing `level`"))?;
+        value["volume"] = level;
+        Ok(value)
+    }
+}
+
+/// Version 2 stored a `dark` flag, version 3 stores the theme name.
+struct DarkToTheme;
+
+impl Migration for DarkToTheme {
+    fn from_version(&self) -> u8 {
+        2
+    }
+
+    fn migrate(&self, value: Value) -> Result<Value, AsyncError> {
+        let theme = if value["dark"].as_bool().unwrap_or_default() { "dark" } else { "light" };
+        Ok(json!({ "volume": value["volume"], "theme": theme }))
+    }
+}
+
+fn migrated_backend(path: &PathBuf, errors: Arc<Mutex<Vec<MigrationError>>>) -> FilePersistence {
+    FilePersistence::new(path)
+        .with_migrations(3, vec![Box::new(RenameLevel), Box::new(DarkToTheme)])
+        .on_migration_error(move |error| errors.lock().unwrap().push(error.clone()))
+}
+
+fn write_fixture(path: &PathBuf, version: u8, value: &Value) {
+    std::fs::write(path, encode_versioned(&JsonCodec, version, value).unwrap()).unwrap();
+}
+
+#[tokio::test]
+async fn test_file_persistence_migrates_v1_snapshot() -> Result<(), AsyncError> {
+    let path = temp_file("migrate-v1");
+    write_fixture(&path, 1, &json!({ "level": 7, "dark": true }));
+    let errors = Arc::default();
+    let backend = migrated_backend(&path, Arc::clone(&errors));
+
+    let expected = Settings {
+        volume: 7,
+        theme: "dark".to_string(),
+    };
+    let store = StateStore::new(Settings::default()).with_persistence(backend.clone());
+    wait_for_state(&store, &expected).await;
+    assert!(errors.lock().unwrap().is_empty());
+
+    // Saved again with the current schema version, so it loads without migrating
+    store.set_state(|state| Settings { volume: 8, ..state })?;
+    let saved = Settings { volume: 8, ..expected };
+    wait_for_saved(&backend, &saved).await;
+    let (version, _): (u8, Settings) = decode_versioned(&JsonCodec, &std::fs::read(&path).unwrap())?;
+    assert_eq!(version, 3);
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_persistence_migration_error_keeps_file() -> Result<(), AsyncError> {
+    let path = temp_file("migrate-missing");
+    // Nothing migrates from version 0
+    let fixture = json!({ "level": 7 });
+    std::fs::write(&path, serde_json::to_vec(&fixture).unwrap()).unwrap();
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let backend = migrated_backend(&path, Arc::clone(&errors));
+
+    let loaded: Result<Option<Settings>, _> = backend.load().await;
+    assert!(loaded.is_err());
+    assert_eq!(*errors.lock().unwrap(), vec![MigrationError::Missing { from_version: 0 }]);
+
+    let store = StateStore::new(Settings::default()).with_persistence(backend);
+    store.set_state(|state| Settings { volume: 1, ..state })?;
+    store.await_state().await?;
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert_eq!(errors.lock().unwrap().len(), 2);
+    assert_eq!(std::fs::read(&path).unwrap(), serde_json::to_vec(&fixture).unwrap());
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_file_persistence_rejects_newer_and_failed_migrations() -> Result<(), AsyncError> {
+    let path = temp_file("migrate-newer");
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let backend = migrated_backend(&path, Arc::clone(&errors));
+
+    write_fixture(&path, 4, &json!({ "volume": 1, "theme": "dark" }));
+    assert!(StatePersistence::<Settings>::load(&backend).await.is_err());
+    write_fixture(&path, 1, &json!({ "dark": true }));
+    assert!(StatePersistence::<Settings>::load(&backend).await.is_err());
+    assert_eq!(
+        *errors.lock().unwrap(),
+        vec![
+            MigrationError::Newer { version: 4, current: 3 },
+            MigrationError::Failed {
+                from_version: 1,
+                error: AsyncError::error("missing `level`"),
+            },
+        ]
+    );
+
+    let _ = std::fs::remove_file(&path);
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use crate::unit_tests::TestState;
+use crate::{assert_async_flow, Async, AsyncError, RecoveryAction, StateStore};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+fn drafted() -> TestState {
+    TestState::default().set_async_data(Async::success("draft".to_string()))
+}
+
+#[tokio::test]
+async fn test_placeholder_is_replaced_on_success() -> Result<(), AsyncError> {
+    let store = StateStore::new(drafted());
+    let ticket = store.async_execute_with_placeholder(
+        Some("optimistic".to_string()),
+        async { "saved".to_string() },
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [success("draft"), loading(Some("optimistic")), success("saved")]
+    );
+    ticket.await.unwrap()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failure_retains_original_value_not_placeholder() -> Result<(), AsyncError> {
+    let store = StateStore::new(drafted());
+    let ticket = store.async_execute_with_placeholder(
+        Some("optimistic".to_string()),
+        async { Err::<String, _>("offline") },
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [success("draft"), loading(Some("optimistic")), fail("offline", Some("draft"))]
+    );
+    ticket.await.unwrap()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failure_without_previous_value_retains_nothing() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .execute_with_placeholder(
+            Some("optimistic".to_string()),
+            || Err::<String, _>("offline"),
+            |state| &state.data,
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(store.await_state().await?.data, Async::fail_with_message("offline", None));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_blocking_placeholder_flow() -> Result<(), AsyncError> {
+    let store = StateStore::new(drafted());
+    let ticket = store.execute_with_placeholder(
+        None,
+        || Err::<String, _>("offline"),
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    // A `None` placeholder hides the previous value while loading, but failures still retain it
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [success("draft"), loading(None), fail("offline", Some("draft"))]
+    );
+    ticket.await.unwrap()?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancellation_retains_original_value() -> Result<(), AsyncError> {
+    let store = StateStore::new(drafted());
+    let token = CancellationToken::new();
+    let ticket = store.async_execute_cancellable_with_placeholder(
+        token.clone(),
+        Some("optimistic".to_string()),
+        |token| async move {
+            token.cancelled().await;
+            "unreachable".to_string()
+        },
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert_eq!(store.get_state().data, Async::loading(Some("optimistic".to_string())));
+
+    token.cancel();
+    ticket.await.unwrap()?;
+    assert_eq!(
+        store.await_state().await?.data,
+        Async::fail_with_cancelled(Some("draft".to_string()))
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_ignored_failure_restores_original_value() -> Result<(), AsyncError> {
+    let store = StateStore::new(drafted()).with_error_recovery(|_| RecoveryAction::Ignore);
+    store
+        .async_execute_with_placeholder(
+            Some("optimistic".to_string()),
+            async { Err::<String, _>("offline") },
+            |state| &state.data,
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(store.await_state().await?.data, Async::success("draft".to_string()));
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use crate::unit_tests::TestState;
+use crate::{assert_async_flow, Async, AsyncError, PollFailure, StateStore};
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+type Script = Arc<Mutex<VecDeque<Result<&'static str, &'static str>>>>;
+
+fn script(steps: &[Result<&'static str, &'static str>]) -> Script {
+    Arc::new(Mutex::new(steps.iter().copied().collect()))
+}
+
+fn next_status(script: &Script) -> impl std::future::Future<Output = Result<String, String>> {
+    let step = script.lock().unwrap().pop_front().unwrap_or(Ok("pending"));
+    async move { step.map(str::to_string).map_err(str::to_string) }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_async_execute_until_done() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let steps = script(&[Ok("pending"), Ok("pending"), Ok("done")]);
+
+    let handle = store.async_execute_until(
+        move || next_status(&steps),
+        |status| status == "done",
+        Duration::from_secs(1),
+        PollFailure::Stop,
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [
+            uninitialized,
+            loading(None),
+            success("pending"),
+            loading(Some("pending")),
+            success("pending"),
+            loading(Some("pending")),
+            success("done"),
+        ]
+    );
+    handle.await.unwrap()?;
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_async_execute_until_stops_on_failure() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let steps = script(&[Ok("pending"), Err("unreachable"), Ok("done")]);
+    let remaining = steps.clone();
+
+    let handle = store.async_execute_until(
+        move || next_status(&steps),
+        |status| status == "done",
+        Duration::from_secs(1),
+        PollFailure::Stop,
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+    handle.await.unwrap()?;
+
+    assert_eq!(
+        store.await_state().await?.data,
+        Async::fail_with_message("unreachable", Some("pending".to_string()))
+    );
+    assert_eq!(remaining.lock().unwrap().len(), 1);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_async_execute_until_keeps_polling_after_failure() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let steps = script(&[Ok("pending"), Err("unreachable"), Ok("done")]);
+
+    let handle = store.async_execute_until(
+        move || next_status(&steps),
+        |status| status == "done",
+        Duration::from_secs(1),
+        PollFailure::KeepPolling,
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [
+            uninitialized,
+            loading(None),
+            success("pending"),
+            loading(Some("pending")),
+            fail("unreachable", Some("pending")),
+            loading(Some("pending")),
+            success("done"),
+        ]
+    );
+    handle.await.unwrap()?;
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_async_execute_until_cancel() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let handle = store.async_execute_until(
+        || async {
+            tokio::time::sleep(Duration::from_secs(10)).await;
+            "pending".to_string()
+        },
+        |status| status == "done",
+        Duration::from_secs(1),
+        PollFailure::Stop,
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    );
+
+    tokio::time::sleep(Duration::from_secs(15)).await;
+    handle.cancel();
+    assert!(handle.is_cancelled());
+    handle.await.unwrap()?;
+
+    assert_eq!(
+        store.await_state().await?.data,
+        Async::fail_with_cancelled(Some("pending".to_string()))
+    );
+    Ok(())
+}
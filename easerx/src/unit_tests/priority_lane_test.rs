@@ -0,0 +1,164 @@
+use std::time::Duration;
+use crate::testing::{tagged, DeterministicStore, StepKind};
+#[cfg(feature = "execute")]
+use crate::unit_tests::TestState;
+use crate::{AsyncError, LatencyHistogram, State, StateStore};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct LogState {
+    log: Vec<&'static str>,
+}
+
+impl State for LogState {}
+
+fn push(entry: &'static str) -> impl FnOnce(LogState) -> LogState {
+    move |mut state| {
+        state.log.push(entry);
+        state
+    }
+}
+
+#[tokio::test]
+async fn test_priority_update_overtakes_backlog() -> Result<(), AsyncError> {
+    let store = StateStore::new(LogState::default());
+    for _ in 0..1_000 {
+        store.set_state(push("normal"))?;
+    }
+    store.priority_set_state(push("priority"))?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.log.len(), 1_001);
+    let position = state.log.iter().position(|entry| *entry == "priority");
+    assert_eq!(position, Some(0));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_priority_lane_interleaves_with_backlog() -> Result<(), AsyncError> {
+    let store = StateStore::new(LogState::default());
+    for entry in ["N1", "N2", "N3", "N4"] {
+        store.set_state(push(entry))?;
+    }
+    for entry in ["P1", "P2", "P3"] {
+        store.priority_set_state(push(entry))?;
+    }
+
+    // At most one priority update runs before each regular one
+    let state = store.await_state().await?;
+    assert_eq!(state.log, ["P1", "N1", "P2", "N2", "P3", "N3", "N4"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_priority_lane_drains_when_regular_lane_is_empty() -> Result<(), AsyncError> {
+    let store = StateStore::new(LogState::default());
+    for entry in ["P1", "P2", "P3"] {
+        store.priority_set_state(push(entry))?;
+    }
+    store.set_state(push("N1"))?;
+
+    let state = store.await_state().await?;
+    assert_eq!(state.log, ["P1", "N1", "P2", "P3"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_deterministic_store_steps_priority_lane() -> Result<(), AsyncError> {
+    let mut harness = DeterministicStore::new(LogState::default());
+    let store = harness.store().clone();
+    store.set_state(tagged("N1", push("N1")))?;
+    store.set_state(tagged("N2", push("N2")))?;
+    store.priority_set_state(tagged("P1", push("P1")))?;
+    store.priority_set_state(tagged("P2", push("P2")))?;
+
+    let steps = harness.run_until_idle().await;
+    let order: Vec<_> = steps.iter().map(|step| (step.kind(), step.tag().unwrap())).collect();
+    assert_eq!(
+        order,
+        [
+            (StepKind::PrioritySetState, "P1"),
+            (StepKind::SetState, "N1"),
+            (StepKind::PrioritySetState, "P2"),
+            (StepKind::SetState, "N2"),
+        ]
+    );
+    assert_eq!(store.get_state().log, ["P1", "N1", "P2", "N2"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_queue_latency_is_off_by_default() {
+    let store = StateStore::new(LogState::default());
+    assert!(store.queue_latency().is_none());
+}
+
+#[tokio::test]
+async fn test_queue_latency_records_every_reducer() -> Result<(), AsyncError> {
+    let store = StateStore::builder(LogState::default()).track_queue_latency().build();
+    // Everything queued behind this reducer waits at least as long as it runs
+    store.set_state(|state| {
+        std::thread::sleep(Duration::from_millis(20));
+        state
+    })?;
+    for _ in 0..98 {
+        store.set_state(push("normal"))?;
+    }
+    store.priority_set_state(push("priority"))?;
+    store.await_state().await?;
+
+    let latency = store.queue_latency().unwrap();
+    assert_eq!(latency.count(), 100);
+    assert!(latency.max() >= Duration::from_millis(20), "max: {:?}", latency.max());
+    assert!(latency.max() < Duration::from_secs(5), "max: {:?}", latency.max());
+    assert!(latency.mean() <= latency.max());
+    assert!(latency.percentile(0.5) <= latency.percentile(0.99));
+    assert!(latency.percentile(0.99) >= Duration::from_millis(20));
+    assert!(latency.percentile(0.99) <= latency.max());
+    assert_eq!(latency.buckets().iter().map(|(_, count)| count).sum::<u64>(), 100);
+
+    // Clones share the counters
+    store.queue_latency().unwrap().reset();
+    assert_eq!(latency.count(), 0);
+    assert_eq!(latency.max(), Duration::ZERO);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_queue_latency_records_execution_reducers() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).track_queue_latency().build();
+    store
+        .execute(|| "done".to_string(), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    store.await_state().await?;
+
+    // The Loading and the result reducers
+    assert_eq!(store.queue_latency().unwrap().count(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_latency_histogram_percentiles() {
+    let histogram = LatencyHistogram::new();
+    assert_eq!(histogram.percentile(0.99), Duration::ZERO);
+    assert_eq!(histogram.mean(), Duration::ZERO);
+
+    for _ in 0..90 {
+        histogram.record(Duration::from_micros(3));
+    }
+    for _ in 0..10 {
+        histogram.record(Duration::from_millis(1));
+    }
+
+    assert_eq!(histogram.count(), 100);
+    assert_eq!(histogram.max(), Duration::from_millis(1));
+    assert_eq!(histogram.percentile(0.5), Duration::from_micros(4));
+    assert_eq!(histogram.percentile(0.9), Duration::from_micros(4));
+    // Bucket bounds are capped at the largest recorded latency
+    assert_eq!(histogram.percentile(0.99), Duration::from_millis(1));
+    assert_eq!(
+        histogram.buckets(),
+        vec![(Duration::from_micros(4), 90), (Duration::from_micros(1_024), 10)]
+    );
+}
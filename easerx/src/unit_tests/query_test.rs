@@ -0,0 +1,89 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, Query, StateStore};
+
+struct GetCount;
+
+impl Query<TestState> for GetCount {
+    type Output = i32;
+}
+
+struct GetDataOr(String);
+
+impl Query<TestState> for GetDataOr {
+    type Output = String;
+}
+
+#[tokio::test]
+async fn test_query_with_two_types() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.register_query::<GetCount, _>(|state, _| state.count);
+    store.register_query::<GetDataOr, _>(|state, GetDataOr(fallback)| {
+        state.data.value_ref_clone().unwrap_or(fallback)
+    });
+
+    assert_eq!(store.query(GetCount).await?, 0);
+    assert_eq!(store.query(GetDataOr("none".to_string())).await?, "none");
+
+    store.set_state(|state| state.set_async_data(Async::success("data".to_string())))?;
+    assert_eq!(store.query(GetDataOr("none".to_string())).await?, "data");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_sees_pending_writes() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.register_query::<GetCount, _>(|state, _| state.count);
+
+    for _ in 0..100 {
+        store.set_state(|state| state.add_count(1))?;
+    }
+    let count = store.query(GetCount).await?;
+
+    assert_eq!(count, 100);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_shared_between_clones() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let store_clone = store.clone();
+    store.register_query::<GetCount, _>(|state, _| state.count + 1);
+
+    assert_eq!(store_clone.query(GetCount).await?, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_query_unregistered() {
+    let store = StateStore::new(TestState::default());
+
+    let result = store.query(GetCount).await;
+
+    match result {
+        Err(AsyncError::Error { message, .. }) => assert!(message.contains("GetCount")),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+struct Explode;
+
+impl Query<TestState> for Explode {
+    type Output = i32;
+}
+
+#[tokio::test]
+async fn test_query_handler_panic_keeps_store_running() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.register_query::<Explode, _>(|_, _| panic!("handler exploded"));
+    store.register_query::<GetCount, _>(|state, _| state.count);
+
+    match store.query(Explode).await {
+        Err(AsyncError::Panic { message }) => assert_eq!(message, "handler exploded"),
+        other => panic!("unexpected result: {:?}", other),
+    }
+
+    store.set_state(|state| state.add_count(1))?;
+    assert_eq!(store.query(GetCount).await?, 1);
+    assert_eq!(store.await_state().await?.count, 1);
+    Ok(())
+}
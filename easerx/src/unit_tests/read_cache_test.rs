@@ -0,0 +1,63 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+
+#[tokio::test]
+async fn test_get_state_matches_await_state() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    assert_eq!(store.get_state_arc().count, 0);
+    for i in 1..=100 {
+        store.set_state(|state| state.add_count(1))?;
+        let awaited = store.await_state().await?;
+        assert_eq!(awaited.count, i);
+        assert_eq!(store.get_state().count, i);
+        assert_eq!(store.get_state_arc().count, i);
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_skipped_update_keeps_cached_state() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.add_count(1))?;
+    store.await_state().await?;
+    let before = store.get_state_arc();
+    let stale = store.set_state_if_version(0, |state| state.add_count(1)).await;
+    assert!(stale.is_err());
+    assert!(Arc::ptr_eq(&before, &store.get_state_arc()));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_reads_only_see_committed_states() -> Result<(), AsyncError> {
+    const WRITES: i32 = 2_000;
+    let store = StateStore::new(TestState::default());
+    let done = Arc::new(AtomicBool::new(false));
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let store = store.clone();
+            let done = Arc::clone(&done);
+            std::thread::spawn(move || {
+                let mut last = 0;
+                while !done.load(Ordering::Acquire) {
+                    let count = store.get_state().count;
+                    assert!((last..=WRITES).contains(&count), "read {count} after {last}");
+                    last = count;
+                }
+            })
+        })
+        .collect();
+
+    for _ in 0..WRITES {
+        store.set_state(|state| state.add_count(1))?;
+    }
+    let awaited = store.await_state().await?;
+    done.store(true, Ordering::Release);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(awaited.count, WRITES);
+    assert_eq!(store.get_state().count, WRITES);
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use futures_signals::signal::SignalExt;
+use futures::StreamExt;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, Query, StateStore};
+
+struct GetCount;
+
+impl Query<TestState> for GetCount {
+    type Output = i32;
+}
+
+#[tokio::test]
+async fn test_read_only_sees_writes() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let reader = store.read_only();
+    let clone = reader.clone();
+    let mut stream = reader.to_stream();
+    assert_eq!(stream.next().await.map(|state| state.count), Some(0));
+
+    store.set_state(|state| state.set_count(3))?;
+    assert_eq!(reader.await_state().await?.count, 3);
+    assert_eq!(clone.get_state().count, 3);
+    assert_eq!(stream.next().await.map(|state| state.count), Some(3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_signals_and_queries() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.register_query::<GetCount, _>(|state, _| state.count);
+    let reader = store.read_only();
+    let mut counts = reader.signal_map(|state| state.count).to_stream();
+    let mut states = reader.to_signal().to_stream();
+
+    store.set_state(|state| state.set_count(7))?;
+    assert_eq!(reader.query(GetCount).await?, 7);
+    assert_eq!(counts.next().await, Some(7));
+    assert_eq!(states.next().await.map(|state| state.count), Some(7));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_read_only_keeps_store_running() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let reader = store.read_only();
+    store.set_state(|state| state.set_count(1))?;
+    drop(store);
+    assert_eq!(reader.await_state().await?.count, 1);
+    Ok(())
+}
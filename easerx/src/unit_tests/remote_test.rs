@@ -0,0 +1,55 @@
+use crate::remote::AsyncRemoteExt;
+use crate::{Async, AsyncData, AsyncError, AsyncRemote};
+
+#[test]
+fn test_aliases_are_async() {
+    let data: AsyncData<i32> = Async::success(1);
+    let remote: AsyncRemote<i32> = data.clone();
+    assert_eq!(data, remote);
+}
+
+#[test]
+fn test_is_network_error() {
+    let timeout: AsyncRemote<i32> = Async::fail_with_timeout(None);
+    assert!(timeout.is_network_error());
+
+    let refused: AsyncRemote<i32> = Async::fail_with_message("Connection refused", Some(1));
+    assert!(refused.is_network_error());
+
+    let unreachable: AsyncRemote<i32> = Async::fail_with_message("Network is unreachable", None);
+    assert!(unreachable.is_network_error());
+
+    let domain: AsyncRemote<i32> = Async::fail_with_message("invalid user id", None);
+    assert!(!domain.is_network_error());
+
+    // Hints match substrings, so none may be part of an unrelated word
+    let disconnected: AsyncRemote<i32> = Async::fail_with_message("Session disconnected by the user", None);
+    assert!(!disconnected.is_network_error());
+
+    let cancelled: AsyncRemote<i32> = Async::fail_with_cancelled(None);
+    assert!(!cancelled.is_network_error());
+
+    let none: AsyncRemote<i32> = Async::fail_with_none(None);
+    assert!(!none.is_network_error());
+
+    let success: AsyncRemote<i32> = Async::success(1);
+    assert!(!success.is_network_error());
+
+    let loading: AsyncRemote<i32> = Async::loading(None);
+    assert!(!loading.is_network_error());
+}
+
+#[test]
+fn test_should_retry_after_connectivity_restored() {
+    let timeout: AsyncRemote<i32> = Async::fail(AsyncError::Timeout, Some(1));
+    assert!(timeout.should_retry_after_connectivity_restored());
+
+    let dns: AsyncRemote<i32> = Async::fail_with_message("DNS lookup failed", None);
+    assert!(dns.should_retry_after_connectivity_restored());
+
+    let domain: AsyncRemote<i32> = Async::fail_with_message("permission denied", None);
+    assert!(!domain.should_retry_after_connectivity_restored());
+
+    let uninitialized: AsyncRemote<i32> = Async::Uninitialized;
+    assert!(!uninitialized.should_retry_after_connectivity_restored());
+}
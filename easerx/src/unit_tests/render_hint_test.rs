@@ -0,0 +1,38 @@
+use crate::{Async, RenderHint, RenderHints, WithRenderHints};
+
+#[test]
+fn test_default_render_hints() {
+    assert_eq!(Async::<i32>::Uninitialized.render_hint(), RenderHint::Hide);
+    assert_eq!(Async::<i32>::loading(None).render_hint(), RenderHint::Skeleton);
+    assert_eq!(Async::loading(Some(1)).render_hint(), RenderHint::Fade);
+    assert_eq!(Async::success(1).render_hint(), RenderHint::Show);
+    assert_eq!(Async::<i32>::fail_with_timeout(None).render_hint(), RenderHint::Show);
+    assert_eq!(Async::fail_with_cancelled(Some(1)).render_hint(), RenderHint::Show);
+}
+
+#[test]
+fn test_custom_render_hints() {
+    let hints = RenderHints {
+        loading_retained: RenderHint::Show,
+        fail: RenderHint::Hide,
+        ..RenderHints::default()
+    };
+    let field = WithRenderHints::new(Async::loading(Some(1)), hints);
+    assert_eq!(field.render_hint(), RenderHint::Show);
+
+    let field = field.set(Async::fail_with_none(Some(1)));
+    assert_eq!(field.render_hint(), RenderHint::Hide);
+    assert_eq!(field.hints(), &hints);
+
+    let field = field.set(Async::loading(None));
+    assert_eq!(field.render_hint(), RenderHint::Skeleton);
+    assert_eq!(field.into_inner(), Async::loading(None));
+}
+
+#[test]
+fn test_with_render_hints_defaults() {
+    let field: WithRenderHints<i32> = WithRenderHints::default();
+    assert_eq!(field.value(), &Async::Uninitialized);
+    assert_eq!(field.render_hint(), RenderHint::Hide);
+    assert_eq!(WithRenderHints::from(Async::success(1)).render_hint(), RenderHint::Show);
+}
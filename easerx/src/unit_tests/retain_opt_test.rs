@@ -0,0 +1,220 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use crate::{Async, AsyncError, State, StateStore};
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct PagesState {
+    pages: HashMap<u32, Async<String>>,
+}
+
+impl State for PagesState {}
+
+impl PagesState {
+    fn with_page(mut self, id: u32, page: Async<String>) -> Self {
+        self.pages.insert(id, page);
+        self
+    }
+}
+
+fn loaded_pages() -> PagesState {
+    PagesState::default().with_page(1, Async::success("cached".to_string()))
+}
+
+/// Records every value page `id` takes, in order.
+fn page_recorder(
+    id: u32,
+    seen: Arc<Mutex<Vec<Async<String>>>>,
+) -> impl FnOnce(PagesState, Async<String>) -> PagesState + Clone + Send + 'static {
+    move |state, page| {
+        seen.lock().unwrap().push(page.clone());
+        state.with_page(id, page)
+    }
+}
+
+#[tokio::test]
+async fn test_execute_with_retain_opt_present_key() -> Result<(), AsyncError> {
+    let store = StateStore::new(loaded_pages());
+    let seen = Default::default();
+    store
+        .execute_with_retain_opt(
+            || Err::<String, _>(AsyncError::error("offline")),
+            |state| state.pages.get(&1),
+            page_recorder(1, Arc::clone(&seen)),
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            Async::loading(Some("cached".to_string())),
+            Async::fail_with_message("offline", Some("cached".to_string())),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_execute_with_retain_opt_absent_key() -> Result<(), AsyncError> {
+    let store = StateStore::new(loaded_pages());
+    let seen = Default::default();
+    store
+        .execute_with_retain_opt(
+            || "page 2".to_string(),
+            |state| state.pages.get(&2),
+            page_recorder(2, Arc::clone(&seen)),
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![Async::loading(None), Async::success("page 2".to_string())]
+    );
+    let pages = store.await_state().await?.pages;
+    assert_eq!(pages[&1], Async::success("cached".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_execute_with_retain_opt_present_key() -> Result<(), AsyncError> {
+    let store = StateStore::new(loaded_pages());
+    let seen = Default::default();
+    store
+        .async_execute_with_retain_opt(
+            async { "fresh".to_string() },
+            |state| state.pages.get(&1),
+            page_recorder(1, Arc::clone(&seen)),
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![
+            Async::loading(Some("cached".to_string())),
+            Async::success("fresh".to_string()),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_execute_with_retain_opt_absent_key() -> Result<(), AsyncError> {
+    let store = StateStore::new(loaded_pages());
+    let seen = Default::default();
+    store
+        .async_execute_with_retain_opt(
+            async { Err::<String, _>(AsyncError::error("not found")) },
+            |state| state.pages.get(&2),
+            page_recorder(2, Arc::clone(&seen)),
+        )
+        .await
+        .unwrap()?;
+
+    assert_eq!(
+        *seen.lock().unwrap(),
+        vec![Async::loading(None), Async::fail_with_message("not found", None)]
+    );
+    Ok(())
+}
+
+fn items(state: &PagesState) -> &HashMap<u32, Async<String>> {
+    &state.pages
+}
+
+#[tokio::test]
+async fn test_execute_keyed_creates_and_retains_entries() -> Result<(), AsyncError> {
+    let store = StateStore::new(loaded_pages());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let record = {
+        let seen = seen.clone();
+        move |state: PagesState, id: u32, page: Async<String>| {
+            seen.lock().unwrap().push((id, page.clone()));
+            state.with_page(id, page)
+        }
+    };
+
+    let first = store.execute_keyed(1, || "fresh".to_string(), items, record.clone());
+    let second = store.async_execute_keyed(2, async { "new".to_string() }, items, record);
+    first.await.unwrap()?;
+    second.await.unwrap()?;
+
+    let seen = seen.lock().unwrap().clone();
+    let of = |id| seen.iter().filter(|(key, _)| *key == id).map(|(_, page)| page.clone()).collect::<Vec<_>>();
+    assert_eq!(of(1), vec![Async::loading(Some("cached".to_string())), Async::success("fresh".to_string())]);
+    assert_eq!(of(2), vec![Async::loading(None), Async::success("new".to_string())]);
+
+    let pages = store.await_state().await?.pages;
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[&1], Async::success("fresh".to_string()));
+    assert_eq!(pages[&2], Async::success("new".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cancel_one_of_two_keyed_executions() -> Result<(), AsyncError> {
+    let store = StateStore::new(loaded_pages());
+    let cancelled = CancellationToken::new();
+
+    let first = store.execute_cancellable_keyed(
+        1,
+        cancelled.clone(),
+        |token| {
+            while !token.is_cancelled() {
+                std::thread::sleep(Duration::from_millis(5));
+            }
+            "too late".to_string()
+        },
+        items,
+        PagesState::with_page,
+    );
+    let second = store.async_execute_cancellable_keyed(
+        2,
+        CancellationToken::new(),
+        |_token| async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            "page 2".to_string()
+        },
+        items,
+        PagesState::with_page,
+    );
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let loading = store.get_state().pages;
+    assert_eq!(loading[&1], Async::loading(Some("cached".to_string())));
+    assert_eq!(loading[&2], Async::loading(None));
+
+    cancelled.cancel();
+    first.await.unwrap()?;
+    second.await.unwrap()?;
+
+    let pages = store.await_state().await?.pages;
+    assert_eq!(pages[&1], Async::fail_with_cancelled(Some("cached".to_string())));
+    assert_eq!(pages[&2], Async::success("page 2".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_async_cancel_keyed_without_entry() -> Result<(), AsyncError> {
+    let store = StateStore::new(PagesState::default());
+    let token = CancellationToken::new();
+    token.cancel();
+    store
+        .async_execute_cancellable_keyed(
+            3,
+            token,
+            |token| async move {
+                token.cancelled().await;
+                "never".to_string()
+            },
+            items,
+            PagesState::with_page,
+        )
+        .await
+        .unwrap()?;
+    assert_eq!(store.await_state().await?.pages[&3], Async::fail_with_cancelled(None));
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use crate::{Async, AsyncError, ExecuteOptions, RetainPolicy, State, StateStore};
+
+#[derive(Clone, Debug, PartialEq)]
+struct TestState {
+    data: Async<String>,
+}
+
+impl State for TestState {}
+
+#[derive(Clone, Copy, Debug)]
+enum Outcome {
+    Success,
+    Fail,
+    Cancel,
+}
+
+const OLD: &str = "old";
+
+/// Returns the values `data` takes when a retaining execution ends with `outcome` under `policy`.
+async fn run(policy: RetainPolicy, outcome: Outcome) -> Result<Vec<Async<String>>, AsyncError> {
+    let store = StateStore::new(TestState { data: Async::success(OLD.to_string()) });
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let recorder = {
+        let seen = Arc::clone(&seen);
+        move |_: TestState, data: Async<String>| {
+            seen.lock().unwrap().push(data.clone());
+            TestState { data }
+        }
+    };
+    let token = CancellationToken::new();
+    let ticket = store.async_execute_cancellable_with_options_and_retain(
+        token.clone(),
+        ExecuteOptions::new().retain(policy),
+        move |token| async move {
+            match outcome {
+                Outcome::Success => Ok("new".to_string()),
+                Outcome::Fail => Err(AsyncError::error("offline")),
+                Outcome::Cancel => {
+                    token.cancelled().await;
+                    Ok("late".to_string())
+                }
+            }
+        },
+        |state| &state.data,
+        recorder,
+    );
+    if let Outcome::Cancel = outcome {
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+    }
+    ticket.await.unwrap()?;
+    store.await_state().await?;
+    let seen = seen.lock().unwrap().clone();
+    Ok(seen)
+}
+
+#[tokio::test]
+async fn test_retain_policy_table() -> Result<(), AsyncError> {
+    let old = || Some(OLD.to_string());
+    let cases = [
+        (RetainPolicy::LoadingAndFail, Outcome::Success, old(), Async::success("new".to_string())),
+        (RetainPolicy::LoadingAndFail, Outcome::Fail, old(), Async::fail_with_message("offline", old())),
+        (RetainPolicy::LoadingAndFail, Outcome::Cancel, old(), Async::fail_with_cancelled(old())),
+        (RetainPolicy::LoadingOnly, Outcome::Success, old(), Async::success("new".to_string())),
+        (RetainPolicy::LoadingOnly, Outcome::Fail, old(), Async::fail_with_message("offline", None)),
+        (RetainPolicy::LoadingOnly, Outcome::Cancel, old(), Async::fail_with_cancelled(None)),
+        (RetainPolicy::FailOnly, Outcome::Success, None, Async::success("new".to_string())),
+        (RetainPolicy::FailOnly, Outcome::Fail, None, Async::fail_with_message("offline", old())),
+        (RetainPolicy::FailOnly, Outcome::Cancel, None, Async::fail_with_cancelled(old())),
+    ];
+    for (policy, outcome, loading, last) in cases {
+        let seen = run(policy, outcome).await?;
+        assert_eq!(seen, vec![Async::loading(loading), last], "{policy:?} / {outcome:?}");
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retain_policy_default_keeps_loading_and_fail() {
+    let policy = ExecuteOptions::new().retain_setting();
+    assert_eq!(policy, RetainPolicy::LoadingAndFail);
+    assert!(policy.retains_loading() && policy.retains_fail());
+    assert!(!RetainPolicy::LoadingOnly.retains_fail());
+    assert!(!RetainPolicy::FailOnly.retains_loading());
+}
+
+#[tokio::test]
+async fn test_execute_with_options_and_retain_loading_only() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState { data: Async::success(OLD.to_string()) });
+    store
+        .execute_with_options_and_retain(
+            ExecuteOptions::new().retain(RetainPolicy::LoadingOnly),
+            || Err::<String, _>("offline"),
+            |state| &state.data,
+            |_, data| TestState { data },
+        )
+        .await
+        .unwrap()?;
+    let data = store.await_state().await?.data;
+    assert!(data.is_fail());
+    assert_eq!(data.value(), None);
+    Ok(())
+}
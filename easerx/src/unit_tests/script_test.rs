@@ -0,0 +1,147 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::StreamExt;
+use tokio::time::Instant;
+use crate::testing::ScriptStep;
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore};
+
+type Recording = Arc<Mutex<Vec<(Duration, Async<String>)>>>;
+
+/// Records every committed `data` value of `store` with the time it was observed.
+fn record(store: &StateStore<TestState>) -> (Recording, tokio::task::JoinHandle<()>) {
+    let recording = Recording::default();
+    let events = store.subscribe_all();
+    let started = Instant::now();
+    let handle = tokio::spawn({
+        let recording = recording.clone();
+        events.for_each(move |event| {
+            if let Some(state) = event.state() {
+                recording.lock().unwrap().push((started.elapsed(), state.data));
+            }
+            futures::future::ready(())
+        })
+    });
+    (recording, handle)
+}
+
+#[cfg(feature = "execute")]
+fn values(recording: &Recording) -> Vec<Async<String>> {
+    recording.lock().unwrap().iter().map(|(_, value)| value.clone()).collect()
+}
+
+fn initial() -> TestState {
+    TestState::default().set_async_data(Async::success("initial".to_string()))
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_with_retain_script_replays_real_execution() -> Result<(), AsyncError> {
+    let real = StateStore::new(initial());
+    let (real_recording, _) = record(&real);
+    real.execute_with_retain(
+        || Err::<String, _>("boom"),
+        |state| &state.data,
+        |state, data| state.set_async_data(data),
+    )
+    .await
+    .unwrap()?;
+    real.await_state().await?;
+
+    let scripted = StateStore::new(initial());
+    let (scripted_recording, _) = record(&scripted);
+    scripted
+        .script(ScriptStep::execute_with_retain(
+            |state: &TestState| &state.data,
+            |state, data| state.set_async_data(data),
+            Async::fail(AsyncError::error("boom"), None),
+        ))
+        .play()
+        .await?;
+    tokio::task::yield_now().await;
+
+    let expected = vec![
+        Async::loading(Some("initial".to_string())),
+        Async::fail(AsyncError::error("boom"), Some("initial".to_string())),
+    ];
+    assert_eq!(values(&real_recording), expected);
+    assert_eq!(values(&scripted_recording), expected);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_script_replays_real_execution() -> Result<(), AsyncError> {
+    let real = StateStore::new(initial());
+    let (real_recording, _) = record(&real);
+    real.execute(|| "done".to_string(), |state, data| state.set_async_data(data))
+        .await
+        .unwrap()?;
+    real.await_state().await?;
+
+    let scripted = StateStore::new(initial());
+    let (scripted_recording, _) = record(&scripted);
+    scripted
+        .script(ScriptStep::execute(
+            |state: TestState, data| state.set_async_data(data),
+            Async::success("done".to_string()),
+        ))
+        .play()
+        .await?;
+    tokio::task::yield_now().await;
+
+    assert_eq!(values(&scripted_recording), values(&real_recording));
+    assert_eq!(
+        values(&scripted_recording),
+        vec![Async::loading(None), Async::success("done".to_string())]
+    );
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_script_waits_between_emissions() -> Result<(), AsyncError> {
+    let store = StateStore::new(initial());
+    let (recording, _) = record(&store);
+    let steps = vec![
+        ScriptStep::Emit(initial().set_count(1)),
+        ScriptStep::Wait(Duration::from_millis(100)),
+        ScriptStep::emit_async(
+            |state: TestState, data| state.set_async_data(data),
+            Async::loading(Some("initial".to_string())),
+        ),
+        ScriptStep::Wait(Duration::from_millis(250)),
+    ];
+    store
+        .script(steps)
+        .then([ScriptStep::emit_async(
+            |state: TestState, data| state.set_async_data(data),
+            Async::success("loaded".to_string()),
+        )])
+        .play()
+        .await?;
+    tokio::task::yield_now().await;
+
+    let recording = recording.lock().unwrap().clone();
+    let times: Vec<_> = recording.iter().map(|(at, _)| at.as_millis()).collect();
+    assert_eq!(times, [0, 100, 350]);
+    assert_eq!(
+        recording.into_iter().map(|(_, value)| value).collect::<Vec<_>>(),
+        vec![
+            Async::success("initial".to_string()),
+            Async::loading(Some("initial".to_string())),
+            Async::success("loaded".to_string()),
+        ]
+    );
+    assert_eq!(store.get_state().count, 1);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_script_fails_on_closed_store() {
+    let store = StateStore::new(initial());
+    store.close();
+    store.closed().await;
+    let script = store.script(vec![ScriptStep::Emit(initial())]);
+    assert_eq!(format!("{script:?}"), "Script { steps: [Emit(..)], .. }");
+    assert!(script.play().await.is_err());
+}
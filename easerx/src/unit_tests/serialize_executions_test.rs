@@ -0,0 +1,184 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::StreamExt;
+use tokio_util::sync::CancellationToken;
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, State, StateStore};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn record(log: &Log, entry: String) {
+    log.lock().unwrap().push(entry);
+}
+
+fn entries(log: &Log) -> Vec<String> {
+    log.lock().unwrap().clone()
+}
+
+#[tokio::test]
+async fn test_serialized_executions_run_in_submission_order() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).serialize_executions().build();
+    let log = Log::default();
+    let tickets: Vec<_> = [30u64, 20, 10]
+        .into_iter()
+        .enumerate()
+        .map(|(i, millis)| {
+            let log = Arc::clone(&log);
+            store.async_execute(
+                async move {
+                    record(&log, format!("start {i}"));
+                    tokio::time::sleep(Duration::from_millis(millis)).await;
+                    record(&log, format!("end {i}"));
+                    i.to_string()
+                },
+                |state, data| state.set_async_data(data),
+            )
+        })
+        .collect();
+
+    // The pending work shows right away
+    tokio::time::sleep(Duration::from_millis(5)).await;
+    assert!(store.get_state().data.is_loading());
+
+    for ticket in tickets {
+        ticket.await.unwrap()?;
+    }
+    assert_eq!(entries(&log), ["start 0", "end 0", "start 1", "end 1", "start 2", "end 2"]);
+    assert_eq!(store.await_state().await?.data, Async::success("2".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serialized_blocking_executions_run_in_submission_order() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).serialize_executions().build();
+    let log = Log::default();
+    let tickets: Vec<_> = [30u64, 20, 10]
+        .into_iter()
+        .enumerate()
+        .map(|(i, millis)| {
+            let log = Arc::clone(&log);
+            store.execute(
+                move || {
+                    record(&log, format!("start {i}"));
+                    std::thread::sleep(Duration::from_millis(millis));
+                    record(&log, format!("end {i}"));
+                    i.to_string()
+                },
+                |state, data| state.set_async_data(data),
+            )
+        })
+        .collect();
+    for ticket in tickets {
+        ticket.await.unwrap()?;
+    }
+    assert_eq!(entries(&log), ["start 0", "end 0", "start 1", "end 1", "start 2", "end 2"]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_serialized_execution_cancelled_while_queued_never_starts() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).serialize_executions().build();
+    let mut events = store.subscribe_all();
+    let log = Log::default();
+    let tokens: Vec<_> = (0..3).map(|_| CancellationToken::new()).collect();
+    let tickets: Vec<_> = tokens
+        .iter()
+        .enumerate()
+        .map(|(i, token)| {
+            let log = Arc::clone(&log);
+            store.async_execute_cancellable(
+                token.clone(),
+                move |_| async move {
+                    record(&log, format!("start {i}"));
+                    tokio::time::sleep(Duration::from_millis(30)).await;
+                    record(&log, format!("end {i}"));
+                    i.to_string()
+                },
+                |state, data| state.set_async_data(data),
+            )
+        })
+        .collect();
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    tokens[1].cancel();
+    for ticket in tickets {
+        ticket.await.unwrap()?;
+    }
+    assert_eq!(entries(&log), ["start 0", "end 0", "start 2", "end 2"]);
+
+    // The cancelled execution ends like any cancellation, without running its computation
+    store.await_state().await?;
+    let mut written = Vec::new();
+    while let Ok(Some(event)) = tokio::time::timeout(Duration::from_millis(20), events.next()).await {
+        written.extend(event.state().map(|state| state.data));
+    }
+    let loading = Async::loading(None);
+    assert_eq!(
+        written,
+        [
+            loading.clone(),
+            loading.clone(),
+            loading,
+            Async::fail_with_cancelled(None),
+            Async::success("0".to_string()),
+            Async::success("2".to_string()),
+        ]
+    );
+    Ok(())
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct FieldsState {
+    fields: [Async<usize>; 3],
+}
+
+impl State for FieldsState {}
+
+#[tokio::test]
+async fn test_serialized_execution_cancelled_while_queued_fails_its_field() -> Result<(), AsyncError> {
+    let store = StateStore::builder(FieldsState::default()).serialize_executions().build();
+    store.set_state(|state| {
+        let mut fields = state.fields;
+        fields[2] = Async::success(20);
+        FieldsState { fields }
+    })?;
+    let tokens: Vec<_> = (0..3).map(|_| CancellationToken::new()).collect();
+    let write = |i: usize| {
+        move |state: FieldsState, value: Async<usize>| {
+            let mut fields = state.fields;
+            fields[i] = value;
+            FieldsState { fields }
+        }
+    };
+    let slow = |i: usize| {
+        move |_| async move {
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            i
+        }
+    };
+    let tickets = vec![
+        store.async_execute_cancellable(tokens[0].clone(), slow(0), write(0)),
+        store.execute_cancellable(
+            tokens[1].clone(),
+            |_| {
+                std::thread::sleep(Duration::from_millis(30));
+                1
+            },
+            write(1),
+        ),
+        store.async_execute_cancellable_with_retain(tokens[2].clone(), slow(2), |state| &state.fields[2], write(2)),
+    ];
+
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    tokens[1].cancel();
+    tokens[2].cancel();
+    for ticket in tickets {
+        ticket.await.unwrap()?;
+    }
+
+    let state = store.await_state().await?;
+    assert_eq!(state.fields[0], Async::success(0));
+    assert_eq!(state.fields[1], Async::fail_with_cancelled(None));
+    assert_eq!(state.fields[2], Async::fail_with_cancelled(Some(20)));
+    Ok(())
+}
@@ -0,0 +1,147 @@
+use std::sync::{Arc, Mutex};
+use tokio::sync::Barrier;
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, Startup, StartupError, StateStore};
+
+type Log = Arc<Mutex<Vec<String>>>;
+
+fn set_data(state: TestState, data: Async<String>) -> TestState {
+    state.set_async_data(data)
+}
+
+/// An initializer that logs when it starts and finishes, optionally meeting `barrier` in between.
+fn logged(
+    log: &Log,
+    name: &'static str,
+    barrier: Option<Arc<Barrier>>,
+) -> impl FnOnce() -> std::pin::Pin<Box<dyn std::future::Future<Output = String> + Send>> + Send + 'static {
+    let log = log.clone();
+    move || {
+        Box::pin(async move {
+            log.lock().unwrap().push(format!("start {name}"));
+            if let Some(barrier) = barrier {
+                barrier.wait().await;
+            }
+            log.lock().unwrap().push(format!("end {name}"));
+            name.to_string()
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_diamond_runs_in_dependency_order() -> Result<(), StartupError> {
+    let stores: Vec<_> = (0..4).map(|_| StateStore::new(TestState::default())).collect();
+    let log = Log::default();
+    // `auth` and `flags` only pass the barrier if they run at the same time
+    let barrier = Arc::new(Barrier::new(2));
+
+    let app_stores = (stores[1].clone(), stores[2].clone());
+    Startup::new()
+        .task("app", &stores[3], move || async move {
+            // Both dependencies are committed before a dependent starts
+            let auth = app_stores.0.get_state().data;
+            let flags = app_stores.1.get_state().data;
+            format!("{} {}", auth.value_ref_clone().unwrap(), flags.value_ref_clone().unwrap())
+        }, set_data)
+        .after("auth")
+        .after("flags")
+        .task("auth", &stores[1], logged(&log, "auth", Some(barrier.clone())), set_data)
+        .after("config")
+        .task("flags", &stores[2], logged(&log, "flags", Some(barrier)), set_data)
+        .after("config")
+        .task("config", &stores[0], logged(&log, "config", None), set_data)
+        .run()
+        .await?;
+
+    let log = log.lock().unwrap().clone();
+    assert_eq!(log[..2], ["start config", "end config"]);
+    let mut parallel = log[2..4].to_vec();
+    parallel.sort();
+    assert_eq!(parallel, ["start auth", "start flags"]);
+    assert_eq!(log.len(), 6);
+    assert_eq!(stores[3].get_state().data, Async::success("auth flags".to_string()));
+    for (store, name) in stores.iter().zip(["config", "auth", "flags"]) {
+        assert_eq!(store.get_state().data, Async::success(name.to_string()));
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_failing_dependency_aborts_dependents() -> Result<(), AsyncError> {
+    let config = StateStore::new(TestState::default());
+    let auth = StateStore::new(TestState::default());
+    let app = StateStore::new(TestState::default());
+    let metrics = StateStore::new(TestState::default());
+    let log = Log::default();
+
+    let result = Startup::new()
+        .task("config", &config, || async { Err::<String, _>("no config file") }, set_data)
+        .task("auth", &auth, logged(&log, "auth", None), set_data)
+        .after("config")
+        .task("app", &app, logged(&log, "app", None), set_data)
+        .after("auth")
+        .task("metrics", &metrics, logged(&log, "metrics", None), set_data)
+        .run()
+        .await;
+
+    assert_eq!(
+        result,
+        Err(StartupError::TaskFailed {
+            task: "config",
+            error: AsyncError::error("no config file"),
+            aborted: vec!["auth", "app"],
+        })
+    );
+    assert_eq!(*log.lock().unwrap(), ["start metrics", "end metrics"]);
+    assert_eq!(config.await_state().await?.data, Async::fail_with_message("no config file", None));
+    for store in [&auth, &app] {
+        let data = store.await_state().await?.data;
+        assert_eq!(
+            data,
+            Async::fail_with_message("Startup dependency `config` failed: no config file", None)
+        );
+    }
+    assert_eq!(metrics.get_state().data, Async::success("metrics".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_cycle_is_rejected_before_running() {
+    let store = StateStore::new(TestState::default());
+    let log = Log::default();
+    let startup = Startup::new()
+        .task("config", &store, logged(&log, "config", None), set_data)
+        .task("a", &store, logged(&log, "a", None), set_data)
+        .after("config")
+        .after("c")
+        .task("b", &store, logged(&log, "b", None), set_data)
+        .after("a")
+        .task("c", &store, logged(&log, "c", None), set_data)
+        .after("b");
+
+    let error = startup.validate().unwrap_err();
+    assert_eq!(error, StartupError::Cycle { tasks: vec!["a", "c", "b"] });
+    assert_eq!(error.to_string(), "Startup tasks form a cycle: a -> c -> b -> a");
+    assert_eq!(startup.run().await, Err(error));
+    assert!(log.lock().unwrap().is_empty());
+    assert_eq!(store.get_state().data, Async::Uninitialized);
+}
+
+#[tokio::test]
+async fn test_invalid_dependencies_are_rejected() {
+    let store = StateStore::new(TestState::default());
+    let log = Log::default();
+    let unknown = Startup::new()
+        .task("auth", &store, logged(&log, "auth", None), set_data)
+        .after("config");
+    assert_eq!(
+        unknown.run().await,
+        Err(StartupError::UnknownDependency { task: "auth", dependency: "config" })
+    );
+
+    let duplicate = Startup::new()
+        .task("auth", &store, logged(&log, "auth", None), set_data)
+        .task("auth", &store, logged(&log, "auth", None), set_data);
+    assert_eq!(duplicate.validate(), Err(StartupError::DuplicateTask { task: "auth" }));
+    assert!(log.lock().unwrap().is_empty());
+}
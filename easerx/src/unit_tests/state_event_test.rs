@@ -0,0 +1,149 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateEvent, StateStore};
+use futures::StreamExt;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_subscribe_all_receives_every_state() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let first = store.subscribe_all();
+    let second = store.subscribe_all();
+
+    for count in 1..=20 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+
+    let expected: Vec<_> = (1..=20)
+        .map(|count| StateEvent::State(TestState::default().set_count(count)))
+        .collect();
+    assert_eq!(first.take(20).collect::<Vec<_>>().await, expected);
+    assert_eq!(second.take(20).collect::<Vec<_>>().await, expected);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribe_all_slow_subscriber_lags() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .broadcast_capacity(4)
+        .build();
+    let mut slow = store.subscribe_all();
+    let mut fast = store.subscribe_all();
+
+    let mut fast_counts = Vec::new();
+    for count in 1..=10 {
+        store.set_state(move |state| state.set_count(count))?;
+        // The fast subscriber keeps up with every commit
+        match fast.next().await {
+            Some(StateEvent::State(state)) => fast_counts.push(state.count),
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+    assert_eq!(fast_counts, (1..=10).collect::<Vec<_>>());
+
+    // The slow subscriber only reads now: 6 states were dropped, the last 4 remain
+    assert_eq!(slow.next().await, Some(StateEvent::Lagged(6)));
+    let remaining: Vec<_> = slow
+        .take(4)
+        .map(|event| event.state().unwrap().count)
+        .collect()
+        .await;
+    assert_eq!(remaining, vec![7, 8, 9, 10]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribe_all_ends_when_store_dropped() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let events = store.subscribe_all();
+    store.set_state(|state| state.set_count(1))?;
+    drop(store);
+
+    let events = tokio::time::timeout(Duration::from_secs(1), events.collect::<Vec<_>>())
+        .await
+        .unwrap();
+    assert_eq!(
+        events,
+        vec![StateEvent::State(TestState::default().set_count(1))]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_broadcast_receivers_see_every_update() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let first = store.broadcast();
+    let second = store.broadcast();
+
+    for count in 1..=10 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+
+    let first: Vec<_> = first.take(10).map(|state| state.count).collect().await;
+    let second: Vec<_> = second.take(10).map(|state| state.count).collect().await;
+    assert_eq!(first, (1..=10).collect::<Vec<_>>());
+    assert_eq!(second, first);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_broadcast_keeps_latest_states_up_to_capacity() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .broadcast_capacity(4)
+        .build();
+    let mut receiver = store.broadcast();
+
+    for count in 1..=10 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    store.await_state().await?;
+    drop(store);
+
+    let mut counts = Vec::new();
+    while let Some(state) = receiver.next().await {
+        counts.push(state.count);
+    }
+    assert_eq!(counts, vec![7, 8, 9, 10]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribers_attached_after_bulk_writes() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    for count in 1..=1_000 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    assert_eq!(store.await_state().await?.count, 1_000);
+
+    // Nothing was listening so far; every kind of consumer starts from the current state
+    let events = store.subscribe_all();
+    let receiver = store.broadcast();
+    let mut stream = store.to_stream();
+    assert_eq!(stream.next().await.map(|state| state.count), Some(1_000));
+
+    for count in 1_001..=1_010 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    let expected: Vec<_> = (1_001..=1_010).collect();
+    let counts: Vec<_> = events.take(10).map(|event| event.state().unwrap().count).collect().await;
+    assert_eq!(counts, expected);
+    let counts: Vec<_> = receiver.take(10).map(|state| state.count).collect().await;
+    assert_eq!(counts, expected);
+    assert_eq!(stream.next().await.map(|state| state.count), Some(1_010));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribe_all_after_last_subscriber_dropped() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    drop(store.subscribe_all());
+    for count in 1..=100 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    store.await_state().await?;
+
+    let events = store.subscribe_all();
+    store.set_state(|state| state.set_count(101))?;
+    let counts: Vec<_> = events.take(1).map(|event| event.state().unwrap().count).collect().await;
+    assert_eq!(counts, vec![101]);
+    Ok(())
+}
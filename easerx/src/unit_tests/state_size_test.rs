@@ -0,0 +1,82 @@
+use futures::StreamExt;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore, StoreError};
+
+const INLINE: usize = size_of::<TestState>();
+
+/// Pretends every unit of `count` owns a kilobyte.
+fn fake_heap_size(state: &TestState) -> usize {
+    state.count as usize * 1024
+}
+
+async fn reported(store: StateStore<TestState>, mut errors: crate::StoreErrorStream) -> Vec<StoreError> {
+    store.close();
+    store.closed().await;
+    drop(store);
+    let mut reported = Vec::new();
+    while let Some(error) = errors.next().await {
+        reported.push(error);
+    }
+    reported
+}
+
+#[tokio::test]
+async fn test_heap_size_crossing_the_budget_is_reported_once() -> Result<(), AsyncError> {
+    let limit = INLINE + 10 * 1024;
+    let store = StateStore::builder(TestState::default())
+        .warn_if_state_larger_than(limit)
+        .approx_heap_size(fake_heap_size)
+        .state_size_sample_interval(1)
+        .build();
+    let errors = store.errors();
+    for count in [5, 20, 30, 0, 40] {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    store.await_state().await?;
+
+    // Reported when crossing the budget, not again while it stays over, and again after dropping below
+    assert_eq!(
+        reported(store, errors).await,
+        vec![
+            StoreError::StateTooLarge { bytes: INLINE + 20 * 1024, limit },
+            StoreError::StateTooLarge { bytes: INLINE + 40 * 1024, limit },
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_heap_size_is_sampled_every_interval() -> Result<(), AsyncError> {
+    let limit = INLINE + 10 * 1024;
+    let store = StateStore::builder(TestState::default())
+        .warn_if_state_larger_than(limit)
+        .approx_heap_size(fake_heap_size)
+        .state_size_sample_interval(3)
+        .build();
+    let errors = store.errors();
+    // Versions 1 and 2 are over the budget but not sampled, version 3 is sampled
+    for count in [50, 60, 70] {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    store.await_state().await?;
+
+    assert_eq!(
+        reported(store, errors).await,
+        vec![StoreError::StateTooLarge { bytes: INLINE + 70 * 1024, limit }]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_state_within_budget_is_not_reported() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .warn_if_state_larger_than(INLINE)
+        .state_size_sample_interval(1)
+        .build();
+    let errors = store.errors();
+    store.set_state(|state| state.set_count(1_000_000))?;
+    store.await_state().await?;
+    // Without a heap size function only the inline size counts, which fits
+    assert!(reported(store, errors).await.is_empty());
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::unit_tests::TestState;
-use crate::{Async, StateStore};
+use crate::{Async, StateStore, WaitError};
 use futures::stream::StreamExt;
+use futures_signals::signal::SignalExt;
 use std::time::Duration;
 use tokio::time::sleep;
 use crate::async_error::AsyncError;
@@ -42,6 +43,43 @@ async fn test_set_state() -> Result<(), AsyncError> {
     Ok(())
 }
 
+// Test compute functionality
+#[tokio::test]
+async fn test_compute_commits_without_loading() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default().set_count(3));
+    let events = store.subscribe_all();
+
+    store.compute(|state| {
+        TestState::default()
+            .set_count(state.count * 2)
+            .set_async_data(Async::success(format!("count was {}", state.count)))
+    })?;
+    let state = store.await_state().await?;
+    assert_eq!(state.count, 6);
+    assert_eq!(state.data, Async::success("count was 3".to_string()));
+    assert_eq!(store.version(), 1);
+
+    drop(store);
+    let committed: Vec<_> = events
+        .filter_map(|event| futures::future::ready(event.state()))
+        .map(|state| state.data)
+        .collect()
+        .await;
+    assert_eq!(committed, vec![Async::success("count was 3".to_string())]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_compute_runs_in_queue_order() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.add_count(1))?;
+    store.compute(|state| state.clone().add_count(state.count * 10))?;
+    store.set_state(|state| state.add_count(100))?;
+
+    assert_eq!(store.await_state().await?.count, 111);
+    Ok(())
+}
+
 /*#[tokio::test]
 async fn test_set_state_panic() -> Result<(), AsyncError> {
     let store = StateStore::new(TestState::default());
@@ -153,3 +191,284 @@ async fn test_state_stream() -> Result<(), AsyncError> {
     assert_eq!(collected_updates, [1, 2, 3]);
     Ok(())
 }
+
+#[tokio::test]
+async fn test_forget_variants() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state_forget(|state| state.add_count(5));
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    store.with_state_forget(move |state| {
+        let _ = tx.send(state.count);
+    });
+
+    assert_eq!(rx.await.unwrap(), 5);
+    Ok(())
+}
+
+// The deprecated underscore methods must keep working until they are removed
+#[tokio::test]
+#[allow(deprecated)]
+async fn test_deprecated_underscore_shims() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store._set_state(|state| state.add_count(7));
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    store._with_state(move |state| {
+        let _ = tx.send(state.count);
+    });
+
+    assert_eq!(rx.await.unwrap(), 7);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_clone_snapshot_is_independent() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.add_count(1))?;
+    store.await_state().await?;
+
+    let snapshot = store.clone_snapshot();
+    assert_eq!(snapshot.get_state().count, 1);
+    assert_eq!(snapshot.version(), 0);
+
+    snapshot.set_state(|state| state.add_count(10))?;
+    store.set_state(|state| state.set_async_data(Async::success("original".to_string())))?;
+
+    let snapshot_state = snapshot.await_state().await?;
+    let store_state = store.await_state().await?;
+    assert_eq!(snapshot_state.count, 11);
+    assert_eq!(snapshot_state.data, Async::Uninitialized);
+    assert_eq!(store_state.count, 1);
+    assert_eq!(store_state.data, Async::success("original".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_drains_queued_updates() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.add_count(1))?;
+    store.close();
+    store.closed().await;
+
+    assert_eq!(store.get_state().count, 1);
+    assert!(store.set_state(|state| state.add_count(1)).is_err());
+    assert!(store.await_state().await.is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_to_broadcaster_shares_current_state() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let broadcaster = store.to_broadcaster();
+    let early = broadcaster.signal_ref(|state| state.count).to_stream();
+
+    store.set_state(|state| state.set_count(5))?;
+    store.await_state().await?;
+
+    let late_count = store
+        .clone()
+        .to_broadcaster()
+        .signal_ref(|state| state.count)
+        .first()
+        .to_future()
+        .await;
+    assert_eq!(late_count, 5);
+
+    store.set_state(|state| state.set_count(6))?;
+    let reached = early
+        .filter(|count| futures::future::ready(*count == 6))
+        .next()
+        .await;
+    assert_eq!(reached, Some(6));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_success_resolves_immediately() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.set_async_data(Async::success("ready".to_string())))?;
+    store.await_state().await?;
+
+    let data = tokio::time::timeout(
+        Duration::from_millis(50),
+        store.await_success(|state| &state.data),
+    )
+    .await
+    .expect("already successful field should resolve immediately")?;
+    assert_eq!(data, "ready");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_success_waits_for_transition() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let waiter = tokio::spawn(store.await_success(|state| &state.data));
+
+    store.set_state(|state| state.set_async_data(Async::loading(None)))?;
+    store.await_state().await?;
+    sleep(Duration::from_millis(10)).await;
+    assert!(!waiter.is_finished());
+
+    store.set_state(|state| state.set_async_data(Async::success("done".to_string())))?;
+    assert_eq!(waiter.await.unwrap()?, "done");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_success_returns_fail_error() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let waiter = tokio::spawn(store.await_success(|state| &state.data));
+
+    store.set_state(|state| state.set_async_data(Async::fail_with_timeout(None)))?;
+    assert_eq!(waiter.await.unwrap(), Err(WaitError::Failed(AsyncError::Timeout)));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_async_holds_write_slot() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let (release_tx, release_rx) = tokio::sync::oneshot::channel::<()>();
+
+    let updater = {
+        let store = store.clone();
+        tokio::spawn(async move {
+            store
+                .update_async(|state| {
+                    Box::pin(async move {
+                        let _ = release_rx.await;
+                        let count = state.count;
+                        state.set_count(count + 10)
+                    })
+                })
+                .await
+        })
+    };
+    sleep(Duration::from_millis(10)).await;
+
+    // A concurrent writer queues behind the held update instead of interleaving
+    store.set_state(|state| {
+        let count = state.count;
+        state.set_count(count * 2)
+    })?;
+    // Reads are still serviced while the update is pending
+    let during = tokio::time::timeout(Duration::from_millis(50), store.await_state())
+        .await
+        .expect("reads should not wait for the held update")?;
+    assert_eq!(during.count, 0);
+
+    release_tx.send(()).unwrap();
+    updater.await.unwrap()?;
+    assert_eq!(store.await_state().await?.count, 20);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_async_with_timeout_releases_slot() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let result = store
+        .update_async_with_timeout(
+            |state| {
+                Box::pin(async move {
+                    sleep(Duration::from_secs(10)).await;
+                    state.set_count(99)
+                })
+            },
+            Duration::from_millis(20),
+        )
+        .await;
+    assert_eq!(result, Err(AsyncError::Timeout));
+
+    store.set_state(|state| state.set_count(1))?;
+    assert_eq!(store.await_state().await?.count, 1);
+    Ok(())
+}
+
+async fn ticker_progress_during_burst(yield_batch_size: usize) -> usize {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    const BURST: i32 = 10_000;
+    let store = StateStore::builder(TestState::default())
+        .yield_batch_size(yield_batch_size)
+        .build();
+    let ticks = Arc::new(AtomicUsize::new(0));
+    let running = Arc::new(AtomicBool::new(true));
+    let ticker = tokio::spawn({
+        let (ticks, running) = (ticks.clone(), running.clone());
+        async move {
+            while running.load(Ordering::Acquire) {
+                ticks.fetch_add(1, Ordering::AcqRel);
+                tokio::task::yield_now().await;
+            }
+        }
+    });
+    // Let the ticker start before the burst
+    tokio::task::yield_now().await;
+
+    let (first, last) = (Arc::new(AtomicUsize::new(0)), Arc::new(AtomicUsize::new(0)));
+    for i in 1..=BURST {
+        let (ticks, first, last) = (ticks.clone(), first.clone(), last.clone());
+        store
+            .set_state(move |state| {
+                if i == 1 {
+                    first.store(ticks.load(Ordering::Acquire), Ordering::Release);
+                } else if i == BURST {
+                    last.store(ticks.load(Ordering::Acquire), Ordering::Release);
+                }
+                state.add_count(1)
+            })
+            .unwrap();
+    }
+    assert_eq!(store.await_state().await.unwrap().count, BURST);
+    running.store(false, Ordering::Release);
+    ticker.await.unwrap();
+    last.load(Ordering::Acquire) - first.load(Ordering::Acquire)
+}
+
+#[tokio::test(flavor = "current_thread")]
+async fn test_yield_batch_size_keeps_current_thread_runtime_responsive() {
+    let progress = ticker_progress_during_burst(64).await;
+    // The queue yields at least once per 64 reducers, letting the ticker run in between
+    assert!(progress >= 10_000 / 64, "ticker only advanced {progress} times");
+}
+
+#[tokio::test]
+async fn test_new_with_hides_pre_init_state() -> Result<(), AsyncError> {
+    let store = StateStore::new_with(TestState::default(), |state| state.set_count(10));
+    let events = store.subscribe_all();
+
+    assert_eq!(store.get_state().count, 10);
+    assert_eq!(store.to_stream().next().await.map(|state| state.count), Some(10));
+
+    store.set_state(|state| state.add_count(1))?;
+    store.await_state().await?;
+    drop(store);
+    let counts = events
+        .filter_map(|event| futures::future::ready(event.state()))
+        .map(|state| state.count)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(counts, vec![11]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_new_initializing() -> Result<(), AsyncError> {
+    let store = StateStore::new_initializing(TestState::default(), |state| async move {
+        sleep(Duration::from_millis(20)).await;
+        Ok(state.set_async_data(Async::success("restored".to_string())))
+    })
+    .await?;
+
+    assert_eq!(store.version(), 0);
+    let first = store.to_stream().next().await.unwrap();
+    assert_eq!(first.data, Async::success("restored".to_string()));
+
+    let failed = StateStore::new_initializing(TestState::default(), |_| async {
+        Err(AsyncError::error("corrupted"))
+    })
+    .await;
+    assert_eq!(failed.unwrap_err(), AsyncError::error("corrupted"));
+    Ok(())
+}
@@ -0,0 +1,62 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore, StateStream};
+use futures::StreamExt;
+
+struct CountView {
+    stream: StateStream<TestState>,
+}
+
+#[tokio::test]
+async fn test_state_stream_with_stream_ext_combinators() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let view = CountView {
+        stream: store.to_stream(),
+    };
+    for _ in 0..3 {
+        store.set_state(|state| state.add_count(1))?;
+    }
+    store.await_state().await?;
+
+    let counts = view
+        .stream
+        .map(|state| state.count)
+        .filter(|count| futures::future::ready(count % 2 == 1))
+        .take(1)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(counts, vec![3]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_state_stream_inherent_stop_if() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let stream = store.to_stream();
+    let store_clone = store.clone();
+    tokio::spawn(async move {
+        for _ in 0..5 {
+            store_clone.set_state(|state| state.add_count(1)).unwrap();
+            tokio::task::yield_now().await;
+        }
+    });
+
+    let counts = stream
+        .stop_if(|state| state.count >= 5)
+        .map(|state| state.count)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(counts.last(), Some(&5));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_state_stream_next_and_into_inner() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default().set_count(7));
+    let mut stream = store.to_stream();
+    assert_eq!(stream.next().await.map(|state| state.count), Some(7));
+
+    store.set_state(|state| state.set_count(8))?;
+    let mut inner = stream.into_inner();
+    assert_eq!(inner.next().await.map(|state| state.count), Some(8));
+    Ok(())
+}
@@ -0,0 +1,109 @@
+use futures_signals::signal::SignalExt;
+use futures::StreamExt;
+use crate::{state_variants, AsyncError, State, StateStore, StateVariant, VariantMismatch};
+
+#[derive(Clone, Debug, PartialEq)]
+struct Login {
+    user: String,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+struct Dashboard {
+    user: String,
+    unread: u32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Screen {
+    Login(Login),
+    Loading,
+    Dashboard(Dashboard),
+}
+
+impl State for Screen {}
+
+state_variants!(Screen { Login(Login), Dashboard(Dashboard) });
+
+fn login() -> Screen {
+    Screen::Login(Login { user: "ada".to_string() })
+}
+
+fn open_dashboard(login: Login) -> Screen {
+    Screen::Dashboard(Dashboard { user: login.user, unread: 0 })
+}
+
+#[test]
+fn test_state_variants_generates_projections() {
+    assert_eq!(Login::try_from(login()), Ok(Login { user: "ada".to_string() }));
+    assert_eq!(Dashboard::try_from(Screen::Loading), Err(Screen::Loading));
+    assert!(Login::matches(&login()));
+    assert!(!Dashboard::matches(&login()));
+    assert_eq!(<Dashboard as StateVariant<Screen>>::NAME, "Dashboard");
+}
+
+#[tokio::test]
+async fn test_set_state_variant_updates_matching_variant() -> Result<(), AsyncError> {
+    let store = StateStore::new(login());
+    store.set_state_variant(open_dashboard).await.unwrap();
+    store
+        .set_state_variant(|dashboard: Dashboard| Screen::Dashboard(Dashboard { unread: 3, ..dashboard }))
+        .await
+        .unwrap();
+
+    assert_eq!(
+        store.await_state().await?,
+        Screen::Dashboard(Dashboard { user: "ada".to_string(), unread: 3 })
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_variant_rejects_wrong_variant() -> Result<(), AsyncError> {
+    let store = StateStore::new(Screen::Loading);
+    let version = store.version();
+
+    let result = store.set_state_variant(open_dashboard).await;
+    assert_eq!(result, Err(VariantMismatch::Wrong { expected: "Login" }));
+    assert_eq!(store.await_state().await?, Screen::Loading);
+    assert_eq!(store.version(), version);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_variant_checks_variant_when_applied() -> Result<(), AsyncError> {
+    let store = StateStore::new(login());
+    // Both updates are queued while the state is still `Login`; the first one switches the variant
+    store.set_state(|_| Screen::Loading)?;
+    let result = store.set_state_variant(open_dashboard);
+    store.set_state(|_| login())?;
+
+    assert_eq!(result.await, Err(VariantMismatch::Wrong { expected: "Login" }));
+    assert_eq!(store.await_state().await?, login());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_variant_on_closed_store() {
+    let store = StateStore::new(login());
+    store.close();
+    store.closed().await;
+    let result = store.set_state_variant(open_dashboard).await;
+    assert!(matches!(result, Err(VariantMismatch::Store(_))));
+}
+
+#[tokio::test]
+async fn test_is_variant_emits_on_variant_changes() -> Result<(), AsyncError> {
+    let store = StateStore::new(login());
+    let mut is_login = store.is_variant::<Login>().to_stream();
+    assert_eq!(is_login.next().await, Some(true));
+
+    store.set_state(|_| Screen::Loading)?;
+    assert_eq!(is_login.next().await, Some(false));
+
+    // Updates within the variant are not reported
+    store.set_state(|_| Screen::Dashboard(Dashboard { user: "ada".to_string(), unread: 0 }))?;
+    store.await_state().await?;
+    store.set_state(|_| login())?;
+    assert_eq!(is_login.next().await, Some(true));
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+#[cfg(feature = "execute")]
+use crate::StoreError;
+use futures::StreamExt;
+use std::time::Duration;
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_errors_reports_kinds_in_order() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default())
+        .broadcast_capacity(1)
+        .build();
+    let mut errors = store.errors();
+
+    // A panicking reducer is skipped and the queue keeps running
+    store.set_state(|_| panic!("invalid transition"))?;
+    store.set_state(|state| state.set_count(1))?;
+    assert_eq!(store.await_state().await?.count, 1);
+
+    // A subscriber that falls behind reports its lag
+    let mut events = store.subscribe_all();
+    store.set_state(|state| state.set_count(2))?;
+    store.set_state(|state| state.set_count(3))?;
+    store.set_state(|state| state.set_count(4))?;
+    store.await_state().await?;
+    assert!(events.next().await.unwrap().is_lagged());
+
+    // An execution cannot write its result once the store is closed
+    store.close();
+    store.closed().await;
+    let result = store
+        .async_execute(async { "late".to_string() }, |state, data| state.set_async_data(data))
+        .await
+        .unwrap();
+    assert!(result.is_err());
+
+    let reported = tokio::time::timeout(Duration::from_secs(1), errors.by_ref().take(3).collect::<Vec<_>>())
+        .await
+        .expect("three errors should be reported");
+    assert_eq!(
+        reported[0],
+        StoreError::ReducerPanic {
+            message: "invalid transition".to_string()
+        }
+    );
+    assert_eq!(reported[1], StoreError::Lagged { skipped: 2 });
+    assert!(matches!(reported[2], StoreError::ExecutionFailed { .. }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_errors_ends_when_store_dropped() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let errors = store.errors();
+    store.set_state(|state| state.set_count(1))?;
+    store.await_state().await?;
+    drop(store);
+
+    let reported = tokio::time::timeout(Duration::from_secs(1), errors.collect::<Vec<_>>())
+        .await
+        .expect("the stream should end");
+    assert!(reported.is_empty());
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StoreMap};
+#[cfg(feature = "execute")]
+use crate::Async;
+use futures_signals::signal::SignalExt;
+use futures::StreamExt;
+#[cfg(feature = "execute")]
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_store_map_lifecycle() -> Result<(), AsyncError> {
+    let map = StoreMap::new();
+    assert!(map.is_empty());
+
+    let first = map.get_or_create(1, TestState::default);
+    let same = map.get_or_create(1, || panic!("the child already exists"));
+    same.set_state(|state| state.set_count(5))?;
+    assert_eq!(first.await_state().await?.count, 5);
+
+    map.get_or_create(2, TestState::default);
+    assert_eq!(map.keys(), vec![1, 2]);
+    assert_eq!(map.len(), 2);
+
+    assert!(map.remove(&1));
+    assert!(!map.remove(&1));
+    assert!(map.get(&1).is_none());
+    assert_eq!(map.keys(), vec![2]);
+
+    // The removed child is closed, but its last state stays readable
+    assert!(first.is_closed());
+    first.closed().await;
+    assert!(first.await_state().await.is_err());
+    assert!(first.set_state(|state| state.set_count(6)).is_err());
+    assert_eq!(first.get_state().count, 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_store_map_drop_closes_children() {
+    let map = StoreMap::new();
+    let child = map.get_or_create("a", TestState::default);
+    drop(map);
+
+    assert!(child.is_closed());
+    child.closed().await;
+    assert!(child.await_state().await.is_err());
+}
+
+#[tokio::test]
+async fn test_store_map_keys_signal() {
+    let map = StoreMap::new();
+    let mut keys = map.keys_signal().to_stream();
+    assert_eq!(keys.next().await, Some(vec![]));
+
+    map.get_or_create("a", TestState::default);
+    assert_eq!(keys.next().await, Some(vec!["a"]));
+
+    map.get_or_create("b", TestState::default);
+    assert_eq!(keys.next().await, Some(vec!["a", "b"]));
+
+    map.remove(&"a");
+    assert_eq!(keys.next().await, Some(vec!["b"]));
+}
+
+#[tokio::test]
+async fn test_store_map_combined_signal() -> Result<(), AsyncError> {
+    let map = StoreMap::new();
+    let a = map.get_or_create("a", TestState::default);
+    map.get_or_create("b", TestState::default);
+
+    a.set_state(|state| state.set_count(3))?;
+    let combined = map
+        .combined_signal()
+        .to_stream()
+        .filter(|snapshot| futures::future::ready(snapshot["a"].count == 3))
+        .next()
+        .await
+        .unwrap();
+    assert_eq!(combined.len(), 2);
+    assert_eq!(combined["b"], TestState::default());
+
+    map.remove(&"b");
+    let combined = map
+        .combined_signal()
+        .to_stream()
+        .next()
+        .await
+        .unwrap();
+    assert_eq!(combined.keys().collect::<Vec<_>>(), vec![&"a"]);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_removed_child_ignores_late_results() -> Result<(), AsyncError> {
+    let map = StoreMap::new();
+    let child = map.get_or_create("a", TestState::default);
+
+    let ticket = child.execute(
+        || {
+            std::thread::sleep(Duration::from_millis(50));
+            "late".to_string()
+        },
+        |state, data| state.set_async_data(data),
+    );
+    child
+        .to_signal()
+        .stop_if(|state| state.data.is_loading())
+        .for_each(|_| async {})
+        .await;
+    map.remove(&"a");
+
+    assert!(ticket.await.unwrap().is_err());
+    assert_eq!(child.get_state().data, Async::loading(None));
+    assert!(map.get(&"a").is_none());
+    assert!(map.is_empty());
+    Ok(())
+}
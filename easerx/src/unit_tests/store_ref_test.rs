@@ -0,0 +1,111 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, State, StateStore, StoreRef};
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct Node {
+    name: String,
+    peer: Option<StoreRef<Node>>,
+    /// Counts the states alive
+    tracker: Arc<()>,
+}
+
+impl State for Node {}
+
+#[tokio::test]
+async fn test_store_ref_resolves_to_the_same_store() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let store_ref = store.store_ref();
+    assert_eq!(store_ref.id(), store.id());
+
+    let resolved = store_ref.resolve().expect("the store is alive");
+    assert_eq!(resolved.id(), store.id());
+    resolved.set_state(|state| state.set_count(7))?;
+    assert_eq!(store.await_state().await?.count, 7);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_store_ref_resolves_to_none_after_drop() {
+    let store = StateStore::new(TestState::default());
+    let store_ref = store.store_ref();
+    let clone = store.clone();
+    drop(store);
+    // Any handle keeps the store resolvable
+    assert!(store_ref.resolve().is_some());
+
+    drop(clone);
+    assert!(store_ref.resolve().is_none());
+    assert!(store_ref.clone().resolve().is_none());
+}
+
+#[tokio::test]
+async fn test_store_ref_equality_by_id() {
+    let first = StateStore::new(TestState::default());
+    let second = StateStore::new(TestState::default());
+
+    assert_eq!(first.store_ref(), first.clone().store_ref());
+    assert_ne!(first.store_ref(), second.store_ref());
+    assert_ne!(first.id(), second.id());
+    // Snapshots are new stores
+    assert_ne!(first.clone_snapshot().id(), first.id());
+
+    let hash = |store_ref: StoreRef<TestState>| {
+        let mut hasher = DefaultHasher::new();
+        store_ref.hash(&mut hasher);
+        hasher.finish()
+    };
+    assert_eq!(hash(first.store_ref()), hash(first.store_ref()));
+    assert_ne!(hash(first.store_ref()), hash(second.store_ref()));
+
+    // Equality doesn't depend on the target being alive
+    let dangling = second.store_ref();
+    drop(second);
+    assert_eq!(dangling, dangling.clone());
+    assert_eq!(format!("{:?}", dangling), format!("StoreRef({:?})", dangling.id()));
+}
+
+#[tokio::test]
+async fn test_store_refs_in_state_dont_keep_stores_alive() -> Result<(), AsyncError> {
+    let tracker = Arc::new(());
+    let node = |name: &str| Node {
+        name: name.to_string(),
+        peer: None,
+        tracker: Arc::clone(&tracker),
+    };
+    let alice = StateStore::new(node("alice"));
+    let bob = StateStore::new(node("bob"));
+    let (alice_ref, bob_ref) = (alice.store_ref(), bob.store_ref());
+    {
+        let bob_ref = bob_ref.clone();
+        alice.set_state(move |state| Node { peer: Some(bob_ref), ..state })?;
+    }
+    {
+        let alice_ref = alice_ref.clone();
+        bob.set_state(move |state| Node { peer: Some(alice_ref), ..state })?;
+    }
+
+    let peer = alice.await_state().await?.peer.and_then(|peer| peer.resolve()).expect("bob is alive");
+    assert_eq!(peer.await_state().await?.name, "bob");
+    drop(peer);
+
+    // The stores refer to each other, yet both are freed once their handles are dropped
+    alice.close();
+    bob.close();
+    alice.closed().await;
+    bob.closed().await;
+    drop(alice);
+    drop(bob);
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while Arc::strong_count(&tracker) > 1 {
+            tokio::task::yield_now().await;
+        }
+    })
+    .await
+    .expect("both stores should be freed");
+    assert!(alice_ref.resolve().is_none() && bob_ref.resolve().is_none());
+    Ok(())
+}
@@ -1,6 +1,7 @@
-use crate::{EaseRxStreamExt, State, StateStore};
+use crate::{forward_throttled, EaseRxStreamExt, State, StateStore, StreamEvent};
 use futures::StreamExt;
 use std::sync::Arc;
+use std::time::Duration;
 use crate::async_error::AsyncError;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -86,19 +87,239 @@ async fn test_stream_ext_loop() -> Result<(), AsyncError> {
     let mut data_vec = Vec::new();
     let mut progress_vec = Vec::new();
     let mut state_flow = store.to_stream();
-    loop {
-        match state_flow.next().await {
-            Some(state) => {
-                data_vec.push(state.data);
-                progress_vec.push(state.progress);
-                if state.data >= 3 {
-                    break;
-                }
-            }
-            None => break,
+    while let Some(state) = state_flow.next().await {
+        data_vec.push(state.data);
+        progress_vec.push(state.progress);
+        if state.data >= 3 {
+            break;
         }
     }
     assert_eq!(data_vec, vec![0, 1, 2, 3]);
     assert_eq!(progress_vec, vec![0.0, 0.1, 0.2, 0.3]);
     Ok(())
 }
+
+#[tokio::test(start_paused = true)]
+async fn test_stop_if_with_grace_captures_trailing_emissions() -> Result<(), AsyncError> {
+    let store = Arc::new(StateStore::new(TestStreamState::default()));
+
+    let store_clone = store.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        store_clone.set_state(|state| state.set_data(1))?;
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        // Inside the grace window
+        store_clone.set_state(|state| state.set_progress(0.5))?;
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        // After the grace window
+        store_clone.set_state(|state| state.set_data(2))?;
+        Ok::<(), AsyncError>(())
+    });
+
+    let states: Vec<TestStreamState> = store
+        .to_stream()
+        .stop_if_with_grace(|state| state.data >= 1, Duration::from_millis(100))
+        .collect()
+        .await;
+
+    assert_eq!(
+        states,
+        vec![
+            TestStreamState::default(),
+            TestStreamState::default().set_data(1),
+            TestStreamState::default().set_data(1).set_progress(0.5),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_stop_if_with_grace_without_trailing_emissions() -> Result<(), AsyncError> {
+    let store = Arc::new(StateStore::new(TestStreamState::default()));
+
+    let store_clone = store.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        store_clone.set_state(|state| state.set_data(1))?;
+        Ok::<(), AsyncError>(())
+    });
+
+    let data: Vec<i32> = store
+        .to_stream()
+        .stop_if_with_grace(|state| state.data >= 1, Duration::from_millis(100))
+        .map(|state| state.data)
+        .collect()
+        .await;
+
+    assert_eq!(data, vec![0, 1]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_if_last_returns_stopping_state() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestStreamState::default());
+
+    let store_clone = store.clone();
+    tokio::spawn(async move {
+        for data in 1..=5 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            store_clone.set_state(move |state| state.set_data(data))?;
+        }
+        Ok::<(), AsyncError>(())
+    });
+
+    let last = EaseRxStreamExt::last(store.to_stream().stop_if(|state| state.data >= 3)).await;
+
+    assert_eq!(last.map(|state| state.data), Some(3));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_last_of_empty_stream() {
+    let last = EaseRxStreamExt::last(futures::stream::empty::<i32>()).await;
+    assert_eq!(last, None);
+}
+
+#[tokio::test]
+async fn test_stop_after_one_emits_first_item_and_ends() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestStreamState::default().set_data(7));
+    // A state stream never ends on its own, so this only resolves if the stream stops itself
+    let states = tokio::time::timeout(Duration::from_secs(1), store.to_stream().stop_after(1).collect::<Vec<_>>())
+        .await
+        .expect("stop_after(1) must end right after the first state");
+    assert_eq!(states.iter().map(|state| state.data).collect::<Vec<_>>(), vec![7]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_stop_after_three_emits_exactly_three_items() {
+    let items = futures::stream::iter(1..=10).stop_after(3).collect::<Vec<_>>().await;
+    assert_eq!(items, vec![1, 2, 3]);
+
+    let stream = futures::stream::iter(1..=10).stop_after(3);
+    assert_eq!(futures_core::Stream::size_hint(&stream), (3, Some(3)));
+}
+
+#[tokio::test]
+async fn test_stop_after_shorter_or_empty() {
+    let items = futures::stream::iter(1..=2).stop_after(3).collect::<Vec<_>>().await;
+    assert_eq!(items, vec![1, 2]);
+
+    let items = futures::stream::iter(1..=2).stop_after(0).collect::<Vec<_>>().await;
+    assert!(items.is_empty());
+}
+
+#[tokio::test]
+async fn test_materialize_emits_end_after_stop_if() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestStreamState::default());
+
+    let store_clone = store.clone();
+    tokio::spawn(async move {
+        for data in 1..=5 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            store_clone.set_state(move |state| state.set_data(data))?;
+        }
+        Ok::<(), AsyncError>(())
+    });
+
+    let events = store
+        .to_stream()
+        .stop_if(|state| state.data >= 2)
+        .map(|state| state.data)
+        .materialize()
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        events,
+        vec![
+            StreamEvent::Item(0),
+            StreamEvent::Item(1),
+            StreamEvent::Item(2),
+            StreamEvent::End
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_materialize_of_empty_stream_emits_only_end() {
+    let events = futures::stream::empty::<i32>().materialize().collect::<Vec<_>>().await;
+    assert_eq!(events, vec![StreamEvent::End]);
+}
+
+#[tokio::test]
+async fn test_dematerialize_round_trip() {
+    let items = futures::stream::iter([1, 2, 3])
+        .materialize()
+        .dematerialize()
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(items, vec![Ok(1), Ok(2), Ok(3)]);
+}
+
+#[tokio::test]
+async fn test_dematerialize_stops_at_end_and_error() {
+    let events = [StreamEvent::Item(1), StreamEvent::End, StreamEvent::Item(2)];
+    let items = futures::stream::iter(events).dematerialize().collect::<Vec<_>>().await;
+    assert_eq!(items, vec![Ok(1)]);
+
+    let events = [
+        StreamEvent::Item(1),
+        StreamEvent::Error(AsyncError::error("broken")),
+        StreamEvent::Item(2),
+    ];
+    let items = futures::stream::iter(events).dematerialize().collect::<Vec<_>>().await;
+    assert_eq!(items, vec![Ok(1), Err(AsyncError::error("broken"))]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_forward_throttled_conflates_bursts() {
+    let forwarded = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    let forwarding = forward_throttled(rx, Duration::from_millis(33), {
+        let forwarded = forwarded.clone();
+        move |item| forwarded.lock().unwrap().push(item)
+    });
+    for item in 0..10 {
+        tx.unbounded_send(item).unwrap();
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    drop(tx);
+    forwarding.await.unwrap();
+
+    // One item per 33ms window, and the last item once the stream ended
+    assert_eq!(*forwarded.lock().unwrap(), vec![0, 3, 6, 9]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_forward_throttled_flushes_held_item_at_end() {
+    let forwarded = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+    let forwarding = forward_throttled(futures::stream::iter(0..100), Duration::from_secs(1), {
+        let forwarded = forwarded.clone();
+        move |item| forwarded.lock().unwrap().push(item)
+    });
+    forwarding.await.unwrap();
+
+    assert_eq!(*forwarded.lock().unwrap(), vec![0, 99]);
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_forward_throttled_stops_when_aborted() {
+    let forwarded = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (tx, rx) = futures::channel::mpsc::unbounded();
+
+    let forwarding = forward_throttled(rx, Duration::from_millis(10), {
+        let forwarded = forwarded.clone();
+        move |item| forwarded.lock().unwrap().push(item)
+    });
+    tx.unbounded_send(1).unwrap();
+    tokio::time::sleep(Duration::from_millis(20)).await;
+    forwarding.abort();
+    assert!(forwarding.await.unwrap_err().is_cancelled());
+
+    assert!(tx.unbounded_send(2).is_err());
+    assert_eq!(*forwarded.lock().unwrap(), vec![1]);
+}
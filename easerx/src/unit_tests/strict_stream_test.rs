@@ -0,0 +1,39 @@
+use crate::testing::STRICT_BROADCAST_CAPACITY;
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore};
+use futures::StreamExt;
+
+#[tokio::test]
+async fn test_strict_stream_observes_every_state() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).strict_observation().build();
+    let mut states = store.strict_stream();
+    for count in 1..=200 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    store.await_state().await?;
+
+    let counts: Vec<i32> = states.by_ref().take(200).map(|state| state.count).collect().await;
+    assert_eq!(counts, (1..=200).collect::<Vec<_>>());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_strict_observation_keeps_larger_capacity() {
+    let builder = StateStore::builder(TestState::default()).broadcast_capacity(STRICT_BROADCAST_CAPACITY * 2);
+    assert_eq!(builder.strict_observation().broadcast_capacity, STRICT_BROADCAST_CAPACITY * 2);
+    let builder = StateStore::builder(TestState::default()).broadcast_capacity(1);
+    assert_eq!(builder.strict_observation().broadcast_capacity, STRICT_BROADCAST_CAPACITY);
+}
+
+#[tokio::test]
+#[should_panic(expected = "strict stream skipped 2 state(s)")]
+async fn test_strict_stream_panics_on_overflow() {
+    let store = StateStore::builder(TestState::default()).broadcast_capacity(1).build();
+    let mut states = store.strict_stream();
+    for count in 1..=3 {
+        store.set_state(move |state| state.set_count(count)).unwrap();
+    }
+    store.await_state().await.unwrap();
+
+    states.next().await;
+}
@@ -0,0 +1,74 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::time::sleep;
+
+#[tokio::test]
+async fn test_subscribe_field_reports_old_and_new() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let changes = Arc::new(Mutex::new(Vec::new()));
+
+    let changes_clone = changes.clone();
+    let _guard = store.subscribe_field(
+        |state| &state.count,
+        move |old, new| changes_clone.lock().unwrap().push((*old, *new)),
+    );
+    sleep(Duration::from_millis(10)).await;
+
+    for count in 1..=3 {
+        store.set_state(move |state| state.set_count(count))?;
+        sleep(Duration::from_millis(10)).await;
+    }
+
+    assert_eq!(*changes.lock().unwrap(), vec![(0, 1), (1, 2), (2, 3)]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribe_field_ignores_unrelated_changes() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let changes = Arc::new(Mutex::new(Vec::new()));
+
+    let changes_clone = changes.clone();
+    let _guard = store.subscribe_field(
+        |state| &state.count,
+        move |old, new| changes_clone.lock().unwrap().push((*old, *new)),
+    );
+    sleep(Duration::from_millis(10)).await;
+
+    store.set_state(|state| state.set_async_data(Async::loading(None)))?;
+    sleep(Duration::from_millis(10)).await;
+    store.set_state(|state| state.set_count(0))?;
+    sleep(Duration::from_millis(10)).await;
+    store.set_state(|state| state.set_async_data(Async::success("data".to_string())))?;
+    sleep(Duration::from_millis(10)).await;
+
+    assert!(changes.lock().unwrap().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_subscribe_field_stops_on_guard_drop() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let changes = Arc::new(Mutex::new(Vec::new()));
+
+    let changes_clone = changes.clone();
+    let guard = store.subscribe_field(
+        |state| &state.data,
+        move |old, new| changes_clone.lock().unwrap().push((old.clone(), new.clone())),
+    );
+    sleep(Duration::from_millis(10)).await;
+
+    store.set_state(|state| state.set_async_data(Async::loading(None)))?;
+    sleep(Duration::from_millis(10)).await;
+    guard.unsubscribe();
+    store.set_state(|state| state.set_async_data(Async::success("data".to_string())))?;
+    sleep(Duration::from_millis(10)).await;
+
+    assert_eq!(
+        *changes.lock().unwrap(),
+        vec![(Async::Uninitialized, Async::loading(None))]
+    );
+    Ok(())
+}
@@ -0,0 +1,48 @@
+use crate::testing::assert_async_flow;
+use crate::unit_tests::TestState;
+use crate::{assert_async_flow, Async, StateStore};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_assert_async_flow_collapses_unchanged_field() -> Result<(), crate::AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.set_count(1))?;
+    store.set_state(|state| state.set_async_data(Async::loading(None)))?;
+    store.set_state(|state| state.set_count(2))?;
+    store.set_state(|state| state.set_async_data(Async::fail_with_cancelled(None)))?;
+
+    assert_async_flow!(
+        store,
+        |state| &state.data,
+        [uninitialized, loading(None), { Async::fail_with_cancelled(None) }],
+        timeout = Duration::from_secs(1),
+    );
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+#[should_panic(expected = "async flow diverged at step 1")]
+async fn test_assert_async_flow_reports_divergence() {
+    let store = StateStore::new(TestState::default());
+    store.execute(
+        || Err::<String, _>("boom"),
+        |state, async_data| state.set_async_data(async_data),
+    );
+
+    assert_async_flow!(store, |state| &state.data, [uninitialized, success("x"), success("y")]);
+}
+
+#[tokio::test]
+#[should_panic(expected = "async flow incomplete, timed out after 50ms: observed 1 of 2 steps")]
+async fn test_assert_async_flow_times_out() {
+    let store = StateStore::new(TestState::default());
+
+    assert_async_flow(
+        &store,
+        |state| &state.data,
+        vec![Async::Uninitialized, Async::success("x".to_string())],
+        Duration::from_millis(50),
+    )
+    .await;
+}
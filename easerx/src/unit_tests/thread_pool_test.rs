@@ -0,0 +1,81 @@
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore};
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_executions_stay_within_thread_pool() -> Result<(), AsyncError> {
+    let pool = Arc::new(
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .thread_name(|index| format!("easerx-test-pool-{index}"))
+            .build()
+            .unwrap(),
+    );
+    let store = StateStore::new(TestState::default());
+    let threads = Arc::new(Mutex::new(HashSet::new()));
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+
+    let tickets: Vec<_> = (0..5)
+        .map(|i| {
+            let threads = threads.clone();
+            let running = running.clone();
+            let max_running = max_running.clone();
+            store.execute_with_thread_pool(
+                pool.clone(),
+                move || {
+                    let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_running.fetch_max(now, Ordering::SeqCst);
+                    let name = std::thread::current().name().map(String::from);
+                    threads.lock().unwrap().insert(name);
+                    std::thread::sleep(Duration::from_millis(20));
+                    running.fetch_sub(1, Ordering::SeqCst);
+                    format!("result {i}")
+                },
+                |state, data| state.set_async_data(data),
+            )
+        })
+        .collect();
+    for ticket in tickets {
+        ticket.await.unwrap()?;
+    }
+
+    assert!(max_running.load(Ordering::SeqCst) <= 2);
+    let threads = threads.lock().unwrap().clone();
+    assert!(!threads.is_empty() && threads.len() <= 2);
+    assert!(threads
+        .iter()
+        .all(|name| name.as_deref().is_some_and(|name| name.starts_with("easerx-test-pool-"))));
+    assert!(store.await_state().await?.data.is_success());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_thread_pool_panic_fails_state() -> Result<(), AsyncError> {
+    let pool = Arc::new(rayon::ThreadPoolBuilder::new().num_threads(1).build().unwrap());
+    let store = StateStore::new(TestState::default());
+
+    store
+        .execute_with_thread_pool(
+            pool,
+            || -> String { panic!("pool panic") },
+            |state, data| state.set_async_data(data),
+        )
+        .await
+        .unwrap()?;
+
+    let state = store.await_state().await?;
+    assert_eq!(
+        state.data,
+        Async::fail(
+            AsyncError::Panic {
+                message: "pool panic".to_string()
+            },
+            None
+        )
+    );
+    Ok(())
+}
@@ -0,0 +1,115 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore, TransitionInfo, TransitionOrigin};
+
+fn origins(transitions: &[TransitionInfo]) -> Vec<TransitionOrigin> {
+    transitions.iter().map(TransitionInfo::origin).collect()
+}
+
+fn lines(transitions: &[TransitionInfo]) -> Vec<Option<u32>> {
+    transitions
+        .iter()
+        .map(|transition| transition.location().map(|location| location.line()))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_set_state_records_caller_location() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store.set_state(|state| state.set_count(1))?;
+    let set_state_line = line!() - 1;
+    store.compute(|state| state.clone().add_count(1))?;
+    let compute_line = line!() - 1;
+    store.await_state().await?;
+    store.priority_set_state(|state| state.add_count(1))?;
+    let priority_line = line!() - 1;
+    store.await_state().await?;
+
+    let transitions = store.recent_transitions();
+    assert_eq!(
+        origins(&transitions),
+        [TransitionOrigin::SetState, TransitionOrigin::SetState, TransitionOrigin::PrioritySetState]
+    );
+    assert_eq!(lines(&transitions), [Some(set_state_line), Some(compute_line), Some(priority_line)]);
+    for transition in &transitions {
+        assert_eq!(transition.location().unwrap().file(), file!());
+    }
+    let versions: Vec<_> = transitions.iter().map(TransitionInfo::version).collect();
+    assert_eq!(versions, [1, 2, 3]);
+    Ok(())
+}
+
+#[cfg(feature = "execute")]
+#[tokio::test]
+async fn test_execute_records_loading_and_result() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let ticket = store.execute(|| "done".to_string(), |state, data| state.set_async_data(data));
+    let execute_line = line!() - 1;
+    ticket.await.unwrap()?;
+    // Chained calls are located at the method name
+    store
+        .async_execute_with_retain(async { Err::<String, _>("boom") }, |state| &state.data, |state, data| {
+            state.set_async_data(data)
+        })
+        .await
+        .unwrap()?;
+    let retain_line = line!() - 5;
+    store.await_state().await?;
+
+    let transitions = store.recent_transitions();
+    assert_eq!(
+        origins(&transitions),
+        [
+            TransitionOrigin::ExecuteLoading,
+            TransitionOrigin::ExecuteResult,
+            TransitionOrigin::ExecuteLoading,
+            TransitionOrigin::ExecuteResult,
+        ]
+    );
+    assert_eq!(
+        lines(&transitions),
+        [Some(execute_line), Some(execute_line), Some(retain_line), Some(retain_line)]
+    );
+    assert!(transitions.iter().all(|transition| transition.location().unwrap().file() == file!()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_update_async_has_no_location() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    store
+        .update_async(|state| Box::pin(async move { state.set_count(5) }))
+        .await?;
+
+    let transitions = store.recent_transitions();
+    assert_eq!(origins(&transitions), [TransitionOrigin::UpdateAsync]);
+    assert_eq!(lines(&transitions), [None]);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_skipped_updates_are_not_recorded() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    assert!(store.set_state_if_version(7, |state| state.set_count(1)).await.is_err());
+    store.set_state(|_| panic!("invalid transition"))?;
+    store.await_state().await?;
+
+    assert!(store.recent_transitions().is_empty());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_history_keeps_the_most_recent_transitions() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).transition_history(3).build();
+    for count in 1..=5 {
+        store.set_state(move |state| state.set_count(count))?;
+    }
+    store.await_state().await?;
+    let versions: Vec<_> = store.recent_transitions().iter().map(TransitionInfo::version).collect();
+    assert_eq!(versions, [3, 4, 5]);
+
+    let disabled = StateStore::builder(TestState::default()).transition_history(0).build();
+    disabled.set_state(|state| state.set_count(1))?;
+    disabled.await_state().await?;
+    assert!(disabled.recent_transitions().is_empty());
+    Ok(())
+}
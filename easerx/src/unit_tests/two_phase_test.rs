@@ -0,0 +1,95 @@
+use crate::unit_tests::TestState;
+use crate::{two_phase_commit, AnyStateStore, AsyncError, State, StateStore};
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq, Default)]
+struct AuthState {
+    token: Option<String>,
+}
+
+impl State for AuthState {}
+
+fn erased(
+    counter: &StateStore<TestState>,
+    auth: &StateStore<AuthState>,
+) -> Vec<Arc<dyn AnyStateStore>> {
+    vec![Arc::new(counter.clone()), Arc::new(auth.clone())]
+}
+
+fn commit_both(store: &dyn AnyStateStore) -> Result<(), AsyncError> {
+    if let Some(counter) = store.downcast_ref::<TestState>() {
+        counter.set_state(|state| state.set_count(1))?;
+    } else if let Some(auth) = store.downcast_ref::<AuthState>() {
+        auth.set_state(|_| AuthState {
+            token: Some("token".to_string()),
+        })?;
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_two_phase_commit_commits_all_stores() -> Result<(), AsyncError> {
+    let counter = StateStore::new(TestState::default());
+    let auth = StateStore::new(AuthState::default());
+    let prepared = Arc::new(Mutex::new(Vec::new()));
+
+    let log = prepared.clone();
+    two_phase_commit(
+        erased(&counter, &auth),
+        move |store| {
+            log.lock().unwrap().push(store.state_type_name());
+            Ok(())
+        },
+        commit_both,
+        |_| panic!("nothing should be rolled back"),
+    )
+    .await?;
+
+    assert_eq!(prepared.lock().unwrap().len(), 2);
+    assert_eq!(counter.get_state().count, 1);
+    assert_eq!(auth.get_state().token.as_deref(), Some("token"));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_two_phase_commit_rolls_back_prepared_stores() -> Result<(), AsyncError> {
+    let counter = StateStore::new(TestState::default());
+    let auth = StateStore::new(AuthState::default());
+    let rolled_back = Arc::new(Mutex::new(Vec::new()));
+    let committed = Arc::new(Mutex::new(0));
+
+    let log = rolled_back.clone();
+    let commits = committed.clone();
+    let result = two_phase_commit(
+        erased(&counter, &auth),
+        |store| match store.downcast_ref::<AuthState>() {
+            Some(_) => Err(AsyncError::error("auth unavailable")),
+            None => Ok(()),
+        },
+        move |_| {
+            *commits.lock().unwrap() += 1;
+            Ok(())
+        },
+        move |store| log.lock().unwrap().push(store.state_type_name()),
+    )
+    .await;
+
+    assert_eq!(result, Err(AsyncError::error("auth unavailable")));
+    assert_eq!(
+        *rolled_back.lock().unwrap(),
+        vec![std::any::type_name::<TestState>()]
+    );
+    assert_eq!(*committed.lock().unwrap(), 0);
+    assert_eq!(counter.await_state().await?.count, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_any_state_store_downcast() {
+    let counter = StateStore::new(TestState::default());
+    let store: Arc<dyn AnyStateStore> = Arc::new(counter);
+    assert!(store.downcast_ref::<TestState>().is_some());
+    assert!(store.downcast_ref::<AuthState>().is_none());
+    assert_eq!(store.version(), 0);
+    assert!(!store.is_closed());
+}
@@ -0,0 +1,104 @@
+use crate::unit_tests::TestState;
+use crate::{AsyncError, StateStore, VersionConflict};
+
+#[tokio::test]
+async fn test_version_bumps_on_commit() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    assert_eq!(store.get_versioned_state(), (0, TestState::default()));
+
+    store.set_state(|state| state.add_count(1))?;
+    store.set_state(|state| state.add_count(1))?;
+    store.await_state().await?;
+
+    let (version, state) = store.get_versioned_state();
+    assert_eq!(version, 2);
+    assert_eq!(state.count, 2);
+    assert_eq!(store.version(), 2);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_if_version_success() -> Result<(), VersionConflict> {
+    let store = StateStore::new(TestState::default());
+
+    let (version, state) = store.get_versioned_state();
+    let count = state.count + 5;
+    store
+        .set_state_if_version(version, move |state| state.set_count(count))
+        .await?;
+
+    let (version, state) = store.get_versioned_state();
+    assert_eq!(version, 1);
+    assert_eq!(state.count, 5);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_if_version_conflict() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let (version, _) = store.get_versioned_state();
+    store.set_state(|state| state.set_count(10))?;
+
+    let result = store
+        .set_state_if_version(version, |state| state.set_count(99))
+        .await;
+    assert_eq!(
+        result,
+        Err(VersionConflict::Stale {
+            expected: 0,
+            actual: 1
+        })
+    );
+
+    // The rejected reducer neither committed nor bumped the version
+    let (version, state) = store.get_versioned_state();
+    assert_eq!(version, 1);
+    assert_eq!(state.count, 10);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_if_version_aba() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let (version, _) = store.get_versioned_state();
+    // A -> B -> A: the state looks unchanged but was committed twice
+    store.set_state(|state| state.set_count(1))?;
+    store.set_state(|state| state.set_count(0))?;
+    store.await_state().await?;
+    assert_eq!(store.get_state(), TestState::default());
+
+    let result = store
+        .set_state_if_version(version, |state| state.set_count(7))
+        .await;
+    assert_eq!(
+        result,
+        Err(VersionConflict::Stale {
+            expected: 0,
+            actual: 2
+        })
+    );
+    assert_eq!(store.get_state().count, 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_set_state_if_version_only_first_writer_wins() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+
+    let (version, _) = store.get_versioned_state();
+    let first = store.set_state_if_version(version, |state| state.set_count(1));
+    let second = store.set_state_if_version(version, |state| state.set_count(2));
+
+    assert_eq!(first.await, Ok(()));
+    assert_eq!(
+        second.await,
+        Err(VersionConflict::Stale {
+            expected: 0,
+            actual: 1
+        })
+    );
+    assert_eq!(store.get_versioned_state(), (1, TestState::default().set_count(1)));
+    Ok(())
+}
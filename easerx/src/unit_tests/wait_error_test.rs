@@ -0,0 +1,100 @@
+use std::time::Duration;
+use crate::unit_tests::TestState;
+use crate::{Async, AsyncError, StateStore, StoreBarrier, WaitError};
+
+#[tokio::test]
+async fn test_await_state_closed() {
+    let store = StateStore::new(TestState::default());
+    store.close();
+    store.closed().await;
+    assert_eq!(store.await_state().await, Err(WaitError::Closed));
+    assert_eq!(store.barrier().await, Err(WaitError::Closed));
+}
+
+#[tokio::test]
+async fn test_await_state_matching_resolves_with_current_and_later_states() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let current = store.await_state_matching(|state| state.count == 0, Duration::from_secs(1)).await?;
+    assert_eq!(current.count, 0);
+
+    let later = store.await_state_matching(|state| state.count == 2, Duration::from_secs(1));
+    for _ in 0..3 {
+        store.set_state(|state| state.add_count(1))?;
+    }
+    assert_eq!(later.await?.count, 2);
+    Ok(())
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_await_state_matching_timeout() {
+    let store = StateStore::new(TestState::default());
+    let result = store
+        .await_state_matching(|state| state.count > 0, Duration::from_millis(50))
+        .await;
+    assert_eq!(result, Err(WaitError::Timeout { after: Duration::from_millis(50) }));
+}
+
+#[tokio::test]
+async fn test_await_state_matching_lagged() -> Result<(), AsyncError> {
+    let store = StateStore::builder(TestState::default()).broadcast_capacity(1).build();
+    let waiter = store.await_state_matching(|state| state.count == 1, Duration::from_secs(1));
+    for _ in 0..3 {
+        store.set_state(|state| state.add_count(1))?;
+    }
+    store.await_state().await?;
+    assert_eq!(waiter.await, Err(WaitError::Lagged { skipped: 2 }));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_state_matching_closed_without_match() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let waiter = tokio::spawn(store.await_state_matching(|state| state.count > 0, Duration::from_secs(5)));
+    store.close();
+    assert_eq!(waiter.await.unwrap(), Err(WaitError::Closed));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_success_closed() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let waiter = tokio::spawn(store.await_success(|state| &state.data));
+    store.close();
+    let result = tokio::time::timeout(Duration::from_secs(5), waiter).await.expect("the waiter resolves");
+    assert_eq!(result.unwrap(), Err(WaitError::Closed));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_success_settled_before_close() -> Result<(), AsyncError> {
+    let store = StateStore::new(TestState::default());
+    let waiter = tokio::spawn(store.await_success(|state| &state.data));
+    store.set_state(|state| state.set_async_data(Async::success("done".to_string())))?;
+    store.close();
+    assert_eq!(waiter.await.unwrap(), Ok("done".to_string()));
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_await_success_failed() {
+    let store = StateStore::new(TestState {
+        data: Async::fail_with_message("offline", None),
+        ..TestState::default()
+    });
+    let result = store.await_success(|state| &state.data).await;
+    assert_eq!(result, Err(WaitError::Failed(AsyncError::error("offline"))));
+}
+
+#[test]
+fn test_wait_error_into_async_error() {
+    assert_eq!(AsyncError::from(WaitError::Timeout { after: Duration::from_secs(1) }), AsyncError::Timeout);
+    assert_eq!(AsyncError::from(WaitError::Failed(AsyncError::Cancelled)), AsyncError::Cancelled);
+    assert_eq!(
+        AsyncError::from(WaitError::Closed),
+        AsyncError::error("State store closed while waiting")
+    );
+    assert_eq!(
+        AsyncError::from(WaitError::Lagged { skipped: 3 }),
+        AsyncError::error("Waiter lagged behind by 3 states")
+    );
+}
@@ -0,0 +1,42 @@
+use std::time::Duration;
+use thiserror::Error;
+use crate::AsyncError;
+
+/// Why a future awaiting a store resolved without the awaited state, returned by
+/// [`StateStore::await_state`](crate::StateStore::await_state),
+/// [`await_state_matching`](crate::StateStore::await_state_matching),
+/// [`await_success`](crate::StateStore::await_success) and the barriers.
+///
+/// Converts into an [`AsyncError`], so `?` keeps working in functions returning one.
+/// New kinds of errors may be reported in the future, so matches need a wildcard arm.
+#[derive(Error, Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum WaitError {
+    /// The store's queue stopped, after [`close`](crate::StateStore::close) or once every clone was
+    /// dropped, so the awaited state can no longer come.
+    #[error("State store closed while waiting")]
+    Closed,
+
+    /// The awaited state did not come within the given time.
+    #[error("Timed out after {after:?} while waiting for the state")]
+    Timeout { after: Duration },
+
+    /// The waiter fell behind and skipped the given number of states, one of which may have been
+    /// the awaited one.
+    #[error("Waiter lagged behind by {skipped} states")]
+    Lagged { skipped: u64 },
+
+    /// The awaited `Async` field failed, see [`await_success`](crate::StateStore::await_success).
+    #[error("{0}")]
+    Failed(AsyncError),
+}
+
+impl From<WaitError> for AsyncError {
+    fn from(error: WaitError) -> Self {
+        match error {
+            WaitError::Timeout { .. } => AsyncError::Timeout,
+            WaitError::Failed(error) => error,
+            error => AsyncError::error(error.to_string()),
+        }
+    }
+}
@@ -17,9 +17,6 @@ impl Default for TestState {
 
 impl TestState {
     pub fn set_num(self, async_data: Async<u64>) -> Self {
-        Self {
-            num: async_data,
-            ..self
-        }
+        Self { num: async_data }
     }
 }
\ No newline at end of file
@@ -1,3 +1,5 @@
+#![cfg(feature = "execute")]
+
 use std::time::Instant;
 use futures_signals::signal::SignalExt;
 use easerx::StateStore;
@@ -1,5 +1,5 @@
 use crate::tracing_setup::tracing_init;
-use easerx::{Async, State, StateStore};
+use easerx::{Async, AsyncError, State, StateStore};
 use futures_signals::signal::SignalExt;
 use std::sync::Arc;
 use std::time::Duration;
@@ -37,7 +37,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::Success { value: 200_000_000 } == state.num)
+        .stop_if(|state| state.num.success_eq(&200_000_000))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -63,7 +63,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::Success { value: 200_000_000 } == state.num)
+        .stop_if(|state| state.num.success_eq(&200_000_000))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -90,7 +90,7 @@ async fn main() {
     let state_flow = store.to_signal();
     state_flow
         .stop_if(|state| {
-            Async::fail_with_message("Computation was cancelled".to_string(), None) == state.num
+            state.num.error_eq(&AsyncError::error("Computation was cancelled"))
         })
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
@@ -117,7 +117,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::success(200_000_000) == state.num)
+        .stop_if(|state| state.num.success_eq(&200_000_000))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -143,7 +143,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::fail_with_none(None) == state.num)
+        .stop_if(|state| state.num.error_eq(&AsyncError::None))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
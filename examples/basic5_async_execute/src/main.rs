@@ -1,5 +1,5 @@
 use crate::tracing_setup::tracing_init;
-use easerx::{Async, State, StateStore};
+use easerx::{Async, AsyncError, State, StateStore};
 use futures_signals::signal::SignalExt;
 use std::sync::Arc;
 use std::time::Duration;
@@ -34,7 +34,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::success(200_000_000) == state.num)
+        .stop_if(|state| state.num.success_eq(&200_000_000))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -57,7 +57,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::success(200_000_000) == state.num)
+        .stop_if(|state| state.num.success_eq(&200_000_000))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -80,7 +80,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::fail_with_message("Computation was cancelled", None) == state.num)
+        .stop_if(|state| state.num.error_eq(&AsyncError::error("Computation was cancelled")))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -103,7 +103,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::success(200_000_000) == state.num)
+        .stop_if(|state| state.num.success_eq(&200_000_000))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
@@ -126,7 +126,7 @@ async fn main() {
     });
     let state_flow = store.to_signal();
     state_flow
-        .stop_if(|state| Async::fail_with_none(None) == state.num)
+        .stop_if(|state| state.num.error_eq(&AsyncError::None))
         .for_each(|state| async move {
             info!("  Main | show state: {:?} ", state);
         })
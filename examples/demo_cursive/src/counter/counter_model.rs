@@ -33,7 +33,7 @@ impl CounterModel {
                     }
                     // If counter is started, increment it
                     if state.started {
-                        tick_store._set_state(|state| state.increment_count());
+                        tick_store.set_state_forget(|state| state.increment_count());
                     }
                 }
             }
@@ -49,32 +49,32 @@ impl CounterModel {
 
     // Manually increment counter
     pub fn increment_count(&self) {
-        self.store._set_state(|state| state.increment_count());
+        self.store.set_state_forget(|state| state.increment_count());
     }
 
     // Manually decrement counter
     pub fn decrement_count(&self) {
-        self.store._set_state(|state| state.decrement_count());
+        self.store.set_state_forget(|state| state.decrement_count());
     }
 
     // Start automatic counting
     pub fn start_counter(&self) {
-        self.store._set_state(|state| state.set_started(true));
+        self.store.set_state_forget(|state| state.set_started(true));
     }
 
     // Stop automatic counting
     pub fn stop_counter(&self) {
-        self.store._set_state(|state| state.set_started(false));
+        self.store.set_state_forget(|state| state.set_started(false));
     }
 
     // Reset counter to 0
     pub fn reset_counter(&self) {
-        self.store._set_state(|state| state.reset_count());
+        self.store.set_state_forget(|state| state.reset_count());
     }
 
     // Signal exit to stop background task
     pub fn request_exit(&self) {
-        self.store._set_state(|state| state.set_exit());
+        self.store.set_state_forget(|state| state.set_exit());
     }
     
 } 
\ No newline at end of file
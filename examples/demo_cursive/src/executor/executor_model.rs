@@ -21,10 +21,10 @@ impl ExecutorModel {
     // Request calculation (async operation)
     pub fn request_calc(&self) {
         let store_set = self.store.clone();
-        self.store._with_state(move |state| {
+        self.store.with_state_forget(move |state| {
             if state.async_num.is_loading() {
                 //show repeated clicks and return
-                store_set._set_state(|state| state.set_repeated_clicks(true));
+                store_set.set_state_forget(|state| state.set_repeated_clicks(true));
             } else {
                 store_set.execute(heavy_computation, |state, num| state.set_async_num(num));
             }
@@ -32,7 +32,7 @@ impl ExecutorModel {
     }
 
     pub fn reset_num(&self) {
-        self.store._set_state(|state| state.reset_num());
+        self.store.set_state_forget(|state| state.reset_num());
     }
 }
 
@@ -27,7 +27,7 @@ impl InputHandler {
 
     // Request application exit
     pub fn request_exit(&self) {
-        self.store._set_state(|state| state.set_exit());
+        self.store.set_state_forget(|state| state.set_exit());
     }
 }
 
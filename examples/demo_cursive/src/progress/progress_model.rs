@@ -21,27 +21,27 @@ impl ProgressModel {
 
     // Increment progress by 1%
     pub fn increment_progress(&self) {
-        self.store._set_state(|state| state.increment_progress());
+        self.store.set_state_forget(|state| state.increment_progress());
     }
 
     // Decrement progress by 1%
     pub fn decrement_progress(&self) {
-        self.store._set_state(|state| state.decrement_progress());
+        self.store.set_state_forget(|state| state.decrement_progress());
     }
 
     // Cycle to next color
     pub fn change_color_up(&self) {
-        self.store._set_state(|state| state.increment_color());
+        self.store.set_state_forget(|state| state.increment_color());
     }
 
     // Cycle to previous color
     pub fn change_color_down(&self) {
-        self.store._set_state(|state| state.decrement_color());
+        self.store.set_state_forget(|state| state.decrement_color());
     }
 
     // Reset progress to 50% and color to default
     pub fn reset_progress(&self) {
         self.store
-            ._set_state(|state| state.reset_progress_and_color());
+            .set_state_forget(|state| state.reset_progress_and_color());
     }
 }
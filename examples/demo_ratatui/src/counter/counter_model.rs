@@ -24,7 +24,7 @@ impl CounterViewModel {
                         break;
                     }
                     if state.started {
-                        tick_store._set_state(|state| state.increment_count());
+                        tick_store.set_state_forget(|state| state.increment_count());
                     }
                 }
             }
@@ -37,26 +37,26 @@ impl CounterViewModel {
     }
 
     pub fn increment_count(&self) {
-        self.store._set_state(|state| state.increment_count());
+        self.store.set_state_forget(|state| state.increment_count());
     }
 
     pub fn decrement_count(&self) {
-        self.store._set_state(|state| state.decrement_count());
+        self.store.set_state_forget(|state| state.decrement_count());
     }
 
     pub fn start_counter(&self) {
-        self.store._set_state(|state| state.set_started(true));
+        self.store.set_state_forget(|state| state.set_started(true));
     }
 
     pub fn stop_counter(&self) {
-        self.store._set_state(|state| state.set_started(false));
+        self.store.set_state_forget(|state| state.set_started(false));
     }
 
     pub fn reset_counter(&self) {
-        self.store._set_state(|state| state.reset_count());
+        self.store.set_state_forget(|state| state.reset_count());
     }
 
     pub fn request_exit(&self) {
-        self.store._set_state(|state| state.set_exit());
+        self.store.set_state_forget(|state| state.set_exit());
     }
 }
@@ -24,7 +24,7 @@ impl ExecutorModel {
                         break;
                     }
                     if state.async_num.is_loading() {
-                        tick_store._set_state(|state| state.on_tick());
+                        tick_store.set_state_forget(|state| state.on_tick());
                     }
                 }
             }
@@ -38,10 +38,10 @@ impl ExecutorModel {
 
     pub fn request_calc(&self) {
         let store_set = self.store.clone();
-        self.store._with_state(move |state| {
+        self.store.with_state_forget(move |state| {
             if state.async_num.is_loading() {
                 //show repeated clicks and return
-                store_set._set_state(|state| state.set_repeated_clicks(true));
+                store_set.set_state_forget(|state| state.set_repeated_clicks(true));
             } else {
                 store_set.execute(heavy_computation, |state, num| state.set_async_num(num));
             }
@@ -49,11 +49,11 @@ impl ExecutorModel {
     }
 
     pub fn reset_num(&self) {
-        self.store._set_state(|state| state.reset_num());
+        self.store.set_state_forget(|state| state.reset_num());
     }
 
     pub fn request_exit(&self) {
-        self.store._set_state(|state| state.set_exit());
+        self.store.set_state_forget(|state| state.set_exit());
     }
 }
 
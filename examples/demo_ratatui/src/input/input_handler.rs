@@ -25,11 +25,11 @@ impl InputHandler {
     }
 
     pub fn send_draw_event(&self) {
-        self.store._set_state(|state| state.send_draw_event());
+        self.store.set_state_forget(|state| state.send_draw_event());
     }
 
     pub fn request_exit(&self) {
-        self.store._set_state(|state| state.set_exit());
+        self.store.set_state_forget(|state| state.set_exit());
     }
 }
 
@@ -18,22 +18,22 @@ impl ProgressViewModel {
     }
 
     pub fn increment_progress(&self) {
-        self.store._set_state(|state| state.increment_progress());
+        self.store.set_state_forget(|state| state.increment_progress());
     }
 
     pub fn decrement_progress(&self) {
-        self.store._set_state(|state| state.decrement_progress());
+        self.store.set_state_forget(|state| state.decrement_progress());
     }
 
     pub fn change_color_up(&self) {
-        self.store._set_state(|state| state.increment_color());
+        self.store.set_state_forget(|state| state.increment_color());
     }
 
     pub fn change_color_down(&self) {
-        self.store._set_state(|state| state.decrement_color());
+        self.store.set_state_forget(|state| state.decrement_color());
     }
 
     pub fn reset_progress(&self) {
-        self.store._set_state(|state| state.reset_progress_and_color());
+        self.store.set_state_forget(|state| state.reset_progress_and_color());
     }
 }
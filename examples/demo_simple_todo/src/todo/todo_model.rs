@@ -1,10 +1,10 @@
 use crate::todo::todo_state::{Todo, TodoState};
 use easerx::AsyncError;
+use easerx::ExecutionTicket;
 use easerx::StateStore;
 use std::sync::Arc;
 use std::thread::sleep;
 use std::time::Duration;
-use tokio::task::JoinHandle;
 
 pub struct TodoModel {
     store: Arc<StateStore<TodoState>>,
@@ -35,7 +35,7 @@ impl TodoModel {
             .set_state(move |state| state.remove_completed_todos())
     }
 
-    pub fn resolve_todo(&self, index: usize) -> JoinHandle<Result<(), AsyncError>> {
+    pub fn resolve_todo(&self, index: usize) -> ExecutionTicket {
         self.store.execute(
             || fibonacci_result(92),
             move |state, num| state.resolve_todo(index, num),
@@ -1,5 +1,5 @@
 use crate::tracing_setup::tracing_init;
-use easerx::{State, StateStore};
+use easerx::{GlobalStore, State};
 use std::time::Duration;
 use tokio::time::sleep;
 use tracing::{debug, info, warn};
@@ -13,22 +13,21 @@ struct Counter {
 
 impl State for Counter {}
 
-// Create global state store
-static STORE: once_cell::sync::Lazy<StateStore<Counter>> =
-    once_cell::sync::Lazy::new(|| StateStore::new(Counter::default()));
+// Create global state store, created on first access from within the tokio runtime
+static STORE: GlobalStore<Counter> = GlobalStore::lazy(Counter::default);
 
 fn set_state<F>(reducer: F)
 where
     F: FnOnce(Counter) -> Counter + Send + 'static,
 {
-    STORE._set_state(reducer);
+    STORE.set_state_forget(reducer);
 }
 
 fn with_state<F>(action: F)
 where
     F: FnOnce(Counter) + Send + 'static,
 {
-    STORE._with_state(action);
+    STORE.with_state_forget(action);
 }
 
 #[tokio::main]
@@ -88,7 +88,7 @@ async fn main() {
     info!("==========================================");
     warn!("C. Cancel from computation Closure and retain previous value");
 
-    store._set_state(|state| state.set_num(Async::success(2)));
+    store.set_state_forget(|state| state.set_num(Async::success(2)));
     sleep(Duration::from_millis(1)).await;
 
     let cancellation_token = CancellationToken::new();